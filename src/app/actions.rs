@@ -0,0 +1,37 @@
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::runner::command::shell_quote;
+
+/// A user-defined external command shown in the actions menu
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomAction {
+    /// Label shown in the menu
+    pub name: String,
+    /// Shell command to run from the project directory.
+    ///
+    /// May contain `{file}`, `{path}`, `{dir}`, and `{test}` placeholders.
+    pub command: String,
+}
+
+impl CustomAction {
+    /// The command with placeholders replaced by shell-quoted values.
+    ///
+    /// Fails if the command uses a placeholder that has no value here, e.g.
+    /// `{test}` when no individual test is selected.
+    pub fn expand(&self, values: &HashMap<&str, String>) -> Result<String, String> {
+        let placeholder_regex = Regex::new(r"\{(file|path|dir|test)\}").unwrap();
+        
+        if let Some(missing) = placeholder_regex
+            .captures_iter(&self.command)
+            .map(|cap| cap[1].to_string())
+            .find(|name| !values.contains_key(name.as_str()))
+        {
+            return Err(format!("{{{}}} isn't available here", missing));
+        }
+        
+        Ok(placeholder_regex
+            .replace_all(&self.command, |cap: &Captures| shell_quote(&values[&cap[1]]))
+            .into_owned())
+    }
+}
@@ -0,0 +1,266 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Instant,
+};
+use crate::git;
+use crate::runner::{command::JestCommand, output_parser, test_runner};
+
+/// Which tree a benchmark run was made against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The revision being compared against
+    Baseline,
+    /// The project's working tree
+    Current,
+}
+
+/// How a single run of a test file went
+#[derive(Debug, Clone, Copy)]
+pub struct RunOutcome {
+    /// Wall-clock duration of the run in ms
+    pub duration_ms: u64,
+    /// Whether Jest reported no failures
+    pub passed: bool,
+}
+
+/// Timings for one test file on both sides of the comparison
+#[derive(Debug, Clone)]
+pub struct BenchmarkEntry {
+    /// Test file, relative to the project directory
+    pub file: String,
+    /// Run against the baseline revision
+    pub baseline: Option<RunOutcome>,
+    /// Run against the working tree
+    pub current: Option<RunOutcome>,
+}
+
+impl BenchmarkEntry {
+    /// Change in duration from baseline to current, once both have run
+    pub fn delta_ms(&self) -> Option<i64> {
+        Some(self.current?.duration_ms as i64 - self.baseline?.duration_ms as i64)
+    }
+}
+
+/// Progress reported by the benchmark thread
+enum BenchmarkUpdate {
+    /// A test file started running on one side
+    Running(usize, Side),
+    /// A test file finished running on one side
+    Finished(usize, Side, RunOutcome),
+    /// Every file has been run on both sides
+    Done,
+    /// The baseline couldn't be prepared
+    Failed(String),
+}
+
+/// Where the baseline side of a benchmark runs
+struct Baseline {
+    /// Project directory inside the baseline tree
+    project_dir: PathBuf,
+    /// Worktree created for the comparison, removed once it's done, and its repository
+    worktree: Option<(PathBuf, PathBuf)>,
+}
+
+/// A comparison of test file durations between a baseline revision and the working tree
+#[derive(Debug)]
+pub struct Benchmark {
+    /// The git ref or directory being compared against
+    pub baseline: String,
+    /// Results for each test file, in run order
+    pub entries: Vec<BenchmarkEntry>,
+    /// The file and side currently running
+    pub running: Option<(usize, Side)>,
+    /// Whether every file has been run
+    pub finished: bool,
+    /// Why the benchmark stopped early, if it did
+    pub error: Option<String>,
+    receiver: Option<mpsc::Receiver<BenchmarkUpdate>>,
+}
+
+impl Benchmark {
+    /// Start running each test file against the baseline and the working tree.
+    ///
+    /// `baseline` is either a directory containing the project at another
+    /// revision or a git ref, which is checked out into a temporary worktree.
+    /// Each command is run as given for the working tree and moved into the
+    /// baseline tree for the other side, alternating so that machine load
+    /// affects both sides evenly.
+    pub fn start(baseline: &str, project_dir: &Path, runs: Vec<(String, JestCommand)>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let entries = runs
+            .iter()
+            .map(|(file, _)| BenchmarkEntry { file: file.clone(), baseline: None, current: None })
+            .collect();
+        
+        let baseline_arg = baseline.to_string();
+        let project_dir = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+        std::thread::spawn(move || {
+            let baseline = match prepare_baseline(&baseline_arg, &project_dir) {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    let _ = tx.send(BenchmarkUpdate::Failed(e.to_string()));
+                    return;
+                }
+            };
+            
+            for (index, (_, command)) in runs.iter().enumerate() {
+                let baseline_command = rebase_command(command, &project_dir, &baseline.project_dir);
+                for (side, command) in [(Side::Baseline, &baseline_command), (Side::Current, command)] {
+                    if tx.send(BenchmarkUpdate::Running(index, side)).is_err() {
+                        break;
+                    }
+                    let _ = tx.send(BenchmarkUpdate::Finished(index, side, time_run(command)));
+                }
+            }
+            
+            if let Some((repo, worktree)) = &baseline.worktree {
+                let _ = git::remove_worktree(repo, worktree);
+            }
+            let _ = tx.send(BenchmarkUpdate::Done);
+        });
+        
+        Self {
+            baseline: baseline.to_string(),
+            entries,
+            running: None,
+            finished: false,
+            error: None,
+            receiver: Some(rx),
+        }
+    }
+    
+    /// Apply any progress made by the benchmark thread
+    pub fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+        
+        loop {
+            match receiver.try_recv() {
+                Ok(BenchmarkUpdate::Running(index, side)) => self.running = Some((index, side)),
+                Ok(BenchmarkUpdate::Finished(index, side, outcome)) => {
+                    if let Some(entry) = self.entries.get_mut(index) {
+                        match side {
+                            Side::Baseline => entry.baseline = Some(outcome),
+                            Side::Current => entry.current = Some(outcome),
+                        }
+                    }
+                },
+                Ok(BenchmarkUpdate::Done) => {
+                    self.finish(None);
+                    return;
+                },
+                Ok(BenchmarkUpdate::Failed(error)) => {
+                    self.finish(Some(error));
+                    return;
+                },
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.finish(Some("benchmark stopped unexpectedly".to_string()));
+                    return;
+                }
+            }
+        }
+    }
+    
+    fn finish(&mut self, error: Option<String>) {
+        self.running = None;
+        self.finished = true;
+        self.error = error;
+        self.receiver = None;
+    }
+    
+    /// Total baseline and current durations over files that ran on both sides
+    pub fn totals(&self) -> (u64, u64) {
+        self.entries
+            .iter()
+            .filter_map(|entry| Some((entry.baseline?.duration_ms, entry.current?.duration_ms)))
+            .fold((0, 0), |(baseline, current), (b, c)| (baseline + b, current + c))
+    }
+    
+    /// Number of individual runs completed out of the total
+    pub fn progress(&self) -> (usize, usize) {
+        let done = self.entries
+            .iter()
+            .map(|entry| entry.baseline.is_some() as usize + entry.current.is_some() as usize)
+            .sum();
+        (done, self.entries.len() * 2)
+    }
+}
+
+/// Run a command and time it
+fn time_run(command: &JestCommand) -> RunOutcome {
+    let started_at = Instant::now();
+    let result = test_runner::run_jest_test(command);
+    let duration_ms = started_at.elapsed().as_millis() as u64;
+    
+    let passed = match result {
+        Ok((stdout, stderr)) => output_parser::parse_summary(&format!("{}\n{}", stdout, stderr))
+            .is_some_and(|summary| summary.is_success()),
+        Err(_) => false,
+    };
+    
+    RunOutcome { duration_ms, passed }
+}
+
+/// Find or create the tree the baseline side runs in
+fn prepare_baseline(baseline: &str, project_dir: &Path) -> io::Result<Baseline> {
+    // A directory is used as-is, e.g. an existing worktree of the project
+    let baseline_dir = Path::new(baseline);
+    if baseline_dir.is_dir() {
+        return Ok(Baseline {
+            project_dir: baseline_dir.canonicalize()?,
+            worktree: None,
+        });
+    }
+    
+    let repo = git::repo_root(project_dir)?;
+    let relative = project_dir.strip_prefix(&repo).unwrap_or(Path::new(""));
+    let worktree = std::env::temp_dir().join(format!("surely-you-jest-benchmark-{}", std::process::id()));
+    git::add_worktree(&repo, baseline, &worktree)?;
+    
+    // Dependencies usually aren't committed, so borrow the working tree's
+    link_node_modules(&repo, &worktree, relative);
+    
+    Ok(Baseline {
+        project_dir: worktree.join(relative),
+        worktree: Some((repo, worktree)),
+    })
+}
+
+/// Symlink every `node_modules` between the repository root and the project
+/// directory into the same place in the worktree
+fn link_node_modules(repo: &Path, worktree: &Path, relative: &Path) {
+    for dir in relative.ancestors() {
+        let source = repo.join(dir).join("node_modules");
+        let target = worktree.join(dir).join("node_modules");
+        if source.is_dir() && !target.exists() {
+            #[cfg(unix)]
+            let _ = std::os::unix::fs::symlink(&source, &target);
+            #[cfg(windows)]
+            let _ = std::os::windows::fs::symlink_dir(&source, &target);
+        }
+    }
+}
+
+/// Move a command from one project directory to another, including any
+/// arguments that are paths inside the original directory
+fn rebase_command(command: &JestCommand, from: &Path, to: &Path) -> JestCommand {
+    let mut rebased = command.clone();
+    rebased.cwd = to.to_path_buf();
+    for arg in rebased.args.iter_mut() {
+        // Relative paths resolve against the new directory on their own
+        let path = Path::new(arg.as_str());
+        if !path.is_absolute() {
+            continue;
+        }
+        
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if let Ok(relative) = path.strip_prefix(from) {
+            *arg = to.join(relative).to_string_lossy().to_string();
+        }
+    }
+    rebased
+}
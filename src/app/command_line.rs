@@ -0,0 +1,319 @@
+use std::{fs, io, path::PathBuf};
+use crate::app::history::project_data_path;
+use crate::app::session::ExportFormat;
+use crate::app::state::SortMode;
+
+/// Number of commands kept in the command history
+const MAX_HISTORY: usize = 100;
+
+/// Names of the commands understood by the command line, used for completion
+pub const COMMAND_NAMES: &[&str] = &[
+    "action", "audit", "benchmark", "config", "coverage", "export", "filter", "inspect", "layout",
+    "patterns", "quit", "reset", "root", "run", "runall", "set", "shell", "slowest", "sort",
+    "stats", "summary", "theme", "unset", "watch",
+];
+
+/// Formats accepted by `:export`
+pub const EXPORT_FORMATS: &[&str] = &["json", "junit"];
+
+/// Arguments accepted by `:theme`
+pub const THEME_NAMES: &[&str] = &["dark", "high-contrast", "light"];
+
+/// Arguments accepted by `:filter`
+pub const FILTER_NAMES: &[&str] = &["changed", "clear", "failed", "related"];
+
+/// Arguments accepted by `:coverage`
+pub const COVERAGE_NAMES: &[&str] = &["off", "on"];
+
+/// Arguments accepted by `:sort`
+pub const SORT_NAMES: &[&str] = &["duration", "failed", "lastrun", "name", "recency"];
+
+/// Common Jest CLI options offered when completing `:set`
+pub const JEST_OPTIONS: &[&str] = &[
+    "bail", "ci", "coverage", "detectOpenHandles", "maxWorkers=", "runInBand",
+    "silent", "testTimeout=", "verbose",
+];
+
+/// What `:filter` should narrow the test list to
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterCommand {
+    /// Files changed since a git ref (the default branch when not given)
+    Changed(Option<String>),
+    /// Files whose last recorded run had failures
+    Failed,
+    /// Tests that depend on a source file (prompting for one when not given)
+    Related(Option<String>),
+    /// Remove any filter
+    Clear,
+}
+
+/// A parsed `:` command
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExCommand {
+    /// Run the selected file, or the files matching a pattern
+    Run(Option<String>),
+    /// Run every file in the list
+    RunAll,
+    /// Open the summary of the last run of every listed file
+    Summary,
+    /// Turn coverage collection on or off, or open the coverage view when `None`
+    Coverage(Option<bool>),
+    /// Narrow or reset the test list
+    Filter(FilterCommand),
+    /// Change the list ordering
+    Sort(SortMode),
+    /// Switch to a layout preset
+    Layout(String),
+    /// Set a Jest CLI option for every run, or list them when `None`
+    Set(Option<(String, String)>),
+    /// Remove a Jest CLI option
+    Unset(String),
+    /// Run a custom action by name
+    Action(String),
+    /// Benchmark the listed tests against a git ref or directory
+    Benchmark(String),
+    /// Search for tests in another directory
+    Root(String),
+    /// Search with comma-separated testMatch patterns (Jest defaults when empty)
+    Patterns(String),
+    /// Open the audit view
+    Audit,
+    /// Open the directory statistics view
+    Stats,
+    /// Open the slowest tests leaderboard
+    Slowest,
+    /// Open the config inspector
+    Inspect,
+    /// Open the config picker
+    Config,
+    /// Turn watch mode on or off
+    Watch,
+    /// Export the session's runs as JSON or JUnit XML, to a path or the configured one
+    Export(ExportFormat, Option<String>),
+    /// Switch to a built-in theme
+    Theme(String),
+    /// Clear the session's results
+    Reset,
+    /// Open a shell in the project directory
+    Shell,
+    /// Jump to a line of the file viewer's file
+    Line(usize),
+    /// Quit the application
+    Quit,
+}
+
+impl ExCommand {
+    /// Parse a command line (without the leading `:`)
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let (name, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+        let rest = rest.trim();
+        let argument = || (!rest.is_empty()).then(|| rest.to_string());
+        let required = |usage: &str| argument().ok_or_else(|| format!("Usage: :{} {}", name, usage));
+
+        // A number on its own is a line to go to, like in vim
+        if let Ok(line) = name.parse() {
+            return Ok(ExCommand::Line(line));
+        }
+
+        match name {
+            "run" | "r" => Ok(ExCommand::Run(argument())),
+            "runall" => Ok(ExCommand::RunAll),
+            "summary" => Ok(ExCommand::Summary),
+            "coverage" => match rest {
+                "" => Ok(ExCommand::Coverage(None)),
+                "on" => Ok(ExCommand::Coverage(Some(true))),
+                "off" => Ok(ExCommand::Coverage(Some(false))),
+                _ => Err(format!("Usage: :coverage [{}]", COVERAGE_NAMES.join("|"))),
+            },
+            "filter" | "f" => Self::parse_filter(rest).map(ExCommand::Filter),
+            "sort" => match rest {
+                "name" => Ok(ExCommand::Sort(SortMode::Name)),
+                "lastrun" | "run" => Ok(ExCommand::Sort(SortMode::LastRun)),
+                "failed" | "failures" => Ok(ExCommand::Sort(SortMode::Failed)),
+                "duration" => Ok(ExCommand::Sort(SortMode::Duration)),
+                "recency" | "modified" => Ok(ExCommand::Sort(SortMode::Recency)),
+                _ => Err(format!("Usage: :sort {}", SORT_NAMES.join("|"))),
+            },
+            "layout" => required("<name>").map(ExCommand::Layout),
+            "set" => Ok(ExCommand::Set(argument().map(|option| match option.split_once('=') {
+                Some((key, value)) => (key.trim().to_string(), value.trim().to_string()),
+                None => (option, String::new()),
+            }))),
+            "unset" => required("<option>").map(ExCommand::Unset),
+            "action" => required("<name>").map(ExCommand::Action),
+            "benchmark" => required("<git ref or directory>").map(ExCommand::Benchmark),
+            "root" => required("<directory>").map(ExCommand::Root),
+            "patterns" => Ok(ExCommand::Patterns(rest.to_string())),
+            "audit" => Ok(ExCommand::Audit),
+            "stats" => Ok(ExCommand::Stats),
+            "slowest" => Ok(ExCommand::Slowest),
+            "inspect" => Ok(ExCommand::Inspect),
+            "config" => Ok(ExCommand::Config),
+            "watch" => Ok(ExCommand::Watch),
+            "export" => {
+                let (format, path) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                let path = (!path.trim().is_empty()).then(|| path.trim().to_string());
+                match format {
+                    "json" => Ok(ExCommand::Export(ExportFormat::Json, path)),
+                    "junit" => Ok(ExCommand::Export(ExportFormat::Junit, path)),
+                    _ => Ok(ExCommand::Export(ExportFormat::Json, argument())),
+                }
+            },
+            "theme" => match rest {
+                name if THEME_NAMES.contains(&name) => Ok(ExCommand::Theme(name.to_string())),
+                _ => Err(format!("Usage: :theme {}", THEME_NAMES.join("|"))),
+            },
+            "reset" => Ok(ExCommand::Reset),
+            "shell" | "sh" => Ok(ExCommand::Shell),
+            "quit" | "q" => Ok(ExCommand::Quit),
+            "" => Err("No command given".to_string()),
+            _ => Err(format!("Unknown command '{}'", name)),
+        }
+    }
+
+    fn parse_filter(rest: &str) -> Result<FilterCommand, String> {
+        let (kind, argument) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let argument = argument.trim();
+
+        match kind {
+            "changed" => Ok(FilterCommand::Changed((!argument.is_empty()).then(|| argument.to_string()))),
+            "failed" => Ok(FilterCommand::Failed),
+            "related" => Ok(FilterCommand::Related((!argument.is_empty()).then(|| argument.to_string()))),
+            "clear" | "none" | "" => Ok(FilterCommand::Clear),
+            _ => Err(format!("Usage: :filter {}", FILTER_NAMES.join("|"))),
+        }
+    }
+}
+
+/// Previously entered commands for a project, oldest first
+#[derive(Debug, Default)]
+pub struct CommandHistory {
+    /// Where the history is stored on disk
+    path: Option<PathBuf>,
+    /// Entered commands
+    pub entries: Vec<String>,
+}
+
+impl CommandHistory {
+    /// Load the command history for the given project, starting empty if none exists
+    pub fn load(project_dir: &str) -> Self {
+        let path = project_data_path("commands", project_dir);
+        let entries = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self { path, entries }
+    }
+
+    /// Add a command, moving it to the end if it was entered before, and save
+    pub fn push(&mut self, command: &str) -> io::Result<()> {
+        self.entries.retain(|entry| entry != command);
+        self.entries.push(command.to_string());
+        if self.entries.len() > MAX_HISTORY {
+            self.entries.drain(..self.entries.len() - MAX_HISTORY);
+        }
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string(&self.entries).map_err(io::Error::other)?;
+        fs::write(path, content)
+    }
+}
+
+/// The `:` command line being edited
+#[derive(Debug, Clone, Default)]
+pub struct CommandLine {
+    /// Current input, without the leading `:`
+    pub input: String,
+    /// History entry shown while browsing with Up/Down
+    history_index: Option<usize>,
+    /// Candidates offered by the last Tab press
+    pub completions: Vec<String>,
+    /// Which completion is in the input
+    pub completion_index: usize,
+}
+
+impl CommandLine {
+    /// Whether Tab is cycling through completions
+    pub fn is_completing(&self) -> bool {
+        !self.completions.is_empty()
+    }
+
+    /// Type a character
+    pub fn push(&mut self, c: char) {
+        self.completions.clear();
+        self.input.push(c);
+    }
+
+    /// Delete the last character
+    pub fn pop(&mut self) {
+        self.completions.clear();
+        self.input.pop();
+    }
+
+    /// Start completing with the given full-input candidates
+    pub fn start_completion(&mut self, candidates: Vec<String>) {
+        match candidates.as_slice() {
+            [] => {},
+            [only] => self.input = only.clone(),
+            [first, ..] => {
+                self.input = first.clone();
+                self.completions = candidates;
+                self.completion_index = 0;
+            },
+        }
+    }
+
+    /// Move to the next (or previous) completion candidate
+    pub fn cycle_completion(&mut self, backwards: bool) {
+        let count = self.completions.len();
+        if count == 0 {
+            return;
+        }
+
+        self.completion_index = if backwards {
+            (self.completion_index + count - 1) % count
+        } else {
+            (self.completion_index + 1) % count
+        };
+        self.input = self.completions[self.completion_index].clone();
+    }
+
+    /// Show the previous history entry
+    pub fn history_previous(&mut self, history: &[String]) {
+        if history.is_empty() {
+            return;
+        }
+
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None => history.len() - 1,
+        };
+        self.history_index = Some(index);
+        self.completions.clear();
+        self.input = history[index].clone();
+    }
+
+    /// Show the next history entry, or an empty line after the last one
+    pub fn history_next(&mut self, history: &[String]) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+
+        self.completions.clear();
+        if index + 1 < history.len() {
+            self.history_index = Some(index + 1);
+            self.input = history[index + 1].clone();
+        } else {
+            self.history_index = None;
+            self.input.clear();
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use serde::Deserialize;
+use crate::app::{
+    actions::CustomAction, format::FormatOptions, keys::KeyMap, layout::LayoutPreset,
+    queue::QueueOptions, session::ExportOptions,
+};
+use crate::runner::{backend::Runner, command::JestCommand, project::DiscoveryBackend, test_script::TestScriptMode};
+use crate::widgets::theme::ThemeOptions;
+use std::{collections::BTreeMap, env, fs, io, path::{Path, PathBuf}};
+
+/// Name of the project-local config file, whose settings override the user's
+pub const PROJECT_CONFIG_FILE: &str = ".syj.toml";
+
+/// User settings, loaded from `config.toml` in the config directory and
+/// `.syj.toml` in the project directory
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// How durations and timestamps are rendered
+    pub format: FormatOptions,
+    /// Layout presets available in every project, by name
+    pub layouts: BTreeMap<String, LayoutPreset>,
+    /// External commands listed in the actions menu
+    pub actions: Vec<CustomAction>,
+    /// How batch runs of marked files are executed
+    pub queue: QueueOptions,
+    /// Where session exports are written
+    pub export: ExportOptions,
+    /// Extra flags for every run
+    pub runner: RunnerOptions,
+    /// How test files are found when the runner has no config
+    pub discovery: DiscoveryOptions,
+    /// How text is copied to the clipboard
+    pub clipboard: ClipboardOptions,
+    /// What's written to the terminal when a run finishes
+    pub bell: BellOptions,
+    /// Remapped keys, as `pressed key = key it acts as`
+    pub keys: BTreeMap<String, String>,
+    /// Colors the UI is drawn with
+    pub theme: ThemeOptions,
+}
+
+/// Options passed to the test runner
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunnerOptions {
+    /// Flags appended to every run, e.g. `["--maxWorkers=50%"]`
+    pub args: Vec<String>,
+    /// When runs go through the package.json `test` script
+    pub test_script: TestScriptMode,
+}
+
+impl RunnerOptions {
+    /// The command a run in the project directory starts from: the `test`
+    /// script when it should be used, otherwise the runner itself
+    pub fn base_command(&self, project_dir: &str, runner: Runner) -> JestCommand {
+        if self.test_script.use_script(Path::new(project_dir), runner) {
+            JestCommand::test_script(project_dir, runner)
+        } else {
+            JestCommand::new(project_dir, runner)
+        }
+    }
+}
+
+/// Options for finding test files
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscoveryOptions {
+    /// Patterns used instead of the runner's defaults when it has no config
+    pub test_match: Vec<String>,
+    /// How test files are found
+    pub backend: DiscoveryBackend,
+}
+
+/// Options for copying to the clipboard
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClipboardOptions {
+    /// Command that reads the text to copy from stdin, e.g. `"xclip -selection clipboard"`.
+    /// Defaults to `pbcopy`.
+    pub command: Option<String>,
+}
+
+/// Sequences written to the terminal when a run finishes, so a run in a
+/// background pane can be heard (or seen, by terminals that flag a ringing tab)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BellOptions {
+    /// Written when a run passes, e.g. `"\u0007"` for the terminal bell.
+    /// Nothing is written when it's empty.
+    pub success: String,
+    /// Written when a run fails or can't be run
+    pub failure: String,
+}
+
+impl BellOptions {
+    /// The sequence for a run that passed or failed
+    pub fn sequence(&self, passed: bool) -> &str {
+        if passed { &self.success } else { &self.failure }
+    }
+}
+
+impl Config {
+    /// Load the user's config with the project's config merged over it, using
+    /// defaults for anything neither sets.
+    ///
+    /// A file that can't be read or is invalid is left out entirely, and what's
+    /// wrong with it is returned alongside the config so it can be shown. When
+    /// the files are valid alone but not together (e.g. both bind a key to
+    /// different actions), the defaults are used and that's returned too.
+    pub fn load(project_dir: &Path) -> (Self, Vec<String>) {
+        let paths = config_path().into_iter().chain([project_dir.join(PROJECT_CONFIG_FILE)]);
+        let mut merged = toml::Table::new();
+        let mut errors = Vec::new();
+        
+        for path in paths {
+            match read_table(&path) {
+                Ok(Some(table)) => merge(&mut merged, table),
+                Ok(None) => {},
+                Err(message) => errors.push(format!("{}: {}", path.display(), message)),
+            }
+        }
+        
+        let config = toml::Value::Table(merged)
+            .try_into()
+            .map_err(|e: toml::de::Error| e.message().to_string())
+            .and_then(|config: Config| config.validate().map(|()| config));
+        match config {
+            Ok(config) => (config, errors),
+            Err(message) => {
+                errors.push(format!("config files combined: {}", message));
+                (Self::default(), errors)
+            },
+        }
+    }
+    
+    /// Check settings that parse but don't make sense
+    fn validate(&self) -> Result<(), String> {
+        self.format.validate()?;
+        self.queue.validate()?;
+        KeyMap::from_config(&self.keys)?;
+        self.theme.resolve()?;
+        Ok(())
+    }
+    
+    /// Patterns to find tests with when the runner has no config
+    pub fn default_test_matches(&self, runner: Runner) -> Vec<String> {
+        if self.discovery.test_match.is_empty() {
+            runner.default_test_matches()
+        } else {
+            self.discovery.test_match.clone()
+        }
+    }
+}
+
+/// Read a config file as a table, checking it's a valid config by itself.
+/// Returns `None` when there's no such file.
+fn read_table(path: &Path) -> Result<Option<toml::Table>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    
+    // Keep errors to one line so they fit in the status bar
+    let table: toml::Table = toml::from_str(&content).map_err(|e| match e.span() {
+        Some(span) => format!(
+            "line {}: {}",
+            content[..span.start].matches('\n').count() + 1,
+            e.message().replace('\n', "; ")
+        ),
+        None => e.message().to_string(),
+    })?;
+    let config: Config = toml::Value::Table(table.clone())
+        .try_into()
+        .map_err(|e: toml::de::Error| e.message().to_string())?;
+    config.validate()?;
+    Ok(Some(table))
+}
+
+/// Merge `overrides` into `base`, combining tables key by key and replacing
+/// everything else
+fn merge(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => merge(base, overrides),
+            (_, value) => {
+                base.insert(key, value);
+            },
+        }
+    }
+}
+
+/// Directory where user settings are stored
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("surely-you-jest"))
+}
+
+/// Location of the user's config file
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
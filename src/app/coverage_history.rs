@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use crate::app::history::project_data_path;
+use crate::runner::coverage::{CoverageSummary, FileCoverage};
+
+/// Number of coverage runs kept per project
+const MAX_RECORDS: usize = 50;
+
+/// Changes in line coverage smaller than this, in percentage points, are rounding noise
+const MIN_DELTA: f64 = 0.05;
+
+/// Coverage written by one run, and the commit it was made on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageRecord {
+    /// When the run finished, in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Full hash of the checked out commit, if the project is in a git repository
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes
+    pub dirty: bool,
+    /// Coverage across every file
+    pub total: FileCoverage,
+    /// Per-file coverage, with paths relative to the project directory
+    pub files: BTreeMap<String, FileCoverage>,
+}
+
+/// Persisted coverage of a project's recent coverage runs, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CoverageHistory {
+    /// Where the history is stored on disk
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    pub records: Vec<CoverageRecord>,
+}
+
+impl CoverageHistory {
+    /// Load the coverage history for the given project, starting empty if none exists
+    pub fn load(project_dir: &str) -> Self {
+        let path = project_data_path("coverage", project_dir);
+
+        let mut history = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<CoverageHistory>(&content).ok())
+            .unwrap_or_default();
+
+        history.path = path;
+        history
+    }
+
+    /// Write the history back to disk
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, content)
+    }
+
+    /// Record the coverage a run wrote
+    pub fn record(&mut self, summary: &CoverageSummary, commit: Option<String>, dirty: bool) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        self.records.push(CoverageRecord {
+            timestamp,
+            commit,
+            dirty,
+            total: summary.total,
+            files: summary.files.iter().cloned().collect(),
+        });
+        if self.records.len() > MAX_RECORDS {
+            self.records.drain(..self.records.len() - MAX_RECORDS);
+        }
+    }
+
+    /// The latest coverage recorded on a commit, preferring runs made without
+    /// uncommitted changes
+    pub fn at_commit(&self, commit: &str) -> Option<&CoverageRecord> {
+        let on_commit = || self.records.iter().rev().filter(|record| record.commit.as_deref() == Some(commit));
+        on_commit().find(|record| !record.dirty).or_else(|| on_commit().next())
+    }
+}
+
+/// How a file's coverage changed between the base and the last coverage run
+#[derive(Debug, Clone)]
+pub struct FileDelta {
+    /// Source file, relative to the project directory
+    pub file: String,
+    /// Coverage on the base, if the file was covered there
+    pub base: Option<FileCoverage>,
+    /// Coverage in the last run, if the file was covered there
+    pub current: Option<FileCoverage>,
+}
+
+impl FileDelta {
+    /// Change in the percentage of lines covered, when both sides have one
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.current?.lines.pct? - self.base?.lines.pct?)
+    }
+
+    /// Whether the file lost line coverage
+    pub fn is_regression(&self) -> bool {
+        self.delta().is_some_and(|delta| delta <= -MIN_DELTA)
+    }
+}
+
+/// Per-file coverage changes between a base commit's recorded coverage and
+/// the last coverage run, e.g. to check a branch before opening a PR
+#[derive(Debug, Clone)]
+pub struct CoverageDelta {
+    /// The ref the comparison was asked for, e.g. "main"
+    pub base_ref: String,
+    /// Full hash of the commit whose coverage is compared against
+    pub base_commit: String,
+    /// When the base coverage was recorded, in seconds since the Unix epoch
+    pub base_timestamp: u64,
+    /// Coverage across every file on the base
+    pub base_total: FileCoverage,
+    /// Coverage across every file in the last run
+    pub current_total: FileCoverage,
+    /// Files whose coverage changed, regressions first, largest first
+    pub files: Vec<FileDelta>,
+}
+
+impl CoverageDelta {
+    /// Compare the last coverage run against coverage recorded on the base
+    pub fn compare(base_ref: &str, base: &CoverageRecord, current: &CoverageSummary) -> Self {
+        let current_files: BTreeMap<&String, FileCoverage> = current.files.iter().map(|(file, coverage)| (file, *coverage)).collect();
+
+        let mut files: Vec<FileDelta> = base.files
+            .keys()
+            .chain(current_files.keys().copied())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|file| FileDelta {
+                file: file.clone(),
+                base: base.files.get(file).copied(),
+                current: current_files.get(file).copied(),
+            })
+            .filter(|delta| match (delta.base, delta.current) {
+                (Some(base), Some(current)) => {
+                    (base.lines.covered, base.lines.total) != (current.lines.covered, current.lines.total)
+                },
+                _ => true,
+            })
+            .collect();
+        // Files only on one side sit between the regressions and the improvements
+        files.sort_by(|a, b| a.delta().unwrap_or(0.0).total_cmp(&b.delta().unwrap_or(0.0)));
+
+        Self {
+            base_ref: base_ref.to_string(),
+            base_commit: base.commit.clone().unwrap_or_default(),
+            base_timestamp: base.timestamp,
+            base_total: base.total,
+            current_total: current.total,
+            files,
+        }
+    }
+
+    /// Change in the percentage of lines covered across every file
+    pub fn total_delta(&self) -> Option<f64> {
+        Some(self.current_total.lines.pct? - self.base_total.lines.pct?)
+    }
+
+    /// Number of files that lost line coverage
+    pub fn regression_count(&self) -> usize {
+        self.files.iter().filter(|delta| delta.is_regression()).count()
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Instant,
+};
+use tracing::{info, warn};
+use crate::runner::{
+    command::JestCommand,
+    config_finder::{self, JestProject, PathFilters},
+    project,
+};
+
+/// What to search for, copied from the app so the search can run on its own thread
+pub struct DiscoveryRequest {
+    /// Directory to search, which test paths are made relative to
+    pub search_path: PathBuf,
+    /// Patterns test files are matched with
+    pub test_matches: Vec<String>,
+    /// testRegex and ignore patterns from the config
+    pub path_filters: PathFilters,
+    /// Projects of a multi-project config, whose tests are found instead
+    pub projects: Vec<JestProject>,
+    /// Command to ask Jest for the test files with once the walk is done, when
+    /// that's the configured backend
+    pub list_command: Option<JestCommand>,
+}
+
+/// The outcome of a discovery
+pub struct DiscoveryResult {
+    /// Every test file found, sorted
+    pub tests: Vec<String>,
+    /// The projects, each with its tests
+    pub projects: Vec<JestProject>,
+    /// Why the search failed or Jest couldn't list the tests; `tests` holds
+    /// whatever was found anyway
+    pub error: Option<String>,
+    /// Whether the search was stopped before it finished
+    pub cancelled: bool,
+}
+
+/// Messages from the discovery thread
+enum DiscoveryMessage {
+    /// A test file, as soon as the walk comes across it
+    Found(String),
+    /// The search is over
+    Finished(DiscoveryResult),
+}
+
+/// Test discovery running on a background thread, streaming test files as
+/// they're found
+pub struct Discovery {
+    /// Messages from the discovery thread
+    receiver: mpsc::Receiver<DiscoveryMessage>,
+    /// Set to stop the walk early
+    cancelled: Arc<AtomicBool>,
+    /// How many test files have been found so far
+    pub found: usize,
+}
+
+impl std::fmt::Debug for Discovery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Discovery")
+            .field("found", &self.found)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Discovery {
+    /// Start searching for test files on a background thread
+    pub fn start(request: DiscoveryRequest) -> Self {
+        let (tx, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let thread_cancelled = Arc::clone(&cancelled);
+
+        thread::spawn(move || {
+            let DiscoveryRequest { search_path, test_matches, path_filters, mut projects, list_command } = request;
+            let started_at = Instant::now();
+            info!(
+                path = %search_path.display(),
+                patterns = ?test_matches,
+                projects = projects.len(),
+                list_tests = list_command.is_some(),
+                "Discovering tests"
+            );
+
+            // A multi-project config's tests are whatever its projects match
+            let found = if projects.is_empty() {
+                config_finder::scan_matching_tests(
+                    &test_matches,
+                    &path_filters,
+                    &search_path,
+                    &thread_cancelled,
+                    &mut |file| {
+                        let _ = tx.send(DiscoveryMessage::Found(file.to_string()));
+                    }
+                )
+            } else {
+                config_finder::find_project_tests(&mut projects, &search_path)
+            };
+            let (mut tests, mut error) = match found {
+                Ok(tests) => (tests, None),
+                Err(e) => (Vec::new(), Some(format!("Could not search for tests: {}", e))),
+            };
+
+            let cancelled = thread_cancelled.load(Ordering::Relaxed);
+            if let Some(command) = list_command && error.is_none() && !cancelled {
+                match project::list_tests(command) {
+                    Ok(listed) => tests = listed,
+                    Err(e) => error = Some(format!("Could not list tests with Jest, using test patterns instead: {}", e)),
+                }
+            }
+
+            match &error {
+                Some(error) => warn!("{}", error),
+                None => info!(
+                    files = tests.len(),
+                    cancelled,
+                    elapsed_ms = started_at.elapsed().as_millis() as u64,
+                    "Discovery finished"
+                ),
+            }
+            let _ = tx.send(DiscoveryMessage::Finished(DiscoveryResult { tests, projects, error, cancelled }));
+        });
+
+        Self {
+            receiver,
+            cancelled,
+            found: 0,
+        }
+    }
+
+    /// Stop the walk, keeping the files found so far
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// The test files found since the last call, and the outcome once the
+    /// search is over
+    pub fn poll(&mut self) -> (Vec<String>, Option<DiscoveryResult>) {
+        let mut found = Vec::new();
+        for message in self.receiver.try_iter() {
+            match message {
+                DiscoveryMessage::Found(file) => found.push(file),
+                DiscoveryMessage::Finished(result) => {
+                    self.found += found.len();
+                    return (found, Some(result));
+                },
+            }
+        }
+        self.found += found.len();
+        (found, None)
+    }
+}
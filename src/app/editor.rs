@@ -0,0 +1,95 @@
+use crossterm::{
+    execute,
+    terminal::{EnterAlternateScreen, enable_raw_mode},
+};
+use ratatui::DefaultTerminal;
+use std::{
+    env, io,
+    path::Path,
+    process::{Command, ExitStatus},
+};
+
+/// Editors that take `-g file:line` to open a file at a line
+const GOTO_EDITORS: &[&str] = &["code", "code-insiders", "codium", "cursor"];
+
+/// Environment variable holding the last test command in a shell opened with `!`
+pub const LAST_COMMAND_ENV: &str = "SYJ_LAST_COMMAND";
+
+/// The command that opens `path` at `line` in the user's editor: `$VISUAL`,
+/// then `$EDITOR`, then vi. VS Code and its forks get `-g file:line`,
+/// everything else `+line file`.
+pub fn editor_command(path: &Path, line: usize) -> Command {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    
+    // The variable may carry its own arguments, e.g. "code --wait"
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let mut command = Command::new(program);
+    command.args(words);
+    
+    let name = Path::new(program)
+        .file_stem()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if GOTO_EDITORS.contains(&name.as_str()) {
+        command.arg("-g").arg(format!("{}:{}", path.display(), line));
+    } else {
+        command.arg(format!("+{}", line)).arg(path);
+    }
+    command
+}
+
+/// Stop the app like a shell job stopped with Ctrl+Z, handing the terminal back
+/// to the shell, then take it back and redraw once the job is resumed (`fg`).
+///
+/// The terminal is in raw mode, so Ctrl+Z reaches the app as a key instead of
+/// stopping it; the app restores the terminal and stops itself instead.
+#[cfg(unix)]
+pub fn suspend(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    let mut command = Command::new("kill");
+    command.arg("-TSTP").arg(std::process::id().to_string());
+    run_outside_tui(terminal, &mut command, None).map(|_| ())
+}
+
+/// Windows has no job control to suspend to
+#[cfg(windows)]
+pub fn suspend(_terminal: &mut DefaultTerminal) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "suspending isn't supported on Windows"))
+}
+
+/// The user's shell, started in `dir`: `$SHELL` (falling back to sh), or
+/// `%COMSPEC%` (falling back to cmd) on Windows. The last test command, if
+/// any, is passed on in `$SYJ_LAST_COMMAND`.
+pub fn shell_command(dir: &Path, last_command: Option<&str>) -> Command {
+    let (variable, fallback) = if cfg!(windows) { ("COMSPEC", "cmd") } else { ("SHELL", "sh") };
+    let shell = env::var(variable)
+        .ok()
+        .filter(|shell| !shell.trim().is_empty())
+        .unwrap_or_else(|| fallback.to_string());
+    
+    let mut command = Command::new(shell);
+    command.current_dir(dir);
+    if let Some(last_command) = last_command {
+        command.env(LAST_COMMAND_ENV, last_command);
+    }
+    command
+}
+
+/// Give the terminal to a command until it exits, then take it back and redraw.
+/// A message, if any, is printed once the terminal is back to normal.
+pub fn run_outside_tui(terminal: &mut DefaultTerminal, command: &mut Command, message: Option<&str>) -> io::Result<ExitStatus> {
+    ratatui::restore();
+    if let Some(message) = message {
+        println!("{}", message);
+    }
+    let status = command.status();
+    
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    terminal.clear()?;
+    status
+}
@@ -0,0 +1,98 @@
+use std::{collections::HashMap, path::Path};
+use crate::runner::{json_results::{AssertionStatus, JsonResults}, stack_trace};
+
+/// A failing test, or a test file that failed to run
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedTest {
+    /// Test file, relative to the search path
+    pub file: String,
+    /// Full name of the test, or `None` when the whole file failed to run
+    pub test: Option<String>,
+    /// Line the test is declared on, when the runner reported it
+    pub line: Option<usize>,
+}
+
+/// Failures with the same error message, give or take the numbers in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureGroup {
+    /// Message of the group's first failure, without its stack trace
+    pub message: String,
+    /// Every failure in the group, in the order they were reported
+    pub tests: Vec<FailedTest>,
+}
+
+/// A run's failures grouped by error message, so one root cause breaking
+/// many tests shows up as one group
+#[derive(Debug, Clone, Default)]
+pub struct FailureGroups {
+    /// Groups with the most failures first
+    pub groups: Vec<FailureGroup>,
+}
+
+/// What failures are grouped by: the message with colors stripped, numbers
+/// (counts, ids, line numbers, timings) masked, and whitespace collapsed
+fn message_key(message: &str) -> String {
+    let mut key = String::with_capacity(message.len());
+    let mut in_number = false;
+    for c in message.chars() {
+        if c.is_ascii_digit() {
+            if !in_number {
+                key.push('#');
+            }
+            in_number = true;
+            continue;
+        }
+        in_number = false;
+        key.push(c);
+    }
+    key.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl FailureGroups {
+    /// Group the failures in the given results. Files that failed to run are
+    /// grouped by their error too.
+    pub fn build<'a>(results: impl IntoIterator<Item = &'a JsonResults>, search_path: &Path) -> Self {
+        let root = search_path.canonicalize().unwrap_or_else(|_| search_path.to_path_buf());
+
+        let mut groups: Vec<FailureGroup> = Vec::new();
+        let mut by_key: HashMap<String, usize> = HashMap::new();
+        let mut add = |message: String, test: FailedTest| {
+            let index = *by_key.entry(message_key(&message)).or_insert_with(|| {
+                groups.push(FailureGroup { message, tests: Vec::new() });
+                groups.len() - 1
+            });
+            groups[index].tests.push(test);
+        };
+
+        for file in results.into_iter().flat_map(|results| &results.test_results) {
+            let relative = Path::new(&file.name)
+                .strip_prefix(&root)
+                .map(|relative| relative.display().to_string())
+                .unwrap_or_else(|_| file.name.clone());
+
+            if file.failed_to_run() {
+                // Every such message starts with the same heading, so it's left out
+                let (message, _) = stack_trace::split_failure(&file.message);
+                let message = message.trim().trim_start_matches("● Test suite failed to run").trim();
+                add(message.to_string(), FailedTest { file: relative.clone(), test: None, line: None });
+            }
+            for test in file.assertion_results.iter().filter(|test| test.status == AssertionStatus::Failed) {
+                let (message, _) = stack_trace::split_failure(&test.failure_messages.join("\n"));
+                add(message.trim().to_string(), FailedTest {
+                    file: relative.clone(),
+                    test: Some(test.full_name.clone()),
+                    line: test.location.map(|location| location.line),
+                });
+            }
+        }
+
+        // Stable, so groups of the same size stay in the order they were reported
+        groups.sort_by_key(|group| std::cmp::Reverse(group.tests.len()));
+        Self { groups }
+    }
+
+    /// Number of failures across every group
+    pub fn failure_count(&self) -> usize {
+        self.groups.iter().map(|group| group.tests.len()).sum()
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::{Local, TimeZone, format::{Item, StrftimeItems}};
+use serde::Deserialize;
+
+/// Format a duration in milliseconds for display, e.g. "850ms", "2.3s", "1m 23s"
+pub fn format_duration(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m {}s", ms / 60_000, (ms % 60_000) / 1000)
+    }
+}
+
+/// Format a Unix timestamp relative to now, e.g. "5m ago", "3d ago"
+pub fn format_relative_time(timestamp: u64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let elapsed = now.saturating_sub(timestamp);
+    
+    match elapsed {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{}m ago", elapsed / 60),
+        3_600..86_400 => format!("{}h ago", elapsed / 3_600),
+        86_400..2_592_000 => format!("{}d ago", elapsed / 86_400),
+        2_592_000..31_536_000 => format!("{}mo ago", elapsed / 2_592_000),
+        _ => format!("{}y ago", elapsed / 31_536_000),
+    }
+}
+
+
+/// How durations are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationStyle {
+    /// Scaled to the duration, e.g. "850ms", "2.3s", "1m 23s"
+    #[default]
+    Human,
+    /// Always in milliseconds, e.g. "83000ms"
+    #[serde(alias = "ms")]
+    Milliseconds,
+    /// Always in seconds, e.g. "83.0s"
+    #[serde(alias = "s")]
+    Seconds,
+}
+
+/// How timestamps are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampStyle {
+    /// Relative to now, e.g. "2m ago"
+    #[default]
+    Relative,
+    /// Local date and time using the configured format
+    Absolute,
+}
+
+/// Configured formatting for durations and timestamps
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct FormatOptions {
+    /// Style for durations
+    pub duration: DurationStyle,
+    /// Style for timestamps
+    pub timestamp: TimestampStyle,
+    /// strftime-style format for absolute timestamps, e.g. "%d/%m/%Y %H:%M"
+    pub timestamp_format: String,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            duration: DurationStyle::default(),
+            timestamp: TimestampStyle::default(),
+            timestamp_format: "%Y-%m-%d %H:%M".to_string(),
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Check that the absolute timestamp format is valid
+    pub fn validate(&self) -> Result<(), String> {
+        if StrftimeItems::new(&self.timestamp_format).any(|item| matches!(item, Item::Error)) {
+            return Err(format!("invalid timestamp_format '{}'", self.timestamp_format));
+        }
+        Ok(())
+    }
+    
+    /// Format a duration in milliseconds using the configured style
+    pub fn duration(&self, ms: u64) -> String {
+        match self.duration {
+            DurationStyle::Human => format_duration(ms),
+            DurationStyle::Milliseconds => format!("{}ms", ms),
+            DurationStyle::Seconds => format!("{:.1}s", ms as f64 / 1000.0),
+        }
+    }
+    
+    /// Format how long a run has been going, against how long it usually takes
+    pub fn elapsed(&self, elapsed_ms: u64, estimate_ms: Option<u64>) -> String {
+        match estimate_ms {
+            Some(estimate_ms) if elapsed_ms <= estimate_ms => format!(
+                "{} of ~{}, ~{} left",
+                self.duration(elapsed_ms),
+                self.duration(estimate_ms),
+                self.duration(estimate_ms - elapsed_ms)
+            ),
+            Some(estimate_ms) => format!("{}, usually ~{}", self.duration(elapsed_ms), self.duration(estimate_ms)),
+            None => self.duration(elapsed_ms),
+        }
+    }
+    
+    /// Format a Unix timestamp using the configured style
+    pub fn timestamp(&self, timestamp: u64) -> String {
+        match self.timestamp {
+            TimestampStyle::Relative => format_relative_time(timestamp),
+            TimestampStyle::Absolute => match Local.timestamp_opt(timestamp as i64, 0).single() {
+                Some(time) => time.format(&self.timestamp_format).to_string(),
+                None => format_relative_time(timestamp),
+            },
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use crate::runner::{
+    json_results::{AssertionStatus, FileResult},
+    output_parser::RunSummary,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Number of recent runs kept per test file
+const MAX_RECORDS_PER_FILE: usize = 20;
+
+/// Number of recent runs used when computing averages
+const AVERAGE_WINDOW: usize = 10;
+
+/// A single test's result in a recorded run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestRecord {
+    /// Describe names and the test name joined with spaces
+    pub name: String,
+    pub status: AssertionStatus,
+    /// Duration in ms, when the runner measured it
+    pub duration_ms: Option<u64>,
+}
+
+impl TestRecord {
+    /// The results of every test in the given files
+    pub fn from_results<'a>(files: impl IntoIterator<Item = &'a FileResult>) -> Vec<Self> {
+        files
+            .into_iter()
+            .flat_map(|file| &file.assertion_results)
+            .map(|test| TestRecord {
+                name: test.full_name.clone(),
+                status: test.status,
+                duration_ms: test.duration.map(|duration| duration as u64),
+            })
+            .collect()
+    }
+}
+
+/// A single recorded run of a test file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// When the run finished, in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Wall-clock duration of the run in ms
+    pub duration_ms: u64,
+    /// Test counts reported by Jest, if the summary could be parsed
+    #[serde(default)]
+    pub summary: Option<RunSummary>,
+    /// Whether the file failed without running its tests, or Jest couldn't be run
+    #[serde(default)]
+    pub failed_to_run: bool,
+    /// Each test's result, when the runner reported them (empty for runs
+    /// recorded before results were kept)
+    #[serde(default)]
+    pub tests: Vec<TestRecord>,
+}
+
+impl RunRecord {
+    /// Whether the run passed, if that's known
+    pub fn passed(&self) -> Option<bool> {
+        if self.failed_to_run {
+            return Some(false);
+        }
+        self.summary.map(|summary| summary.is_success())
+    }
+}
+
+/// Persisted run history for a single project, keyed by test file
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    /// Where the history is stored on disk
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Recorded runs for each test file, oldest first
+    pub files: HashMap<String, Vec<RunRecord>>,
+    /// Files not run since the session's results were cleared, whose
+    /// recorded runs don't count as their last run
+    #[serde(skip)]
+    cleared: HashSet<String>,
+}
+
+impl History {
+    /// Load the history for the given project, starting empty if none exists
+    pub fn load(project_dir: &str) -> Self {
+        let path = project_data_path("history", project_dir);
+        
+        let mut history = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<History>(&content).ok())
+            .unwrap_or_default();
+        
+        history.path = path;
+        history
+    }
+    
+    /// Write the history back to disk
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        
+        let content = serde_json::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, content)
+    }
+    
+    /// Record a finished run of a test file
+    pub fn record(&mut self, test_file: &str, duration_ms: u64, summary: Option<RunSummary>, failed_to_run: bool, tests: Vec<TestRecord>) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        
+        self.cleared.remove(test_file);
+        let records = self.files.entry(test_file.to_string()).or_default();
+        records.push(RunRecord { timestamp, duration_ms, summary, failed_to_run, tests });
+        
+        // Only keep the most recent runs
+        if records.len() > MAX_RECORDS_PER_FILE {
+            records.drain(..records.len() - MAX_RECORDS_PER_FILE);
+        }
+    }
+    
+    /// The most recent run of a test file
+    pub fn last_run(&self, test_file: &str) -> Option<&RunRecord> {
+        if self.cleared.contains(test_file) {
+            return None;
+        }
+        self.files.get(test_file)?.last()
+    }
+    
+    /// Stop showing the runs recorded so far as each file's last run, keeping
+    /// them for durations and the run history
+    pub fn clear_last_runs(&mut self) {
+        self.cleared.extend(self.files.keys().cloned());
+    }
+    
+    /// Durations of the recorded runs of a test file, oldest first
+    pub fn durations(&self, test_file: &str) -> Vec<u64> {
+        self.files
+            .get(test_file)
+            .map(|records| records.iter().map(|r| r.duration_ms).collect())
+            .unwrap_or_default()
+    }
+    
+    /// Average duration of the most recent runs of a test file
+    pub fn average_duration(&self, test_file: &str) -> Option<u64> {
+        let records = self.files.get(test_file)?;
+        let recent = &records[records.len().saturating_sub(AVERAGE_WINDOW)..];
+        
+        if recent.is_empty() {
+            return None;
+        }
+        
+        Some(recent.iter().map(|r| r.duration_ms).sum::<u64>() / recent.len() as u64)
+    }
+}
+
+/// Directory where per-project data (history, etc.) is stored
+pub fn data_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .map(|dir| dir.join("surely-you-jest"))
+}
+
+/// Location of a per-project data file in the given data subdirectory
+pub fn project_data_path(subdir: &str, project_dir: &str) -> Option<PathBuf> {
+    let project = Path::new(project_dir)
+        .canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(project_dir));
+    
+    // Flatten the project path into a single file name
+    let file_name: String = project
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .collect();
+    
+    data_dir().map(|dir| dir.join(subdir).join(format!("{}.json", file_name)))
+}
\ No newline at end of file
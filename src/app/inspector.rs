@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+use crate::runner::config_finder::PatternDiagnostic;
+
+/// Everything the app resolved about how tests are discovered and run
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReport {
+    /// The Jest config file in use, if any
+    pub config_path: Option<PathBuf>,
+    /// Every Jest config file that was found
+    pub config_files: Vec<PathBuf>,
+    /// Directory patterns are resolved against
+    pub root: String,
+    /// What `<rootDir>` in patterns resolves to, when a config is in use
+    pub root_dir: Option<PathBuf>,
+    /// Directories searched for tests, when the config sets `roots`
+    pub roots: Vec<PathBuf>,
+    /// Each testMatch pattern (or testRegex) and how many files it matched on its own
+    pub patterns: Vec<PatternDiagnostic>,
+    /// Whether files are matched with the config's testRegex instead of testMatch
+    pub uses_test_regex: bool,
+    /// Paths excluded from discovery
+    pub ignore_patterns: Vec<String>,
+    /// The command that runs a test file, with a placeholder for the file
+    pub command_template: String,
+    /// Test runner the project uses
+    pub runner: &'static str,
+    /// Package manager the runner is run through, detected from lockfiles
+    pub package_manager: &'static str,
+    /// Total number of test files discovered
+    pub total_files: usize,
+}
+
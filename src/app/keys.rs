@@ -0,0 +1,92 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+
+/// A key and the modifiers held with it
+type KeyChord = (KeyModifiers, KeyCode);
+
+/// Keys remapped in the config's `[keys]` table, e.g. `"ctrl+r" = "R"` to
+/// make Ctrl+R run every test
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    remapped: HashMap<KeyChord, KeyChord>,
+}
+
+impl KeyMap {
+    /// Build the map from `pressed key = key it acts as` pairs
+    pub fn from_config(keys: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut remapped = HashMap::new();
+        for (from, to) in keys {
+            remapped.insert(parse_key(from)?, parse_key(to)?);
+        }
+        Ok(Self { remapped })
+    }
+
+    /// The key a press should be handled as
+    pub fn translate(&self, key: KeyEvent) -> KeyEvent {
+        match self.remapped.get(&normalize(key.modifiers, key.code)) {
+            Some(&(modifiers, code)) => KeyEvent { code, modifiers, ..key },
+            None => key,
+        }
+    }
+}
+
+/// Parse a key like `x`, `R`, `ctrl+r`, `alt+enter`, `space`, or `f5`
+pub fn parse_key(text: &str) -> Result<KeyChord, String> {
+    let invalid = || format!("invalid key '{}'", text);
+    let mut parts: Vec<&str> = text.split('+').collect();
+    // A trailing "+" means the plus key itself, e.g. "ctrl++"
+    if text.ends_with("++") || text == "+" {
+        parts.truncate(parts.len().saturating_sub(2));
+        parts.push("+");
+    }
+    let (key, modifier_names) = parts.split_last().ok_or_else(invalid)?;
+
+    let mut modifiers = KeyModifiers::NONE;
+    for name in modifier_names {
+        modifiers |= match name.to_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "meta" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(invalid()),
+        };
+    }
+
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match key.to_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            function => function
+                .strip_prefix('f')
+                .and_then(|number| number.parse().ok())
+                .filter(|number| (1..=12).contains(number))
+                .map(KeyCode::F)
+                .ok_or_else(invalid)?,
+        },
+    };
+
+    Ok(normalize(modifiers, code))
+}
+
+/// Compare characters by the character typed, since terminals differ in
+/// whether they also report Shift for capitals and symbols
+fn normalize(modifiers: KeyModifiers, code: KeyCode) -> KeyChord {
+    match code {
+        KeyCode::Char(_) => (modifiers - KeyModifiers::SHIFT, code),
+        _ => (modifiers, code),
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use crate::app::history::project_data_path;
+use crate::app::state::SortMode;
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+/// Default width of the test list in the results view, as a percentage
+pub const DEFAULT_RESULTS_SPLIT: u16 = 40;
+
+/// A named set of layout choices that can be switched between at runtime
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutPreset {
+    /// Test list: whether the imports pane is shown
+    pub show_imports: bool,
+    /// Test list: how files are ordered
+    pub sort: SortMode,
+    /// Test list: only show files changed since this git ref
+    pub changed_since: Option<String>,
+    /// Results view: width of the test list as a percentage
+    pub results_split: u16,
+}
+
+impl Default for LayoutPreset {
+    fn default() -> Self {
+        Self {
+            show_imports: false,
+            sort: SortMode::default(),
+            changed_since: None,
+            results_split: DEFAULT_RESULTS_SPLIT,
+        }
+    }
+}
+
+impl LayoutPreset {
+    /// One-line summary of the preset's choices
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("sort by {}", self.sort.label())];
+        if self.show_imports {
+            parts.push("imports pane".to_string());
+        }
+        if let Some(git_ref) = &self.changed_since {
+            parts.push(format!("changed since {}", git_ref));
+        }
+        parts.push(match self.results_split {
+            100 => "results details hidden".to_string(),
+            split => format!("results split {}%", split),
+        });
+        parts.join(", ")
+    }
+}
+
+/// Layout presets saved for a single project, and the one last applied
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectLayouts {
+    /// Where the presets are stored on disk
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Presets saved from within the app, by name
+    pub presets: BTreeMap<String, LayoutPreset>,
+    /// Name of the preset applied most recently
+    pub active: Option<String>,
+}
+
+impl ProjectLayouts {
+    /// Load the saved layouts for the given project, starting empty if none exist
+    pub fn load(project_dir: &str) -> Self {
+        let path = project_data_path("layouts", project_dir);
+        
+        let mut layouts = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<ProjectLayouts>(&content).ok())
+            .unwrap_or_default();
+        
+        layouts.path = path;
+        layouts
+    }
+    
+    /// Write the layouts back to disk
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        
+        let content = serde_json::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, content)
+    }
+}
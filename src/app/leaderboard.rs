@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use crate::app::{history::History, session::Session};
+
+/// Whether the leaderboard ranks individual tests or whole files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeaderboardKind {
+    /// Tests timed in this session's JSON results
+    #[default]
+    Tests,
+    /// Test files timed in the persisted run history
+    Files,
+}
+
+/// How leaderboard entries are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeaderboardSort {
+    /// By the slowest time seen
+    #[default]
+    Slowest,
+    /// By the average time
+    Average,
+    /// By the time spent across every run
+    Total,
+}
+
+impl LeaderboardSort {
+    /// The sort that follows this one when cycling
+    pub fn next(self) -> Self {
+        match self {
+            LeaderboardSort::Slowest => LeaderboardSort::Average,
+            LeaderboardSort::Average => LeaderboardSort::Total,
+            LeaderboardSort::Total => LeaderboardSort::Slowest,
+        }
+    }
+
+    /// Short label shown in the title
+    pub fn label(self) -> &'static str {
+        match self {
+            LeaderboardSort::Slowest => "slowest run",
+            LeaderboardSort::Average => "average",
+            LeaderboardSort::Total => "total time",
+        }
+    }
+}
+
+/// Timings of one test or test file
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    /// Test file, relative to the search path
+    pub file: String,
+    /// Full name of the test, for test entries
+    pub test: Option<String>,
+    /// Line the test is declared on, when the runner reported it
+    pub line: Option<usize>,
+    /// Slowest time seen, in ms
+    pub max_ms: u64,
+    /// Sum of every time seen, in ms
+    pub total_ms: u64,
+    /// How many times it was timed
+    pub runs: usize,
+}
+
+impl LeaderboardEntry {
+    /// Average time, in ms
+    pub fn average_ms(&self) -> u64 {
+        self.total_ms / self.runs.max(1) as u64
+    }
+
+    /// Add one timing
+    fn add(&mut self, duration_ms: u64) {
+        self.max_ms = self.max_ms.max(duration_ms);
+        self.total_ms += duration_ms;
+        self.runs += 1;
+    }
+}
+
+/// The slowest tests and files, for hunting down where suite time goes
+#[derive(Debug, Clone, Default)]
+pub struct Leaderboard {
+    /// Which entries are shown
+    pub kind: LeaderboardKind,
+    /// How entries are ordered
+    pub sort: LeaderboardSort,
+    /// Every test timed in the session
+    pub tests: Vec<LeaderboardEntry>,
+    /// Every listed file with run history
+    pub files: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Gather per-test timings from the session's runs and per-file timings
+    /// from the run history of the listed files
+    pub fn build(session: &Session, history: &History, test_files: &[String]) -> Self {
+        let mut tests: HashMap<(String, String), LeaderboardEntry> = HashMap::new();
+        for file in session.runs.iter().flat_map(|run| &run.files) {
+            for test in &file.tests {
+                let Some(duration) = test.duration_ms else {
+                    continue;
+                };
+                tests
+                    .entry((file.file.clone(), test.name.clone()))
+                    .or_insert_with(|| LeaderboardEntry {
+                        file: file.file.clone(),
+                        test: Some(test.name.clone()),
+                        line: test.line,
+                        max_ms: 0,
+                        total_ms: 0,
+                        runs: 0,
+                    })
+                    .add(duration.round() as u64);
+            }
+        }
+
+        let files = test_files
+            .iter()
+            .filter_map(|test_file| {
+                let runs = history.files.get(test_file).filter(|runs| !runs.is_empty())?;
+                let mut entry = LeaderboardEntry {
+                    file: test_file.clone(),
+                    test: None,
+                    line: None,
+                    max_ms: 0,
+                    total_ms: 0,
+                    runs: 0,
+                };
+                for run in runs {
+                    entry.add(run.duration_ms);
+                }
+                Some(entry)
+            })
+            .collect();
+
+        let mut leaderboard = Self {
+            tests: tests.into_values().collect(),
+            files,
+            ..Self::default()
+        };
+        leaderboard.sort_entries();
+        leaderboard
+    }
+
+    /// The entries being shown, in order
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        match self.kind {
+            LeaderboardKind::Tests => &self.tests,
+            LeaderboardKind::Files => &self.files,
+        }
+    }
+
+    /// Switch between ranking tests and files
+    pub fn toggle_kind(&mut self) {
+        self.kind = match self.kind {
+            LeaderboardKind::Tests => LeaderboardKind::Files,
+            LeaderboardKind::Files => LeaderboardKind::Tests,
+        };
+    }
+
+    /// Order by the next sort
+    pub fn cycle_sort(&mut self) {
+        self.set_sort(self.sort.next());
+    }
+
+    /// Order by the given sort
+    pub fn set_sort(&mut self, sort: LeaderboardSort) {
+        self.sort = sort;
+        self.sort_entries();
+    }
+
+    /// Order both lists by the current sort, slowest first
+    fn sort_entries(&mut self) {
+        let sort = self.sort;
+        let key = |entry: &LeaderboardEntry| match sort {
+            LeaderboardSort::Slowest => entry.max_ms,
+            LeaderboardSort::Average => entry.average_ms(),
+            LeaderboardSort::Total => entry.total_ms,
+        };
+        for entries in [&mut self.tests, &mut self.files] {
+            entries.sort_by(|a, b| {
+                key(b).cmp(&key(a))
+                    .then_with(|| a.file.cmp(&b.file))
+                    .then_with(|| a.test.cmp(&b.test))
+            });
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use tracing_subscriber::{fmt::MakeWriter, EnvFilter};
+use crate::app::history::data_dir;
+
+/// Environment variable choosing what gets logged, e.g. `SYJ_LOG=debug`
+const LOG_ENV: &str = "SYJ_LOG";
+
+/// What gets logged when `SYJ_LOG` isn't set
+const DEFAULT_FILTER: &str = "info";
+
+/// Size past which the log file is rotated
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+/// How many rotated log files are kept next to the current one
+const KEPT_LOG_FILES: usize = 3;
+
+/// How many of the latest log lines the log viewer can show
+const RECENT_LOG_LINES: usize = 500;
+
+/// The latest log lines, oldest first, shared between the logger and the
+/// log viewer
+#[derive(Debug, Clone, Default)]
+pub struct RecentLogs {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    /// File the log is written to, if it could be opened
+    pub path: Option<PathBuf>,
+}
+
+impl RecentLogs {
+    /// Keep a line, dropping the oldest once there are too many
+    fn push(&self, line: String) {
+        let Ok(mut lines) = self.lines.lock() else {
+            return;
+        };
+        if lines.len() == RECENT_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// A copy of the kept lines, oldest first
+    pub fn lines(&self) -> Vec<String> {
+        self.lines
+            .lock()
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// The log file being written, rotated once it grows past `MAX_LOG_SIZE`
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl LogFile {
+    /// Open the log file for appending, creating its directory if needed
+    fn open(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path: path.to_path_buf(), file, size })
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.size > 0 && self.size + bytes.len() as u64 > MAX_LOG_SIZE {
+            self.rotate()?;
+        }
+        self.file.write_all(bytes)?;
+        self.size += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Shift `syj.log` to `syj.log.1`, `syj.log.1` to `syj.log.2`, and so on,
+    /// dropping the oldest, then start a new `syj.log`
+    fn rotate(&mut self) -> io::Result<()> {
+        for index in (1..KEPT_LOG_FILES).rev() {
+            let _ = fs::rename(rotated_path(&self.path, index), rotated_path(&self.path, index + 1));
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// Path of the `index`th rotated log file, e.g. `syj.log.2`
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Where formatted log events are written
+#[derive(Clone)]
+struct LogSink {
+    file: Option<Arc<Mutex<LogFile>>>,
+    recent: RecentLogs,
+}
+
+impl<'a> MakeWriter<'a> for LogSink {
+    type Writer = EventWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        EventWriter { sink: self.clone(), buffer: Vec::new() }
+    }
+}
+
+/// Collects one formatted event, and hands it to the sink once it's complete
+struct EventWriter {
+    sink: LogSink,
+    buffer: Vec<u8>,
+}
+
+impl Write for EventWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for EventWriter {
+    fn drop(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        // Logging must never take the app down, so a failed write is dropped
+        if let Some(file) = &self.sink.file
+            && let Ok(mut file) = file.lock() {
+            let _ = file.write(&self.buffer);
+        }
+        for line in String::from_utf8_lossy(&self.buffer).lines() {
+            self.sink.recent.push(line.to_string());
+        }
+    }
+}
+
+/// Location of the log file, in the data directory
+fn log_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("logs").join("syj.log"))
+}
+
+/// Send log events to the log file (rotated as it grows) and keep the latest
+/// lines for the log viewer. What gets logged is set by `SYJ_LOG`, using
+/// `tracing` filter directives (e.g. `debug`, or `syj=trace`).
+///
+/// The terminal belongs to the UI, so nothing is ever logged to it; if the
+/// log file can't be opened, lines are only kept in memory.
+pub fn init() -> RecentLogs {
+    let file = log_path().and_then(|path| LogFile::open(&path).ok());
+    let recent = RecentLogs {
+        path: file.as_ref().map(|file| file.path.clone()),
+        ..RecentLogs::default()
+    };
+    let file = file.map(|file| Arc::new(Mutex::new(file)));
+    let filter = EnvFilter::try_from_env(LOG_ENV).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_writer(LogSink { file, recent: recent.clone() })
+        .try_init();
+
+    recent
+}
@@ -0,0 +1,154 @@
+use std::collections::{HashMap, VecDeque};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Most replayed keys handled between two checks for input, so a long replay
+/// doesn't freeze the UI
+pub const REPLAY_KEYS_PER_TICK: usize = 50;
+
+/// What the key after `m` or `@` selects a register for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AwaitingRegister {
+    Record,
+    Replay,
+}
+
+/// How a key press was handled by the macro recorder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroEvent {
+    /// Not a macro key - handle it normally
+    Ignored,
+    /// Consumed by the recorder, with a status message to show if any
+    Handled(Option<String>),
+}
+
+/// Records key sequences into named registers and replays them.
+///
+/// `m<reg>` starts recording into a register and `m` stops it. `@<reg>`
+/// replays a register, `@@` replays the last one, and a count prefix such as
+/// `5@a` replays it several times.
+#[derive(Debug, Default)]
+pub struct Macros {
+    /// Recorded key sequences by register
+    registers: HashMap<char, Vec<KeyEvent>>,
+    /// The register being recorded and the keys recorded so far
+    recording: Option<(char, Vec<KeyEvent>)>,
+    /// Set after `m` or `@` until the register key arrives
+    awaiting: Option<AwaitingRegister>,
+    /// Count typed before `@`
+    count: usize,
+    /// Register replayed most recently, for `@@`
+    last_replayed: Option<char>,
+    /// Keys still to be replayed
+    queue: VecDeque<KeyEvent>,
+}
+
+impl Macros {
+    /// The register currently being recorded, if any
+    pub fn recording_register(&self) -> Option<char> {
+        self.recording.as_ref().map(|(register, _)| *register)
+    }
+
+    /// Whether replayed keys are still waiting to be handled
+    pub fn is_replaying(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Drop any keys still waiting to be replayed
+    pub fn cancel_replay(&mut self) {
+        self.queue.clear();
+    }
+
+    /// The next replayed key to handle
+    pub fn next_replay_key(&mut self) -> Option<KeyEvent> {
+        self.queue.pop_front()
+    }
+
+    /// Add a key to the macro being recorded, if any
+    pub fn record(&mut self, key: KeyEvent) {
+        if let Some((_, keys)) = &mut self.recording {
+            keys.push(key);
+        }
+    }
+
+    /// Handle a key press, recording it unless it controls the recorder itself
+    pub fn on_key(&mut self, key: KeyEvent) -> MacroEvent {
+        let char_key = match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => Some(c),
+            _ => None,
+        };
+
+        // Register selection after `m` or `@`
+        if let Some(awaiting) = self.awaiting.take() {
+            let count = std::mem::take(&mut self.count).max(1);
+            let message = match (awaiting, char_key) {
+                (AwaitingRegister::Record, Some(register)) if register.is_ascii_alphanumeric() => {
+                    self.recording = Some((register, Vec::new()));
+                    format!("Recording macro @{} (m to stop)", register)
+                },
+                (AwaitingRegister::Replay, Some('@')) => match self.last_replayed {
+                    Some(register) => self.replay(register, count),
+                    None => "No macro has been replayed yet".to_string(),
+                },
+                (AwaitingRegister::Replay, Some(register)) if register.is_ascii_alphanumeric() => {
+                    self.replay(register, count)
+                },
+                _ => "Macro cancelled".to_string(),
+            };
+            return MacroEvent::Handled(Some(message));
+        }
+
+        match char_key {
+            Some('m') => {
+                self.count = 0;
+                match self.recording.take() {
+                    Some((register, keys)) => {
+                        let message = format!("Recorded {} keys into @{}", keys.len(), register);
+                        self.registers.insert(register, keys);
+                        MacroEvent::Handled(Some(message))
+                    },
+                    None => {
+                        self.awaiting = Some(AwaitingRegister::Record);
+                        MacroEvent::Handled(Some("Record macro into register...".to_string()))
+                    },
+                }
+            },
+            Some('@') => {
+                self.awaiting = Some(AwaitingRegister::Replay);
+                MacroEvent::Handled(Some("Replay macro from register...".to_string()))
+            },
+            // Counts can't start with 0
+            Some(digit @ '0'..='9') if digit != '0' || self.count > 0 => {
+                let value = digit.to_digit(10).unwrap_or(0) as usize;
+                self.count = self.count.saturating_mul(10).saturating_add(value).min(999);
+                MacroEvent::Handled(Some(format!("Count: {}", self.count)))
+            },
+            _ => {
+                self.count = 0;
+                self.record(key);
+                MacroEvent::Ignored
+            },
+        }
+    }
+
+    /// Queue a register's keys to be replayed `count` times
+    fn replay(&mut self, register: char, count: usize) -> String {
+        if self.recording_register() == Some(register) {
+            return format!("Can't replay @{} while recording it", register);
+        }
+
+        let Some(keys) = self.registers.get(&register).filter(|keys| !keys.is_empty()) else {
+            return format!("Register @{} is empty", register);
+        };
+
+        for _ in 0..count {
+            self.queue.extend(keys.iter().copied());
+        }
+        self.last_replayed = Some(register);
+
+        if count > 1 {
+            format!("Replaying @{} {} times", register, count)
+        } else {
+            format!("Replaying @{}", register)
+        }
+    }
+}
@@ -1,2 +1,31 @@
+pub mod actions;
+pub mod benchmark;
+pub mod command_line;
+pub mod config;
+pub mod coverage_history;
+pub mod discovery;
+pub mod editor;
+pub mod failure_groups;
+pub mod format;
+pub mod history;
+pub mod inspector;
+pub mod keys;
+pub mod layout;
+pub mod leaderboard;
+pub mod logging;
+pub mod macros;
+pub mod output_rows;
+pub mod prompt;
+pub mod run_compare;
+pub mod run_options;
+pub mod queue;
+pub mod run_summary;
+pub mod run_tabs;
+pub mod search;
+pub mod session;
 pub mod state;
+pub mod stats;
+pub mod test_tree;
+pub mod toast;
+pub mod watch;
 pub use state::App;
\ No newline at end of file
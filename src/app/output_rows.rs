@@ -0,0 +1,117 @@
+use std::ops::Range;
+use crate::runner::ansi;
+
+/// Fewest library frames in a row worth folding; a single one is left alone,
+/// since its summary would take the same line
+const MIN_FOLDED_FRAMES: usize = 2;
+
+/// A line of the output as it's shown, with library stack frames folded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputRow {
+    /// A 0-based line of the output, shown as is
+    Line(usize),
+    /// Consecutive library frames, shown as one summary line
+    Fold(Range<usize>),
+}
+
+impl OutputRow {
+    /// The output lines the row shows
+    fn lines(&self) -> Range<usize> {
+        match self {
+            OutputRow::Line(line) => *line..line + 1,
+            OutputRow::Fold(lines) => lines.clone(),
+        }
+    }
+}
+
+/// How a run's output is shown: optionally with runs of stack frames from
+/// libraries and Node's internals folded into a summary line, so failures
+/// read as the test's own frames, and optionally with only the failures.
+/// Rows are what's shown, lines are the output's own lines.
+#[derive(Debug, Clone, Default)]
+pub struct OutputRows {
+    /// Rows shown, in order
+    pub rows: Vec<OutputRow>,
+}
+
+/// Whether an output line is an `at ...` frame outside the project's own code
+fn is_library_frame(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("at ")
+        && (line.contains("node_modules")
+            || line.contains("node:")
+            || line.contains("(internal/")
+            || line.starts_with("at internal/")
+            || line.contains("(<anonymous>)"))
+}
+
+/// Spaces before a line's text
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Which output lines belong to failures: the header of each failed file,
+/// and each `●` block, with its message, Expected/Received, code frame, and
+/// stack trace. A block runs until the next line at most as indented as its
+/// `●` line, which is where console output, the next file's header, or the
+/// summary starts.
+fn failure_lines(lines: &[String]) -> Vec<bool> {
+    let mut kept = Vec::with_capacity(lines.len());
+    let mut block_indent = None;
+    for line in lines {
+        let text = line.trim_start();
+        if text.starts_with("● ") && !text.starts_with("● Console") {
+            block_indent = Some(indent(line));
+        } else if !text.is_empty() && block_indent.is_some_and(|block_indent| indent(line) <= block_indent) {
+            block_indent = None;
+        }
+        kept.push(block_indent.is_some() || text.starts_with("FAIL "));
+    }
+    kept
+}
+
+impl OutputRows {
+    /// Lay out the output, folding library frames and keeping only the
+    /// failures as asked
+    pub fn new(output: &str, fold_frames: bool, failures_only: bool) -> Self {
+        let lines: Vec<String> = ansi::strip(output).lines().map(str::to_string).collect();
+        let kept = if failures_only { failure_lines(&lines) } else { vec![true; lines.len()] };
+
+        let mut rows = Vec::new();
+        let mut index = 0;
+        while index < lines.len() {
+            if !kept[index] {
+                index += 1;
+                continue;
+            }
+
+            // Fold the library frames starting here, if there are enough of them
+            let frames = lines[index..]
+                .iter()
+                .zip(&kept[index..])
+                .take_while(|(line, kept)| **kept && is_library_frame(line))
+                .count();
+            if fold_frames && frames >= MIN_FOLDED_FRAMES {
+                rows.push(OutputRow::Fold(index..index + frames));
+                index += frames;
+            } else {
+                rows.push(OutputRow::Line(index));
+                index += 1;
+            }
+        }
+        Self { rows }
+    }
+
+    /// The row showing a line: a fold's summary for a folded line, or the
+    /// next row shown for a hidden one
+    pub fn row_of(&self, line: usize) -> usize {
+        self.rows
+            .partition_point(|row| row.lines().end <= line)
+            .min(self.rows.len().saturating_sub(1))
+    }
+
+    /// The first line shown by a row
+    pub fn line_of(&self, row: usize) -> usize {
+        self.rows.get(row).or(self.rows.last()).map_or(0, |row| row.lines().start)
+    }
+}
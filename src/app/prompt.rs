@@ -0,0 +1,105 @@
+use std::{fs, path::Path};
+
+/// What a text prompt's input will be used for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    /// Git ref to compare against when filtering to changed files
+    ChangedSinceRef,
+    /// Name to save the current layout under
+    SaveLayout,
+    /// Git ref or directory to benchmark the listed tests against
+    BenchmarkBaseline,
+    /// Directory to search for tests in
+    SearchRoot,
+    /// Comma-separated testMatch patterns to search with
+    TestPatterns,
+    /// Source file to find the tests that depend on
+    RelatedSource,
+    /// Extra arguments passed to the runner on the following runs
+    ExtraArgs,
+    /// Line of the file viewer's file to jump to
+    GotoLine,
+    /// Git ref whose recorded coverage the last coverage run is compared against
+    CoverageBase,
+}
+
+impl PromptKind {
+    /// Title shown on the prompt popup
+    pub fn title(&self) -> &'static str {
+        match self {
+            PromptKind::ChangedSinceRef => "Show tests changed since git ref",
+            PromptKind::SaveLayout => "Save current layout as",
+            PromptKind::BenchmarkBaseline => "Benchmark listed tests against git ref or directory",
+            PromptKind::SearchRoot => "Search for tests in directory",
+            PromptKind::TestPatterns => "testMatch patterns (comma separated)",
+            PromptKind::RelatedSource => "Show tests related to source file (Tab completes)",
+            PromptKind::ExtraArgs => "Extra arguments for the following runs",
+            PromptKind::GotoLine => "Go to line",
+            PromptKind::CoverageBase => "Compare coverage against git ref",
+        }
+    }
+}
+
+/// A single-line text input shown as a popup over the current view
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    /// What the input is for
+    pub kind: PromptKind,
+    /// Current input text
+    pub input: String,
+}
+
+impl Prompt {
+    /// Create a new prompt with some initial text
+    pub fn new(kind: PromptKind, initial: impl Into<String>) -> Self {
+        Self {
+            kind,
+            input: initial.into(),
+        }
+    }
+    
+    /// Complete the input as a path relative to `root`, as far as every matching
+    /// entry agrees. Directories get a trailing slash so completion can continue into them.
+    pub fn complete_path(&mut self, root: &Path) {
+        let (dir, prefix) = match self.input.rfind('/') {
+            Some(index) => self.input.split_at(index + 1),
+            None => ("", self.input.as_str()),
+        };
+        let Ok(entries) = fs::read_dir(root.join(dir)) else {
+            return;
+        };
+        
+        let mut matches: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let mut name = entry.file_name().to_string_lossy().to_string();
+                let hidden = name.starts_with('.') && !prefix.starts_with('.');
+                if !name.starts_with(prefix) || hidden || name == "node_modules" {
+                    return None;
+                }
+                if entry.path().is_dir() {
+                    name.push('/');
+                }
+                Some(name)
+            })
+            .collect();
+        matches.sort();
+        
+        let Some(first) = matches.first() else {
+            return;
+        };
+        let common = matches.iter().fold(first.as_str(), |common, name| common_prefix(common, name));
+        self.input = format!("{}{}", dir, common);
+    }
+}
+
+/// The longest start `a` and `b` have in common
+fn common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let len = a
+        .char_indices()
+        .zip(b.chars())
+        .take_while(|((_, x), y)| x == y)
+        .last()
+        .map_or(0, |((index, c), _)| index + c.len_utf8());
+    &a[..len]
+}
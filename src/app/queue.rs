@@ -0,0 +1,194 @@
+use serde::Deserialize;
+use std::{
+    path::PathBuf,
+    sync::mpsc,
+    time::Instant,
+};
+use crate::runner::{
+    command::JestCommand,
+    json_results::{self, FileResult, JsonResults},
+    output_parser::{self, RunSummary},
+    test_runner::{self, TestResult},
+};
+
+/// How queued runs are executed
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct QueueOptions {
+    /// How many queued files run at the same time
+    pub concurrency: usize,
+}
+
+impl Default for QueueOptions {
+    fn default() -> Self {
+        Self { concurrency: 1 }
+    }
+}
+
+impl QueueOptions {
+    /// Check the options make sense
+    pub fn validate(&self) -> Result<(), String> {
+        if self.concurrency == 0 {
+            return Err("queue concurrency must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A test file waiting in, running in, or finished by the queue
+#[derive(Debug)]
+pub struct QueueEntry {
+    /// Test file, relative to the project directory
+    pub file: String,
+    /// The command that runs the file
+    pub command: JestCommand,
+    /// Where Jest writes this run's JSON results
+    results_path: PathBuf,
+    /// When the run started
+    pub started_at: Option<Instant>,
+    /// How long the run took in ms, once it has finished
+    pub duration_ms: Option<u64>,
+    /// Output printed by the run so far
+    pub output: String,
+    /// Structured results, if Jest wrote them
+    pub results: Option<JsonResults>,
+    /// Why Jest couldn't be run, if it couldn't
+    pub error: Option<String>,
+    receiver: Option<mpsc::Receiver<TestResult>>,
+}
+
+impl QueueEntry {
+    /// Whether the run is still waiting to start
+    pub fn is_pending(&self) -> bool {
+        self.started_at.is_none()
+    }
+
+    /// Whether the run has finished
+    pub fn is_finished(&self) -> bool {
+        self.duration_ms.is_some()
+    }
+
+    /// Test counts of a finished run
+    pub fn summary(&self) -> Option<RunSummary> {
+        self.results
+            .as_ref()
+            .map(JsonResults::summary)
+            .or_else(|| output_parser::parse_summary(&self.output))
+    }
+
+    /// Whether Jest couldn't be run, or the file failed without running its tests
+    pub fn failed_to_run(&self) -> bool {
+        // A file that couldn't run at all has no failing tests, only a message
+        self.error.is_some() || self.results
+            .as_ref()
+            .is_some_and(|results| results.test_results.iter().any(FileResult::failed_to_run))
+    }
+
+    /// Whether a finished run passed
+    pub fn passed(&self) -> Option<bool> {
+        if !self.is_finished() {
+            return None;
+        }
+        Some(!self.failed_to_run() && self.summary().is_some_and(|summary| summary.is_success()))
+    }
+}
+
+/// Test files run one after another, or a few at a time
+#[derive(Debug)]
+pub struct RunQueue {
+    /// Files in the order they were queued
+    pub entries: Vec<QueueEntry>,
+    /// How many files run at the same time
+    pub concurrency: usize,
+}
+
+impl RunQueue {
+    /// Create an empty queue
+    pub fn new(options: &QueueOptions) -> Self {
+        Self {
+            entries: Vec::new(),
+            concurrency: options.concurrency.max(1),
+        }
+    }
+
+    /// Add a file to the end of the queue.
+    ///
+    /// The command should have Jest write JSON results to `results_path`.
+    pub fn push(&mut self, file: &str, command: JestCommand, results_path: PathBuf) {
+        self.entries.push(QueueEntry {
+            file: file.to_string(),
+            command,
+            results_path,
+            started_at: None,
+            duration_ms: None,
+            output: String::new(),
+            results: None,
+            error: None,
+            receiver: None,
+        });
+    }
+
+    /// Collect output from running files and start waiting ones when there's room,
+    /// returning the indices of the files that finished
+    pub fn poll(&mut self) -> Vec<usize> {
+        let mut finished = Vec::new();
+
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            let Some(receiver) = &entry.receiver else {
+                continue;
+            };
+
+            let mut done = false;
+            loop {
+                match receiver.try_recv() {
+                    Ok(TestResult::Running) => {},
+                    Ok(TestResult::OutputChunk(chunk)) => entry.output.push_str(&chunk),
+                    Ok(TestResult::Completed(result)) => {
+                        entry.error = result.err().map(|e| e.to_string());
+                        entry.results = json_results::take_results(&entry.results_path).ok();
+                        done = true;
+                        break;
+                    },
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        entry.error.get_or_insert_with(|| "run stopped unexpectedly".to_string());
+                        done = true;
+                        break;
+                    }
+                }
+            }
+
+            if done {
+                let duration = entry.started_at.map_or(0, |started_at| started_at.elapsed().as_millis() as u64);
+                entry.duration_ms = Some(duration);
+                entry.receiver = None;
+                finished.push(index);
+            }
+        }
+
+        // Start waiting files in order until the concurrency limit is reached
+        let room = self.concurrency.saturating_sub(self.running());
+        for entry in self.entries.iter_mut().filter(|entry| entry.is_pending()).take(room) {
+            entry.started_at = Some(Instant::now());
+            entry.receiver = Some(test_runner::start_async_test(entry.command.clone()));
+        }
+
+        finished
+    }
+
+    /// Number of files currently running
+    pub fn running(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.receiver.is_some()).count()
+    }
+
+    /// Number of finished files out of the total
+    pub fn progress(&self) -> (usize, usize) {
+        let done = self.entries.iter().filter(|entry| entry.is_finished()).count();
+        (done, self.entries.len())
+    }
+
+    /// Whether every queued file has finished
+    pub fn is_finished(&self) -> bool {
+        self.entries.iter().all(QueueEntry::is_finished)
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use crate::app::history::{RunRecord, TestRecord};
+use crate::runner::json_results::AssertionStatus;
+
+/// Factor a test's duration has to change by, either way, to be reported
+const SIGNIFICANT_RATIO: f64 = 1.5;
+
+/// Fewest ms a test's duration has to change by to be reported, so the
+/// jitter of fast tests isn't
+const MIN_DURATION_CHANGE_MS: u64 = 20;
+
+/// How a test's result changed between two runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TestChange {
+    /// Failed in the later run but not in the earlier one
+    NewlyFailing,
+    /// Passed in the later run after failing in the earlier one
+    NewlyPassing,
+    /// Took significantly longer
+    Slower,
+    /// Took significantly less time
+    Faster,
+    /// Only in the later run
+    Added,
+    /// Only in the earlier run
+    Removed,
+}
+
+impl TestChange {
+    /// Short description shown next to the test
+    pub fn label(self) -> &'static str {
+        match self {
+            TestChange::NewlyFailing => "newly failing",
+            TestChange::NewlyPassing => "newly passing",
+            TestChange::Slower => "slower",
+            TestChange::Faster => "faster",
+            TestChange::Added => "added",
+            TestChange::Removed => "removed",
+        }
+    }
+}
+
+/// A test that changed between two runs
+#[derive(Debug, Clone)]
+pub struct ComparedTest {
+    pub change: TestChange,
+    /// The test in the earlier run
+    pub before: Option<TestRecord>,
+    /// The test in the later run
+    pub after: Option<TestRecord>,
+}
+
+impl ComparedTest {
+    /// The test's full name
+    pub fn name(&self) -> &str {
+        self.after.as_ref().or(self.before.as_ref()).map_or("", |test| test.name.as_str())
+    }
+}
+
+/// What changed between two recorded runs of a test file
+#[derive(Debug, Clone)]
+pub struct RunComparison {
+    /// Tests that changed, newly failing first
+    pub changes: Vec<ComparedTest>,
+    /// Number of tests in both runs that didn't change
+    pub unchanged: usize,
+    /// Whether either run was recorded without per-test results
+    pub missing_results: bool,
+}
+
+/// Whether a duration changed enough to be worth reporting
+fn significant(before: u64, after: u64) -> bool {
+    let (shorter, longer) = (before.min(after), before.max(after));
+    longer - shorter >= MIN_DURATION_CHANGE_MS && longer as f64 >= shorter as f64 * SIGNIFICANT_RATIO
+}
+
+impl RunComparison {
+    /// Compare an earlier run of a file with a later one
+    pub fn compare(before: &RunRecord, after: &RunRecord) -> Self {
+        let earlier: HashMap<&str, &TestRecord> = before.tests.iter().map(|test| (test.name.as_str(), test)).collect();
+        let later: HashMap<&str, &TestRecord> = after.tests.iter().map(|test| (test.name.as_str(), test)).collect();
+
+        let mut changes = Vec::new();
+        let mut unchanged = 0;
+        let mut add = |change, before: Option<&TestRecord>, after: Option<&TestRecord>| {
+            changes.push(ComparedTest { change, before: before.cloned(), after: after.cloned() });
+        };
+
+        for test in &after.tests {
+            let Some(&earlier_test) = earlier.get(test.name.as_str()) else {
+                add(TestChange::Added, None, Some(test));
+                continue;
+            };
+            let failed = |test: &TestRecord| test.status == AssertionStatus::Failed;
+            let change = match (failed(earlier_test), failed(test)) {
+                (false, true) => Some(TestChange::NewlyFailing),
+                (true, false) if test.status == AssertionStatus::Passed => Some(TestChange::NewlyPassing),
+                _ => match (earlier_test.duration_ms, test.duration_ms) {
+                    (Some(was), Some(is)) if significant(was, is) => {
+                        Some(if is > was { TestChange::Slower } else { TestChange::Faster })
+                    },
+                    _ => None,
+                },
+            };
+            match change {
+                Some(change) => add(change, Some(earlier_test), Some(test)),
+                None => unchanged += 1,
+            }
+        }
+        for test in before.tests.iter().filter(|test| !later.contains_key(test.name.as_str())) {
+            add(TestChange::Removed, Some(test), None);
+        }
+
+        // Stable, so tests of the same kind stay in the order they're declared
+        changes.sort_by_key(|test| test.change);
+        Self {
+            changes,
+            unchanged,
+            missing_results: before.tests.is_empty() || after.tests.is_empty(),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+/// A Jest flag that can be switched on in the run options popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunOption {
+    /// The flag's name, without the leading dashes
+    pub flag: &'static str,
+    /// What the flag does, shown next to it
+    pub description: &'static str,
+}
+
+/// The flag that turns on coverage mode rather than being passed as is,
+/// so runs also write the reports the coverage view reads
+pub const COVERAGE_FLAG: &str = "coverage";
+
+/// Every flag in the run options popup, in the order shown
+pub const RUN_OPTIONS: &[RunOption] = &[
+    RunOption { flag: COVERAGE_FLAG, description: "Collect coverage (same as %)" },
+    RunOption { flag: "verbose", description: "Report every individual test" },
+    RunOption { flag: "runInBand", description: "Run tests serially in the current process" },
+    RunOption { flag: "ci", description: "Fail instead of writing new snapshots" },
+    RunOption { flag: "silent", description: "Hide console output from tests" },
+    RunOption { flag: "detectOpenHandles", description: "Report handles keeping Jest from exiting" },
+    RunOption { flag: "updateSnapshot", description: "Rewrite failing snapshots" },
+];
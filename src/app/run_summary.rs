@@ -0,0 +1,72 @@
+use serde::Serialize;
+use std::path::Path;
+use crate::runner::{json_results::JsonResults, output_parser::RunSummary};
+
+/// How one test file fared in a run of many files
+#[derive(Debug, Clone, Serialize)]
+pub struct FileOutcome {
+    /// Test file path relative to the search path
+    pub file: String,
+    /// Absolute path of the file as reported by Jest
+    pub path: String,
+    /// Test counts for the file
+    pub summary: RunSummary,
+    /// How long Jest spent running the file in ms
+    pub duration_ms: u64,
+    /// Why the file couldn't run at all, if it couldn't
+    pub error: Option<String>,
+}
+
+impl FileOutcome {
+    /// Whether every test in the file passed
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.summary.is_success()
+    }
+}
+
+/// Aggregated results of running every listed test file
+#[derive(Debug, Clone)]
+pub struct RunAllSummary {
+    /// Per-file results, failed files first
+    pub files: Vec<FileOutcome>,
+    /// Test counts across every file
+    pub totals: RunSummary,
+    /// Wall clock time of the whole run in ms
+    pub duration_ms: u64,
+    /// The run's full results, for drilling into a file
+    pub results: JsonResults,
+}
+
+impl RunAllSummary {
+    /// Summarize Jest's JSON results for a run of many files
+    pub fn from_results(results: &JsonResults, search_path: &Path, duration_ms: u64) -> Self {
+        let root = search_path.canonicalize().unwrap_or_else(|_| search_path.to_path_buf());
+
+        let mut files: Vec<FileOutcome> = results.test_results
+            .iter()
+            .map(|file| FileOutcome {
+                file: Path::new(&file.name)
+                    .strip_prefix(&root)
+                    .map(|relative| relative.display().to_string())
+                    .unwrap_or_else(|_| file.name.clone()),
+                path: file.name.clone(),
+                summary: file.summary(),
+                duration_ms: file.duration_ms(),
+                error: file.failed_to_run().then(|| file.message.clone()),
+            })
+            .collect();
+        files.sort_by(|a, b| a.passed().cmp(&b.passed()).then_with(|| a.file.cmp(&b.file)));
+
+        Self {
+            files,
+            totals: results.summary(),
+            duration_ms,
+            results: results.clone(),
+        }
+    }
+
+    /// Number of files with failing tests or that failed to run
+    pub fn files_failed(&self) -> usize {
+        self.files.iter().filter(|file| !file.passed()).count()
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Instant,
+};
+use crate::app::state::TestInfo;
+use crate::runner::{
+    command::JestCommand,
+    json_results::JsonResults,
+    output_parser,
+    stack_trace::StackFrame,
+    test_runner::TestResult,
+};
+use crate::widgets::run_tabs::RunTabState;
+
+/// Most tabs kept open at once; finished tabs are closed to make room
+pub const MAX_RUN_TABS: usize = 9;
+
+/// A run kept in a tab while another tab is shown.
+///
+/// The shown tab's run lives in the `App` fields of the same names, and is
+/// swapped with the tab's when switching tabs.
+#[derive(Debug, Default)]
+pub struct RunTab {
+    pub last_command: Option<JestCommand>,
+    pub test_run_output: String,
+    pub terminal_scroll: usize,
+    pub follow_output: bool,
+    pub test_loading: bool,
+    pub test_receiver: Option<mpsc::Receiver<TestResult>>,
+    pub individual_tests: Vec<TestInfo>,
+    pub selected_test_index: usize,
+    pub collapsed_describes: HashSet<Vec<String>>,
+    pub auto_show_test_results: bool,
+    pub running_individual_test: bool,
+    pub run_started_at: Option<Instant>,
+    pub running_test_file: Option<String>,
+    pub running_action: Option<String>,
+    pub last_results: Option<JsonResults>,
+    pub running_all: Option<usize>,
+    pub output_frames: Vec<StackFrame>,
+    pub selected_frame: Option<usize>,
+    pub results_path: PathBuf,
+}
+
+impl RunTab {
+    /// An empty tab whose runs write their JSON results to `results_path`
+    pub fn new(results_path: PathBuf) -> Self {
+        Self {
+            follow_output: true,
+            results_path,
+            ..Self::default()
+        }
+    }
+
+    /// The tab's label and state, for the tab bar
+    pub fn summary(&self) -> (String, RunTabState) {
+        (
+            tab_label(self.running_action.as_ref(), self.running_test_file.as_ref(), self.running_all),
+            tab_state(self.test_loading, self.last_results.as_ref(), &self.test_run_output),
+        )
+    }
+}
+
+/// What a run is running, for labels
+pub fn run_label(action: Option<&String>, test_file: Option<&String>, running_all: Option<usize>) -> Option<String> {
+    action
+        .cloned()
+        .or_else(|| test_file.cloned())
+        .or_else(|| running_all.map(|count| format!("all {} test files", count)))
+}
+
+/// A short label for a run's tab: just the file name when a file was run
+pub fn tab_label(action: Option<&String>, test_file: Option<&String>, running_all: Option<usize>) -> String {
+    let label = run_label(action, test_file, running_all).unwrap_or_else(|| "(no run)".to_string());
+    match (action, Path::new(&label).file_name()) {
+        (None, Some(name)) => name.to_string_lossy().into_owned(),
+        _ => label,
+    }
+}
+
+/// How a run in a tab is going, or how it went
+pub fn tab_state(loading: bool, results: Option<&JsonResults>, output: &str) -> RunTabState {
+    if loading {
+        return RunTabState::Running;
+    }
+    let summary = results
+        .map(JsonResults::summary)
+        .or_else(|| output_parser::parse_summary(output));
+    match summary {
+        Some(summary) if summary.is_success() => RunTabState::Passed,
+        Some(_) => RunTabState::Failed,
+        None => RunTabState::Finished,
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+/// Score for each matched character
+const SCORE_MATCH: i64 = 16;
+/// Bonus for a match at the start of a word (after `/`, `.`, `-`, `_`, or a space)
+const BONUS_BOUNDARY: i64 = 8;
+/// Bonus for a match at a camelCase hump
+const BONUS_CAMEL: i64 = 7;
+/// Bonus for a match right after the previous one
+const BONUS_CONSECUTIVE: i64 = 4;
+/// Bonus for a match in the file name rather than its directories
+const BONUS_FILE_NAME: i64 = 2;
+/// Penalty for starting a gap between matches
+const PENALTY_GAP_START: i64 = 3;
+/// Penalty for each further character in a gap
+const PENALTY_GAP_EXTENSION: i64 = 1;
+
+/// How well a query matched a candidate
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// Higher is better
+    pub score: i64,
+    /// Indices of the matched characters in the candidate
+    pub positions: Vec<usize>,
+}
+
+/// Bonus for matching the character at `index`, based on what precedes it
+fn position_bonus(chars: &[char], index: usize) -> i64 {
+    let Some(&previous) = index.checked_sub(1).and_then(|i| chars.get(i)) else {
+        return BONUS_BOUNDARY;
+    };
+
+    if matches!(previous, '/' | '.' | '-' | '_' | ' ') {
+        BONUS_BOUNDARY
+    } else if previous.is_lowercase() && chars[index].is_uppercase() {
+        BONUS_CAMEL
+    } else {
+        0
+    }
+}
+
+/// Fuzzy-match a query against a candidate, fzf style.
+///
+/// Every query character must appear in order. Matching is case-insensitive
+/// unless the query contains an uppercase letter. Among the places the query
+/// could match, the shortest span ending at the first complete match is
+/// scored, favoring word starts, consecutive runs, and the file name.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let case_sensitive = query.chars().any(char::is_uppercase);
+    let normalize = |c: char| if case_sensitive { c } else { c.to_ascii_lowercase() };
+
+    let query: Vec<char> = query.chars().map(normalize).collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    let folded: Vec<char> = chars.iter().copied().map(normalize).collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+
+    // Forward pass: where the first complete match ends
+    let mut query_index = 0;
+    let mut end = None;
+    for (index, &c) in folded.iter().enumerate() {
+        if c == query[query_index] {
+            query_index += 1;
+            if query_index == query.len() {
+                end = Some(index);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    // Backward pass: the latest start that still matches, giving the tightest span
+    let mut positions = Vec::with_capacity(query.len());
+    let mut query_index = query.len();
+    for index in (0..=end).rev() {
+        if folded[index] == query[query_index - 1] {
+            positions.push(index);
+            query_index -= 1;
+            if query_index == 0 {
+                break;
+            }
+        }
+    }
+    positions.reverse();
+
+    let file_name_start = chars.iter().rposition(|&c| c == '/').map_or(0, |i| i + 1);
+    let mut score = 0;
+    for (i, &position) in positions.iter().enumerate() {
+        score += SCORE_MATCH + position_bonus(&chars, position);
+        if position >= file_name_start {
+            score += BONUS_FILE_NAME;
+        }
+
+        if let Some(&previous) = i.checked_sub(1).and_then(|i| positions.get(i)) {
+            match position - previous - 1 {
+                0 => score += BONUS_CONSECUTIVE,
+                gap => score -= PENALTY_GAP_START + PENALTY_GAP_EXTENSION * (gap as i64 - 1),
+            }
+        }
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Live fuzzy search narrowing the test list
+#[derive(Debug, Clone)]
+pub struct Search {
+    /// What has been typed
+    pub query: String,
+    /// Whether keys are going into the query
+    pub editing: bool,
+    /// The list being searched, restored when the search is cleared
+    pub base: Vec<String>,
+    /// Matched character positions of each result, for highlighting
+    pub matches: HashMap<String, Vec<usize>>,
+}
+
+impl Search {
+    /// Start searching a list
+    pub fn new(base: Vec<String>) -> Self {
+        Self {
+            query: String::new(),
+            editing: true,
+            base,
+            matches: HashMap::new(),
+        }
+    }
+
+    /// The files in the list matching the query, best match first
+    pub fn results(&mut self) -> Vec<String> {
+        self.matches.clear();
+        if self.query.is_empty() {
+            return self.base.clone();
+        }
+
+        let mut scored: Vec<(i64, &String)> = Vec::new();
+        for file in &self.base {
+            if let Some(found) = fuzzy_match(&self.query, file) {
+                scored.push((found.score, file));
+                self.matches.insert(file.clone(), found.positions);
+            }
+        }
+
+        // Stable, so equal scores keep the list's order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, file)| file.clone()).collect()
+    }
+}
+
+/// Where a file search query was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextMatch {
+    /// 1-based line the match is on
+    pub line: usize,
+    /// Byte offset of the match within the line
+    pub start: usize,
+    /// Byte offset just past the match
+    pub end: usize,
+}
+
+/// Plain-text search through the file viewer's content, moving between
+/// matches with n/N
+#[derive(Debug, Clone, Default)]
+pub struct FileSearch {
+    /// What has been typed
+    pub query: String,
+    /// Whether keys are going into the query
+    pub editing: bool,
+    /// Every match in the file, in order
+    pub matches: Vec<TextMatch>,
+    /// Index of the match moved to last
+    pub current: usize,
+}
+
+impl FileSearch {
+    /// Start typing a new search
+    pub fn new() -> Self {
+        Self { editing: true, ..Self::default() }
+    }
+
+    /// Find every match of the query in the content. Matching is
+    /// case-insensitive unless the query contains an uppercase letter, like
+    /// the list's search.
+    pub fn find(&mut self, content: &str) {
+        self.matches.clear();
+        self.current = 0;
+        if self.query.is_empty() {
+            return;
+        }
+
+        // ASCII folding keeps byte offsets the same in the folded text
+        let case_sensitive = self.query.chars().any(char::is_uppercase);
+        let fold = |text: &str| if case_sensitive { text.to_string() } else { text.to_ascii_lowercase() };
+        let query = fold(&self.query);
+        for (index, line) in content.lines().enumerate() {
+            self.matches.extend(fold(line).match_indices(&query).map(|(start, found)| TextMatch {
+                line: index + 1,
+                start,
+                end: start + found.len(),
+            }));
+        }
+    }
+
+    /// The match moved to last, if there are any
+    pub fn current_match(&self) -> Option<TextMatch> {
+        self.matches.get(self.current).copied()
+    }
+
+    /// Move to the first match on or after the given line, wrapping around
+    /// to the top
+    pub fn move_to_line(&mut self, line: usize) -> Option<TextMatch> {
+        self.current = self.matches.iter().position(|found| found.line >= line).unwrap_or(0);
+        self.current_match()
+    }
+
+    /// Move to the next match, or the previous one going backwards, wrapping
+    /// around at either end
+    pub fn step(&mut self, forward: bool) -> Option<TextMatch> {
+        let count = self.matches.len();
+        if count == 0 {
+            return None;
+        }
+        self.current = if forward { (self.current + 1) % count } else { (self.current + count - 1) % count };
+        self.current_match()
+    }
+}
@@ -0,0 +1,331 @@
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use crate::runner::{
+    ansi,
+    command::JestCommand,
+    json_results::{AssertionStatus, FileResult, JsonResults},
+    output_parser::{self, RunSummary},
+};
+
+/// Where session exports are written
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// File to write, relative to the project directory. `{timestamp}` is
+    /// replaced with the time of the export.
+    pub path: String,
+    /// File a run's output is saved to, like `path`
+    pub output: String,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            path: "syj-session-{timestamp}.json".to_string(),
+            output: "syj-output-{timestamp}.log".to_string(),
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Where an export made now should be written
+    pub fn resolve(&self, project_dir: &str) -> PathBuf {
+        timestamped(&self.path, project_dir)
+    }
+
+    /// Where output saved now should be written
+    pub fn resolve_output(&self, project_dir: &str) -> PathBuf {
+        timestamped(&self.output, project_dir)
+    }
+}
+
+/// What a session export is written as
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A JSON document with every run's command, output, and results
+    #[default]
+    Json,
+    /// JUnit XML, with a test suite for each file run, for CI tools
+    Junit,
+}
+
+/// A path relative to the project directory, with `{timestamp}` replaced by the time now
+fn timestamped(path: &str, project_dir: &str) -> PathBuf {
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    Path::new(project_dir).join(path.replace("{timestamp}", &timestamp))
+}
+
+/// One test's result in an exported run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTest {
+    /// Describe names and the test name joined with spaces
+    pub name: String,
+    pub status: AssertionStatus,
+    pub duration_ms: Option<f64>,
+    pub line: Option<usize>,
+    pub failure_messages: Vec<String>,
+}
+
+/// One test file's results in an exported run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionFile {
+    /// Test file path relative to the project directory
+    pub file: String,
+    pub summary: RunSummary,
+    pub duration_ms: u64,
+    /// Why the file couldn't run at all, if it couldn't
+    pub error: Option<String>,
+    pub tests: Vec<SessionTest>,
+}
+
+impl SessionFile {
+    fn from_result(result: &FileResult, root: &Path) -> Self {
+        Self {
+            file: Path::new(&result.name)
+                .strip_prefix(root)
+                .map(|relative| relative.display().to_string())
+                .unwrap_or_else(|_| result.name.clone()),
+            summary: result.summary(),
+            duration_ms: result.duration_ms(),
+            error: result.failed_to_run().then(|| result.message.clone()),
+            tests: result.assertion_results
+                .iter()
+                .map(|test| SessionTest {
+                    name: test.full_name.clone(),
+                    status: test.status,
+                    duration_ms: test.duration,
+                    line: test.location.map(|location| location.line),
+                    failure_messages: test.failure_messages.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A finished run, as recorded for export
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRun {
+    /// What was run, e.g. a file name
+    pub label: String,
+    /// The command line that was run
+    pub command: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: u64,
+    /// Test counts across the run, if they're known
+    pub summary: Option<RunSummary>,
+    pub files: Vec<SessionFile>,
+    /// Everything the runner printed
+    pub output: String,
+    /// Why the runner couldn't be run, if it couldn't
+    pub error: Option<String>,
+}
+
+impl SessionRun {
+    /// Record a run that was started at `started_at` and has just finished
+    pub fn finished(
+        label: String,
+        command: &JestCommand,
+        started_at: Instant,
+        output: &str,
+        results: Option<&JsonResults>,
+        error: Option<String>,
+    ) -> Self {
+        let finished_at = Local::now();
+        let elapsed = started_at.elapsed();
+        let started_at = finished_at - TimeDelta::from_std(elapsed).unwrap_or_default();
+        let root = command.cwd.canonicalize().unwrap_or_else(|_| command.cwd.clone());
+
+        Self {
+            label,
+            command: command.display(),
+            started_at: started_at.to_rfc3339(),
+            finished_at: finished_at.to_rfc3339(),
+            duration_ms: elapsed.as_millis() as u64,
+            summary: results
+                .map(JsonResults::summary)
+                .or_else(|| output_parser::parse_summary(output)),
+            files: results
+                .map(|results| results.test_results.iter().map(|file| SessionFile::from_result(file, &root)).collect())
+                .unwrap_or_default(),
+            output: ansi::strip(output).into_owned(),
+            error,
+        }
+    }
+}
+
+/// Totals across the runs finished in a session
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SessionStats {
+    /// Test files run, counting a file each time it ran
+    pub files: usize,
+    /// Test counts summed over the runs that reported them
+    pub tests: RunSummary,
+    /// Time spent running, in ms
+    pub duration_ms: u64,
+}
+
+/// Every run finished since the app started
+#[derive(Debug)]
+pub struct Session {
+    /// When the app started
+    pub started_at: DateTime<Local>,
+    /// Finished runs, oldest first
+    pub runs: Vec<SessionRun>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            started_at: Local::now(),
+            runs: Vec::new(),
+        }
+    }
+}
+
+/// Escape text for an XML attribute or element, dropping the control
+/// characters XML can't hold
+fn xml_escape(text: &str) -> String {
+    text.chars()
+        .filter(|&c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                c => escaped.push(c),
+            }
+            escaped
+        })
+}
+
+/// A duration in ms as JUnit's seconds
+fn junit_seconds(ms: f64) -> String {
+    format!("{:.3}", ms / 1000.0)
+}
+
+/// Create the directory `path` is in, then write it
+fn write_file(path: &Path, content: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)
+}
+
+/// The exported JSON document
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionExport<'a> {
+    version: &'static str,
+    project: &'a str,
+    runner: &'static str,
+    started_at: String,
+    exported_at: String,
+    runs: &'a [SessionRun],
+}
+
+impl Session {
+    /// Totals across the finished runs
+    pub fn stats(&self) -> SessionStats {
+        let mut stats = SessionStats::default();
+        for run in &self.runs {
+            stats.files += run.files.len();
+            stats.duration_ms += run.duration_ms;
+            if let Some(summary) = run.summary {
+                stats.tests.passed += summary.passed;
+                stats.tests.failed += summary.failed;
+                stats.tests.skipped += summary.skipped;
+                stats.tests.todo += summary.todo;
+                stats.tests.total += summary.total;
+            }
+        }
+        stats
+    }
+
+    /// Write the session to `path` as a JSON document
+    pub fn export(&self, path: &Path, project_dir: &str, runner: &'static str) -> io::Result<()> {
+        let document = SessionExport {
+            version: env!("CARGO_PKG_VERSION"),
+            project: project_dir,
+            runner,
+            started_at: self.started_at.to_rfc3339(),
+            exported_at: Local::now().to_rfc3339(),
+            runs: &self.runs,
+        };
+
+        let content = serde_json::to_string_pretty(&document).map_err(io::Error::other)?;
+        write_file(path, &content)
+    }
+
+    /// Write the session to `path` as JUnit XML, with a test suite for each
+    /// file each run ran. Files that failed to run get a test case with the
+    /// error, since JUnit has nowhere else to put it.
+    pub fn export_junit(&self, path: &Path) -> io::Result<()> {
+        let files: Vec<&SessionFile> = self.runs.iter().flat_map(|run| &run.files).collect();
+        let count = |file: &SessionFile, status: AssertionStatus| file.tests.iter().filter(|test| test.status == status).count();
+        let tests: usize = files.iter().map(|file| file.tests.len() + usize::from(file.error.is_some())).sum();
+        let failures: usize = files.iter().map(|file| count(file, AssertionStatus::Failed)).sum();
+        let errors = files.iter().filter(|file| file.error.is_some()).count();
+        let duration_ms: u64 = files.iter().map(|file| file.duration_ms).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"syj\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">\n",
+            tests, failures, errors, junit_seconds(duration_ms as f64)
+        ));
+        for file in files {
+            let name = xml_escape(&file.file);
+            let skipped = file.tests
+                .iter()
+                .filter(|test| !matches!(test.status, AssertionStatus::Passed | AssertionStatus::Failed | AssertionStatus::Focused))
+                .count();
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+                name,
+                file.tests.len() + usize::from(file.error.is_some()),
+                count(file, AssertionStatus::Failed),
+                usize::from(file.error.is_some()),
+                skipped,
+                junit_seconds(file.duration_ms as f64)
+            ));
+            if let Some(error) = &file.error {
+                xml.push_str(&format!(
+                    "    <testcase name=\"Test suite failed to run\" classname=\"{}\" time=\"0.000\">\n      <error message=\"Test suite failed to run\">{}</error>\n    </testcase>\n",
+                    name, xml_escape(error)
+                ));
+            }
+            for test in &file.tests {
+                xml.push_str(&format!(
+                    "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+                    xml_escape(&test.name), name, junit_seconds(test.duration_ms.unwrap_or(0.0))
+                ));
+                match test.status {
+                    AssertionStatus::Passed | AssertionStatus::Focused => xml.push_str("/>\n"),
+                    AssertionStatus::Failed => {
+                        let message = ansi::strip(&test.failure_messages.join("\n")).into_owned();
+                        let first_line = message.lines().find(|line| !line.trim().is_empty()).unwrap_or("").trim();
+                        xml.push_str(&format!(
+                            ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                            xml_escape(first_line), xml_escape(&message)
+                        ));
+                    },
+                    _ => xml.push_str(">\n      <skipped/>\n    </testcase>\n"),
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+
+        write_file(path, &xml)
+    }
+}
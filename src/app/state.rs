@@ -1,15 +1,58 @@
 use color_eyre::Result;
+use serde::{Deserialize, Serialize};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
     DefaultTerminal, Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     widgets::{Block, Borders},
 };
-use std::{io, path::PathBuf, process::{Command, Stdio}, sync::mpsc};
-use crate::jest::test_runner::{self, TestResult};
+use std::{collections::{HashMap, HashSet}, io::{self, Write}, mem, path::{Path, PathBuf}, process::{Command, Stdio}, sync::mpsc};
+use tracing::warn;
+use crate::app::benchmark::Benchmark;
+use crate::app::command_line::{self, CommandHistory, CommandLine, ExCommand, FilterCommand};
+use crate::app::config::Config;
+use crate::app::coverage_history::{CoverageDelta, CoverageHistory};
+use crate::app::discovery::{Discovery, DiscoveryRequest, DiscoveryResult};
+use crate::app::editor;
+use crate::app::failure_groups::FailureGroups;
+use crate::app::output_rows::OutputRows;
+use crate::app::run_compare::RunComparison;
+use crate::app::history::{History, RunRecord, TestRecord};
+use crate::app::inspector::ConfigReport;
+use crate::app::keys::KeyMap;
+use crate::app::layout::{LayoutPreset, ProjectLayouts, DEFAULT_RESULTS_SPLIT};
+use crate::app::logging::RecentLogs;
+use crate::app::macros::{MacroEvent, Macros, REPLAY_KEYS_PER_TICK};
+use crate::app::prompt::{Prompt, PromptKind};
+use crate::app::queue::RunQueue;
+use crate::app::run_options::{RunOption, COVERAGE_FLAG, RUN_OPTIONS};
+use crate::app::run_summary::RunAllSummary;
+use crate::app::run_tabs::{self, RunTab, MAX_RUN_TABS};
+use crate::app::search::{FileSearch, Search};
+use crate::app::session::{ExportFormat, Session, SessionRun};
+use crate::app::stats::{self, DirectoryStats};
+use crate::app::leaderboard::Leaderboard;
+use crate::app::test_tree::{self, TreeRow, TreeRowKind};
+use crate::app::toast::{ToastKind, Toasts};
+use crate::app::watch::FileWatcher;
+use crate::git::{self, BlameInfo};
+use crate::runner::{ansi, command::{self, JestCommand}, config_finder::{self, DisplayName, JestProject, PathFilters, PatternDiagnostic}, output_parser, related};
+use crate::runner::coverage::{self, CoverageSummary};
+use crate::runner::imports::{self, LocalImport};
+use crate::runner::json_results::{self, AssertionStatus, FileResult, JsonResults};
+use crate::runner::package_manager::PackageManager;
+use crate::runner::project::DiscoveryBackend;
+use crate::runner::snapshots::{self, SnapshotFile};
+use crate::runner::stack_trace::{self, StackFrame};
+use crate::runner::backend::Runner;
+use crate::runner::test_parser::{self, BlockKind, ModifierKind, TestBlock, TestModifier};
+use crate::widgets::{run_tabs::RunTabState, theme::{Theme, ThemeName, ThemeOptions}, HelpBarWidget, HelpOverlayWidget};
+use crate::runner::test_runner::{self, TestResult};
+use crate::runner::workspace::Monorepo;
 
 /// The different views of the application.
-#[derive(Debug, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppView {
     /// Viewing the list of test files
     TestList,
@@ -19,6 +62,154 @@ pub enum AppView {
     TestRunning,
     /// Viewing individual test results
     TestResults,
+    /// Reviewing skipped, todo, and focused tests across the project
+    Audit,
+    /// Viewing aggregate statistics per directory
+    DirectoryStats,
+    /// Ranking the slowest tests and test files
+    Leaderboard,
+    /// Choosing which Jest config file to use
+    ConfigPicker,
+    /// Choosing which project of a multi-project config to list and run
+    ProjectPicker,
+    /// Choosing which package of a monorepo to list and run tests in
+    WorkspacePicker,
+    /// Inspecting the resolved discovery and run configuration
+    ConfigInspector,
+    /// Choosing a saved layout preset
+    LayoutPicker,
+    /// Comparing test durations against another revision
+    Benchmark,
+    /// Per-file results of running every listed file
+    RunSummary,
+    /// Progress of marked files queued for a batch run
+    Queue,
+    /// Per-file coverage from the last coverage run
+    Coverage,
+    /// Per-file coverage changes since the coverage recorded on a base ref
+    CoverageDiff,
+    /// Recorded runs of the selected test file, and what changed between two of them
+    RunHistory,
+    /// A run's failures grouped by error message
+    FailureGroups,
+    /// Snapshot files of the selected test file
+    Snapshots,
+}
+
+/// How the test file list is ordered
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    /// Alphabetically by path
+    #[default]
+    Name,
+    /// Most recently run first, by run history
+    LastRun,
+    /// Files whose last run failed first
+    Failed,
+    /// Slowest first, by historical average duration
+    Duration,
+    /// Most recently modified first, by git commit or working tree changes
+    Recency,
+}
+
+impl SortMode {
+    /// The sort mode that follows this one when cycling
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Name => SortMode::LastRun,
+            SortMode::LastRun => SortMode::Failed,
+            SortMode::Failed => SortMode::Duration,
+            SortMode::Duration => SortMode::Recency,
+            SortMode::Recency => SortMode::Name,
+        }
+    }
+    
+    /// Short label shown in the list title
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Name => "name",
+            SortMode::LastRun => "last run",
+            SortMode::Failed => "failures first",
+            SortMode::Duration => "duration",
+            SortMode::Recency => "last modified",
+        }
+    }
+}
+
+/// A filter narrowing the test list to a subset of the discovered files
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListFilter {
+    /// Files changed since a git ref, plus the tests related to them
+    ChangedSince(String),
+    /// Files whose last recorded run had failing tests
+    Failed,
+    /// Tests that depend on a source file
+    RelatedTo(String),
+    /// Files belonging to one project of a multi-project config, by label
+    Project(String),
+}
+
+impl ListFilter {
+    /// Description of the filter shown in the header
+    pub fn description(&self) -> String {
+        match self {
+            ListFilter::ChangedSince(git_ref) => format!("changed since {}", git_ref),
+            ListFilter::Failed => "failed last run".to_string(),
+            ListFilter::RelatedTo(source) => format!("related to {}", source),
+            ListFilter::Project(project) => format!("project {}", project),
+        }
+    }
+}
+
+/// How an individual test did, or whether it will run at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    /// Skipped with `.skip`, `xit`, or by an enclosing skipped describe
+    Skipped,
+    /// Declared with `.todo`, so there's nothing to run yet
+    Todo,
+    /// Found in the file, but not run yet
+    Pending,
+}
+
+impl TestStatus {
+    /// The status of a test Jest reported on
+    pub fn from_assertion(status: AssertionStatus) -> Self {
+        match status {
+            AssertionStatus::Passed => TestStatus::Passed,
+            AssertionStatus::Failed => TestStatus::Failed,
+            AssertionStatus::Skipped | AssertionStatus::Pending | AssertionStatus::Disabled => TestStatus::Skipped,
+            AssertionStatus::Todo => TestStatus::Todo,
+            AssertionStatus::Focused => TestStatus::Pending,
+        }
+    }
+    
+    /// The status summing up several tests, like those in a describe block:
+    /// failed if any failed, otherwise passed if any passed
+    pub fn combine(self, other: TestStatus) -> Self {
+        let rank = |status| match status {
+            TestStatus::Failed => 4,
+            TestStatus::Passed => 3,
+            TestStatus::Pending => 2,
+            TestStatus::Skipped => 1,
+            TestStatus::Todo => 0,
+        };
+        if rank(other) > rank(self) { other } else { self }
+    }
+    
+    /// Short description, e.g. for the results details
+    pub fn label(self) -> &'static str {
+        match self {
+            TestStatus::Passed => "Passed",
+            TestStatus::Failed => "Failed",
+            TestStatus::Skipped => "Skipped",
+            TestStatus::Todo => "Todo",
+            TestStatus::Pending => "Not run yet",
+        }
+    }
 }
 
 /// Information about an individual test case
@@ -26,14 +217,48 @@ pub enum AppView {
 pub struct TestInfo {
     /// The test name/description
     pub name: String,
-    /// Whether the test passed
-    pub passed: bool,
+    /// Enclosing describe names and the test name, joined with spaces
+    pub full_name: String,
+    /// Line the test is declared on, when known
+    pub line: Option<usize>,
+    /// Absolute path of the file the test is in, when known
+    pub file: Option<String>,
+    /// Names of the enclosing describe blocks, outermost first
+    pub ancestors: Vec<String>,
+    /// How the test did, or whether it will run at all
+    pub status: TestStatus,
     /// Any error details
     pub error: Option<String>,
     /// Duration of the test in ms
     pub duration: Option<u64>,
 }
 
+impl TestInfo {
+    /// Whether this entry stands for a whole file that failed to run rather than a test
+    pub fn is_file_failure(&self) -> bool {
+        self.file.is_some() && self.line.is_none()
+    }
+}
+
+/// Who last changed a failing test, from git blame
+#[derive(Debug, Clone, Default)]
+pub struct FailureBlame {
+    /// Last change to the lines declaring the test
+    pub test: Option<BlameInfo>,
+    /// The failure's first stack frame inside the project, as `file:line`
+    /// relative to the project, and the last change to that line
+    pub frame: Option<(String, BlameInfo)>,
+}
+
+/// A skipped, todo, or focused test found while auditing the project
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    /// The test file containing the declaration
+    pub file: String,
+    /// The modified declaration itself
+    pub modifier: TestModifier,
+}
+
 /// The main application which holds the state and logic of the application.
 #[derive(Debug)]
 pub struct App {
@@ -43,8 +268,40 @@ pub struct App {
     pub search_path: String,
     /// The testMatch patterns being used to find tests
     pub test_matches: Vec<String>,
-    /// All the tests that are found in the search path
+    /// testRegex and ignore patterns from the config in use
+    pub path_filters: PathFilters,
+    /// All Jest config files found for the project
+    pub config_files: Vec<PathBuf>,
+    /// The Jest config file currently in use, if any
+    pub config_path: Option<PathBuf>,
+    /// Highlighted entry in the config picker
+    pub selected_config_index: usize,
+    /// Projects declared with the config's `projects` option, each with its tests
+    pub projects: Vec<JestProject>,
+    /// Highlighted entry in the project picker, where 0 is "All projects"
+    pub selected_project_index: usize,
+    /// The monorepo the app was started in, if it was started at one's root
+    pub monorepo: Option<Monorepo>,
+    /// Highlighted entry in the workspace picker, where 0 is the repository root
+    pub selected_workspace_index: usize,
+    /// Per-pattern match counts from the last discovery
+    pub pattern_diagnostics: Vec<PatternDiagnostic>,
+    /// Project displayName of each test file in multi-project setups
+    pub display_names: HashMap<String, DisplayName>,
+    /// The command used for the most recent test run
+    pub last_command: Option<JestCommand>,
+    /// The tests currently shown in the list (all tests, or the filtered subset)
     pub tests: Vec<String>,
+    /// All the tests that are found in the search path
+    pub all_tests: Vec<String>,
+    /// Filter currently applied to the list, if any
+    pub list_filter: Option<ListFilter>,
+    /// Text prompt currently open over the view, if any
+    pub prompt: Option<Prompt>,
+    /// One-off message shown in the header until the next key press
+    pub status_message: Option<String>,
+    /// Short-lived notifications shown in a corner, e.g. after copying
+    pub toasts: Toasts,
     /// Current selected index in the list
     pub selected_index: usize,
     /// First visible item in the scrolling list
@@ -53,24 +310,219 @@ pub struct App {
     pub view: AppView,
     /// Content of the currently selected test file
     pub current_test_content: String,
+    /// Tests and describe blocks declared in `current_test_content`, parsed
+    /// once when it's loaded rather than on every draw. Empty for source files.
+    pub current_test_blocks: Vec<TestBlock>,
     /// Status of the most recent test run
     pub test_run_output: String,
-    /// Terminal output scroll position
+    /// Terminal output scroll position, in rows as shown (with any folds)
     pub terminal_scroll: usize,
-    /// Command that was copied to clipboard
-    pub copied_command: Option<String>,
+    /// Whether the output view stays on the newest output as it streams in
+    pub follow_output: bool,
     /// Whether the test is currently loading
     pub test_loading: bool,
     /// Channel for receiving test run updates
     pub test_receiver: Option<mpsc::Receiver<TestResult>>,
     /// Individual test results parsed from output
     pub individual_tests: Vec<TestInfo>,
-    /// Selected test result index
+    /// Selected row of the test results tree
     pub selected_test_index: usize,
+    /// Describe blocks collapsed in the test results tree, by path of names
+    pub collapsed_describes: HashSet<Vec<String>>,
     /// Flag to automatically show test results when test completes
     pub auto_show_test_results: bool,
     /// Flag indicating if we're running an individual test (vs a full file)
     pub running_individual_test: bool,
+    /// Focused (`.only`) declarations found in each test file, keyed by file
+    pub focused_tests: HashMap<String, Vec<TestModifier>>,
+    /// Skipped, todo, and focused tests found by the last project audit
+    pub audit_entries: Vec<AuditEntry>,
+    /// Selected audit entry index
+    pub selected_audit_index: usize,
+    /// Persisted run history for this project
+    pub history: History,
+    /// When the current run was started
+    pub run_started_at: Option<std::time::Instant>,
+    /// Test file the current or last run was for, independent of the selection
+    pub running_test_file: Option<String>,
+    /// Current ordering of the test list
+    pub sort_mode: SortMode,
+    /// Last modification time of each test file, loaded when sorting by recency
+    pub modified_times: HashMap<String, u64>,
+    /// Git blame results for failing tests looked up with `b`, keyed by (file, full test name)
+    pub blame_cache: HashMap<(String, String), FailureBlame>,
+    /// Whether the imports pane is shown next to the test list
+    pub show_imports: bool,
+    /// Whether keyboard focus is in the imports pane
+    pub imports_focused: bool,
+    /// Selected import in the imports pane
+    pub selected_import_index: usize,
+    /// Resolved local imports of each test file, computed on demand
+    pub import_cache: HashMap<String, Vec<LocalImport>>,
+    /// Source file shown in the detail view instead of the selected test file
+    pub detail_source: Option<String>,
+    /// Resolved configuration shown in the config inspector
+    pub config_report: ConfigReport,
+    /// Scroll position in the config inspector
+    pub inspector_scroll: usize,
+    /// Per-directory rollup shown in the directory stats view
+    pub directory_stats: Vec<DirectoryStats>,
+    /// Selected directory in the stats view
+    pub selected_directory_index: usize,
+    /// Timings ranked in the leaderboard view
+    pub leaderboard: Leaderboard,
+    /// Selected entry in the leaderboard view
+    pub selected_leaderboard_index: usize,
+    /// Failures of the run the failure groups view was opened from
+    pub failure_groups: FailureGroups,
+    /// Selected group in the failure groups view
+    pub selected_failure_group_index: usize,
+    /// View to go back to from the failure groups view
+    pub failure_groups_return_view: AppView,
+    /// Snapshot files of the test file the snapshots view was opened for
+    pub snapshots: Vec<SnapshotFile>,
+    /// Selected row (a file or one of its entries) in the snapshots view
+    pub selected_snapshot_index: usize,
+    /// First line of the selected snapshot file shown
+    pub snapshot_scroll: usize,
+    /// View to go back to from the snapshots view
+    pub snapshots_return_view: AppView,
+    /// Selected run in the run history view, counting from the newest
+    pub selected_history_index: usize,
+    /// Run marked to compare the selected run against, as an index into the
+    /// file's recorded runs
+    pub history_base_index: Option<usize>,
+    /// Recorded keyboard macros and any replay in progress
+    pub macros: Macros,
+    /// Command shown by a dry run instead of being executed
+    pub dry_run: Option<JestCommand>,
+    /// Whether quitting is waiting on a choice about the runs still going
+    pub confirm_quit: bool,
+    /// Whether the app was quit leaving its runs to finish without the UI
+    pub detached: bool,
+    /// Why the runner couldn't be found or started, shown in a popup with how
+    /// to install it
+    pub missing_runner: Option<String>,
+    /// User settings from the config files
+    pub config: Config,
+    /// Keys remapped in the config
+    pub keymap: KeyMap,
+    /// Colors the UI is drawn with
+    pub theme: Theme,
+    /// Layout presets saved for this project
+    pub layouts: ProjectLayouts,
+    /// Width of the test list in the results view, as a percentage
+    pub results_split: u16,
+    /// Whether failed assertions show expected and received values in columns
+    pub side_by_side_diff: bool,
+    /// Presets listed in the layout picker
+    pub layout_entries: Vec<(String, LayoutPreset)>,
+    /// Selected preset in the layout picker
+    pub selected_layout_index: usize,
+    /// Selected entry while the custom actions menu is open
+    pub action_menu: Option<usize>,
+    /// Selected flag in the run options popup, while it's open
+    pub run_options: Option<usize>,
+    /// Arguments typed in for the following runs, kept for the session
+    pub extra_args: Vec<String>,
+    /// Scroll position while the help overlay is open
+    pub help_overlay: Option<usize>,
+    /// Latest log lines, shown in the log viewer
+    pub logs: RecentLogs,
+    /// Lines scrolled back from the newest while the log viewer (F12) is open
+    pub log_viewer: Option<usize>,
+    /// Name of the custom action whose output is being shown, if any
+    pub running_action: Option<String>,
+    /// The latest duration comparison against another revision
+    pub benchmark: Option<Benchmark>,
+    /// Selected file in the benchmark view
+    pub selected_benchmark_index: usize,
+    /// The `:` command line, while it's open
+    pub command_line: Option<CommandLine>,
+    /// Commands entered on the command line in this project
+    pub command_history: CommandHistory,
+    /// Extra Jest CLI options (`--key=value`) added to every run
+    pub jest_options: Vec<(String, String)>,
+    /// File Jest writes its JSON results to
+    pub results_path: PathBuf,
+    /// Structured results of the last Jest run, if it wrote any
+    pub last_results: Option<JsonResults>,
+    /// Watches the project for changes while watch mode is on
+    pub watcher: Option<FileWatcher>,
+    /// Test discovery going on in the background, if any
+    pub discovery: Option<Discovery>,
+    /// File that was selected when discovery started, selected again once
+    /// it's found
+    pub reselect: Option<String>,
+    /// Fuzzy search narrowing the test list, if one is active
+    pub search: Option<Search>,
+    /// Test runner the project uses
+    pub runner: Runner,
+    /// Number of files in the current (or last) run of every listed file
+    pub running_all: Option<usize>,
+    /// Per-file results of the last run of every listed file
+    pub run_summary: Option<RunAllSummary>,
+    /// Currently selected file in the run summary
+    pub selected_summary_index: usize,
+    /// View to return to from the individual results, when they were opened from a summary
+    pub results_return_view: Option<AppView>,
+    /// Files marked with Space for a batch run
+    pub marked_files: Vec<String>,
+    /// Marked files running one after another (or a few at a time)
+    pub queue: Option<RunQueue>,
+    /// Currently selected file in the queue view
+    pub selected_queue_index: usize,
+    /// Runs finished since the app started, for exporting
+    pub session: Session,
+    /// Whether runs collect coverage
+    pub coverage_mode: bool,
+    /// Coverage written by the last run that collected it
+    pub coverage: Option<CoverageSummary>,
+    /// Currently selected file in the coverage view
+    pub selected_coverage_index: usize,
+    /// Persisted coverage of recent coverage runs, by commit
+    pub coverage_history: CoverageHistory,
+    /// Comparison of the last coverage run against a base ref's coverage
+    pub coverage_delta: Option<CoverageDelta>,
+    /// Currently selected file in the coverage diff view
+    pub selected_coverage_delta_index: usize,
+    /// Paths and contents of the detail view's test file imports that have line coverage
+    pub detail_coverage: Vec<(String, String)>,
+    /// Which of those is shown next to the test file
+    pub detail_coverage_index: usize,
+    /// Location to open in the user's editor once the current key is handled
+    pub pending_editor: Option<(PathBuf, usize)>,
+    /// Whether to suspend to the shell once the current key is handled
+    pub pending_suspend: bool,
+    /// Whether to open a shell in the project once the current key is handled
+    pub pending_shell: bool,
+    /// Stack frames pointing into the project found in the run output
+    pub output_frames: Vec<StackFrame>,
+    /// Which of those was jumped to last
+    pub selected_frame: Option<usize>,
+    /// Whether runs of library stack frames in the output are folded into a
+    /// summary line
+    pub fold_frames: bool,
+    /// Whether the output shows only the failures
+    pub failures_only: bool,
+    /// First line shown in the detail view
+    pub detail_scroll: usize,
+    /// Number of lines the detail view showed when last drawn, for paging
+    pub detail_page: usize,
+    /// Line highlighted in the detail view, e.g. the stack frame it was opened at
+    pub detail_highlight: Option<usize>,
+    /// Search through the detail view's file, if one is open
+    pub detail_search: Option<FileSearch>,
+    /// View to go back to from the detail view, when it wasn't opened from the list
+    pub detail_return_view: Option<AppView>,
+    /// Every run's tab, shown above the output and results when there's more
+    /// than one. The shown tab's run lives in the run fields above, so its
+    /// entry here is only a placeholder.
+    pub run_tabs: Vec<RunTab>,
+    /// Index of the shown tab in `run_tabs`
+    pub active_tab: usize,
+    /// How many tabs have been opened, so each gets its own results file
+    pub tabs_opened: usize,
 }
 
 impl Default for App {
@@ -79,20 +531,130 @@ impl Default for App {
             running: false,
             search_path: String::new(),
             test_matches: Vec::new(),
+            path_filters: PathFilters::default(),
+            config_files: Vec::new(),
+            config_path: None,
+            selected_config_index: 0,
+            projects: Vec::new(),
+            selected_project_index: 0,
+            monorepo: None,
+            selected_workspace_index: 0,
+            display_names: HashMap::new(),
+            pattern_diagnostics: Vec::new(),
+            last_command: None,
             tests: Vec::new(),
+            all_tests: Vec::new(),
+            list_filter: None,
+            prompt: None,
+            status_message: None,
+            toasts: Toasts::default(),
             selected_index: 0,
             scroll_offset: 0,
             view: AppView::TestList,
             current_test_content: String::new(),
+            current_test_blocks: Vec::new(),
             test_run_output: String::new(),
             terminal_scroll: 0,
-            copied_command: None,
+            follow_output: true,
             test_loading: false,
             test_receiver: None,
             individual_tests: Vec::new(),
             selected_test_index: 0,
+            collapsed_describes: HashSet::new(),
             auto_show_test_results: false,
             running_individual_test: false,
+            focused_tests: HashMap::new(),
+            audit_entries: Vec::new(),
+            selected_audit_index: 0,
+            history: History::default(),
+            run_started_at: None,
+            running_test_file: None,
+            sort_mode: SortMode::default(),
+            modified_times: HashMap::new(),
+            blame_cache: HashMap::new(),
+            show_imports: false,
+            imports_focused: false,
+            selected_import_index: 0,
+            import_cache: HashMap::new(),
+            detail_source: None,
+            config_report: ConfigReport::default(),
+            inspector_scroll: 0,
+            directory_stats: Vec::new(),
+            selected_directory_index: 0,
+            leaderboard: Leaderboard::default(),
+            selected_leaderboard_index: 0,
+            failure_groups: FailureGroups::default(),
+            selected_failure_group_index: 0,
+            failure_groups_return_view: AppView::TestList,
+            snapshots: Vec::new(),
+            selected_snapshot_index: 0,
+            snapshot_scroll: 0,
+            snapshots_return_view: AppView::TestList,
+            selected_history_index: 0,
+            history_base_index: None,
+            macros: Macros::default(),
+            dry_run: None,
+            confirm_quit: false,
+            detached: false,
+            missing_runner: None,
+            config: Config::default(),
+            keymap: KeyMap::default(),
+            theme: Theme::default(),
+            layouts: ProjectLayouts::default(),
+            results_split: DEFAULT_RESULTS_SPLIT,
+            side_by_side_diff: false,
+            layout_entries: Vec::new(),
+            selected_layout_index: 0,
+            action_menu: None,
+            run_options: None,
+            extra_args: Vec::new(),
+            help_overlay: None,
+            logs: RecentLogs::default(),
+            log_viewer: None,
+            running_action: None,
+            benchmark: None,
+            selected_benchmark_index: 0,
+            command_line: None,
+            command_history: CommandHistory::default(),
+            jest_options: Vec::new(),
+            results_path: json_results::results_path(),
+            last_results: None,
+            watcher: None,
+            discovery: None,
+            reselect: None,
+            search: None,
+            runner: Runner::default(),
+            running_all: None,
+            run_summary: None,
+            selected_summary_index: 0,
+            results_return_view: None,
+            marked_files: Vec::new(),
+            queue: None,
+            selected_queue_index: 0,
+            session: Session::default(),
+            coverage_mode: false,
+            coverage: None,
+            selected_coverage_index: 0,
+            coverage_history: CoverageHistory::default(),
+            coverage_delta: None,
+            selected_coverage_delta_index: 0,
+            detail_coverage: Vec::new(),
+            detail_coverage_index: 0,
+            pending_editor: None,
+            pending_suspend: false,
+            pending_shell: false,
+            output_frames: Vec::new(),
+            selected_frame: None,
+            fold_frames: true,
+            failures_only: false,
+            detail_scroll: 0,
+            detail_page: 0,
+            detail_highlight: None,
+            detail_search: None,
+            detail_return_view: None,
+            run_tabs: vec![RunTab::default()],
+            active_tab: 0,
+            tabs_opened: 1,
         }
     }
 }
@@ -100,746 +662,4888 @@ impl Default for App {
 impl App {
     /// Construct a new instance of [`App`].
     pub fn new(search_path: String, test_matches: Vec<String>, tests: Vec<String>) -> Self {
-        Self {
-            running: false,
+        let mut app = Self {
+            history: History::load(&search_path),
+            layouts: ProjectLayouts::load(&search_path),
+            command_history: CommandHistory::load(&search_path),
+            coverage_history: CoverageHistory::load(&search_path),
             search_path,
             test_matches,
+            all_tests: tests.clone(),
             tests,
-            selected_index: 0,
-            scroll_offset: 0,
-            view: AppView::TestList,
-            current_test_content: String::new(),
-            test_run_output: String::new(),
-            terminal_scroll: 0,
-            copied_command: None,
-            test_loading: false,
-            test_receiver: None,
-            individual_tests: Vec::new(),
-            selected_test_index: 0,
-            auto_show_test_results: false,
-            running_individual_test: false,
-        }
+            ..Self::default()
+        };
+        app.scan_focused_tests();
+        app.load_display_names();
+        app.check_patterns();
+        app
     }
     
-    /// Move selection up in the list
-    pub fn previous(&mut self) {
-        if !self.tests.is_empty() {
-            self.selected_index = self.selected_index.saturating_sub(1);
-            if self.selected_index < self.scroll_offset {
-                self.scroll_offset = self.selected_index;
+    /// Count what each testMatch pattern matched so unproductive ones can be flagged
+    pub fn check_patterns(&mut self) {
+        self.pattern_diagnostics = config_finder::pattern_diagnostics(&self.test_matches, &self.path_filters, Path::new(&self.search_path));
+    }
+    
+    /// Warnings for patterns that contributed no test files
+    pub fn pattern_warnings(&self) -> Vec<String> {
+        self.pattern_diagnostics.iter().filter_map(PatternDiagnostic::warning).collect()
+    }
+    
+    /// Look up the project displayName for every discovered test file
+    pub fn load_display_names(&mut self) {
+        if self.projects.is_empty() {
+            self.display_names = config_finder::find_display_names(&self.all_tests, Path::new(&self.search_path));
+            return;
+        }
+        
+        // Each file is labeled with the project it belongs to, since inline
+        // projects have no config file of their own to find
+        self.display_names.clear();
+        for project in &self.projects {
+            if let Some(display_name) = &project.display_name {
+                for test in &project.tests {
+                    self.display_names.insert(test.clone(), display_name.clone());
+                }
             }
         }
     }
-
-    /// Move selection down in the list
-    pub fn next(&mut self) {
-        if !self.tests.is_empty() {
-            let last_index = self.tests.len() - 1;
-            self.selected_index = (self.selected_index + 1).min(last_index);
+    
+    /// Start finding test files in the background, filling in the list as
+    /// they're found. Goes after `with_user_config`, which picks the backend.
+    pub fn with_discovery(mut self) -> Self {
+        self.rediscover_tests();
+        self
+    }
+    
+    /// Provide the projects of a multi-project config, with their tests
+    pub fn with_projects(mut self, projects: Vec<JestProject>) -> Self {
+        self.projects = projects;
+        self.load_display_names();
+        self
+    }
+    
+    /// Provide the monorepo the app was started at the root of.
+    ///
+    /// Without a config at the root, the app starts in the workspace picker,
+    /// since tests are usually meant to run from inside a package.
+    pub fn with_monorepo(mut self, monorepo: Option<Monorepo>) -> Self {
+        if monorepo.is_some() && self.config_files.is_empty() {
+            self.view = AppView::WorkspacePicker;
         }
+        self.monorepo = monorepo;
+        self
     }
-
-    // We no longer need the update_scroll method as this is now managed by TestListWidget
     
-    /// Load the content of the currently selected test file
-    pub fn load_test_content(&mut self) -> io::Result<()> {
-        if self.tests.is_empty() {
+    /// Show the lines kept by the logger in the log viewer
+    pub fn with_logs(mut self, logs: RecentLogs) -> Self {
+        self.logs = logs;
+        self
+    }
+    
+    /// Open the workspace picker with the package being searched highlighted
+    pub fn show_workspace_picker(&mut self) {
+        let Some(monorepo) = &self.monorepo else {
+            self.status_message = Some("No monorepo workspaces were found".to_string());
+            return;
+        };
+        
+        let search_path = Path::new(&self.search_path).canonicalize().ok();
+        self.selected_workspace_index = monorepo.packages
+            .iter()
+            .position(|package| Some(monorepo.root.join(&package.dir)) == search_path)
+            .map_or(0, |index| index + 1);
+        self.view = AppView::WorkspacePicker;
+    }
+    
+    /// Search the highlighted package (or the repository root) for tests,
+    /// running them from there with its own config
+    pub fn select_workspace(&mut self) -> io::Result<()> {
+        let Some(monorepo) = &self.monorepo else {
             return Ok(());
-        }
+        };
+        let dir = match self.selected_workspace_index.checked_sub(1) {
+            Some(index) => match monorepo.packages.get(index) {
+                Some(package) => monorepo.root.join(&package.dir),
+                None => return Ok(()),
+            },
+            None => monorepo.root.clone(),
+        };
         
-        let test_file = &self.tests[self.selected_index];
-        let full_path = PathBuf::from(&self.search_path).join(test_file);
+        self.view = AppView::TestList;
+        self.change_search_root(&dir.display().to_string())
+    }
+    
+    /// The project the list is narrowed to, if any
+    pub fn active_project(&self) -> Option<&JestProject> {
+        match &self.list_filter {
+            Some(ListFilter::Project(label)) => self.projects.iter().find(|project| project.label() == *label),
+            _ => None,
+        }
+    }
+    
+    /// Open the project picker with the active project highlighted
+    pub fn show_project_picker(&mut self) {
+        if self.projects.is_empty() {
+            self.status_message = Some("The Jest config doesn't declare any projects".to_string());
+            return;
+        }
         
-        match std::fs::read_to_string(&full_path) {
-            Ok(content) => {
-                self.current_test_content = content;
-                self.view = AppView::TestDetail;
-                Ok(())
+        let active = self.active_project().map(JestProject::label);
+        self.selected_project_index = active
+            .and_then(|active| self.projects.iter().position(|project| project.label() == active))
+            .map_or(0, |index| index + 1);
+        self.view = AppView::ProjectPicker;
+    }
+    
+    /// Narrow the list to the highlighted project, or show every project's tests
+    pub fn select_project(&mut self) {
+        self.view = AppView::TestList;
+        match self.selected_project_index.checked_sub(1).and_then(|index| self.projects.get(index)) {
+            Some(project) => {
+                let (label, tests) = (project.label(), project.tests.clone());
+                self.set_filtered_tests(ListFilter::Project(label), tests);
             },
-            Err(e) => {
-                self.current_test_content = format!("Error reading file: {}", e);
-                self.view = AppView::TestDetail;
-                Err(e)
-            }
+            None => self.clear_filter(),
         }
     }
     
-    /// Run the currently selected test file with Jest
-    pub fn run_test(&mut self) -> io::Result<()> {
-        if self.tests.is_empty() {
-            return Ok(());
+    /// The project names to pass to `--selectProjects` when running these
+    /// targets: the active project, or else the named projects the target
+    /// files belong to. Empty when a target belongs to no project or to one
+    /// without a `displayName`, since those can't be selected by name.
+    fn selected_projects(&self, targets: &[String]) -> Vec<String> {
+        if let Some(project) = self.active_project() {
+            return project.display_name.iter().map(|display_name| display_name.name.clone()).collect();
         }
         
-        self.view = AppView::TestRunning;
-        self.test_loading = true;
-        self.test_run_output = String::new(); // Clear previous output
-        self.running_individual_test = false; // Flag that we're running a full test file
+        let mut names = Vec::new();
+        for target in targets {
+            let mut owners = self.projects.iter().filter(|project| project.tests.contains(target)).peekable();
+            if owners.peek().is_none() {
+                return Vec::new();
+            }
+            for project in owners {
+                let Some(display_name) = &project.display_name else {
+                    return Vec::new();
+                };
+                if !names.contains(&display_name.name) {
+                    names.push(display_name.name.clone());
+                }
+            }
+        }
+        names
+    }
+    
+    /// Provide the Jest config files found for the project.
+    ///
+    /// The first config is used by default; when there is more than one, the app
+    /// starts in the config picker so the choice isn't made silently.
+    pub fn with_configs(mut self, config_files: Vec<PathBuf>) -> Self {
+        self.config_path = config_files.first().cloned();
+        if config_files.len() > 1 {
+            self.view = AppView::ConfigPicker;
+        }
+        self.config_files = config_files;
+        self
+    }
+    
+    /// Use the given test runner (Jest unless a Vitest project was detected)
+    pub fn with_runner(mut self, runner: Runner) -> Self {
+        self.runner = runner;
+        self
+    }
+    
+    /// Provide the testRegex and ignore patterns read from the config
+    pub fn with_path_filters(mut self, path_filters: PathFilters) -> Self {
+        self.path_filters = path_filters;
+        self.check_patterns();
+        self
+    }
+    
+    /// Provide the user's settings.
+    ///
+    /// Also restores the layout last used in this project, since it may be
+    /// one of the presets defined in the config.
+    pub fn with_user_config(mut self, config: Config, errors: Vec<String>) -> Self {
+        // The config was validated when it was loaded
+        self.keymap = KeyMap::from_config(&config.keys).unwrap_or_default();
+        self.theme = config.theme.resolve().unwrap_or_default();
+        self.config = config;
+        if let Some(name) = self.layouts.active.clone()
+            && let Err(e) = self.apply_layout(&name) {
+            self.status_message = Some(format!("Could not restore layout '{}': {}", name, e));
+        }
+        for error in errors {
+            warn!("Ignored invalid config: {}", error);
+            self.toasts.push(ToastKind::Error, format!("Ignored invalid config: {}", error));
+        }
+        self
+    }
+    
+    /// Every available layout preset, with presets saved in the project
+    /// taking precedence over ones from the config file
+    pub fn layout_presets(&self) -> Vec<(String, LayoutPreset)> {
+        let mut presets = self.config.layouts.clone();
+        presets.extend(self.layouts.presets.clone());
+        presets.into_iter().collect()
+    }
+    
+    /// The current layout choices as a preset
+    pub fn current_layout(&self) -> LayoutPreset {
+        LayoutPreset {
+            show_imports: self.show_imports,
+            sort: self.sort_mode,
+            changed_since: self.list_filter
+                .as_ref()
+                .and_then(|filter| match filter {
+                    ListFilter::ChangedSince(git_ref) => Some(git_ref.clone()),
+                    _ => None,
+                }),
+            results_split: self.results_split,
+        }
+    }
+    
+    /// Switch to the named layout preset and remember it for this project
+    pub fn apply_layout(&mut self, name: &str) -> io::Result<()> {
+        let Some((_, preset)) = self.layout_presets().into_iter().find(|(n, _)| n == name) else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("no layout named '{}'", name)));
+        };
         
-        // Need to clone these for the async task
-        let test_file = self.tests[self.selected_index].clone();
-        let project_dir = self.search_path.clone();
+        self.show_imports = preset.show_imports;
+        self.imports_focused = false;
+        self.results_split = preset.results_split.clamp(10, 100);
+        self.sort_mode = preset.sort;
+        if self.sort_mode == SortMode::Recency {
+            self.load_modified_times();
+        }
         
-        // Start the async test process
-        self.test_receiver = Some(test_runner::start_async_test(&test_file, &project_dir));
+        self.layouts.active = Some(name.to_string());
+        match &preset.changed_since {
+            Some(git_ref) => self.filter_changed_since(git_ref)?,
+            None if self.list_filter.is_some() => self.clear_filter(),
+            None => self.sort_tests(),
+        }
         
-        // Show initial "running test" message
-        self.test_run_output = format!("Running test: {}\n", test_file);
+        self.layouts.save()
+    }
+    
+    /// Save the current layout choices as a project preset
+    pub fn save_layout(&mut self, name: &str) -> io::Result<()> {
+        self.layouts.presets.insert(name.to_string(), self.current_layout());
+        self.layouts.active = Some(name.to_string());
+        self.layouts.save()
+    }
+    
+    /// Open the layout picker with the active preset highlighted
+    pub fn show_layout_picker(&mut self) {
+        self.layout_entries = self.layout_presets();
+        self.selected_layout_index = self.layouts.active
+            .as_ref()
+            .and_then(|active| self.layout_entries.iter().position(|(name, _)| name == active))
+            .unwrap_or(0);
+        self.view = AppView::LayoutPicker;
+    }
+    
+    /// Apply the preset highlighted in the layout picker
+    pub fn select_layout(&mut self) {
+        let Some((name, _)) = self.layout_entries.get(self.selected_layout_index).cloned() else {
+            return;
+        };
         
-        Ok(())
+        self.view = AppView::TestList;
+        match self.apply_layout(&name) {
+            Ok(()) => self.status_message = Some(format!("Switched to layout '{}'", name)),
+            Err(e) => self.status_message = Some(format!("Could not apply layout '{}': {}", name, e)),
+        }
     }
     
-    /// Navigate back based on context
-    pub fn go_back(&mut self) {
-        if self.view == AppView::TestRunning && self.running_individual_test && !self.individual_tests.is_empty() {
-            // If we're running an individual test, go back to test results view
-            self.view = AppView::TestResults;
-            self.terminal_scroll = 0;
-            self.copied_command = None;
-            self.running_individual_test = false;
-        } else {
-            // Otherwise go back to the test list
-            self.view = AppView::TestList;
-            self.terminal_scroll = 0;
-            self.copied_command = None;
-            self.running_individual_test = false;
+    /// Remove the preset highlighted in the layout picker from the project
+    pub fn delete_selected_layout(&mut self) {
+        let Some((name, _)) = self.layout_entries.get(self.selected_layout_index).cloned() else {
+            return;
+        };
+        
+        if self.layouts.presets.remove(&name).is_none() {
+            self.status_message = Some(format!("'{}' is defined in the config file", name));
+            return;
         }
+        if self.layouts.active.as_ref() == Some(&name) {
+            self.layouts.active = None;
+        }
+        if let Err(e) = self.layouts.save() {
+            self.toasts.push(ToastKind::Error, format!("Could not save layouts: {}", e));
+        }
+        
+        self.layout_entries = self.layout_presets();
+        self.selected_layout_index = self.selected_layout_index.min(self.layout_entries.len().saturating_sub(1));
     }
     
-    /// Compatibility method - delegates to go_back
-    pub fn back_to_list(&mut self) {
-        self.go_back();
+    /// Show the running benchmark, or prompt for a baseline to start a new one
+    pub fn show_benchmark(&mut self) {
+        if self.benchmark.as_ref().is_some_and(|benchmark| !benchmark.finished) {
+            self.view = AppView::Benchmark;
+            return;
+        }
+        
+        let default_ref = git::default_branch(Path::new(&self.search_path)).unwrap_or_default();
+        self.prompt = Some(Prompt::new(PromptKind::BenchmarkBaseline, default_ref));
     }
     
-    /// Scroll terminal output up
-    pub fn scroll_up(&mut self, amount: usize) {
-        if self.view == AppView::TestRunning {
-            self.terminal_scroll = self.terminal_scroll.saturating_sub(amount);
+    /// Benchmark the listed test files against a git ref or directory
+    pub fn start_benchmark(&mut self, baseline: &str) {
+        if self.tests.is_empty() {
+            self.status_message = Some("No test files to benchmark".to_string());
+            return;
         }
+        
+        let runs = self.tests
+            .iter()
+            .map(|test_file| (test_file.clone(), self.jest_command(test_file)))
+            .collect();
+        
+        self.benchmark = Some(Benchmark::start(baseline, Path::new(&self.search_path), runs));
+        self.selected_benchmark_index = 0;
+        self.view = AppView::Benchmark;
     }
     
-    /// Scroll terminal output down
-    pub fn scroll_down(&mut self, amount: usize) {
-        if self.view == AppView::TestRunning {
-            // Count lines in output to determine max scroll
-            let line_count = self.test_run_output.lines().count();
-            self.terminal_scroll = (self.terminal_scroll + amount).min(line_count.saturating_sub(1));
+    /// Open a prompt for the name to save the current layout under
+    pub fn prompt_save_layout(&mut self) {
+        let name = self.layouts.active.clone().unwrap_or_default();
+        self.prompt = Some(Prompt::new(PromptKind::SaveLayout, name));
+    }
+    
+    /// Resize the results view's test list by the given percentage. Growing
+    /// it past 80% hides the details pane, and shrinking it brings it back.
+    pub fn resize_results_split(&mut self, delta: i16) {
+        self.results_split = match self.results_split.saturating_add_signed(delta) {
+            split if split > 80 && delta > 0 => 100,
+            split => split.clamp(20, 80),
+        };
+    }
+    
+    /// Gather the resolved configuration and show the inspector
+    pub fn show_config_inspector(&mut self) {
+        let root = Path::new(&self.search_path);
+        let root_dir = self.config_path.as_deref().map(config_finder::extract_root_dir);
+        
+        self.config_report = ConfigReport {
+            config_path: self.config_path.clone(),
+            config_files: self.config_files.clone(),
+            root: root
+                .canonicalize()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| self.search_path.clone()),
+            roots: match &root_dir {
+                // Only worth listing when they're not just the default
+                Some(root_dir) if self.path_filters.roots != [root_dir.clone()] => self.path_filters.roots.clone(),
+                _ => Vec::new(),
+            },
+            root_dir,
+            patterns: self.pattern_diagnostics.clone(),
+            uses_test_regex: !self.path_filters.test_regex.is_empty(),
+            ignore_patterns: self.path_filters.ignore_patterns(),
+            command_template: self.jest_command("<file>").shell_command(),
+            runner: self.runner.label(),
+            package_manager: PackageManager::detect(root).label(),
+            total_files: self.all_tests.len(),
+        };
+        self.inspector_scroll = 0;
+        self.view = AppView::ConfigInspector;
+    }
+    
+    /// Open the config picker with the active config highlighted
+    pub fn show_config_picker(&mut self) {
+        if self.config_files.is_empty() {
+            self.status_message = Some("No Jest config files found".to_string());
+            return;
         }
+        
+        self.selected_config_index = self.config_path
+            .as_ref()
+            .and_then(|active| self.config_files.iter().position(|c| c == active))
+            .unwrap_or(0);
+        self.view = AppView::ConfigPicker;
     }
     
-    /// Copy the test command to the clipboard
-    pub fn copy_command_to_clipboard(&mut self) -> io::Result<()> {
-        if self.tests.is_empty() || self.view != AppView::TestRunning {
+    /// Switch to the highlighted config and rediscover tests with its patterns
+    pub fn select_config(&mut self) -> io::Result<()> {
+        let Some(config) = self.config_files.get(self.selected_config_index).cloned() else {
             return Ok(());
-        }
+        };
         
-        let test_file = &self.tests[self.selected_index];
+        self.view = AppView::TestList;
+        if self.config_path.as_ref() == Some(&config) {
+            return Ok(());
+        }
         
-        // Use the project root directory (search_path) rather than the test file's directory
-        let project_dir = &self.search_path;
+        self.test_matches = config_finder::extract_test_matches(&config)?;
+        self.path_filters = config_finder::extract_path_filters(&config);
+        self.projects = config_finder::extract_projects(&config);
+        self.config_path = Some(config);
+        self.rediscover_tests();
+        Ok(())
+    }
+    
+    /// Find test files with the current root and patterns, resetting the list
+    pub fn rediscover_tests(&mut self) {
+        self.discover_tests(true);
+    }
+    
+    /// Start finding test files in the background, asking Jest for them when
+    /// that's the configured backend and `ask_jest` is set, and with the
+    /// patterns otherwise. The list fills in as files are found.
+    fn discover_tests(&mut self, ask_jest: bool) {
+        self.cancel_discovery();
+        let ask_jest = ask_jest && self.config.discovery.backend == DiscoveryBackend::Jest && self.runner == Runner::Jest;
+        self.discovery = Some(Discovery::start(DiscoveryRequest {
+            search_path: PathBuf::from(&self.search_path),
+            test_matches: self.test_matches.clone(),
+            path_filters: self.path_filters.clone(),
+            projects: self.projects.clone(),
+            list_command: ask_jest.then(|| self.list_tests_command()),
+        }));
         
-        // Build the shell command - cd to project root, then run Jest with relative test path
-        let shell_command = format!(
-            "cd {} && npx jest {} --no-cache", 
-            project_dir,
-            test_file  // Use relative path from project root
-        );
+        self.reselect = self.tests.get(self.selected_index).cloned();
+        self.all_tests.clear();
+        self.list_filter = None;
+        self.search = None;
+        self.tests.clear();
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+    }
+    
+    /// Detect the config and find test files again, e.g. after adding a test
+    /// file or editing the config, keeping the selected file selected
+    pub fn refresh_tests(&mut self) -> io::Result<()> {
+        let root = PathBuf::from(&self.search_path);
+        self.detect_config(&root)?;
+        self.modified_times.clear();
+        self.import_cache.clear();
+        self.rediscover_tests();
+        Ok(())
+    }
+    
+    /// Stop a discovery going on in the background, keeping the files it found
+    pub fn cancel_discovery(&mut self) {
+        if let Some(discovery) = &self.discovery {
+            discovery.cancel();
+        }
+    }
+    
+    /// Add the test files found since the last check to the list, and wrap up
+    /// once discovery is over
+    fn check_discovery(&mut self) {
+        let Some(discovery) = self.discovery.as_mut() else {
+            return;
+        };
+        let (found, result) = discovery.poll();
         
-        // Use pbcopy on macOS to copy to clipboard
-        let copy_result = Command::new("pbcopy")
-            .stdin(Stdio::piped())
-            .spawn()
-            .and_then(|mut child| {
-                use std::io::Write;
-                child.stdin.as_mut().unwrap().write_all(shell_command.as_bytes())?;
-                child.wait().map(|_| ())
-            });
-            
-        match copy_result {
-            Ok(_) => {
-                // Store the command that was copied
-                self.copied_command = Some(shell_command.clone());
-                Ok(())
+        if !found.is_empty() {
+            self.all_tests.extend(found);
+            self.show_discovered_tests();
+        }
+        if let Some(result) = result {
+            self.discovery = None;
+            self.finish_discovery(result);
+        }
+    }
+    
+    /// Show every discovered test file in the list, unless it's filtered,
+    /// keeping any search going
+    fn show_discovered_tests(&mut self) {
+        if self.list_filter.is_some() {
+            return;
+        }
+        match self.search.as_mut() {
+            Some(search) => {
+                let selected = self.tests.get(self.selected_index).cloned();
+                search.base = self.all_tests.clone();
+                self.tests = search.results();
+                self.selected_index = selected
+                    .and_then(|selected| self.tests.iter().position(|t| *t == selected))
+                    .unwrap_or(0);
+            },
+            None => {
+                self.tests = self.all_tests.clone();
+                self.sort_tests();
+                if let Some(index) = self.reselect
+                    .as_ref()
+                    .and_then(|reselect| self.tests.iter().position(|t| t == reselect)) {
+                    self.selected_index = index;
+                    self.reselect = None;
+                }
             },
-            Err(e) => {
-                // Mark that copy failed
-                self.copied_command = None;
-                Err(e)
-            }
         }
     }
     
-    /// Parse individual test results from Jest output
-    pub fn parse_test_results(&mut self) {
-        self.individual_tests.clear();
+    /// Take the final list of test files from a finished discovery
+    fn finish_discovery(&mut self, result: DiscoveryResult) {
+        self.all_tests = result.tests;
+        self.projects = result.projects;
+        self.show_discovered_tests();
+        self.reselect = None;
+        self.scan_focused_tests();
+        self.load_display_names();
+        self.check_patterns();
         
-        let mut current_test_name = String::new();
-        let mut current_test_passed = false;
-        let mut current_test_error = None;
-        let mut current_test_duration = None;
+        match result.error {
+            Some(error) => self.toasts.push(ToastKind::Error, error),
+            None if result.cancelled => self.toasts.push(ToastKind::Info, format!("Stopped scanning after {} test files", self.all_tests.len())),
+            None if self.all_tests.is_empty() => self.toasts.push(ToastKind::Info, "No test files found"),
+            None => self.toasts.push(ToastKind::Info, format!("Found {} test files", self.all_tests.len())),
+        }
+    }
+    
+    /// Search a different directory, picking up its Jest config and history
+    pub fn change_search_root(&mut self, path: &str) -> io::Result<()> {
+        let root = PathBuf::from(path);
+        if !root.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("{} is not a directory", path)));
+        }
         
-        // Process each line to find test results
-        for line in self.test_run_output.lines() {
-            let line = line.trim();
-            
-            // Check for test start
-            if line.starts_with("✓") || line.starts_with("PASS") {
-                // Test passed
-                if !current_test_name.is_empty() {
-                    // Save previous test if we have one
-                    self.individual_tests.push(TestInfo {
-                        name: current_test_name.clone(),
-                        passed: true,
-                        error: None,
-                        duration: current_test_duration,
-                    });
-                }
-                
-                // Extract test name - remove the "✓ " prefix and extract the name
-                let name_parts: Vec<&str> = line.splitn(2, ' ').collect();
-                if name_parts.len() > 1 {
-                    current_test_name = name_parts[1].trim().to_string();
-                    current_test_passed = true;
-                    current_test_error = None;
-                    
-                    // Try to extract duration if it's in the format "name (Duration: 10ms)"
-                    if let Some(duration_idx) = current_test_name.find("(") {
-                        if let Some(end_idx) = current_test_name.find(")") {
-                            let duration_str = &current_test_name[duration_idx + 1..end_idx];
-                            if duration_str.contains("ms") {
-                                // Extract number from string like "10ms"
-                                if let Some(ms_idx) = duration_str.find("ms") {
-                                    let number_str = &duration_str[0..ms_idx].trim();
-                                    if let Ok(duration) = number_str.parse::<u64>() {
-                                        current_test_duration = Some(duration);
-                                    }
-                                }
-                            }
-                            // Remove the duration part from the name
-                            current_test_name = current_test_name[0..duration_idx].trim().to_string();
-                        }
-                    }
-                }
-            } else if line.starts_with("×") || line.starts_with("FAIL") {
-                // Test failed
-                if !current_test_name.is_empty() {
-                    // Save previous test if we have one
-                    self.individual_tests.push(TestInfo {
-                        name: current_test_name.clone(),
-                        passed: current_test_passed,
-                        error: current_test_error.clone(),
-                        duration: current_test_duration,
-                    });
-                }
-                
-                // Extract test name - remove the "× " prefix and extract the name
-                let name_parts: Vec<&str> = line.splitn(2, ' ').collect();
-                if name_parts.len() > 1 {
-                    current_test_name = name_parts[1].trim().to_string();
-                    current_test_passed = false;
-                    current_test_error = Some(String::new()); // Will be populated with subsequent error lines
-                    current_test_duration = None;
-                    
-                    // Try to extract duration
-                    if let Some(duration_idx) = current_test_name.find("(") {
-                        if let Some(end_idx) = current_test_name.find(")") {
-                            let duration_str = &current_test_name[duration_idx + 1..end_idx];
-                            if duration_str.contains("ms") {
-                                // Extract number from string like "10ms"
-                                if let Some(ms_idx) = duration_str.find("ms") {
-                                    let number_str = &duration_str[0..ms_idx].trim();
-                                    if let Ok(duration) = number_str.parse::<u64>() {
-                                        current_test_duration = Some(duration);
-                                    }
-                                }
-                            }
-                            // Remove the duration part from the name
-                            current_test_name = current_test_name[0..duration_idx].trim().to_string();
-                        }
-                    }
-                }
-            } else if !current_test_name.is_empty() && line.contains("Error:") && !current_test_passed {
-                // Found error details
-                if let Some(ref mut error) = current_test_error {
-                    error.push_str(line);
-                    error.push('\n');
-                }
-            }
-        }
-        
-        // Add the last test if there is one
-        if !current_test_name.is_empty() {
-            self.individual_tests.push(TestInfo {
-                name: current_test_name,
-                passed: current_test_passed,
-                error: current_test_error,
-                duration: current_test_duration,
-            });
+        self.detect_config(&root)?;
+        self.search_path = root.display().to_string();
+        self.history = History::load(&self.search_path);
+        self.layouts = ProjectLayouts::load(&self.search_path);
+        self.command_history = CommandHistory::load(&self.search_path);
+        self.coverage_history = CoverageHistory::load(&self.search_path);
+        self.modified_times.clear();
+        self.blame_cache.clear();
+        self.import_cache.clear();
+        if self.watcher.is_some() {
+            self.watcher = FileWatcher::start(&root, &self.config.export).ok();
         }
+        self.rediscover_tests();
+        Ok(())
+    }
+    
+    /// Detect the runner and read its config in a directory, keeping the
+    /// chosen config file when there's still one to choose
+    fn detect_config(&mut self, root: &Path) -> io::Result<()> {
+        self.runner = Runner::detect(root);
+        self.config_files = self.runner.find_config_files(root)?;
+        self.config_path = self.config_path
+            .take()
+            .filter(|config| self.config_files.contains(config))
+            .or_else(|| self.config_files.first().cloned());
+        self.test_matches = match &self.config_path {
+            Some(config) => config_finder::extract_test_matches(config)?,
+            None => self.config.default_test_matches(self.runner),
+        };
+        self.path_filters = self.config_path
+            .as_deref()
+            .map(config_finder::extract_path_filters)
+            .unwrap_or_default();
+        self.projects = self.config_path
+            .as_deref()
+            .map(config_finder::extract_projects)
+            .unwrap_or_default();
+        Ok(())
+    }
+    
+    /// Search with the given comma-separated patterns, or Jest's defaults if empty
+    pub fn set_test_patterns(&mut self, patterns: &str) -> io::Result<()> {
+        let patterns: Vec<String> = patterns
+            .split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(String::from)
+            .collect();
         
-        // Reset selection
-        self.selected_test_index = 0;
+        self.test_matches = if patterns.is_empty() {
+            self.runner.default_test_matches()
+        } else {
+            patterns
+        };
+        // Patterns chosen here take over from the config's testRegex and
+        // projects, and from Jest's own list of tests
+        self.path_filters.test_regex.clear();
+        self.projects.clear();
+        self.discover_tests(false);
+        Ok(())
     }
     
-    /// View individual test results from test output
-    pub fn view_test_results(&mut self) {
-        if self.view == AppView::TestRunning && !self.test_loading {
-            // Parse the results first
-            self.parse_test_results();
-            
-            // Only switch view if we found some tests
-            if !self.individual_tests.is_empty() {
-                self.view = AppView::TestResults;
-                self.selected_test_index = 0;
+    /// Report the outcome of a discovery started from the onboarding screen
+    fn report_discovery(&mut self, result: io::Result<()>) {
+        self.status_message = Some(match result {
+            // Discovery reports what it found once it's done
+            Ok(()) if self.discovery.is_some() => return,
+            Ok(()) if self.all_tests.is_empty() => "Still no test files found".to_string(),
+            Ok(()) => format!("Found {} test files", self.all_tests.len()),
+            Err(e) => format!("Could not search for tests: {}", e),
+        });
+    }
+    
+    /// Scan every discovered test file for focused (`.only`) tests
+    pub fn scan_focused_tests(&mut self) {
+        self.focused_tests.clear();
+        
+        for test_file in &self.all_tests {
+            let full_path = PathBuf::from(&self.search_path).join(test_file);
+            if let Ok(content) = std::fs::read_to_string(&full_path) {
+                let focused = test_parser::find_focused_tests(&content);
+                if !focused.is_empty() {
+                    self.focused_tests.insert(test_file.clone(), focused);
+                }
             }
         }
     }
     
-    /// Load and parse test file to extract individual tests without running them
-    pub fn load_and_parse_individual_tests(&mut self) -> io::Result<()> {
-        if self.tests.is_empty() {
-            return Ok(());
+    /// Refresh the focused test entry for a file from freshly read content
+    fn update_focused_tests(&mut self, test_file: &str, content: &str) {
+        let focused = test_parser::find_focused_tests(content);
+        if focused.is_empty() {
+            self.focused_tests.remove(test_file);
+        } else {
+            self.focused_tests.insert(test_file.to_string(), focused);
         }
-        
-        // Clear any previous test results
-        self.individual_tests.clear();
-        
-        // Get the test file path
-        let test_file = &self.tests[self.selected_index];
-        let full_path = PathBuf::from(&self.search_path).join(test_file);
-        
-        // Read the file content
-        let content = std::fs::read_to_string(&full_path)?;
-        
-        // Parse the file to find test definitions
-        self.parse_test_definitions(&content);
-        
-        Ok(())
     }
     
-    /// Parse test content to extract individual test definitions
-    pub fn parse_test_definitions(&mut self, content: &str) {
-        self.individual_tests.clear();
-        
-        // Common Jest/Testing Library test patterns
-        let test_patterns = [
-            // Jest/Testing Library pattern: test('description', () => {});
-            r#"(?:test|it)\s*\(\s*['"](.+?)['"]"#,
-            // Describe blocks: describe('description', () => {});
-            r#"describe\s*\(\s*['"](.+?)['"]"#,
-        ];
-        
-        // Process each line to find test definitions
-        let mut in_comment_block = false;
+    /// Scan every discovered test file for skipped, todo, and focused tests
+    pub fn run_audit(&mut self) {
+        self.audit_entries.clear();
+        self.focused_tests.clear();
         
-        for line in content.lines() {
-            let line = line.trim();
-            
-            // Skip empty lines
-            if line.is_empty() {
-                continue;
-            }
-            
-            // Handle comment blocks
-            if line.starts_with("/*") {
-                in_comment_block = true;
-            }
-            if line.contains("*/") {
-                in_comment_block = false;
-                continue;
-            }
-            if in_comment_block || line.starts_with("//") {
+        for test_file in &self.all_tests {
+            let full_path = PathBuf::from(&self.search_path).join(test_file);
+            let Ok(content) = std::fs::read_to_string(&full_path) else {
                 continue;
-            }
+            };
             
-            // Look for test patterns
-            for pattern in &test_patterns {
-                // Create a regex for the pattern
-                if let Ok(re) = regex::Regex::new(pattern) {
-                    // Find all matches in the line
-                    for cap in re.captures_iter(line) {
-                        if let Some(description) = cap.get(1) {
-                            let description = description.as_str().trim().to_string();
-                            
-                            // Skip if we already have this test
-                            if self.individual_tests.iter().any(|t| t.name == description) {
-                                continue;
-                            }
-                            
-                            // Add the test to our list
-                            self.individual_tests.push(TestInfo {
-                                name: description,
-                                passed: false, // We don't know yet
-                                error: None,
-                                duration: None,
-                            });
-                        }
-                    }
+            for modifier in test_parser::find_test_modifiers(&content) {
+                if modifier.kind == ModifierKind::Only {
+                    self.focused_tests
+                        .entry(test_file.clone())
+                        .or_default()
+                        .push(modifier.clone());
                 }
+                self.audit_entries.push(AuditEntry {
+                    file: test_file.clone(),
+                    modifier,
+                });
             }
         }
         
-        // Reset the selection index
-        self.selected_test_index = 0;
+        // Focused tests are the most urgent, so list them first
+        self.audit_entries.sort_by_key(|entry| match entry.modifier.kind {
+            ModifierKind::Only => 0,
+            ModifierKind::Skip => 1,
+            ModifierKind::Todo => 2,
+        });
+        
+        self.selected_audit_index = 0;
+        self.view = AppView::Audit;
     }
     
-    /// Run an individual test using Jest's testNamePattern option
-    pub fn run_individual_test(&mut self) -> io::Result<()> {
-        if self.individual_tests.is_empty() || self.selected_test_index >= self.individual_tests.len() {
-            return Ok(());
+    /// Open the file of the selected audit entry in the detail view, at the
+    /// modified declaration; going back returns to the audit
+    pub fn open_audit_entry(&mut self) {
+        let Some(entry) = self.audit_entries.get(self.selected_audit_index).cloned() else {
+            return;
+        };
+        
+        // The audit covers every file, so show them all if a filter hides this one
+        if !self.tests.contains(&entry.file) {
+            self.clear_filter();
         }
+        let Some(index) = self.tests.iter().position(|t| *t == entry.file) else {
+            self.status_message = Some(format!("{} isn't in the list", entry.file));
+            return;
+        };
         
-        // Get the currently selected test
-        let selected_test = &self.individual_tests[self.selected_test_index];
-        let test_name = &selected_test.name;
+        self.selected_index = index;
+        let _ = self.load_test_content();
+        self.show_detail_line(entry.modifier.line);
+        self.detail_return_view = Some(AppView::Audit);
+    }
+    
+    /// Compute per-directory statistics and show them
+    pub fn show_directory_stats(&mut self) {
+        self.directory_stats = stats::directory_stats(&self.tests, &self.history);
+        self.selected_directory_index = 0;
+        self.view = AppView::DirectoryStats;
+    }
+    
+    /// Select the first test file in the chosen directory and return to the list
+    pub fn jump_to_directory(&mut self) {
+        let Some(stats) = self.directory_stats.get(self.selected_directory_index) else {
+            return;
+        };
         
-        // Set up state for test running
-        self.view = AppView::TestRunning;
-        self.test_loading = true;
-        self.test_run_output = String::new(); // Clear previous output
-        self.running_individual_test = true; // Flag that we're running an individual test
+        let in_directory = |test_file: &String| match test_file.rsplit_once('/') {
+            Some((directory, _)) => directory == stats.directory,
+            None => stats.directory == ".",
+        };
+        if let Some(index) = self.tests.iter().position(in_directory) {
+            self.selected_index = index;
+        }
+        self.view = AppView::TestList;
+    }
+    
+    /// Rank the tests timed this session and the listed files' run history,
+    /// slowest first, and show them
+    pub fn show_leaderboard(&mut self) {
+        let Leaderboard { kind, sort, .. } = self.leaderboard;
+        self.leaderboard = Leaderboard::build(&self.session, &self.history, &self.tests);
+        self.leaderboard.kind = kind;
+        self.leaderboard.set_sort(sort);
+        self.selected_leaderboard_index = 0;
+        self.view = AppView::Leaderboard;
+    }
+    
+    /// Open the selected entry's file in the detail view, at the test's line
+    /// for test entries; going back returns to the leaderboard
+    pub fn open_leaderboard_entry(&mut self) {
+        let Some(entry) = self.leaderboard.entries().get(self.selected_leaderboard_index).cloned() else {
+            return;
+        };
+        let Some(index) = self.tests.iter().position(|t| *t == entry.file) else {
+            self.status_message = Some(format!("{} isn't in the list", entry.file));
+            return;
+        };
         
-        // Get the file path
-        let test_file = if !self.tests.is_empty() {
-            self.tests[self.selected_index].clone()
-        } else {
-            return Ok(());
+        self.selected_index = index;
+        let _ = self.load_test_content();
+        if let Some(line) = entry.line {
+            self.show_detail_line(line);
+        }
+        self.detail_return_view = Some(AppView::Leaderboard);
+    }
+    
+    /// Group the failures of the run being viewed by error message and show
+    /// them: the shown run's in the output view, every file's in the run
+    /// summary, and every queued file's in the queue
+    pub fn show_failure_groups(&mut self) {
+        let search_path = Path::new(&self.search_path);
+        self.failure_groups = match self.view {
+            AppView::RunSummary => FailureGroups::build(self.run_summary.iter().map(|summary| &summary.results), search_path),
+            AppView::Queue => FailureGroups::build(
+                self.queue.iter().flat_map(|queue| &queue.entries).filter_map(|entry| entry.results.as_ref()),
+                search_path,
+            ),
+            _ => FailureGroups::build(self.last_results.as_ref(), search_path),
+        };
+        self.selected_failure_group_index = 0;
+        self.failure_groups_return_view = self.view;
+        self.view = AppView::FailureGroups;
+    }
+    
+    /// Open the selected group's first failure in the detail view, at the
+    /// test's line; going back returns to the groups
+    pub fn open_failure_group(&mut self) {
+        let Some(failure) = self.failure_groups.groups
+            .get(self.selected_failure_group_index)
+            .and_then(|group| group.tests.first())
+            .cloned() else {
+            return;
+        };
+        let Some(index) = self.tests.iter().position(|t| *t == failure.file) else {
+            self.status_message = Some(format!("{} isn't in the list", failure.file));
+            return;
         };
         
-        // Create a thread to run the specific test
-        let test_name_pattern = test_name.replace("\"", "\\\""); // Escape quotes for shell
-        let test_name_pattern_clone = test_name_pattern.clone(); // Clone for use in closure
-        let test_file_clone = test_file.clone();
-        let project_dir = self.search_path.clone();
-        
-        // Create a channel to receive the results 
-        let (tx, rx) = mpsc::channel();
-        
-        // Spawn a thread to run the test
-        std::thread::spawn(move || {
-            // Execute the Jest test with testNamePattern option
-            let output = Command::new("npx")
-                .args([
-                    "jest", 
-                    &test_file_clone, 
-                    "--no-cache",
-                    "--testNamePattern", 
-                    &format!("^{}$", test_name_pattern_clone), // Exact match pattern
-                ])
-                .current_dir(PathBuf::from(&project_dir))
-                .output();
-            
-            // Send the running signal first
-            let _ = tx.send(TestResult::Running);
-            
-            // Then send the completed result
-            let result = match output {
-                Ok(output) => {
-                    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                    Ok((stdout, stderr))
-                },
-                Err(e) => Err(e)
-            };
-            
-            let _ = tx.send(TestResult::Completed(result));
+        self.selected_index = index;
+        let _ = self.load_test_content();
+        if let Some(line) = failure.line {
+            self.show_detail_line(line);
+        }
+        self.detail_return_view = Some(AppView::FailureGroups);
+    }
+    
+    /// Show the recorded runs of the highlighted test file, comparing the
+    /// newest with the one before it
+    pub fn show_run_history(&mut self) {
+        let Some(test_file) = self.tests.get(self.selected_index) else {
+            return;
+        };
+        if self.history.files.get(test_file).is_none_or(|runs| runs.is_empty()) {
+            self.status_message = Some(format!("No recorded runs of {}", test_file));
+            return;
+        }
+        self.selected_history_index = 0;
+        self.history_base_index = None;
+        self.view = AppView::RunHistory;
+    }
+    
+    /// Recorded runs of the highlighted test file, oldest first
+    pub fn run_history(&self) -> &[RunRecord] {
+        self.tests
+            .get(self.selected_index)
+            .and_then(|test_file| self.history.files.get(test_file))
+            .map_or(&[], Vec::as_slice)
+    }
+    
+    /// The runs compared in the run history view, earlier first, as indexes
+    /// into the recorded runs: the selected run and the marked one, or the
+    /// run before the selected one when none is marked
+    pub fn compared_runs(&self) -> Option<(usize, usize)> {
+        let selected = self.run_history().len().checked_sub(self.selected_history_index + 1)?;
+        let other = match self.history_base_index {
+            Some(base) if base != selected => base,
+            _ => selected.checked_sub(1)?,
+        };
+        Some((selected.min(other), selected.max(other)))
+    }
+    
+    /// What changed between the runs compared in the run history view
+    pub fn run_comparison(&self) -> Option<RunComparison> {
+        let (before, after) = self.compared_runs()?;
+        let runs = self.run_history();
+        Some(RunComparison::compare(&runs[before], &runs[after]))
+    }
+    
+    /// Mark the selected run as the one to compare others against, or unmark it
+    pub fn toggle_history_base(&mut self) {
+        let Some(selected) = self.run_history().len().checked_sub(self.selected_history_index + 1) else {
+            return;
+        };
+        self.history_base_index = (self.history_base_index != Some(selected)).then_some(selected);
+    }
+    
+    /// Show the snapshot files of the highlighted test file. From the file
+    /// viewer, the first snapshot of the test under the cursor is selected.
+    pub fn show_snapshots(&mut self) {
+        let Some(test_file) = self.tests.get(self.selected_index).cloned() else {
+            return;
+        };
+        let files = snapshots::find_snapshot_files(&PathBuf::from(&self.search_path).join(&test_file));
+        if files.is_empty() {
+            self.status_message = Some(format!("No snapshots for {}", test_file));
+            return;
+        }
+        
+        let cursor_test = (self.view == AppView::TestDetail)
+            .then(|| self.detail_cursor_test())
+            .flatten()
+            .map(|block| block.full_name());
+        let selected = cursor_test.and_then(|name| {
+            snapshots::rows(&files)
+                .iter()
+                .position(|&(file, entry)| entry.is_some_and(|entry| files[file].entries[entry].belongs_to(&name)))
         });
+        if selected.is_none() && self.view == AppView::TestDetail {
+            self.status_message = Some("No snapshots for the test under the cursor".to_string());
+        }
         
-        // Store the channel
-        self.test_receiver = Some(rx);
+        self.snapshots = files;
+        self.snapshots_return_view = self.view;
+        self.view = AppView::Snapshots;
+        self.select_snapshot_row(selected.unwrap_or(0));
+    }
+    
+    /// Select a row of the snapshots view, scrolling its file to the entry
+    pub fn select_snapshot_row(&mut self, index: usize) {
+        let rows = snapshots::rows(&self.snapshots);
+        self.selected_snapshot_index = index.min(rows.len().saturating_sub(1));
+        self.snapshot_scroll = match rows.get(self.selected_snapshot_index) {
+            Some(&(file, Some(entry))) => self.snapshots[file].entries[entry].line.saturating_sub(1),
+            _ => 0,
+        };
+    }
+    
+    /// Scroll the selected snapshot file by the given number of lines
+    pub fn scroll_snapshot(&mut self, delta: isize) {
+        let line_count = snapshots::rows(&self.snapshots)
+            .get(self.selected_snapshot_index)
+            .map_or(0, |&(file, _)| self.snapshots[file].content.lines().count());
+        self.snapshot_scroll = self.snapshot_scroll.saturating_add_signed(delta).min(line_count.saturating_sub(1));
+    }
+    
+    /// Open the test that wrote the selected snapshot in the detail view, at
+    /// the test's line; going back returns to the snapshots
+    pub fn open_snapshot_test(&mut self) {
+        let Some(&(file, entry)) = snapshots::rows(&self.snapshots).get(self.selected_snapshot_index) else {
+            return;
+        };
+        let entry = entry.map(|entry| self.snapshots[file].entries[entry].clone());
         
-        // Show initial "running test" message with command info
-        self.test_run_output = format!(
-            "Running individual test: \"{}\"\nFile: {}\nCommand: npx jest {} --testNamePattern=\"^{}$\" --no-cache\n",
-            test_name,
-            test_file,
-            test_file,
-            test_name_pattern
-        );
+        let _ = self.load_test_content();
+        self.detail_return_view = Some(AppView::Snapshots);
+        let Some(entry) = entry else {
+            return;
+        };
+        let test = self.current_test_blocks
+            .iter()
+            .find(|block| block.kind == BlockKind::Test && entry.belongs_to(&block.full_name()));
+        match test {
+            Some(test) => self.show_detail_line(test.start),
+            None => self.status_message = Some(format!("No test declared for snapshot `{}` (obsolete?)", entry.name)),
+        }
+    }
+    
+    /// Git blame already looked up for the selected test
+    pub fn selected_test_blame(&self) -> Option<&FailureBlame> {
+        let test = self.selected_test()?;
+        let test_file = self.tests.get(self.selected_index)?;
+        self.blame_cache.get(&(test_file.clone(), test.full_name.clone()))
+    }
+    
+    /// Run git blame for the selected failing test: on the lines declaring
+    /// it, and on the line of its failure's first stack frame in the project
+    pub fn blame_selected_test(&mut self) {
+        let Some(test) = self.selected_test().filter(|test| test.status == TestStatus::Failed).cloned() else {
+            self.status_message = Some("Git blame is looked up for failing tests".to_string());
+            return;
+        };
+        let Some(test_file) = self.tests.get(self.selected_index).cloned() else {
+            return;
+        };
         
-        Ok(())
+        let project_dir = Path::new(&self.search_path);
+        let full_path = project_dir.join(&test_file);
+        // Tests with the same title in different describe blocks are told
+        // apart by where they're declared
+        let test_blame = std::fs::read_to_string(&full_path)
+            .ok()
+            .and_then(|content| {
+                let line = test.line.or_else(|| {
+                    test_parser::find_test_blocks(&content)
+                        .into_iter()
+                        .find(|block| block.kind == BlockKind::Test && block.full_name() == test.full_name)
+                        .map(|block| block.start)
+                })?;
+                test_parser::find_test_block(&content, line)
+            })
+            .and_then(|(start, end)| {
+                let full_path = full_path.canonicalize().ok()?;
+                git::blame_lines(&full_path, start, end).ok().flatten()
+            });
+        
+        let root = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+        let frame_blame = test.error
+            .as_deref()
+            .and_then(|error| stack_trace::project_frames(error, project_dir).into_iter().next())
+            .and_then(|frame| {
+                let blame = git::blame_lines(&frame.path, frame.line, frame.line).ok().flatten()?;
+                let relative = frame.path.strip_prefix(&root).unwrap_or(&frame.path).display().to_string();
+                Some((format!("{}:{}", relative, frame.line), blame))
+            });
+        
+        if test_blame.is_none() && frame_blame.is_none() {
+            self.status_message = Some("No git history for this test (is it committed?)".to_string());
+        }
+        self.blame_cache.insert((test_file, test.full_name), FailureBlame { test: test_blame, frame: frame_blame });
     }
-
-    /// Check for test results from the async runner
-    pub fn check_test_results(&mut self) {
-        // If we have a receiver and we're in the test running state
-        if let Some(receiver) = &self.test_receiver {
-            // Try to receive a message without blocking
-            match receiver.try_recv() {
-                Ok(TestResult::Running) => {
-                    // Test is still running, keep the loading state
-                    self.test_loading = true;
-                },
-                Ok(TestResult::Completed(result)) => {
-                    // Test is complete, process the result
-                    self.test_loading = false;
-                    
-                    match result {
-                        Ok((stdout, stderr)) => {
-                            // Store raw command and output for display in TUI
-                            self.test_run_output = format!("{}\n{}", stdout, stderr);
-                        },
-                        Err(e) => {
-                            // Simple error message
-                            self.test_run_output = format!("Error running test: {}", e);
-                        }
-                    }
-                    
-                    // We're done with this receiver
-                    self.test_receiver = None;
-                    
-                    // Calculate appropriate scroll position to show last line at the bottom
-                    // First, get a rough estimate of the visible height (we won't know exact until render)
-                    let approx_visible_lines = 20; // Reasonable estimate for most terminals
-                    let line_count = self.test_run_output.lines().count();
-                    
-                    // Set scroll position to show the last page of output
-                    // This puts the last line at the bottom of the window instead of the top
-                    self.terminal_scroll = line_count.saturating_sub(approx_visible_lines).max(0);
-                    
-                    // If auto_show_test_results is enabled, try to parse and show individual tests
-                    if self.auto_show_test_results {
-                        self.auto_show_test_results = false; // Reset the flag
-                        
-                        // Parse and show test results if available
-                        self.parse_test_results();
-                        if !self.individual_tests.is_empty() {
-                            self.view = AppView::TestResults;
-                            self.selected_test_index = 0;
-                        }
-                    }
-                },
-                Err(mpsc::TryRecvError::Empty) => {
-                    // No message yet, keep waiting
-                },
-                Err(mpsc::TryRecvError::Disconnected) => {
-                    // Channel closed, reset state
-                    self.test_loading = false;
-                    if self.test_run_output.is_empty() {
-                        self.test_run_output = "Test execution failed or was cancelled".to_string();
-                    }
-                    self.test_receiver = None;
-                }
+    
+    /// Focused tests in the currently selected file
+    pub fn selected_focused_tests(&self) -> &[TestModifier] {
+        self.tests
+            .get(self.selected_index)
+            .and_then(|test_file| self.focused_tests.get(test_file))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+    
+    /// Move selection up in the list
+    pub fn previous(&mut self) {
+        if !self.tests.is_empty() {
+            self.selected_index = self.selected_index.saturating_sub(1);
+            if self.selected_index < self.scroll_offset {
+                self.scroll_offset = self.selected_index;
             }
         }
     }
 
+    /// Move selection down in the list
+    pub fn next(&mut self) {
+        if !self.tests.is_empty() {
+            let last_index = self.tests.len() - 1;
+            self.selected_index = (self.selected_index + 1).min(last_index);
+        }
+    }
+
+    /// Cycle to the next sort mode and re-sort the list
+    pub fn cycle_sort_mode(&mut self) {
+        self.set_sort_mode(self.sort_mode.next());
+    }
+    
+    /// Switch to a sort mode and re-sort the list
+    pub fn set_sort_mode(&mut self, sort_mode: SortMode) {
+        self.sort_mode = sort_mode;
+        if self.sort_mode == SortMode::Recency {
+            self.load_modified_times();
+        }
+        self.sort_tests();
+    }
+    
+    /// Look up when each test file was last changed.
+    ///
+    /// Uses the latest git commit for clean files and the file's mtime for files
+    /// with uncommitted changes, or for every file outside a git repository.
+    pub fn load_modified_times(&mut self) {
+        let search_path = Path::new(&self.search_path);
+        let root = search_path.canonicalize().unwrap_or_else(|_| search_path.to_path_buf());
+        let dirty = git::dirty_files(search_path).unwrap_or_default();
+        let committed: Vec<PathBuf> = self.all_tests
+            .iter()
+            .map(|test_file| root.join(test_file))
+            .filter(|path| !dirty.contains(path))
+            .collect();
+        let commit_times = git::last_commit_times(search_path, &committed).ok();
+        
+        let mtime = |path: &Path| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+        };
+        
+        self.modified_times = self.all_tests
+            .iter()
+            .filter_map(|test_file| {
+                let full_path = root.join(test_file);
+                let time = match &commit_times {
+                    Some(times) if !dirty.contains(&full_path) => times.get(&full_path).copied(),
+                    _ => mtime(&full_path),
+                }?;
+                Some((test_file.clone(), time))
+            })
+            .collect();
+    }
+    
+    /// Sort the test list by the current sort mode, keeping the selected file selected
+    pub fn sort_tests(&mut self) {
+        let selected = self.tests.get(self.selected_index).cloned();
+        
+        match self.sort_mode {
+            SortMode::Name => self.tests.sort(),
+            SortMode::LastRun => {
+                // Most recently run first, files without history last
+                let history = &self.history;
+                self.tests.sort_by(|a, b| {
+                    let a_run = history.last_run(a).map(|run| run.timestamp);
+                    let b_run = history.last_run(b).map(|run| run.timestamp);
+                    b_run.cmp(&a_run).then_with(|| a.cmp(b))
+                });
+            },
+            SortMode::Failed => {
+                // Files whose last run failed first, then alphabetically
+                let history = &self.history;
+                self.tests.sort_by_key(|test| {
+                    let failed = history.last_run(test).and_then(RunRecord::passed) == Some(false);
+                    (!failed, test.clone())
+                });
+            },
+            SortMode::Duration => {
+                // Slowest first, files without history last
+                let history = &self.history;
+                self.tests.sort_by(|a, b| {
+                    let a_duration = history.average_duration(a);
+                    let b_duration = history.average_duration(b);
+                    b_duration.cmp(&a_duration).then_with(|| a.cmp(b))
+                });
+            },
+            SortMode::Recency => {
+                // Most recently modified first, unknown last
+                let times = &self.modified_times;
+                self.tests.sort_by(|a, b| {
+                    times.get(b).cmp(&times.get(a)).then_with(|| a.cmp(b))
+                });
+            }
+        }
+        
+        if let Some(selected) = selected {
+            self.selected_index = self.tests.iter().position(|t| *t == selected).unwrap_or(0);
+        }
+    }
+    
+    /// Narrow the list to a filtered subset of all tests
+    fn set_filtered_tests(&mut self, filter: ListFilter, tests: Vec<String>) {
+        self.search = None;
+        self.tests = tests;
+        self.list_filter = Some(filter);
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+        self.sort_tests();
+    }
+    
+    /// Remove any list filter and show all tests again
+    pub fn clear_filter(&mut self) {
+        self.search = None;
+        self.tests = self.all_tests.clone();
+        self.list_filter = None;
+        self.scroll_offset = 0;
+        self.sort_tests();
+    }
+    
+    /// Start a fuzzy search of the list, or go back to editing the current one
+    pub fn open_search(&mut self) {
+        match self.search.as_mut() {
+            Some(search) => search.editing = true,
+            None => self.search = Some(Search::new(self.tests.clone())),
+        }
+    }
+    
+    /// Narrow the list to the files matching the search query
+    fn update_search(&mut self) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        self.tests = search.results();
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+    }
+    
+    /// End the search and show the list it narrowed again
+    pub fn close_search(&mut self) {
+        let Some(search) = self.search.take() else {
+            return;
+        };
+        self.tests = search.base;
+        self.scroll_offset = 0;
+        self.sort_tests();
+    }
+    
+    /// Handles key events while typing a search query
+    fn on_search_key(&mut self, key: KeyEvent) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) => self.close_search(),
+            (_, KeyCode::Enter) if search.query.is_empty() => self.close_search(),
+            (_, KeyCode::Enter) => search.editing = false,
+            (_, KeyCode::Backspace) if search.query.is_empty() => self.close_search(),
+            (_, KeyCode::Backspace) => {
+                search.query.pop();
+                self.update_search();
+            },
+            // Move through the results without leaving the search
+            (_, KeyCode::Up) | (KeyModifiers::CONTROL, KeyCode::Char('p') | KeyCode::Char('k')) => self.previous(),
+            (_, KeyCode::Down) | (KeyModifiers::CONTROL, KeyCode::Char('n') | KeyCode::Char('j')) => self.next(),
+            (modifiers, KeyCode::Char(c)) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                search.query.push(c);
+                self.update_search();
+            },
+            _ => {}
+        }
+    }
+    
+    /// Whether keys are going into a text input rather than triggering actions
+    fn is_typing(&self) -> bool {
+        self.prompt.is_some()
+            || self.command_line.is_some()
+            || self.search.as_ref().is_some_and(|search| search.editing)
+            || self.detail_search.as_ref().is_some_and(|search| search.editing)
+    }
+    
+    /// Whether a modal or popup is open and gets every key, like the quit
+    /// confirmation, the help overlay, or the actions menu
+    fn has_popup(&self) -> bool {
+        self.confirm_quit
+            || self.dry_run.is_some()
+            || self.missing_runner.is_some()
+            || self.action_menu.is_some()
+            || self.run_options.is_some()
+            || self.help_overlay.is_some()
+            || self.log_viewer.is_some()
+    }
+    
+    /// Open a prompt for the git ref to filter changed tests against
+    pub fn prompt_changed_since(&mut self) {
+        let default_ref = git::default_branch(Path::new(&self.search_path)).unwrap_or_default();
+        self.prompt = Some(Prompt::new(PromptKind::ChangedSinceRef, default_ref));
+    }
+    
+    /// Filter the list to tests changed since a git ref, plus their related tests
+    pub fn filter_changed_since(&mut self, git_ref: &str) -> io::Result<()> {
+        let search_path = Path::new(&self.search_path);
+        let changed = git::changed_files(search_path, git_ref)?;
+        let mut related = related::find_related_tests(&changed, &self.all_tests, search_path);
+        
+        // Jest follows the module graph, catching tests that only import the changes indirectly
+        let sources: Vec<PathBuf> = changed.into_iter().filter(|path| path.exists()).collect();
+        if self.runner == Runner::Jest && !sources.is_empty() {
+            match related::list_related_tests(self.list_tests_command(), &sources) {
+                Ok(found) => {
+                    related = self.all_tests
+                        .iter()
+                        .filter(|test_file| related.contains(test_file) || found.contains(test_file))
+                        .cloned()
+                        .collect();
+                },
+                Err(e) => {
+                    self.status_message = Some(format!("Showing only tests that import the changes directly: {}", e));
+                },
+            }
+        }
+        
+        self.set_filtered_tests(ListFilter::ChangedSince(git_ref.to_string()), related);
+        Ok(())
+    }
+    
+    /// Open a prompt for a source file to find related tests for, filled in with
+    /// the source file being looked at, if any
+    pub fn prompt_related_source(&mut self) {
+        let root = PathBuf::from(&self.search_path);
+        let selected_import_index = self.selected_import_index;
+        let initial = match self.view {
+            AppView::TestList if self.imports_focused => self.selected_imports()
+                .get(selected_import_index)
+                .and_then(|import| import.resolved.as_ref())
+                .map(|path| path.strip_prefix(&root).unwrap_or(path).display().to_string()),
+            AppView::TestDetail => self.detail_source.clone(),
+            AppView::Coverage => self.coverage
+                .as_ref()
+                .and_then(|coverage| coverage.files.get(self.selected_coverage_index))
+                .map(|(file, _)| file.clone()),
+            _ => None,
+        };
+        self.prompt = Some(Prompt::new(PromptKind::RelatedSource, initial.unwrap_or_default()));
+    }
+    
+    /// Filter the list to the tests that depend on a source file, as Jest's
+    /// `--findRelatedTests` finds them
+    pub fn filter_related_to(&mut self, source: &str) -> io::Result<()> {
+        let search_path = Path::new(&self.search_path);
+        let path = search_path.join(source);
+        if !path.is_file() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("{} is not a file", source)));
+        }
+        let path = path.canonicalize()?;
+        
+        // Vitest can't list related tests, so fall back to looking at imports
+        let related = match self.runner {
+            Runner::Jest => related::list_related_tests(self.list_tests_command(), &[path])?,
+            Runner::Vitest => related::find_related_tests(&[path], &self.all_tests, search_path),
+        };
+        
+        self.status_message = Some(match related.len() {
+            0 => format!("No tests depend on {}", source),
+            1 => format!("1 test file depends on {}", source),
+            count => format!("{} test files depend on {}", count, source),
+        });
+        self.view = AppView::TestList;
+        self.set_filtered_tests(ListFilter::RelatedTo(source.to_string()), related);
+        Ok(())
+    }
+    
+    /// Filter the list to tests whose last recorded run had failures
+    pub fn filter_failed(&mut self) {
+        let failed = self.all_tests
+            .iter()
+            .filter(|test_file| {
+                self.history
+                    .last_run(test_file)
+                    .is_some_and(|run| run.passed() == Some(false))
+            })
+            .cloned()
+            .collect();
+        self.set_filtered_tests(ListFilter::Failed, failed);
+    }
+    
+    /// Show only the files that failed their last run, or everything again
+    pub fn toggle_failed_filter(&mut self) {
+        if self.list_filter == Some(ListFilter::Failed) {
+            self.clear_filter();
+            return;
+        }
+        
+        let any_failed = self.all_tests
+            .iter()
+            .any(|test_file| self.history.last_run(test_file).is_some_and(|run| run.passed() == Some(false)));
+        if any_failed {
+            self.filter_failed();
+        } else {
+            self.status_message = Some("No files failed their last run".to_string());
+        }
+    }
+    
+    /// Act on the submitted text of a prompt
+    fn submit_prompt(&mut self, prompt: Prompt) {
+        let input = prompt.input.trim();
+        
+        match prompt.kind {
+            PromptKind::ChangedSinceRef => {
+                let git_ref = if input.is_empty() { "HEAD" } else { input };
+                if let Err(e) = self.filter_changed_since(git_ref) {
+                    self.status_message = Some(format!("Could not filter by git changes: {}", e));
+                }
+            },
+            PromptKind::RelatedSource => {
+                if input.is_empty() {
+                    return;
+                }
+                if let Err(e) = self.filter_related_to(input) {
+                    self.status_message = Some(format!("Could not find related tests: {}", e));
+                }
+            },
+            PromptKind::SaveLayout => {
+                if input.is_empty() {
+                    return;
+                }
+                match self.save_layout(input) {
+                    Ok(()) => self.status_message = Some(format!("Saved layout '{}'", input)),
+                    Err(e) => self.status_message = Some(format!("Could not save layout: {}", e)),
+                }
+                if self.view == AppView::LayoutPicker {
+                    self.show_layout_picker();
+                }
+            },
+            PromptKind::BenchmarkBaseline => {
+                if !input.is_empty() {
+                    self.start_benchmark(input);
+                }
+            },
+            PromptKind::SearchRoot => {
+                if !input.is_empty() {
+                    let result = self.change_search_root(input);
+                    self.report_discovery(result);
+                }
+            },
+            PromptKind::TestPatterns => {
+                let result = self.set_test_patterns(input);
+                self.report_discovery(result);
+            },
+            PromptKind::CoverageBase => {
+                let git_ref = if input.is_empty() { "HEAD" } else { input };
+                if let Err(e) = self.compare_coverage(git_ref) {
+                    self.status_message = Some(format!("Could not compare coverage: {}", e));
+                }
+            },
+            PromptKind::GotoLine => match input.parse() {
+                Ok(line) => self.goto_line(line),
+                Err(_) if input.is_empty() => {},
+                Err(_) => self.status_message = Some(format!("Not a line number: {}", input)),
+            },
+            PromptKind::ExtraArgs => match command::shell_split(input) {
+                Ok(args) => {
+                    self.status_message = Some(if args.is_empty() {
+                        "No extra arguments for the following runs".to_string()
+                    } else {
+                        format!("Following runs add: {}", input)
+                    });
+                    self.extra_args = args;
+                },
+                Err(e) => self.status_message = Some(format!("Could not use extra arguments: {}", e)),
+            },
+        }
+    }
+    
+    /// Handles key events while a prompt is open
+    fn on_prompt_key(&mut self, key: KeyEvent) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        
+        match key.code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Enter => {
+                if let Some(prompt) = self.prompt.take() {
+                    self.submit_prompt(prompt);
+                }
+            },
+            KeyCode::Backspace => {
+                prompt.input.pop();
+            },
+            KeyCode::Tab if prompt.kind == PromptKind::RelatedSource => {
+                prompt.complete_path(Path::new(&self.search_path));
+            },
+            KeyCode::Char(c) => prompt.input.push(c),
+            _ => {}
+        }
+    }
+    
+    /// Open the `:` command line
+    pub fn open_command_line(&mut self) {
+        self.command_line = Some(CommandLine::default());
+    }
+    
+    /// Full command lines that could complete the given input
+    fn command_completions(&self, input: &str) -> Vec<String> {
+        let Some((name, argument)) = input.split_once(' ') else {
+            return command_line::COMMAND_NAMES
+                .iter()
+                .filter(|command| command.starts_with(input))
+                .map(|command| format!("{} ", command))
+                .collect();
+        };
+        
+        let owned = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        let candidates = match name {
+            "run" => self.all_tests.iter().filter(|t| t.contains(argument)).cloned().collect(),
+            "filter" => owned(command_line::FILTER_NAMES),
+            "sort" => owned(command_line::SORT_NAMES),
+            "coverage" => owned(command_line::COVERAGE_NAMES),
+            "export" => owned(command_line::EXPORT_FORMATS),
+            "theme" => owned(command_line::THEME_NAMES),
+            "layout" => self.layout_presets().into_iter().map(|(name, _)| name).collect(),
+            "set" => owned(command_line::JEST_OPTIONS),
+            "unset" => self.jest_options.iter().map(|(key, _)| key.clone()).collect(),
+            "action" => self.config.actions.iter().map(|action| action.name.clone()).collect(),
+            _ => Vec::new(),
+        };
+        
+        candidates
+            .into_iter()
+            .filter(|candidate| name == "run" || candidate.starts_with(argument))
+            .map(|candidate| format!("{} {}", name, candidate))
+            .collect()
+    }
+    
+    /// Handles key events while the command line is open
+    fn on_command_line_key(&mut self, key: KeyEvent) {
+        let Some(line) = self.command_line.as_mut() else {
+            return;
+        };
+        
+        match key.code {
+            KeyCode::Esc => self.command_line = None,
+            KeyCode::Enter => {
+                let input = line.input.trim().to_string();
+                self.command_line = None;
+                if !input.is_empty() {
+                    if let Err(e) = self.command_history.push(&input) {
+                        self.toasts.push(ToastKind::Error, format!("Could not save command history: {}", e));
+                    }
+                    self.run_command_line(&input);
+                }
+            },
+            KeyCode::Tab | KeyCode::BackTab if line.is_completing() => {
+                line.cycle_completion(key.code == KeyCode::BackTab);
+            },
+            KeyCode::Tab => {
+                let input = line.input.clone();
+                let candidates = self.command_completions(&input);
+                if let Some(line) = self.command_line.as_mut() {
+                    line.start_completion(candidates);
+                }
+            },
+            KeyCode::Up => line.history_previous(&self.command_history.entries),
+            KeyCode::Down => line.history_next(&self.command_history.entries),
+            // Deleting past the `:` closes the command line, like in vim
+            KeyCode::Backspace if line.input.is_empty() => self.command_line = None,
+            KeyCode::Backspace => line.pop(),
+            KeyCode::Char(c) => line.push(c),
+            _ => {}
+        }
+    }
+    
+    /// Parse and execute an entered command, reporting errors in the status line
+    pub fn run_command_line(&mut self, input: &str) {
+        match ExCommand::parse(input) {
+            Ok(command) => self.execute_command(command),
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+    
+    /// Carry out a command from the command line
+    fn execute_command(&mut self, command: ExCommand) {
+        match command {
+            ExCommand::Run(None) => {
+                if let Err(e) = self.run_test() {
+                    self.status_message = Some(format!("Could not run test: {}", e));
+                }
+            },
+            ExCommand::Run(Some(pattern)) => self.run_matching(&pattern),
+            ExCommand::RunAll => self.run_all(),
+            ExCommand::Summary => self.show_run_summary(),
+            ExCommand::Coverage(None) => self.show_coverage(),
+            ExCommand::Coverage(Some(enabled)) => self.set_coverage_mode(enabled),
+            ExCommand::Filter(FilterCommand::Changed(git_ref)) => {
+                self.view = AppView::TestList;
+                let git_ref = git_ref
+                    .or_else(|| git::default_branch(Path::new(&self.search_path)))
+                    .unwrap_or_else(|| "HEAD".to_string());
+                if let Err(e) = self.filter_changed_since(&git_ref) {
+                    self.status_message = Some(format!("Could not filter by git changes: {}", e));
+                }
+            },
+            ExCommand::Filter(FilterCommand::Failed) => {
+                self.view = AppView::TestList;
+                self.filter_failed();
+            },
+            ExCommand::Filter(FilterCommand::Related(None)) => self.prompt_related_source(),
+            ExCommand::Filter(FilterCommand::Related(Some(source))) => {
+                if let Err(e) = self.filter_related_to(&source) {
+                    self.status_message = Some(format!("Could not find related tests: {}", e));
+                }
+            },
+            ExCommand::Filter(FilterCommand::Clear) => {
+                self.view = AppView::TestList;
+                self.clear_filter();
+            },
+            ExCommand::Sort(sort_mode) => {
+                self.view = AppView::TestList;
+                self.set_sort_mode(sort_mode);
+            },
+            ExCommand::Layout(name) => {
+                self.view = AppView::TestList;
+                match self.apply_layout(&name) {
+                    Ok(()) => self.status_message = Some(format!("Switched to layout '{}'", name)),
+                    Err(e) => self.status_message = Some(format!("Could not apply layout '{}': {}", name, e)),
+                }
+            },
+            ExCommand::Set(None) => {
+                let options = self.jest_options
+                    .iter()
+                    .map(|(key, value)| if value.is_empty() { key.clone() } else { format!("{}={}", key, value) })
+                    .collect::<Vec<_>>();
+                self.status_message = Some(if options.is_empty() {
+                    "No Jest options set".to_string()
+                } else {
+                    format!("Jest options: {}", options.join(" "))
+                });
+            },
+            ExCommand::Set(Some((key, value))) => {
+                let key = key.trim_start_matches('-').to_string();
+                self.jest_options.retain(|(existing, _)| *existing != key);
+                self.jest_options.push((key, value));
+            },
+            ExCommand::Unset(key) => {
+                let key = key.trim_start_matches('-');
+                self.jest_options.retain(|(existing, _)| existing != key);
+            },
+            ExCommand::Action(name) => {
+                match self.config.actions.iter().position(|action| action.name == name) {
+                    Some(index) => self.run_custom_action(index),
+                    None => self.status_message = Some(format!("No action named '{}'", name)),
+                }
+            },
+            ExCommand::Benchmark(baseline) => self.start_benchmark(&baseline),
+            ExCommand::Root(path) => {
+                self.view = AppView::TestList;
+                let result = self.change_search_root(&path);
+                self.report_discovery(result);
+            },
+            ExCommand::Patterns(patterns) => {
+                self.view = AppView::TestList;
+                let result = self.set_test_patterns(&patterns);
+                self.report_discovery(result);
+            },
+            ExCommand::Audit => self.run_audit(),
+            ExCommand::Stats => self.show_directory_stats(),
+            ExCommand::Slowest => self.show_leaderboard(),
+            ExCommand::Inspect => self.show_config_inspector(),
+            ExCommand::Config => self.show_config_picker(),
+            ExCommand::Watch => self.toggle_watch(),
+            ExCommand::Export(format, path) => self.export_session(format, path.as_deref()),
+            ExCommand::Theme(name) => self.set_theme(&name),
+            ExCommand::Reset => self.reset_session(),
+            ExCommand::Shell => self.pending_shell = true,
+            ExCommand::Line(line) => self.goto_line(line),
+            ExCommand::Quit => self.quit(),
+        }
+    }
+    
+    // We no longer need the update_scroll method as this is now managed by TestListWidget
+    
+    /// Load the content of the currently selected test file
+    pub fn load_test_content(&mut self) -> io::Result<()> {
+        if self.tests.is_empty() {
+            return Ok(());
+        }
+        
+        let test_file = self.tests[self.selected_index].clone();
+        let full_path = PathBuf::from(&self.search_path).join(&test_file);
+        
+        self.detail_source = None;
+        self.detail_coverage = self.covered_imports();
+        self.detail_coverage_index = 0;
+        self.reset_detail_position();
+        match std::fs::read_to_string(&full_path) {
+            Ok(content) => {
+                self.update_focused_tests(&test_file, &content);
+                self.current_test_blocks = test_parser::find_test_blocks(&content);
+                self.current_test_content = content;
+                self.view = AppView::TestDetail;
+                Ok(())
+            },
+            Err(e) => {
+                self.current_test_content = format!("Error reading file: {}", e);
+                self.current_test_blocks.clear();
+                self.view = AppView::TestDetail;
+                Err(e)
+            }
+        }
+    }
+    
+    /// Local imports of the highlighted test file, resolved once and cached
+    pub fn selected_imports(&mut self) -> &[LocalImport] {
+        let Some(test_file) = self.tests.get(self.selected_index) else {
+            return &[];
+        };
+        
+        if !self.import_cache.contains_key(test_file) {
+            let full_path = PathBuf::from(&self.search_path).join(test_file);
+            self.import_cache.insert(test_file.clone(), imports::find_local_imports(&full_path));
+        }
+        
+        &self.import_cache[test_file]
+    }
+    
+    /// Toggle the imports pane next to the test list
+    pub fn toggle_imports(&mut self) {
+        self.show_imports = !self.show_imports;
+        self.imports_focused = false;
+    }
+    
+    /// Move keyboard focus between the test list and the imports pane
+    pub fn toggle_imports_focus(&mut self) {
+        if self.show_imports {
+            self.imports_focused = !self.imports_focused;
+            self.selected_import_index = 0;
+        }
+    }
+    
+    /// Open the selected import in the detail view
+    pub fn open_selected_import(&mut self) -> io::Result<()> {
+        let selected_import_index = self.selected_import_index;
+        let Some(path) = self.selected_imports()
+            .get(selected_import_index)
+            .and_then(|import| import.resolved.clone()) else {
+            self.status_message = Some("That import could not be resolved to a file".to_string());
+            return Ok(());
+        };
+        
+        let content = std::fs::read_to_string(&path)?;
+        let root = PathBuf::from(&self.search_path);
+        let display_path = path.strip_prefix(&root).unwrap_or(&path).display().to_string();
+        
+        self.current_test_content = content;
+        self.current_test_blocks.clear();
+        self.detail_source = Some(display_path);
+        self.detail_coverage.clear();
+        self.reset_detail_position();
+        self.imports_focused = false;
+        self.view = AppView::TestDetail;
+        Ok(())
+    }
+    
+    /// What the current (or last) run is running, for labels
+    fn run_label(&self) -> Option<String> {
+        run_tabs::run_label(self.running_action.as_ref(), self.running_test_file.as_ref(), self.running_all)
+    }
+    
+    /// Whether a run is already in progress, telling the user if so
+    fn run_in_progress(&mut self) -> bool {
+        if self.test_loading {
+            self.status_message = Some("A run is already in progress (t shows it)".to_string());
+        }
+        self.test_loading
+    }
+    
+    /// Exchange the run fields with the run kept in a tab
+    fn swap_run_state(&mut self, index: usize) {
+        let tab = &mut self.run_tabs[index];
+        mem::swap(&mut self.last_command, &mut tab.last_command);
+        mem::swap(&mut self.test_run_output, &mut tab.test_run_output);
+        mem::swap(&mut self.terminal_scroll, &mut tab.terminal_scroll);
+        mem::swap(&mut self.follow_output, &mut tab.follow_output);
+        mem::swap(&mut self.test_loading, &mut tab.test_loading);
+        mem::swap(&mut self.test_receiver, &mut tab.test_receiver);
+        mem::swap(&mut self.individual_tests, &mut tab.individual_tests);
+        mem::swap(&mut self.selected_test_index, &mut tab.selected_test_index);
+        mem::swap(&mut self.collapsed_describes, &mut tab.collapsed_describes);
+        mem::swap(&mut self.auto_show_test_results, &mut tab.auto_show_test_results);
+        mem::swap(&mut self.running_individual_test, &mut tab.running_individual_test);
+        mem::swap(&mut self.run_started_at, &mut tab.run_started_at);
+        mem::swap(&mut self.running_test_file, &mut tab.running_test_file);
+        mem::swap(&mut self.running_action, &mut tab.running_action);
+        mem::swap(&mut self.last_results, &mut tab.last_results);
+        mem::swap(&mut self.running_all, &mut tab.running_all);
+        mem::swap(&mut self.output_frames, &mut tab.output_frames);
+        mem::swap(&mut self.selected_frame, &mut tab.selected_frame);
+        mem::swap(&mut self.results_path, &mut tab.results_path);
+    }
+    
+    /// Show the run kept in another tab
+    pub fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.run_tabs.len() {
+            return;
+        }
+        
+        // Keep the shown run in its tab, then bring out the other one
+        self.swap_run_state(self.active_tab);
+        self.swap_run_state(index);
+        self.active_tab = index;
+        
+        // A run that's still going (or printed nothing to parse) has no results to show
+        if self.view == AppView::TestResults {
+            if self.individual_tests.is_empty() && !self.test_loading {
+                self.parse_test_results();
+            }
+            if self.individual_tests.is_empty() {
+                self.view = AppView::TestRunning;
+            }
+        }
+    }
+    
+    /// Show the next (or previous) tab, wrapping around
+    pub fn cycle_tab(&mut self, forward: bool) {
+        let count = self.run_tabs.len();
+        let index = if forward {
+            (self.active_tab + 1) % count
+        } else {
+            (self.active_tab + count - 1) % count
+        };
+        self.switch_tab(index);
+    }
+    
+    /// Close the shown tab once its run has finished, showing the one before it
+    pub fn close_tab(&mut self) {
+        if self.run_tabs.len() < 2 {
+            return;
+        }
+        if self.test_loading {
+            self.status_message = Some("This tab's run is still going".to_string());
+            return;
+        }
+        
+        let closing = self.active_tab;
+        self.switch_tab(if closing == 0 { 1 } else { closing - 1 });
+        self.run_tabs.remove(closing);
+        if self.active_tab > closing {
+            self.active_tab -= 1;
+        }
+    }
+    
+    /// Give a new run a tab of its own if the shown tab's run is still going,
+    /// returning false if there's no room for another tab
+    fn prepare_run_tab(&mut self) -> bool {
+        if !self.test_loading {
+            return true;
+        }
+        
+        // Make room by closing the oldest finished tab
+        if self.run_tabs.len() >= MAX_RUN_TABS {
+            let finished = (0..self.run_tabs.len()).find(|&index| {
+                index != self.active_tab && !self.run_tabs[index].test_loading
+            });
+            let Some(finished) = finished else {
+                self.status_message = Some(format!("Already running {} tests at once", MAX_RUN_TABS));
+                return false;
+            };
+            self.run_tabs.remove(finished);
+            if self.active_tab > finished {
+                self.active_tab -= 1;
+            }
+        }
+        
+        self.tabs_opened += 1;
+        self.run_tabs.push(RunTab::new(json_results::tab_results_path(self.tabs_opened)));
+        self.switch_tab(self.run_tabs.len() - 1);
+        true
+    }
+    
+    /// Index of a tab whose run is still going, preferring the shown one
+    fn running_tab(&self) -> Option<usize> {
+        if self.test_loading {
+            return Some(self.active_tab);
+        }
+        (0..self.run_tabs.len()).find(|&index| index != self.active_tab && self.run_tabs[index].test_loading)
+    }
+    
+    /// How many runs are still going, across the tabs and the run queue
+    fn runs_going(&self) -> usize {
+        let background = self.run_tabs
+            .iter()
+            .enumerate()
+            .filter(|(index, tab)| *index != self.active_tab && tab.test_loading)
+            .count();
+        usize::from(self.test_loading) + background + self.queue.as_ref().map_or(0, RunQueue::running)
+    }
+    
+    /// Return to the output of a run that's still going
+    pub fn show_running(&mut self) {
+        let Some(index) = self.running_tab() else {
+            return;
+        };
+        self.switch_tab(index);
+        self.view = AppView::TestRunning;
+    }
+    
+    /// What's running while its output isn't on screen, for the header:
+    /// one run's label, and how many others are going
+    fn background_run(&self) -> Option<(String, usize)> {
+        if self.view == AppView::TestRunning && self.test_loading {
+            return None;
+        }
+        let index = self.running_tab()?;
+        let tabs = self.run_tab_summaries();
+        let others = tabs.iter().filter(|(_, state)| *state == RunTabState::Running).count() - 1;
+        Some((tabs[index].0.clone(), others))
+    }
+    
+    /// How long the shown run has been going, and how long runs of its file
+    /// usually take, when it's running a whole file that has run before
+    fn run_timing(&self) -> (u64, Option<u64>) {
+        let elapsed_ms = self.run_started_at.map_or(0, |started_at| started_at.elapsed().as_millis() as u64);
+        let estimate_ms = self.running_test_file
+            .as_ref()
+            .filter(|_| !self.running_individual_test && self.running_action.is_none())
+            .and_then(|test_file| self.history.average_duration(test_file));
+        (elapsed_ms, estimate_ms)
+    }
+    
+    /// Label and state of every tab, for the tab bar
+    fn run_tab_summaries(&self) -> Vec<(String, RunTabState)> {
+        self.run_tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| if index == self.active_tab {
+                (
+                    run_tabs::tab_label(self.running_action.as_ref(), self.running_test_file.as_ref(), self.running_all),
+                    run_tabs::tab_state(self.test_loading, self.last_results.as_ref(), &self.test_run_output),
+                )
+            } else {
+                tab.summary()
+            })
+            .collect()
+    }
+    
+    /// Run the currently selected test file with Jest
+    pub fn run_test(&mut self) -> io::Result<()> {
+        if self.tests.is_empty() || !self.runner_ready() || !self.prepare_run_tab() {
+            return Ok(());
+        }
+        
+        self.view = AppView::TestRunning;
+        self.test_loading = true;
+        self.test_run_output = String::new(); // Clear previous output
+        self.running_individual_test = false; // Flag that we're running a full test file
+        self.running_action = None;
+        self.running_all = None;
+        self.run_started_at = Some(std::time::Instant::now());
+        
+        // Build the command for the async task
+        let test_file = self.tests[self.selected_index].clone();
+        let command = self.with_json_results(self.jest_command(&test_file));
+        self.running_test_file = Some(test_file.clone());
+        
+        // Start the async test process
+        self.test_receiver = Some(test_runner::start_async_test(command.clone()));
+        self.last_command = Some(command);
+        
+        // Show initial "running test" message
+        self.begin_output(format!("Running test: {}\n", test_file));
+        
+        Ok(())
+    }
+    
+    /// Run the test files matching a pattern.
+    ///
+    /// A single matching file is selected and run as usual; otherwise the pattern
+    /// is handed to Jest, which treats it as a regex over test file paths.
+    pub fn run_matching(&mut self, pattern: &str) {
+        let matching: Vec<&String> = self.all_tests.iter().filter(|t| t.contains(pattern)).collect();
+        if let [test_file] = matching.as_slice() {
+            let test_file = (*test_file).clone();
+            if !self.tests.contains(&test_file) {
+                self.clear_filter();
+            }
+            self.selected_index = self.tests.iter().position(|t| *t == test_file).unwrap_or(0);
+            if let Err(e) = self.run_test() {
+                self.status_message = Some(format!("Could not run {}: {}", test_file, e));
+            }
+            return;
+        }
+        if !self.runner_ready() || !self.prepare_run_tab() {
+            return;
+        }
+        
+        self.view = AppView::TestRunning;
+        self.test_loading = true;
+        self.running_individual_test = false;
+        self.running_action = None;
+        self.running_all = None;
+        self.run_started_at = Some(std::time::Instant::now());
+        self.running_test_file = Some(pattern.to_string());
+        
+        let command = self.with_json_results(self.jest_command(pattern));
+        self.test_receiver = Some(test_runner::start_async_test(command.clone()));
+        self.last_command = Some(command);
+        self.begin_output(format!("Running tests matching: {}\n", pattern));
+    }
+    
+    /// Run every file in the list: all discovered test files, or just the
+    /// filtered ones, summarizing the results per file when it finishes
+    pub fn run_all(&mut self) {
+        if self.tests.is_empty() || !self.runner_ready() || !self.prepare_run_tab() {
+            return;
+        }
+        
+        self.view = AppView::TestRunning;
+        self.test_loading = true;
+        self.running_individual_test = false;
+        self.running_action = None;
+        self.running_test_file = None;
+        self.running_all = Some(self.tests.len());
+        self.run_started_at = Some(std::time::Instant::now());
+        
+        // Pass the files by path so exactly the listed files run
+        let command = self.with_json_results(self.jest_command_for(&self.tests).exact_paths());
+        self.test_receiver = Some(test_runner::start_async_test(command.clone()));
+        self.last_command = Some(command);
+        self.begin_output(format!("Running {} test files\n", self.tests.len()));
+    }
+    
+    /// Re-run only the tests that failed in the results being shown.
+    ///
+    /// Each file with failures is run with a name pattern matching exactly its
+    /// failing tests; files that failed to run at all are re-run whole.
+    pub fn rerun_failed(&mut self) {
+        if self.run_in_progress() {
+            return;
+        }
+        
+        // The output view parses results on demand, so make sure they're current
+        if self.view == AppView::TestRunning {
+            self.parse_test_results();
+        }
+        
+        let failed: Vec<&TestInfo> = self.individual_tests.iter().filter(|test| test.status == TestStatus::Failed).collect();
+        if failed.is_empty() {
+            self.status_message = Some("No failing tests to re-run".to_string());
+            return;
+        }
+        
+        // Tests scraped from printed output don't know their file, so use the selected one
+        let selected_file = self.tests.get(self.selected_index).cloned().unwrap_or_default();
+        let mut files: Vec<String> = Vec::new();
+        for test in &failed {
+            let file = test.file.clone().unwrap_or_else(|| selected_file.clone());
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+        
+        let mut command = self.jest_command_for(&files).exact_paths();
+        if !failed.iter().any(|test| test.is_file_failure()) {
+            let names: Vec<String> = failed.iter().map(|test| regex::escape(&test.full_name)).collect();
+            command = command.arg("--testNamePattern").arg(format!("^({})$", names.join("|")));
+        }
+        let command = self.with_json_results(command);
+        
+        let root = Path::new(&self.search_path).canonicalize().unwrap_or_default();
+        let label = match files.as_slice() {
+            [file] => Path::new(file).strip_prefix(&root).map_or(file.clone(), |path| path.display().to_string()),
+            files => format!("{} files", files.len()),
+        };
+        self.begin_output(format!(
+            "Re-running {} failing tests in {}\nCommand: {}\n",
+            failed.len(),
+            label,
+            command.display()
+        ));
+        
+        self.view = AppView::TestRunning;
+        self.test_loading = true;
+        self.running_individual_test = true; // Only some of the file's tests run
+        self.running_action = None;
+        self.running_all = None;
+        self.running_test_file = Some(label);
+        self.run_started_at = Some(std::time::Instant::now());
+        self.test_receiver = Some(test_runner::start_async_test(command.clone()));
+        self.last_command = Some(command);
+    }
+    
+    /// Re-run the last run with failing snapshots rewritten and obsolete ones
+    /// removed, keeping its files and test name pattern
+    pub fn update_snapshots(&mut self) {
+        if self.run_in_progress() {
+            return;
+        }
+        let Some(command) = self.last_command.clone().filter(|_| self.running_action.is_none()) else {
+            self.status_message = Some("No test run to update snapshots for".to_string());
+            return;
+        };
+        
+        let command = command.update_snapshots();
+        self.begin_output(format!("Updating snapshots\nCommand: {}\n", command.display()));
+        self.view = AppView::TestRunning;
+        self.test_loading = true;
+        self.run_started_at = Some(std::time::Instant::now());
+        self.test_receiver = Some(test_runner::start_async_test(command.clone()));
+        self.last_command = Some(command);
+    }
+    
+    /// Build the Jest command for running a test file from the project directory
+    pub fn jest_command(&self, test_file: &str) -> JestCommand {
+        self.jest_command_for(&[test_file.to_string()])
+    }
+    
+    /// Build a command that asks the runner about test files rather than running them
+    pub fn list_tests_command(&self) -> JestCommand {
+        self.pin_config(JestCommand::new(&self.search_path, self.runner))
+    }
+    
+    /// Pass the chosen config file, but only when there was a choice to make
+    fn pin_config(&self, command: JestCommand) -> JestCommand {
+        match &self.config_path {
+            Some(config) if self.config_files.len() > 1 => command.arg("--config").arg(config.to_string_lossy()),
+            _ => command,
+        }
+    }
+    
+    /// Build the Jest command for running several test files (or patterns) at once
+    pub fn jest_command_for(&self, targets: &[String]) -> JestCommand {
+        let mut command = self.config.runner.base_command(&self.search_path, self.runner).colors();
+        for target in targets {
+            command = command.arg(target);
+        }
+        if self.runner == Runner::Jest {
+            command = command.arg("--no-cache");
+        }
+        if self.coverage_mode {
+            command = command.coverage();
+        }
+        command = self.pin_config(command);
+        if self.runner == Runner::Jest {
+            for project in self.selected_projects(targets) {
+                command = command.arg(format!("--selectProjects={}", project));
+            }
+        }
+        for arg in &self.config.runner.args {
+            command = command.arg(arg);
+        }
+        
+        for (key, value) in &self.jest_options {
+            command = match value.as_str() {
+                "" => command.arg(format!("--{}", key)),
+                value => command.arg(format!("--{}={}", key, value)),
+            };
+        }
+        for arg in &self.extra_args {
+            command = command.arg(arg);
+        }
+        
+        command
+    }
+    
+    /// Have Jest also write structured results for the run, which are read
+    /// back when it finishes
+    pub fn with_json_results(&self, command: JestCommand) -> JestCommand {
+        command.json_results(&self.results_path)
+    }
+    
+    /// Build the Jest command for running a single test in a file, given its
+    /// full name (describe names and test name joined with spaces).
+    ///
+    /// The name is matched literally, so names with characters like `(`, `+`,
+    /// or `$` select just that test rather than a regex.
+    pub fn individual_test_command(&self, test_file: &str, test_name: &str) -> JestCommand {
+        self.jest_command(test_file)
+            .arg("--testNamePattern")
+            .arg(format!("^{}$", regex::escape(test_name)))
+    }
+    
+    /// The command the current view's run action would execute
+    pub fn pending_command(&self) -> Option<JestCommand> {
+        let test_file = self.tests.get(self.selected_index)?;
+        match self.view {
+            AppView::TestList => Some(self.with_json_results(self.jest_command(test_file))),
+            AppView::TestDetail => Some(self.with_json_results(match self.detail_cursor_test() {
+                Some(test) => self.individual_test_command(test_file, &test.full_name()),
+                None => self.jest_command(test_file),
+            })),
+            AppView::TestResults => self
+                .selected_test()
+                .map(|test| self.with_json_results(self.individual_test_command(test_file, &test.full_name))),
+            AppView::TestRunning => self.last_command.clone(),
+            _ => None,
+        }
+    }
+    
+    /// Show the pending command without running it
+    pub fn show_dry_run(&mut self) {
+        self.dry_run = self.pending_command();
+        if self.dry_run.is_none() {
+            self.status_message = Some("Nothing to run here".to_string());
+        }
+    }
+    
+    /// Handle a key press while the dry-run popup is open
+    fn on_dry_run_key(&mut self, key: KeyEvent) {
+        if let (KeyCode::Char('y'), Some(command)) = (key.code, &self.dry_run) {
+            let shell_command = command.shell_command();
+            self.copy_text(&shell_command, "command");
+            return;
+        }
+        
+        self.dry_run = None;
+    }
+    
+    /// Check the runner is installed before starting a test run, explaining how
+    /// to install it when it isn't. Runs through the package.json `test` script
+    /// are left to the script, which may get the runner from elsewhere.
+    fn runner_ready(&mut self) -> bool {
+        let project_dir = Path::new(&self.search_path);
+        if self.runner.is_installed(project_dir) || self.config.runner.test_script.use_script(project_dir, self.runner) {
+            return true;
+        }
+        
+        self.show_missing_runner(format!(
+            "{} wasn't found in node_modules in {} or any directory above it, or on your PATH.",
+            self.runner.label(),
+            self.search_path
+        ));
+        false
+    }
+    
+    /// Explain that the runner is missing, and how to install it
+    fn show_missing_runner(&mut self, reason: String) {
+        self.missing_runner = Some(reason);
+    }
+    
+    /// The command that installs the runner in the project
+    fn install_command(&self) -> String {
+        PackageManager::detect(Path::new(&self.search_path)).add_dev_command(self.runner.program_args()[0])
+    }
+    
+    /// Handle a key press while the missing runner popup is open
+    fn on_missing_runner_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('y') {
+            let install_command = self.install_command();
+            self.copy_text(&install_command, "install command");
+            return;
+        }
+        
+        self.missing_runner = None;
+    }
+    
+    /// Open the custom actions menu
+    pub fn show_action_menu(&mut self) {
+        if self.config.actions.is_empty() {
+            self.status_message = Some("No custom actions configured (add [[actions]] to config.toml)".to_string());
+            return;
+        }
+        self.action_menu = Some(0);
+    }
+    
+    /// Every flag in the run options popup, and whether it's on
+    fn run_option_states(&self) -> Vec<(RunOption, bool)> {
+        RUN_OPTIONS
+            .iter()
+            .map(|&option| {
+                let enabled = match option.flag {
+                    COVERAGE_FLAG => self.coverage_mode,
+                    flag => self.jest_options.iter().any(|(key, value)| key == flag && value.is_empty()),
+                };
+                (option, enabled)
+            })
+            .collect()
+    }
+    
+    /// Switch a flag from the run options popup on or off for the following runs
+    fn toggle_run_option(&mut self, option: RunOption) {
+        if option.flag == COVERAGE_FLAG {
+            self.set_coverage_mode(!self.coverage_mode);
+            return;
+        }
+        
+        let before = self.jest_options.len();
+        self.jest_options.retain(|(key, _)| key != option.flag);
+        if self.jest_options.len() == before {
+            self.jest_options.push((option.flag.to_string(), String::new()));
+        }
+    }
+    
+    /// Handle a key press while the run options popup is open
+    fn on_run_options_key(&mut self, key: KeyEvent) {
+        let Some(selected) = self.run_options else {
+            return;
+        };
+        
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.run_options = Some(selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.run_options = Some((selected + 1).min(RUN_OPTIONS.len() - 1));
+            },
+            KeyCode::Char(' ') => self.toggle_run_option(RUN_OPTIONS[selected]),
+            KeyCode::Char('e') => {
+                self.run_options = None;
+                let args: Vec<String> = self.extra_args.iter().map(|arg| command::shell_quote(arg)).collect();
+                self.prompt = Some(Prompt::new(PromptKind::ExtraArgs, args.join(" ")));
+            },
+            KeyCode::Enter => {
+                self.run_options = None;
+                if let Err(e) = self.run_test() {
+                    self.status_message = Some(format!("Could not run test: {}", e));
+                }
+            },
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('q') | KeyCode::Char('o') => self.run_options = None,
+            _ => {}
+        }
+    }
+    
+    /// Handles a key press while the help overlay is open
+    fn on_help_overlay_key(&mut self, key: KeyEvent) {
+        let Some(scroll) = self.help_overlay else {
+            return;
+        };
+        
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.help_overlay = Some(scroll.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => {
+                let controls = self.help_bar().controls;
+                let last_line = HelpOverlayWidget::new(&controls, 0).line_count().saturating_sub(1);
+                self.help_overlay = Some((scroll + 1).min(last_line));
+            },
+            KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') => self.help_overlay = None,
+            _ => {}
+        }
+    }
+    
+    /// Handles a key press while the log viewer is open
+    fn on_log_viewer_key(&mut self, key: KeyEvent) {
+        let Some(scroll) = self.log_viewer else {
+            return;
+        };
+        
+        let oldest = self.logs.lines().len().saturating_sub(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.log_viewer = Some((scroll + 1).min(oldest)),
+            KeyCode::Down | KeyCode::Char('j') => self.log_viewer = Some(scroll.saturating_sub(1)),
+            KeyCode::PageUp => self.log_viewer = Some((scroll + 10).min(oldest)),
+            KeyCode::PageDown => self.log_viewer = Some(scroll.saturating_sub(10)),
+            KeyCode::Home | KeyCode::Char('g') => self.log_viewer = Some(oldest),
+            KeyCode::End | KeyCode::Char('G') => self.log_viewer = Some(0),
+            KeyCode::Esc | KeyCode::F(12) | KeyCode::Char('q') => self.log_viewer = None,
+            _ => {}
+        }
+    }
+    
+    /// The help bar listing the current view's keys
+    fn help_bar(&self) -> HelpBarWidget<'static> {
+        match self.view {
+            AppView::TestList if self.all_tests.is_empty() && self.discovery.is_none() => HelpBarWidget::for_onboarding(),
+            AppView::TestList => HelpBarWidget::for_test_list(),
+            AppView::TestDetail => HelpBarWidget::for_test_detail(),
+            AppView::TestRunning => HelpBarWidget::for_test_terminal(),
+            AppView::TestResults => HelpBarWidget::for_test_results(),
+            AppView::Audit => HelpBarWidget::for_audit(),
+            AppView::DirectoryStats => HelpBarWidget::for_directory_stats(),
+            AppView::Leaderboard => HelpBarWidget::for_leaderboard(),
+            AppView::FailureGroups => HelpBarWidget::for_failure_groups(),
+            AppView::Snapshots => HelpBarWidget::for_snapshots(),
+            AppView::RunHistory => HelpBarWidget::for_run_history(),
+            AppView::ConfigPicker => HelpBarWidget::for_config_picker(),
+            AppView::ProjectPicker => HelpBarWidget::for_project_picker(),
+            AppView::WorkspacePicker => HelpBarWidget::for_workspace_picker(),
+            AppView::ConfigInspector => HelpBarWidget::for_config_inspector(),
+            AppView::LayoutPicker => HelpBarWidget::for_layout_picker(),
+            AppView::Benchmark => HelpBarWidget::for_benchmark(),
+            AppView::RunSummary => HelpBarWidget::for_run_summary(),
+            AppView::Queue => HelpBarWidget::for_queue(),
+            AppView::Coverage => HelpBarWidget::for_coverage(),
+            AppView::CoverageDiff => HelpBarWidget::for_coverage_diff(),
+        }
+    }
+    
+    /// Values for the placeholders custom actions can use in the current view
+    fn action_placeholders(&self) -> HashMap<&'static str, String> {
+        let search_path = Path::new(&self.search_path);
+        let root = search_path.canonicalize().unwrap_or_else(|_| search_path.to_path_buf());
+        
+        let mut values = HashMap::from([("dir", root.display().to_string())]);
+        if let Some(test_file) = self.tests.get(self.selected_index) {
+            values.insert("file", test_file.clone());
+            values.insert("path", root.join(test_file).display().to_string());
+        }
+        if self.view == AppView::TestResults
+            && let Some(test) = self.selected_test() {
+            values.insert("test", test.name.clone());
+        }
+        
+        values
+    }
+    
+    /// Run a custom action, showing its output in the test output view
+    pub fn run_custom_action(&mut self, index: usize) {
+        let Some(action) = self.config.actions.get(index).cloned() else {
+            return;
+        };
+        
+        let script = match action.expand(&self.action_placeholders()) {
+            Ok(script) => script,
+            Err(e) => {
+                self.status_message = Some(format!("Can't run '{}': {}", action.name, e));
+                return;
+            }
+        };
+        if !self.prepare_run_tab() {
+            return;
+        }
+        
+        self.view = AppView::TestRunning;
+        self.test_loading = true;
+        self.running_individual_test = false;
+        self.run_started_at = Some(std::time::Instant::now());
+        self.running_test_file = None;
+        self.running_all = None;
+        self.running_action = Some(action.name.clone());
+        
+        let command = JestCommand::shell(&self.search_path, &script);
+        self.test_receiver = Some(test_runner::start_async_test(command.clone()));
+        self.begin_output(format!("Running action: {}\nCommand: {}\n", action.name, script));
+        self.last_command = Some(command);
+    }
+    
+    /// Handle a key press while the custom actions menu is open
+    fn on_action_menu_key(&mut self, key: KeyEvent) {
+        let Some(selected) = self.action_menu else {
+            return;
+        };
+        
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.action_menu = Some(selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last_index = self.config.actions.len().saturating_sub(1);
+                self.action_menu = Some((selected + 1).min(last_index));
+            },
+            KeyCode::Enter | KeyCode::Right => {
+                self.action_menu = None;
+                self.run_custom_action(selected);
+            },
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('q') | KeyCode::Char('a') => self.action_menu = None,
+            _ => {}
+        }
+    }
+    
+    /// Navigate back based on context
+    pub fn go_back(&mut self) {
+        if self.view == AppView::TestDetail
+            && let Some(view) = self.detail_return_view.take() {
+            self.view = view;
+        } else if self.view == AppView::FailureGroups {
+            self.view = self.failure_groups_return_view;
+        } else if self.view == AppView::Snapshots {
+            self.view = self.snapshots_return_view;
+        } else if self.view == AppView::CoverageDiff {
+            self.view = AppView::Coverage;
+        } else if self.view == AppView::TestRunning && self.running_individual_test && !self.individual_tests.is_empty() {
+            // If we're running an individual test, go back to test results view
+            self.view = AppView::TestResults;
+            self.terminal_scroll = 0;
+            self.running_individual_test = false;
+        } else {
+            // Otherwise go back to the test list
+            self.view = AppView::TestList;
+            self.terminal_scroll = 0;
+            self.running_individual_test = false;
+        }
+    }
+    
+    /// Jump to the next (or previous) stack frame in the output that points
+    /// into the project, scrolling the output to it
+    pub fn jump_to_frame(&mut self, forward: bool) {
+        self.output_frames = stack_trace::project_frames(&self.test_run_output, Path::new(&self.search_path));
+        let count = self.output_frames.len();
+        if count == 0 {
+            self.selected_frame = None;
+            self.status_message = Some("No stack frames pointing into the project".to_string());
+            return;
+        }
+        
+        let index = match (self.selected_frame.filter(|&index| index < count), forward) {
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+        let frame = &self.output_frames[index];
+        
+        // Keep a few lines of the failure above the frame in view
+        self.terminal_scroll = self.output_rows().row_of(frame.output_line).saturating_sub(5);
+        self.follow_output = false;
+        let root = Path::new(&self.search_path).canonicalize().unwrap_or_default();
+        self.status_message = Some(format!(
+            "Frame {}/{}: {}:{}:{} (Enter shows it, o opens it in your editor)",
+            index + 1,
+            count,
+            frame.path.strip_prefix(&root).unwrap_or(&frame.path).display(),
+            frame.line,
+            frame.column
+        ));
+        self.selected_frame = Some(index);
+    }
+    
+    /// The stack frame jumped to in the output, if the output still shows it
+    fn selected_output_frame(&self) -> Option<&StackFrame> {
+        let frame = self.output_frames.get(self.selected_frame?)?;
+        let location = format!(":{}:{}", frame.line, frame.column);
+        let line = self.test_run_output.lines().nth(frame.output_line)?;
+        ansi::strip(line).contains(&location).then_some(frame)
+    }
+    
+    /// Show the file of the stack frame jumped to in the detail view, at its line
+    pub fn open_selected_frame(&mut self) -> io::Result<()> {
+        let Some(frame) = self.selected_output_frame().cloned() else {
+            return Ok(());
+        };
+        
+        let root = Path::new(&self.search_path).canonicalize().unwrap_or_default();
+        self.current_test_content = std::fs::read_to_string(&frame.path)?;
+        self.current_test_blocks.clear();
+        self.detail_source = Some(frame.path.strip_prefix(&root).unwrap_or(&frame.path).display().to_string());
+        self.detail_coverage.clear();
+        self.show_detail_line(frame.line);
+        self.detail_return_view = Some(AppView::TestRunning);
+        self.view = AppView::TestDetail;
+        Ok(())
+    }
+    
+    /// Scroll terminal output up
+    pub fn scroll_up(&mut self, amount: usize) {
+        if self.view == AppView::TestRunning {
+            self.terminal_scroll = self.terminal_scroll.saturating_sub(amount);
+            self.follow_output = false;
+        }
+    }
+    
+    /// Scroll terminal output down
+    pub fn scroll_down(&mut self, amount: usize) {
+        if self.view == AppView::TestRunning {
+            // Count rows in output to determine max scroll
+            let row_count = self.output_row_count();
+            self.terminal_scroll = (self.terminal_scroll + amount).min(row_count.saturating_sub(1));
+        }
+    }
+    
+    /// The output's rows as shown, with any folds and filter applied
+    pub fn output_rows(&self) -> OutputRows {
+        OutputRows::new(&self.test_run_output, self.fold_frames, self.failures_only)
+    }
+    
+    /// How many rows the output takes as shown
+    fn output_row_count(&self) -> usize {
+        self.output_rows().rows.len()
+    }
+    
+    /// Change how the output is shown, keeping the same part of it in view
+    fn relayout_output(&mut self, change: impl FnOnce(&mut Self)) {
+        let line = self.output_rows().line_of(self.terminal_scroll);
+        change(self);
+        self.terminal_scroll = self.output_rows().row_of(line);
+    }
+    
+    /// Fold or unfold the library stack frames in the output
+    pub fn toggle_frame_folds(&mut self) {
+        self.relayout_output(|app| app.fold_frames = !app.fold_frames);
+        self.status_message = Some(if self.fold_frames {
+            "Library stack frames folded (z shows them)".to_string()
+        } else {
+            "Library stack frames shown (z folds them)".to_string()
+        });
+    }
+    
+    /// Show only the failures in the output, or everything again
+    pub fn toggle_failures_only(&mut self) {
+        self.relayout_output(|app| app.failures_only = !app.failures_only);
+        self.follow_output = false;
+    }
+    
+    /// Copy the test command to the clipboard
+    pub fn copy_command_to_clipboard(&mut self) {
+        if self.view != AppView::TestRunning {
+            return;
+        }
+        
+        // Copy the command that was actually run - cd to project root, then run Jest
+        if let Some(command) = &self.last_command {
+            let shell_command = command.shell_command();
+            self.copy_text(&shell_command, "command");
+        }
+    }
+    
+    /// Copy the selected test's error message, or the stack trace below it
+    pub fn copy_selected_failure(&mut self, stack: bool) {
+        let Some(error) = self.selected_test().and_then(|test| test.error.clone()) else {
+            self.status_message = Some("The selected test has no error to copy".to_string());
+            return;
+        };
+        
+        let (message, trace) = stack_trace::split_failure(&error);
+        if !stack {
+            self.copy_text(&message, "error message");
+        } else if trace.is_empty() {
+            self.status_message = Some("The error has no stack trace".to_string());
+        } else {
+            self.copy_text(&trace, "stack trace");
+        }
+    }
+    
+    /// Copy the selected test's name, with its enclosing describe names
+    pub fn copy_selected_test_name(&mut self) {
+        if let Some(name) = self.selected_test().map(|test| test.full_name.clone()) {
+            self.copy_text(&name, "test name");
+        }
+    }
+    
+    /// Copy the command the current view's run action would execute, exactly as
+    /// it would be run
+    pub fn copy_pending_command(&mut self) {
+        match self.pending_command() {
+            Some(command) => {
+                let shell_command = command.shell_command();
+                self.copy_text(&shell_command, &format!("command: {}", shell_command));
+            },
+            None => self.status_message = Some("Nothing to run here".to_string()),
+        }
+    }
+    
+    /// Copy text to the clipboard, saying what was copied (or why it couldn't be)
+    fn copy_text(&mut self, text: &str, what: &str) {
+        match copy_to_clipboard(text, self.config.clipboard.command.as_deref()) {
+            Ok(()) => self.toasts.push(ToastKind::Success, format!("Copied {}", what)),
+            Err(e) => {
+                warn!("Could not copy {}: {}", what, e);
+                self.toasts.push(ToastKind::Error, format!("Could not copy {}: {}", what, e));
+            },
+        }
+    }
+    
+    /// Populate the individual results of the last run.
+    ///
+    /// Uses the JSON results Jest wrote when there are any, and falls back to
+    /// scraping the printed output (e.g. for custom actions that run Jest).
+    pub fn parse_test_results(&mut self) {
+        let Some(results) = &self.last_results else {
+            self.scrape_test_results();
+            return;
+        };
+        
+        self.individual_tests = results.test_results.iter().flat_map(file_test_infos).collect();
+        self.selected_test_index = 0;
+        self.collapsed_describes.clear();
+    }
+    
+    /// Show the run summary from the last run of every listed file
+    pub fn show_run_summary(&mut self) {
+        if self.run_summary.is_none() {
+            self.status_message = Some("No run summary yet (R runs every listed file)".to_string());
+            return;
+        }
+        self.view = AppView::RunSummary;
+    }
+    
+    /// Show the individual results of the file selected in the run summary
+    pub fn open_summary_file(&mut self) {
+        let Some(summary) = &self.run_summary else {
+            return;
+        };
+        let Some(outcome) = summary.files.get(self.selected_summary_index) else {
+            return;
+        };
+        
+        let tests: Vec<TestInfo> = summary.results.test_results
+            .iter()
+            .filter(|file| file.name == outcome.path)
+            .flat_map(file_test_infos)
+            .collect();
+        let test_file = outcome.file.clone();
+        self.show_file_results(test_file, tests, AppView::RunSummary);
+    }
+    
+    /// Show a file's individual results opened from a summary view, returning
+    /// to that view on ←
+    fn show_file_results(&mut self, test_file: String, tests: Vec<TestInfo>, return_view: AppView) {
+        if tests.is_empty() {
+            self.status_message = Some(format!("No passed or failed tests in {}", test_file));
+            return;
+        }
+        
+        // Select the file in the list so runs from the results view target it
+        if !self.tests.contains(&test_file) && self.all_tests.contains(&test_file) {
+            self.clear_filter();
+        }
+        if let Some(index) = self.tests.iter().position(|t| *t == test_file) {
+            self.selected_index = index;
+        }
+        
+        self.individual_tests = tests;
+        self.selected_test_index = 0;
+        self.collapsed_describes.clear();
+        self.results_return_view = Some(return_view);
+        self.view = AppView::TestResults;
+    }
+    
+    /// Mark or unmark the selected file for a batch run, moving to the next file
+    pub fn toggle_marked(&mut self) {
+        let Some(test_file) = self.tests.get(self.selected_index).cloned() else {
+            return;
+        };
+        match self.marked_files.iter().position(|marked| *marked == test_file) {
+            Some(index) => {
+                self.marked_files.remove(index);
+            },
+            None => self.marked_files.push(test_file),
+        }
+        self.next();
+    }
+    
+    /// Add the marked files to the run queue and show it
+    pub fn enqueue_marked(&mut self) {
+        if self.marked_files.is_empty() || !self.runner_ready() {
+            return;
+        }
+        
+        // Start a fresh queue unless the last one is still going
+        if self.queue.as_ref().is_none_or(RunQueue::is_finished) {
+            self.queue = Some(RunQueue::new(&self.config.queue));
+            self.selected_queue_index = 0;
+        }
+        let queued = self.queue.as_ref().map_or(0, |queue| queue.entries.len());
+        
+        // Each file gets its own results file so concurrent runs don't clash
+        let runs: Vec<_> = std::mem::take(&mut self.marked_files)
+            .into_iter()
+            .enumerate()
+            .map(|(index, test_file)| {
+                let results_path = json_results::numbered_results_path(queued + index);
+                let command = self.jest_command(&test_file).json_results(&results_path);
+                (test_file, command, results_path)
+            })
+            .collect();
+        
+        if let Some(queue) = self.queue.as_mut() {
+            for (test_file, command, results_path) in runs {
+                queue.push(&test_file, command, results_path);
+            }
+        }
+        self.view = AppView::Queue;
+    }
+    
+    /// Turn coverage collection on or off for the following runs
+    pub fn set_coverage_mode(&mut self, enabled: bool) {
+        self.coverage_mode = enabled;
+        self.status_message = Some(if enabled {
+            "Runs now collect coverage (V shows it after a run)".to_string()
+        } else {
+            "Runs no longer collect coverage".to_string()
+        });
+    }
+    
+    /// Read the coverage summary a run that just finished wrote, if it wrote one
+    fn load_coverage(&mut self, run_duration: std::time::Duration) {
+        let search_path = Path::new(&self.search_path);
+        let path = coverage::summary_path(search_path);
+        
+        // Ignore a summary left behind by an earlier run
+        let written_by_run = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age <= run_duration));
+        if !written_by_run {
+            return;
+        }
+        
+        match CoverageSummary::load(&path, search_path) {
+            Ok(mut summary) => {
+                // Line markers are a bonus; runners configured without the json reporter skip them
+                let _ = summary.load_line_hits(&coverage::final_path(search_path), search_path);
+                self.status_message = summary.total.lines.pct.map(|pct| {
+                    format!("Coverage: {:.1}% of lines (V shows every file)", pct)
+                });
+                self.record_coverage(&summary);
+                self.coverage = Some(summary);
+                self.selected_coverage_index = 0;
+            },
+            Err(e) => self.status_message = Some(format!("Could not read coverage: {}", e)),
+        }
+    }
+    
+    /// Keep the coverage a run wrote, with the commit it ran on, for later
+    /// runs to be compared against
+    fn record_coverage(&mut self, summary: &CoverageSummary) {
+        let dir = Path::new(&self.search_path);
+        let commit = git::resolve_commit(dir, "HEAD").ok();
+        let dirty = git::dirty_files(dir).is_ok_and(|files| !files.is_empty());
+        self.coverage_history.record(summary, commit, dirty);
+        let _ = self.coverage_history.save();
+    }
+    
+    /// Open a prompt for the git ref to compare the last coverage run against
+    pub fn prompt_coverage_base(&mut self) {
+        if self.coverage.is_none() {
+            self.status_message = Some("No coverage yet (% turns on coverage for the next runs)".to_string());
+            return;
+        }
+        let default_ref = git::default_branch(Path::new(&self.search_path)).unwrap_or_default();
+        self.prompt = Some(Prompt::new(PromptKind::CoverageBase, default_ref));
+    }
+    
+    /// Compare the last coverage run against the coverage recorded on a git
+    /// ref's commit or, failing that, on the commit the checked out branch
+    /// forked from it
+    pub fn compare_coverage(&mut self, git_ref: &str) -> io::Result<()> {
+        let Some(current) = &self.coverage else {
+            return Ok(());
+        };
+        let dir = Path::new(&self.search_path);
+        let commit = git::resolve_commit(dir, git_ref)?;
+        let base = match self.coverage_history.at_commit(&commit) {
+            Some(base) => base,
+            None => {
+                let fork = git::merge_base(dir, "HEAD", &commit)?;
+                self.coverage_history.at_commit(&fork).ok_or_else(|| io::Error::other(format!(
+                    "no coverage recorded on {} ({}); check it out and run with coverage (%) there first",
+                    git_ref,
+                    &commit[..8.min(commit.len())]
+                )))?
+            },
+        };
+        
+        self.coverage_delta = Some(CoverageDelta::compare(git_ref, base, current));
+        self.selected_coverage_delta_index = 0;
+        self.view = AppView::CoverageDiff;
+        Ok(())
+    }
+    
+    /// Show per-file coverage from the last coverage run
+    pub fn show_coverage(&mut self) {
+        if self.coverage.is_none() {
+            self.status_message = Some("No coverage yet (% turns on coverage for the next runs)".to_string());
+            return;
+        }
+        self.view = AppView::Coverage;
+    }
+    
+    /// Show the source of the file selected in the coverage view
+    pub fn open_coverage_file(&mut self) -> io::Result<()> {
+        let Some((file, _)) = self.coverage
+            .as_ref()
+            .and_then(|coverage| coverage.files.get(self.selected_coverage_index))
+            .cloned() else {
+            return Ok(());
+        };
+        self.open_source_file(&file)
+    }
+    
+    /// Show the source of the file selected in the coverage diff view; going
+    /// back returns to the diff
+    pub fn open_coverage_delta_file(&mut self) -> io::Result<()> {
+        let Some(file) = self.coverage_delta
+            .as_ref()
+            .and_then(|delta| delta.files.get(self.selected_coverage_delta_index))
+            .map(|delta| delta.file.clone()) else {
+            return Ok(());
+        };
+        self.open_source_file(&file)?;
+        self.detail_return_view = Some(AppView::CoverageDiff);
+        Ok(())
+    }
+    
+    /// Show a source file, relative to the project directory, in the detail
+    /// view with its line coverage
+    fn open_source_file(&mut self, file: &str) -> io::Result<()> {
+        self.current_test_content = std::fs::read_to_string(Path::new(&self.search_path).join(file))?;
+        self.current_test_blocks.clear();
+        self.detail_source = Some(file.to_string());
+        self.detail_coverage.clear();
+        self.reset_detail_position();
+        self.view = AppView::TestDetail;
+        Ok(())
+    }
+    
+    /// Show the detail view's file from the top, coming back to the list
+    fn reset_detail_position(&mut self) {
+        self.detail_scroll = 0;
+        self.detail_highlight = None;
+        self.detail_search = None;
+        self.detail_return_view = None;
+    }
+    
+    /// Scroll the detail view by the given number of lines, stopping once the
+    /// end of the file is in view
+    pub fn scroll_detail(&mut self, delta: isize) {
+        let max_scroll = self.current_test_content.lines().count().saturating_sub(self.detail_page.max(1));
+        self.detail_scroll = self.detail_scroll.saturating_add_signed(delta).min(max_scroll);
+    }
+    
+    /// Scroll the detail view to a 1-based line with a few lines above it, and
+    /// highlight it. Lines past the end go to the last line.
+    pub fn show_detail_line(&mut self, line: usize) {
+        let line_count = self.current_test_content.lines().count();
+        let line = line.clamp(1, line_count.max(1));
+        self.detail_scroll = line.saturating_sub(6).min(line_count.saturating_sub(self.detail_page.max(1)));
+        self.detail_highlight = Some(line);
+    }
+    
+    /// Open a prompt for a line of the file viewer's file to jump to
+    pub fn prompt_goto_line(&mut self) {
+        self.prompt = Some(Prompt::new(PromptKind::GotoLine, ""));
+    }
+    
+    /// Jump to a line typed into the goto-line prompt or the command line
+    fn goto_line(&mut self, line: usize) {
+        if self.view == AppView::TestDetail {
+            self.show_detail_line(line);
+        } else {
+            self.status_message = Some("Going to a line only works in the file viewer".to_string());
+        }
+    }
+    
+    /// Scroll the detail view by a screenful, keeping a line of the previous
+    /// one in view
+    pub fn page_detail(&mut self, pages: isize) {
+        let page = self.detail_page.saturating_sub(1).max(1) as isize;
+        self.scroll_detail(pages * page);
+    }
+    
+    /// Highlight a 1-based line of the detail view, scrolling to it only if
+    /// it's off screen
+    fn reveal_detail_line(&mut self, line: usize) {
+        let visible = self.detail_scroll + 1..=self.detail_scroll + self.detail_page.max(1);
+        if visible.contains(&line) {
+            self.detail_highlight = Some(line);
+        } else {
+            self.show_detail_line(line);
+        }
+    }
+    
+    /// Start typing a search through the detail view's file, or edit the
+    /// current one
+    pub fn open_detail_search(&mut self) {
+        match self.detail_search.as_mut() {
+            Some(search) => search.editing = true,
+            None => self.detail_search = Some(FileSearch::new()),
+        }
+    }
+    
+    /// Find the query as it's typed, moving to the first match from the top
+    /// of the screen down
+    fn update_detail_search(&mut self) {
+        let Some(search) = self.detail_search.as_mut() else {
+            return;
+        };
+        search.find(&self.current_test_content);
+        if let Some(found) = search.move_to_line(self.detail_scroll + 1) {
+            self.reveal_detail_line(found.line);
+        }
+    }
+    
+    /// Move to the next match of the detail view's search, or the previous one
+    pub fn step_detail_search(&mut self, forward: bool) {
+        let Some(search) = self.detail_search.as_mut() else {
+            return;
+        };
+        match search.step(forward) {
+            Some(found) => self.reveal_detail_line(found.line),
+            None => self.status_message = Some(format!("Pattern not found: {}", search.query)),
+        }
+    }
+    
+    /// Handles key events while typing a search through the detail view's file
+    fn on_detail_search_key(&mut self, key: KeyEvent) {
+        let Some(search) = self.detail_search.as_mut() else {
+            return;
+        };
+        
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) => self.detail_search = None,
+            (_, KeyCode::Enter) if search.query.is_empty() => self.detail_search = None,
+            (_, KeyCode::Enter) => search.editing = false,
+            (_, KeyCode::Backspace) if search.query.is_empty() => self.detail_search = None,
+            (_, KeyCode::Backspace) => {
+                search.query.pop();
+                self.update_detail_search();
+            },
+            (modifiers, KeyCode::Char(c)) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                search.query.push(c);
+                self.update_detail_search();
+            },
+            _ => {}
+        }
+    }
+    
+    /// The test the detail view's run action runs: the one around the
+    /// highlighted line when it's in view, otherwise the one around the top
+    /// line, or failing that the first declared further down the screen.
+    /// Source files have no tests of their own.
+    pub fn detail_cursor_test(&self) -> Option<TestBlock> {
+        if self.detail_source.is_some() {
+            return None;
+        }
+        let first = self.detail_scroll + 1;
+        let last = self.detail_scroll + self.detail_page.max(1);
+        let cursor = self.detail_highlight
+            .filter(|line| (first..=last).contains(line))
+            .unwrap_or(first);
+        
+        let blocks: Vec<&TestBlock> = self.current_test_blocks
+            .iter()
+            .filter(|block| block.kind == BlockKind::Test)
+            .collect();
+        blocks
+            .iter()
+            .rev()
+            .find(|block| (block.start..=block.end).contains(&cursor))
+            .or_else(|| blocks.iter().find(|block| (first..=last).contains(&block.start)))
+            .copied()
+            .cloned()
+    }
+    
+    /// Status of each `describe`/`it`/`test` declared in the detail view's
+    /// test file, by 1-based line, from the latest run of each test this
+    /// session. Tests that haven't run are `Pending`, and a describe block
+    /// sums up the tests inside it.
+    pub fn detail_test_marks(&self) -> HashMap<usize, TestStatus> {
+        let Some(test_file) = self.tests.get(self.selected_index).filter(|_| self.detail_source.is_none()) else {
+            return HashMap::new();
+        };
+        
+        // A run filtered to other tests reports this one as skipped, so that
+        // doesn't replace a result from an earlier run
+        let mut results: HashMap<&str, TestStatus> = HashMap::new();
+        for file in self.session.runs.iter().flat_map(|run| &run.files).filter(|file| &file.file == test_file) {
+            for test in &file.tests {
+                let status = TestStatus::from_assertion(test.status);
+                if matches!(status, TestStatus::Passed | TestStatus::Failed) || !results.contains_key(test.name.as_str()) {
+                    results.insert(&test.name, status);
+                }
+            }
+        }
+        
+        let blocks = &self.current_test_blocks;
+        let test_status = |block: &TestBlock| results.get(block.full_name().as_str()).copied().unwrap_or(TestStatus::Pending);
+        blocks
+            .iter()
+            .map(|block| {
+                let status = match block.kind {
+                    BlockKind::Test => test_status(block),
+                    BlockKind::Describe => {
+                        let path: Vec<String> = block.ancestors.iter().chain(std::iter::once(&block.name)).cloned().collect();
+                        blocks
+                            .iter()
+                            .filter(|test| test.kind == BlockKind::Test && test.ancestors.starts_with(&path))
+                            .map(test_status)
+                            .reduce(TestStatus::combine)
+                            .unwrap_or(TestStatus::Pending)
+                    },
+                };
+                (block.start, status)
+            })
+            .collect()
+    }
+    
+    /// Local imports of the highlighted test file that the last coverage run
+    /// has line coverage for, with their contents
+    fn covered_imports(&mut self) -> Vec<(String, String)> {
+        if self.coverage.as_ref().is_none_or(|coverage| coverage.line_hits.is_empty()) {
+            return Vec::new();
+        }
+        
+        let resolved: Vec<PathBuf> = self.selected_imports()
+            .iter()
+            .filter_map(|import| import.resolved.clone())
+            .collect();
+        let Some(coverage) = &self.coverage else {
+            return Vec::new();
+        };
+        
+        // Coverage paths are relative to the canonical project directory
+        let root = Path::new(&self.search_path);
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        resolved
+            .into_iter()
+            .filter_map(|path| {
+                let path = path.canonicalize().ok()?;
+                let relative = path.strip_prefix(&root).ok()?.display().to_string();
+                if !coverage.line_hits.contains_key(&relative) {
+                    return None;
+                }
+                let content = std::fs::read_to_string(&path).ok()?;
+                Some((relative, content))
+            })
+            .collect()
+    }
+    
+    /// Show the next covered import next to the test file in the detail view
+    pub fn next_covered_import(&mut self) {
+        if self.detail_coverage.is_empty() {
+            self.status_message = Some(
+                "No line coverage for this file's imports (% turns on coverage for the next runs)".to_string()
+            );
+            return;
+        }
+        self.detail_coverage_index = (self.detail_coverage_index + 1) % self.detail_coverage.len();
+    }
+    
+    /// Show the run queue
+    pub fn show_queue(&mut self) {
+        if self.queue.is_none() {
+            self.status_message = Some("Nothing queued yet (Space marks files, Enter queues them)".to_string());
+            return;
+        }
+        self.view = AppView::Queue;
+    }
+    
+    /// Show the individual results of the file selected in the queue
+    pub fn open_queue_entry(&mut self) {
+        let Some(entry) = self.queue.as_ref().and_then(|queue| queue.entries.get(self.selected_queue_index)) else {
+            return;
+        };
+        if !entry.is_finished() {
+            self.status_message = Some(format!("{} hasn't finished yet", entry.file));
+            return;
+        }
+        let Some(results) = &entry.results else {
+            let reason = entry.error.clone().unwrap_or_else(|| "Jest wrote no results".to_string());
+            self.status_message = Some(format!("No results for {}: {}", entry.file, reason));
+            return;
+        };
+        
+        let tests = results.test_results.iter().flat_map(file_test_infos).collect();
+        let test_file = entry.file.clone();
+        self.show_file_results(test_file, tests, AppView::Queue);
+    }
+    
+    /// Switch to a built-in theme, keeping the configured color overrides
+    pub fn set_theme(&mut self, name: &str) {
+        let name = match name {
+            "dark" => ThemeName::Dark,
+            "light" => ThemeName::Light,
+            "high-contrast" => ThemeName::HighContrast,
+            _ => {
+                self.status_message = Some(format!("Usage: :theme {}", command_line::THEME_NAMES.join("|")));
+                return;
+            },
+        };
+        let options = ThemeOptions { name, colors: self.config.theme.colors.clone() };
+        match options.resolve() {
+            Ok(theme) => self.theme = theme,
+            Err(e) => self.toasts.push(ToastKind::Error, format!("Could not apply theme: {}", e)),
+        }
+    }
+    
+    /// Write every run finished this session to a JSON or JUnit XML file, at
+    /// `path` (relative to the project directory) or the configured export path,
+    /// with an `.xml` extension for JUnit
+    pub fn export_session(&mut self, format: ExportFormat, path: Option<&str>) {
+        if self.session.runs.is_empty() {
+            self.status_message = Some("Nothing to export: no runs have finished yet".to_string());
+            return;
+        }
+        
+        let path = match (path, format) {
+            (Some(path), _) => Path::new(&self.search_path).join(path),
+            (None, ExportFormat::Json) => self.config.export.resolve(&self.search_path),
+            (None, ExportFormat::Junit) => self.config.export.resolve(&self.search_path).with_extension("xml"),
+        };
+        let exported = match format {
+            ExportFormat::Json => self.session.export(&path, &self.search_path, self.runner.label()),
+            ExportFormat::Junit => self.session.export_junit(&path),
+        };
+        match exported {
+            Ok(()) => self.toasts.push(ToastKind::Success, format!("Exported {} runs to {}", self.session.runs.len(), path.display())),
+            Err(e) => self.toasts.push(ToastKind::Error, format!("Could not export session: {}", e)),
+        }
+    }
+    
+    /// Forget this session's results - run output and tabs, parsed results,
+    /// the run summary and queue, marks, coverage, blame, and the last run
+    /// statuses in the list - going back to the test list as it was at startup.
+    /// Waits for runs still going, which would fill it all in again.
+    pub fn reset_session(&mut self) {
+        if self.runs_going() > 0 {
+            self.status_message = Some("Can't clear results while tests are running".to_string());
+            return;
+        }
+        
+        // Swap a fresh run into the only tab left, replacing the shown run
+        self.run_tabs = vec![RunTab::new(self.results_path.clone())];
+        self.active_tab = 0;
+        self.swap_run_state(0);
+        self.run_tabs[0] = RunTab::default();
+        
+        self.run_summary = None;
+        self.selected_summary_index = 0;
+        self.results_return_view = None;
+        self.marked_files.clear();
+        self.queue = None;
+        self.selected_queue_index = 0;
+        self.session = Session::default();
+        self.failure_groups = FailureGroups::default();
+        self.selected_failure_group_index = 0;
+        self.blame_cache.clear();
+        self.coverage = None;
+        self.coverage_delta = None;
+        self.selected_coverage_index = 0;
+        self.detail_coverage.clear();
+        self.detail_coverage_index = 0;
+        self.detail_return_view = None;
+        self.dry_run = None;
+        
+        self.history.clear_last_runs();
+        if self.list_filter == Some(ListFilter::Failed) {
+            self.clear_filter();
+        } else {
+            self.sort_tests();
+        }
+        self.view = AppView::TestList;
+        self.toasts.push(ToastKind::Success, "Cleared this session's results");
+    }
+    
+    /// Save the shown run's output, as printed so far, to a file in the project
+    /// directory named by the configured output path, with or without its colors
+    pub fn export_output(&mut self, ansi: bool) {
+        if self.test_run_output.is_empty() {
+            self.status_message = Some("Nothing to save: the run hasn't printed anything".to_string());
+            return;
+        }
+        
+        let path = self.config.export.resolve_output(&self.search_path);
+        let content = if ansi {
+            self.test_run_output.clone()
+        } else {
+            ansi::strip(&self.test_run_output).into_owned()
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, content));
+        match written {
+            Ok(()) => self.toasts.push(ToastKind::Success, format!("Saved output to {}", path.display())),
+            Err(e) => self.toasts.push(ToastKind::Error, format!("Could not save output: {}", e)),
+        }
+    }
+    
+    /// Advance the run queue, recording finished files in the run history
+    fn check_queue(&mut self) {
+        let Some(queue) = self.queue.as_mut() else {
+            return;
+        };
+        let finished = queue.poll();
+        if finished.is_empty() {
+            return;
+        }
+        
+        for index in finished {
+            let entry = &queue.entries[index];
+            if self.all_tests.contains(&entry.file) {
+                let tests = entry.results.as_ref().map(|results| TestRecord::from_results(&results.test_results)).unwrap_or_default();
+                self.history.record(&entry.file, entry.duration_ms.unwrap_or(0), entry.summary(), entry.failed_to_run(), tests);
+            }
+            if let Some(started_at) = entry.started_at {
+                self.session.runs.push(SessionRun::finished(
+                    entry.file.clone(),
+                    &entry.command,
+                    started_at,
+                    &entry.output,
+                    entry.results.as_ref(),
+                    entry.error.clone(),
+                ));
+            }
+        }
+        let _ = self.history.save();
+        
+        if queue.is_finished() {
+            let failed = queue.entries.iter().filter(|entry| entry.passed() == Some(false)).count();
+            self.status_message = Some(format!(
+                "Queue finished: {} of {} files failed",
+                failed,
+                queue.entries.len()
+            ));
+            self.ring_bell(failed == 0);
+        }
+    }
+    
+    /// Write the configured bell sequence for a finished run, if there is one
+    fn ring_bell(&self, passed: bool) {
+        let sequence = self.config.bell.sequence(passed);
+        if sequence.is_empty() {
+            return;
+        }
+        
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(sequence.as_bytes());
+        let _ = stdout.flush();
+    }
+    
+    /// Parse individual test results by scraping Jest's printed output
+    fn scrape_test_results(&mut self) {
+        self.individual_tests.clear();
+        
+        let mut current_test_name = String::new();
+        let mut current_test_status = TestStatus::Passed;
+        let mut current_test_error = None;
+        let mut current_test_duration = None;
+        let mut current_ancestors = Vec::new();
+        
+        // Describe blocks enclosing the next test, by indentation, while in
+        // a file's verbose listing of its tests
+        let mut describes: Vec<(usize, String)> = Vec::new();
+        let mut in_listing = false;
+        
+        // Process each line to find test results
+        for line in ansi::strip(&self.test_run_output).lines() {
+            let indent = line.len() - line.trim_start().len();
+            let line = line.trim();
+            let is_test = ["✓", "×", "✕", "○"].iter().any(|marker| line.starts_with(marker));
+            
+            // Track the describe blocks the tests are listed under
+            if line.starts_with("PASS") || line.starts_with("FAIL") {
+                in_listing = true;
+                describes.clear();
+            } else if line.is_empty() || line.starts_with('●') {
+                in_listing = false;
+            }
+            describes.retain(|(describe_indent, _)| *describe_indent < indent);
+            if in_listing && indent > 0 && !is_test {
+                describes.push((indent, line.to_string()));
+                continue;
+            }
+            
+            // Check for test start
+            if line.starts_with("✓") || line.starts_with("PASS") {
+                // Test passed
+                if !current_test_name.is_empty() {
+                    // Save previous test if we have one
+                    self.individual_tests.push(TestInfo {
+                        name: current_test_name.clone(),
+                        full_name: full_test_name(&current_ancestors, &current_test_name),
+                        line: None,
+                        file: None,
+                        ancestors: current_ancestors.clone(),
+                        status: current_test_status,
+                        error: None,
+                        duration: current_test_duration,
+                    });
+                }
+                
+                // Extract test name - remove the "✓ " prefix and extract the name
+                let name_parts: Vec<&str> = line.splitn(2, ' ').collect();
+                if name_parts.len() > 1 {
+                    current_test_name = name_parts[1].trim().to_string();
+                    current_ancestors = describes.iter().map(|(_, name)| name.clone()).collect();
+                    current_test_status = TestStatus::Passed;
+                    current_test_error = None;
+                    
+                    // Try to extract duration if it's in the format "name (Duration: 10ms)"
+                    if let Some(duration_idx) = current_test_name.find("(")
+                        && let Some(end_idx) = current_test_name.find(")") {
+                        let duration_str = &current_test_name[duration_idx + 1..end_idx];
+                        if duration_str.contains("ms") {
+                            // Extract number from string like "10ms"
+                            if let Some(ms_idx) = duration_str.find("ms") {
+                                let number_str = &duration_str[0..ms_idx].trim();
+                                if let Ok(duration) = number_str.parse::<u64>() {
+                                    current_test_duration = Some(duration);
+                                }
+                            }
+                        }
+                        // Remove the duration part from the name
+                        current_test_name = current_test_name[0..duration_idx].trim().to_string();
+                    }
+                }
+            } else if line.starts_with("×") || line.starts_with("FAIL") {
+                // Test failed
+                if !current_test_name.is_empty() {
+                    // Save previous test if we have one
+                    self.individual_tests.push(TestInfo {
+                        name: current_test_name.clone(),
+                        full_name: full_test_name(&current_ancestors, &current_test_name),
+                        line: None,
+                        file: None,
+                        ancestors: current_ancestors.clone(),
+                        status: current_test_status,
+                        error: current_test_error.clone(),
+                        duration: current_test_duration,
+                    });
+                }
+                
+                // Extract test name - remove the "× " prefix and extract the name
+                let name_parts: Vec<&str> = line.splitn(2, ' ').collect();
+                if name_parts.len() > 1 {
+                    current_test_name = name_parts[1].trim().to_string();
+                    current_ancestors = describes.iter().map(|(_, name)| name.clone()).collect();
+                    current_test_status = TestStatus::Failed;
+                    current_test_error = Some(String::new()); // Will be populated with subsequent error lines
+                    current_test_duration = None;
+                    
+                    // Try to extract duration
+                    if let Some(duration_idx) = current_test_name.find("(")
+                        && let Some(end_idx) = current_test_name.find(")") {
+                        let duration_str = &current_test_name[duration_idx + 1..end_idx];
+                        if duration_str.contains("ms") {
+                            // Extract number from string like "10ms"
+                            if let Some(ms_idx) = duration_str.find("ms") {
+                                let number_str = &duration_str[0..ms_idx].trim();
+                                if let Ok(duration) = number_str.parse::<u64>() {
+                                    current_test_duration = Some(duration);
+                                }
+                            }
+                        }
+                        // Remove the duration part from the name
+                        current_test_name = current_test_name[0..duration_idx].trim().to_string();
+                    }
+                }
+            } else if line.starts_with("○") || line.starts_with("✎") {
+                // Skipped or todo test, listed as "○ skipped name" or "✎ todo name"
+                if !current_test_name.is_empty() {
+                    // Save previous test if we have one
+                    self.individual_tests.push(TestInfo {
+                        name: current_test_name.clone(),
+                        full_name: full_test_name(&current_ancestors, &current_test_name),
+                        line: None,
+                        file: None,
+                        ancestors: current_ancestors.clone(),
+                        status: current_test_status,
+                        error: current_test_error.clone(),
+                        duration: current_test_duration,
+                    });
+                }
+                
+                let name = line.split_once(' ').map_or("", |(_, name)| name).trim();
+                let (status, name) = match name.strip_prefix("todo ") {
+                    Some(name) => (TestStatus::Todo, name),
+                    None => (TestStatus::Skipped, name.strip_prefix("skipped ").unwrap_or(name)),
+                };
+                current_test_name = name.to_string();
+                current_ancestors = describes.iter().map(|(_, name)| name.clone()).collect();
+                current_test_status = status;
+                current_test_error = None;
+                current_test_duration = None;
+            } else if !current_test_name.is_empty() && line.contains("Error:") && current_test_status == TestStatus::Failed {
+                // Found error details
+                if let Some(ref mut error) = current_test_error {
+                    error.push_str(line);
+                    error.push('\n');
+                }
+            }
+        }
+        
+        // Add the last test if there is one
+        if !current_test_name.is_empty() {
+            self.individual_tests.push(TestInfo {
+                full_name: full_test_name(&current_ancestors, &current_test_name),
+                name: current_test_name,
+                line: None,
+                file: None,
+                ancestors: current_ancestors,
+                status: current_test_status,
+                error: current_test_error,
+                duration: current_test_duration,
+            });
+        }
+        
+        // Reset selection
+        self.selected_test_index = 0;
+    }
+    
+    /// View individual test results from test output
+    pub fn view_test_results(&mut self) {
+        if self.view == AppView::TestRunning && !self.test_loading {
+            self.results_return_view = None;
+            
+            // Parse the results first
+            self.parse_test_results();
+            
+            // Only switch view if we found some tests
+            if !self.individual_tests.is_empty() {
+                self.view = AppView::TestResults;
+                self.selected_test_index = 0;
+            }
+        }
+    }
+    
+    /// Load and parse test file to extract individual tests without running them
+    pub fn load_and_parse_individual_tests(&mut self) -> io::Result<()> {
+        if self.tests.is_empty() {
+            return Ok(());
+        }
+        
+        // Clear any previous test results
+        self.individual_tests.clear();
+        
+        // Get the test file path
+        let test_file = self.tests[self.selected_index].clone();
+        let full_path = PathBuf::from(&self.search_path).join(&test_file);
+        
+        // Read the file content
+        let content = std::fs::read_to_string(&full_path)?;
+        
+        // Keep the focused test warning in sync with what's on disk
+        self.update_focused_tests(&test_file, &content);
+        
+        // Parse the file to find test definitions
+        self.parse_test_definitions(&content);
+        
+        Ok(())
+    }
+    
+    /// Parse test content to extract individual test definitions
+    pub fn parse_test_definitions(&mut self, content: &str) {
+        self.individual_tests.clear();
+        self.collapsed_describes.clear();
+        
+        let tests = test_parser::find_test_blocks(content)
+            .into_iter()
+            .filter(|block| block.kind == BlockKind::Test);
+        for block in tests {
+            let full_name = block.full_name();
+            
+            // Skip if we already have this test
+            if self.individual_tests.iter().any(|t| t.full_name == full_name) {
+                continue;
+            }
+            
+            let status = match block.modifier {
+                Some(ModifierKind::Todo) => TestStatus::Todo,
+                Some(ModifierKind::Skip) => TestStatus::Skipped,
+                _ => TestStatus::Pending,
+            };
+            self.individual_tests.push(TestInfo {
+                name: block.name,
+                full_name,
+                line: Some(block.start),
+                file: None,
+                ancestors: block.ancestors,
+                status,
+                error: None,
+                duration: None,
+            });
+        }
+        
+        // Reset the selection index
+        self.selected_test_index = 0;
+    }
+    
+    /// Rows of the test results tree, as currently expanded
+    pub fn test_rows(&self) -> Vec<TreeRow> {
+        test_tree::tree_rows(&self.individual_tests, &self.collapsed_describes)
+    }
+    
+    /// The test selected in the results view, unless a describe block is selected
+    pub fn selected_test(&self) -> Option<&TestInfo> {
+        match self.test_rows().get(self.selected_test_index)?.kind {
+            TreeRowKind::Test(index) => self.individual_tests.get(index),
+            TreeRowKind::Describe { .. } => None,
+        }
+    }
+    
+    /// The describe block selected in the results view, and whether it's collapsed
+    fn selected_describe(&self) -> Option<(Vec<String>, bool)> {
+        match self.test_rows().into_iter().nth(self.selected_test_index)?.kind {
+            TreeRowKind::Describe { path, collapsed, .. } => Some((path, collapsed)),
+            TreeRowKind::Test(_) => None,
+        }
+    }
+    
+    /// Open the first stack frame inside the project in the user's editor: from the
+    /// selected test's failure in the results view, otherwise from the run's output
+    pub fn open_failure_in_editor(&mut self) {
+        let project_dir = Path::new(&self.search_path);
+        let location = match self.view {
+            AppView::TestResults => self.selected_test().and_then(|test| {
+                // Fall back to where the test is declared when its failure has no frames
+                test.error
+                    .as_deref()
+                    .and_then(|error| stack_trace::project_frames(error, project_dir).into_iter().next())
+                    .map(|frame| (frame.path, frame.line))
+                    .or_else(|| Some((PathBuf::from(test.file.as_ref()?), test.line?)))
+            }),
+            _ => self.selected_output_frame()
+                .cloned()
+                .or_else(|| stack_trace::project_frames(&self.test_run_output, project_dir).into_iter().next())
+                .map(|frame| (frame.path, frame.line)),
+        };
+        
+        match location {
+            Some(location) => self.pending_editor = Some(location),
+            None => self.status_message = Some("No stack frame pointing into the project".to_string()),
+        }
+    }
+    
+    /// Collapse or expand the selected describe block
+    fn set_describe_collapsed(&mut self, collapsed: bool) {
+        let Some((path, _)) = self.selected_describe() else {
+            return;
+        };
+        if collapsed {
+            self.collapsed_describes.insert(path);
+        } else {
+            self.collapsed_describes.remove(&path);
+        }
+    }
+    
+    /// Run the test selected in the results view
+    pub fn run_individual_test(&mut self) -> io::Result<()> {
+        // Match the full name, so a test sharing its name with one in another describe isn't run too
+        match self.selected_test().map(|test| test.full_name.clone()) {
+            Some(test_name) => self.run_named_test(test_name),
+            None => Ok(()),
+        }
+    }
+    
+    /// Run the test under the detail view's cursor, or the whole file when the
+    /// cursor isn't in one
+    pub fn run_detail_test(&mut self) -> io::Result<()> {
+        match self.detail_cursor_test() {
+            Some(test) => self.run_named_test(test.full_name()),
+            None => self.run_test(),
+        }
+    }
+    
+    /// Run a single test of the selected file, by its full name, using Jest's
+    /// testNamePattern option
+    fn run_named_test(&mut self, test_name: String) -> io::Result<()> {
+        if !self.runner_ready() || !self.prepare_run_tab() {
+            return Ok(());
+        }
+        
+        // Set up state for test running
+        self.view = AppView::TestRunning;
+        self.test_loading = true;
+        self.test_run_output = String::new(); // Clear previous output
+        self.running_individual_test = true; // Flag that we're running an individual test
+        self.running_action = None;
+        self.running_all = None;
+        
+        // Get the file path
+        let test_file = if !self.tests.is_empty() {
+            self.tests[self.selected_index].clone()
+        } else {
+            return Ok(());
+        };
+        
+        // Run only the selected test with an exact match pattern
+        let command = self.with_json_results(self.individual_test_command(&test_file, &test_name));
+        self.running_test_file = Some(test_file.clone());
+        self.run_started_at = Some(std::time::Instant::now());
+        
+        // Start the async test process
+        self.test_receiver = Some(test_runner::start_async_test(command.clone()));
+        
+        // Show initial "running test" message with command info
+        self.begin_output(format!(
+            "Running individual test: \"{}\"\nFile: {}\nCommand: {}\n",
+            test_name,
+            test_file,
+            command.display()
+        ));
+        self.last_command = Some(command);
+        
+        Ok(())
+    }
+
+    /// Check for test results from the async runner, in every tab
+    pub fn check_test_results(&mut self) {
+        // Runs in other tabs keep going in the background
+        let shown = self.active_tab;
+        for index in 0..self.run_tabs.len() {
+            if index == shown || self.run_tabs[index].test_receiver.is_none() {
+                continue;
+            }
+            self.swap_run_state(shown);
+            self.swap_run_state(index);
+            if self.receive_run_updates(false) {
+                let label = self.run_label().unwrap_or_else(|| "Run".to_string());
+                self.status_message = Some(format!("{} finished in tab {}", label, index + 1));
+            }
+            self.swap_run_state(index);
+            self.swap_run_state(shown);
+        }
+        
+        self.receive_run_updates(true);
+    }
+    
+    /// Handle every update the shown run received since the last check,
+    /// without blocking, returning whether the run finished.
+    ///
+    /// Only a run in the shown tab (`foreground`) changes the view.
+    fn receive_run_updates(&mut self, foreground: bool) -> bool {
+        let mut finished = false;
+        while let Some(receiver) = &self.test_receiver {
+            match receiver.try_recv() {
+                Ok(TestResult::Running) => {
+                    // Test is still running, keep the loading state
+                    self.test_loading = true;
+                },
+                Ok(TestResult::OutputChunk(chunk)) => self.append_output(&chunk),
+                Ok(TestResult::Completed(result)) => {
+                    // Test is complete, process the result
+                    self.test_loading = false;
+                    finished = true;
+                    
+                    // A test run whose program isn't there gets the missing runner popup
+                    if let Err(e) = &result
+                        && e.kind() == io::ErrorKind::NotFound
+                        && self.running_action.is_none()
+                        && let Some(command) = &self.last_command {
+                        self.show_missing_runner(format!(
+                            "`{}` couldn't be started ({}). It comes with Node.js, so check Node.js is installed and on your PATH.",
+                            command.program,
+                            e
+                        ));
+                    }
+                    
+                    // The output has already been streamed in, so only errors need adding
+                    let error = result.err().map(|e| e.to_string());
+                    let run_failed = error.is_some();
+                    if let Some(e) = &error {
+                        self.test_run_output.push_str(&format!("Error running test: {}\n", e));
+                    }
+                    
+                    // We're done with this receiver
+                    self.test_receiver = None;
+                    
+                    // Custom actions don't write structured results
+                    self.last_results = match self.running_action {
+                        Some(_) => None,
+                        None => json_results::take_results(&self.results_path).ok(),
+                    };
+                    
+                    // Keep test runs (but not custom actions) for the session export
+                    if self.running_action.is_none()
+                        && let Some(started_at) = self.run_started_at
+                        && let Some(command) = &self.last_command {
+                        self.session.runs.push(SessionRun::finished(
+                            self.run_label().unwrap_or_default(),
+                            command,
+                            started_at,
+                            &self.test_run_output,
+                            self.last_results.as_ref(),
+                            error,
+                        ));
+                    }
+                    
+                    // Ring for test runs, so one left going in a background pane is noticed
+                    if self.running_action.is_none() {
+                        let summary = self.last_results
+                            .as_ref()
+                            .map(JsonResults::summary)
+                            .or_else(|| output_parser::parse_summary(&self.test_run_output));
+                        self.ring_bell(!run_failed && summary.is_some_and(|summary| summary.is_success()));
+                    }
+                    
+                    // Pick up coverage written by a coverage run
+                    if self.running_action.is_none()
+                        && let Some(started_at) = self.run_started_at {
+                        self.load_coverage(started_at.elapsed());
+                    }
+                    
+                    // Summarize runs of every listed file, recording each file's results
+                    if let Some(results) = &self.last_results
+                        && self.running_all.is_some() {
+                        let duration_ms = self.run_started_at
+                            .take()
+                            .map_or(0, |started_at| started_at.elapsed().as_millis() as u64);
+                        let summary = RunAllSummary::from_results(results, Path::new(&self.search_path), duration_ms);
+                        for outcome in &summary.files {
+                            if self.all_tests.contains(&outcome.file) {
+                                let counts = outcome.error.is_none().then_some(outcome.summary);
+                                let tests = TestRecord::from_results(results.test_results.iter().filter(|file| file.name == outcome.path));
+                                self.history.record(&outcome.file, outcome.duration_ms, counts, outcome.error.is_some(), tests);
+                            }
+                        }
+                        let _ = self.history.save();
+                        
+                        self.run_summary = Some(summary);
+                        self.selected_summary_index = 0;
+                        if foreground && self.view == AppView::TestRunning {
+                            self.view = AppView::RunSummary;
+                        }
+                    }
+                    
+                    // Record how long full file runs take for the duration column
+                    if let Some(started_at) = self.run_started_at.take()
+                        && !self.running_individual_test
+                        && let Some(test_file) = &self.running_test_file
+                        && self.all_tests.contains(test_file) {
+                        let duration_ms = started_at.elapsed().as_millis() as u64;
+                        let summary = self.last_results
+                            .as_ref()
+                            .map(JsonResults::summary)
+                            .or_else(|| output_parser::parse_summary(&self.test_run_output));
+                        let failed_to_run = run_failed || self.last_results.as_ref().is_some_and(|results| {
+                            results.test_results.iter().any(FileResult::failed_to_run)
+                        });
+                        let tests = self.last_results
+                            .as_ref()
+                            .map(|results| TestRecord::from_results(&results.test_results))
+                            .unwrap_or_default();
+                        self.history.record(test_file, duration_ms, summary, failed_to_run, tests);
+                        let _ = self.history.save();
+                    }
+                    
+                    // Show the end of the output, unless it was scrolled back to read something
+                    if self.follow_output {
+                        self.scroll_to_end();
+                    }
+                    
+                    // If auto_show_test_results is enabled, try to parse and show individual tests,
+                    // unless the run finished in the background
+                    if self.auto_show_test_results && foreground && self.view == AppView::TestRunning {
+                        self.auto_show_test_results = false; // Reset the flag
+                        
+                        // Parse and show test results if available
+                        self.parse_test_results();
+                        if !self.individual_tests.is_empty() {
+                            self.view = AppView::TestResults;
+                            self.selected_test_index = 0;
+                        }
+                    }
+                },
+                Err(mpsc::TryRecvError::Empty) => {
+                    // No message yet, keep waiting
+                    break;
+                },
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    // Channel closed, reset state
+                    self.test_loading = false;
+                    finished = true;
+                    if self.test_run_output.is_empty() {
+                        self.test_run_output = "Test execution failed or was cancelled".to_string();
+                    }
+                    self.test_receiver = None;
+                }
+            }
+        }
+        finished
+    }
+
+    /// Turn watch mode on or off
+    pub fn toggle_watch(&mut self) {
+        if self.watcher.take().is_some() {
+            self.status_message = Some("Stopped watching for changes".to_string());
+            return;
+        }
+        
+        match FileWatcher::start(Path::new(&self.search_path), &self.config.export) {
+            Ok(watcher) => {
+                self.watcher = Some(watcher);
+                self.status_message = Some("Watching: the selected file re-runs when files change".to_string());
+            },
+            Err(e) => self.status_message = Some(format!("Could not watch for changes: {}", e)),
+        }
+    }
+    
+    /// Re-run the selected test file once watched files have changed
+    fn check_watcher(&mut self) {
+        // Changes made during a run are picked up once it finishes
+        if self.test_loading {
+            return;
+        }
+        let Some(changed) = self.watcher.as_mut().and_then(FileWatcher::poll) else {
+            return;
+        };
+        let Some(test_file) = self.tests.get(self.selected_index).cloned() else {
+            return;
+        };
+        
+        let view = self.view;
+        if let Err(e) = self.run_test() {
+            self.status_message = Some(format!("Could not re-run {}: {}", test_file, e));
+            return;
+        }
+        
+        // Only take over the screen from views that are about runs
+        if !matches!(view, AppView::TestList | AppView::TestRunning | AppView::TestResults) {
+            self.view = view;
+        }
+        
+        let root = Path::new(&self.search_path).canonicalize().unwrap_or_default();
+        let changed = match changed.as_slice() {
+            [path] => path.strip_prefix(&root).unwrap_or(path).display().to_string(),
+            paths => format!("{} files", paths.len()),
+        };
+        self.status_message = Some(format!("{} changed, re-running {}", changed, test_file));
+    }
+    
+    /// Start the output of a new run with a header, following it as it streams in
+    fn begin_output(&mut self, header: String) {
+        self.test_run_output = header;
+        self.terminal_scroll = 0;
+        self.follow_output = true;
+    }
+    
+    /// Add streamed output, keeping the view on the end of the output while following it
+    fn append_output(&mut self, chunk: &str) {
+        self.test_run_output.push_str(chunk);
+        if self.follow_output {
+            self.scroll_to_end();
+        }
+    }
+    
+    /// Scroll the output so its last line is at the bottom of the view
+    fn scroll_to_end(&mut self) {
+        // Rough estimate of the visible height, as the exact one is only known when rendering
+        let approx_visible_lines = 20;
+        self.terminal_scroll = self.output_row_count().saturating_sub(approx_visible_lines);
+    }
+    
+    /// Stay on the newest output again after scrolling away from it
+    pub fn follow(&mut self) {
+        self.follow_output = true;
+        self.scroll_to_end();
+    }
+
     /// Run the application's main loop.
-    pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+    pub fn run(&mut self, mut terminal: DefaultTerminal) -> Result<()> {
         self.running = true;
         
-        // Track the last time we rendered to enforce a minimum frame rate for animations
-        let mut last_render = std::time::Instant::now();
+        // Track the last time we rendered to enforce a minimum frame rate for animations
+        let mut last_render = std::time::Instant::now();
+        
+        while self.running {
+            // Check for test updates
+            self.check_test_results();
+            self.check_discovery();
+            self.check_watcher();
+            self.check_queue();
+            self.toasts.tick(std::time::Instant::now());
+            if let Some(benchmark) = self.benchmark.as_mut() {
+                benchmark.poll();
+            }
+            
+            // Feed replayed macro keys a few at a time, so the UI keeps drawing
+            // and a key press can cancel a long replay, waiting for any test
+            // run they start to finish
+            for _ in 0..REPLAY_KEYS_PER_TICK {
+                if !self.running || self.test_loading {
+                    break;
+                }
+                let Some(key) = self.macros.next_replay_key() else {
+                    break;
+                };
+                self.macros.record(key);
+                self.dispatch_key(key);
+            }
+            
+            // Calculate time since last render
+            let now = std::time::Instant::now();
+            let elapsed = now.duration_since(last_render);
+            
+            // If we're in loading state or enough time has passed, redraw
+            if self.test_loading || elapsed > std::time::Duration::from_millis(100) {
+                // Draw the UI
+                terminal.draw(|frame| self.render(frame))?;
+                last_render = now;
+            }
+            
+            // Use a shorter timeout while loading to keep animation smooth, and
+            // while replaying a macro to get through it quickly
+            let poll_timeout = if self.test_loading || self.macros.is_replaying() {
+                std::time::Duration::from_millis(16) // ~60fps for smooth animation
+            } else {
+                std::time::Duration::from_millis(100)
+            };
+            
+            // Handle user input with a timeout
+            if event::poll(poll_timeout)? {
+                self.handle_crossterm_events()?;
+            }
+            
+            // Hand the terminal over to the editor when a key asked for it
+            if let Some((path, line)) = self.pending_editor.take() {
+                let mut command = editor::editor_command(&path, line);
+                match editor::run_outside_tui(&mut terminal, &mut command, None) {
+                    Ok(status) if !status.success() => {
+                        self.toasts.push(ToastKind::Error, format!("Editor exited with {}", status));
+                    },
+                    Ok(_) => {},
+                    Err(e) => {
+                        warn!(editor = ?command.get_program(), "Could not open editor: {}", e);
+                        self.toasts.push(ToastKind::Error, format!("Could not open editor: {}", e));
+                    },
+                }
+            }
+            
+            // Open a shell in the project on `!`, coming back when it exits
+            if mem::take(&mut self.pending_shell) {
+                self.open_shell(&mut terminal);
+            }
+            
+            // Drop to the shell on Ctrl+Z, coming back on `fg`
+            if mem::take(&mut self.pending_suspend)
+                && let Err(e) = editor::suspend(&mut terminal) {
+                warn!("Could not suspend: {}", e);
+                self.toasts.push(ToastKind::Error, format!("Could not suspend: {}", e));
+            }
+        }
+        
+        Ok(())
+    }
+
+    /// Hand the terminal to the user's shell in the project directory until it
+    /// exits, first printing the last test command (or the one the view would
+    /// run) so it can be run again, tweaked, or debugged
+    fn open_shell(&mut self, terminal: &mut DefaultTerminal) {
+        let last_command = self.last_command
+            .clone()
+            .or_else(|| self.pending_command())
+            .map(|command| command.shell_command());
+        let mut message = format!("Shell in {} - exit to go back to syj", self.search_path);
+        if let Some(last_command) = &last_command {
+            message.push_str(&format!(
+                "\nLast test command (also in ${}):\n  {}",
+                editor::LAST_COMMAND_ENV,
+                last_command
+            ));
+        }
         
-        while self.running {
-            // Check for test updates
-            self.check_test_results();
+        let mut command = editor::shell_command(Path::new(&self.search_path), last_command.as_deref());
+        if let Err(e) = editor::run_outside_tui(terminal, &mut command, Some(&message)) {
+            warn!(shell = ?command.get_program(), "Could not open shell: {}", e);
+            self.toasts.push(ToastKind::Error, format!("Could not open shell: {}", e));
+        }
+    }
+    
+    /// Reads the crossterm events and updates the state of [`App`].
+    ///
+    /// If your application needs to perform work in between handling events, you can use the
+    /// [`event::poll`] function to check if there are any events available with a timeout.
+    fn handle_crossterm_events(&mut self) -> Result<()> {
+        match event::read()? {
+            // it's important to check KeyEventKind::Press to avoid handling key release events
+            Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
+            Event::Mouse(_) => {}
+            Event::Resize(_, _) => {}
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handles the key events and updates the state of [`App`].
+    fn on_key_event(&mut self, key: KeyEvent) {
+        // Status messages only last until the next key press
+        self.status_message = None;
+        
+        // Any key press stops a macro replay in progress
+        if self.macros.is_replaying() {
+            self.macros.cancel_replay();
+            self.status_message = Some("Macro replay cancelled".to_string());
+            return;
+        }
+        
+        // Remapped keys act as the key they're mapped to, except when typing
+        let key = if self.is_typing() { key } else { self.keymap.translate(key) };
+        
+        // Macro keys aren't available while typing into a prompt, command, or
+        // search, or while a popup is open
+        if self.is_typing() || self.has_popup() {
+            self.macros.record(key);
+        } else if let MacroEvent::Handled(message) = self.macros.on_key(key) {
+            self.status_message = message;
+            return;
+        }
+        
+        self.dispatch_key(key);
+    }
+    
+    /// Handles a key press, whether typed or replayed from a macro
+    fn dispatch_key(&mut self, key: KeyEvent) {
+        // Ctrl+Z suspends from anywhere, like in any other terminal program
+        if key.modifiers.contains(KeyModifiers::CONTROL) && matches!(key.code, KeyCode::Char('z') | KeyCode::Char('Z')) {
+            self.pending_suspend = true;
+            return;
+        }
+        
+        // Asking whether to quit captures all input
+        if self.confirm_quit {
+            self.on_confirm_quit_key(key);
+            return;
+        }
+        
+        // An open prompt or command line captures all input
+        if self.prompt.is_some() {
+            self.on_prompt_key(key);
+            return;
+        }
+        if self.command_line.is_some() {
+            self.on_command_line_key(key);
+            return;
+        }
+        if self.search.as_ref().is_some_and(|search| search.editing) {
+            self.on_search_key(key);
+            return;
+        }
+        if self.detail_search.as_ref().is_some_and(|search| search.editing) {
+            self.on_detail_search_key(key);
+            return;
+        }
+        
+        // As do the dry-run and missing runner popups, the actions menu, and the run options
+        if self.dry_run.is_some() {
+            self.on_dry_run_key(key);
+            return;
+        }
+        if self.missing_runner.is_some() {
+            self.on_missing_runner_key(key);
+            return;
+        }
+        if self.action_menu.is_some() {
+            self.on_action_menu_key(key);
+            return;
+        }
+        if self.run_options.is_some() {
+            self.on_run_options_key(key);
+            return;
+        }
+        if self.help_overlay.is_some() {
+            self.on_help_overlay_key(key);
+            return;
+        }
+        if self.log_viewer.is_some() {
+            self.on_log_viewer_key(key);
+            return;
+        }
+        
+        // `:` opens the command line, `w` toggles watch mode, `!` opens a
+        // shell, and `?` lists the keys from any view
+        match key.code {
+            KeyCode::Char('?') => {
+                self.help_overlay = Some(0);
+                return;
+            },
+            KeyCode::F(12) => {
+                self.log_viewer = Some(0);
+                return;
+            },
+            KeyCode::Char('!') => {
+                self.pending_shell = true;
+                return;
+            },
+            KeyCode::Char(':') => {
+                self.open_command_line();
+                return;
+            },
+            KeyCode::Char('w') => {
+                self.toggle_watch();
+                return;
+            },
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.export_session(ExportFormat::Json, None);
+                return;
+            },
+            _ => {}
+        }
+        
+        match self.view {
+            AppView::TestList => match (key.modifiers, key.code) {
+                // Navigate and open imports while the imports pane has focus
+                (_, KeyCode::Up | KeyCode::Char('k')) if self.imports_focused => {
+                    self.selected_import_index = self.selected_import_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) if self.imports_focused => {
+                    let last_index = self.selected_imports().len().saturating_sub(1);
+                    self.selected_import_index = (self.selected_import_index + 1).min(last_index);
+                },
+                (_, KeyCode::Right | KeyCode::Enter) if self.imports_focused => {
+                    if let Err(e) = self.open_selected_import() {
+                        self.status_message = Some(format!("Could not open import: {}", e));
+                    }
+                },
+                (_, KeyCode::Left | KeyCode::Esc) if self.imports_focused => self.imports_focused = false,
+                
+                // Pick up new test files and config changes
+                (_, KeyCode::Char('r')) => {
+                    let result = self.refresh_tests();
+                    self.report_discovery(result);
+                },
+                
+                // Adjust discovery when nothing was found
+                (_, KeyCode::Char('R')) if self.all_tests.is_empty() => {
+                    let root = self.search_path.clone();
+                    self.prompt = Some(Prompt::new(PromptKind::SearchRoot, root));
+                },
+                (_, KeyCode::Char('e')) if self.all_tests.is_empty() => {
+                    let patterns = self.test_matches.join(", ");
+                    self.prompt = Some(Prompt::new(PromptKind::TestPatterns, patterns));
+                },
+                (_, KeyCode::Char('u')) if self.all_tests.is_empty() => {
+                    let result = self.set_test_patterns("");
+                    self.report_discovery(result);
+                },
+                
+                // Toggle the imports pane and move focus into it
+                (_, KeyCode::Char('i')) => self.toggle_imports(),
+                (_, KeyCode::Tab) => self.toggle_imports_focus(),
+                
+                // Browse the highlighted file's snapshots
+                (_, KeyCode::Char('p')) => self.show_snapshots(),
+                
+                // Compare the highlighted file's recorded runs
+                (_, KeyCode::Char('H')) => self.show_run_history(),
+                
+                // Clear this session's results
+                (_, KeyCode::Char('X')) => self.reset_session(),
+                
+                // Fuzzy search the list
+                (_, KeyCode::Char('/')) => self.open_search(),
+                
+                // Show only the files that failed their last run
+                (_, KeyCode::Char('F')) => self.toggle_failed_filter(),
+                
+                // Stop discovery, clear marks, an active search, or a filter before quitting
+                (_, KeyCode::Esc) if self.discovery.is_some() => self.cancel_discovery(),
+                (_, KeyCode::Esc) if !self.marked_files.is_empty() => self.marked_files.clear(),
+                (_, KeyCode::Esc) if self.search.is_some() => self.close_search(),
+                (_, KeyCode::Esc) if self.list_filter.is_some() => self.clear_filter(),
+                
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Navigation keys
+                (_, KeyCode::Up | KeyCode::Char('k')) => self.previous(),
+                (_, KeyCode::Down | KeyCode::Char('j')) => self.next(),
+                
+                // Page up/down for faster navigation
+                (_, KeyCode::PageUp) => {
+                    for _ in 0..10 {
+                        self.previous();
+                    }
+                },
+                (_, KeyCode::PageDown) => {
+                    for _ in 0..10 {
+                        self.next();
+                    }
+                },
+                
+                // Home/End to jump to beginning/end
+                (_, KeyCode::Home) => {
+                    self.selected_index = 0;
+                    self.scroll_offset = 0;
+                },
+                (_, KeyCode::End) if !self.tests.is_empty() => {
+                    self.selected_index = self.tests.len() - 1;
+                },
+                
+                // View test file content (Ctrl+Right arrow)
+                (KeyModifiers::CONTROL, KeyCode::Right) if !self.tests.is_empty() => {
+                    let _ = self.load_test_content();
+                },
+                
+                // View file content and parse tests (right arrow)
+                (_, KeyCode::Right) if !self.tests.is_empty() => {
+                    // First, load the test file content to parse
+                    let _ = self.load_and_parse_individual_tests();
+                    self.results_return_view = None;
+                    
+                    // If we found tests, show the test results view
+                    if !self.individual_tests.is_empty() {
+                        self.view = AppView::TestResults;
+                    }
+                },
+                
+                // Show the command without running it (Shift+Enter or d)
+                (KeyModifiers::SHIFT, KeyCode::Enter) | (_, KeyCode::Char('d')) => self.show_dry_run(),
+                
+                // Mark files for a batch run, and queue them (Enter) or view the queue
+                (_, KeyCode::Char(' ')) => self.toggle_marked(),
+                (_, KeyCode::Enter) if !self.marked_files.is_empty() => self.enqueue_marked(),
+                (_, KeyCode::Char('Q')) => self.show_queue(),
+                
+                // Run test (enter/return)
+                (_, KeyCode::Enter) if !self.tests.is_empty() => {
+                    let _ = self.run_test();
+                },
+                
+                // Run every listed file, and reopen the summary of the last such run
+                (_, KeyCode::Char('R')) => self.run_all(),
+                (_, KeyCode::Char('S')) => self.show_run_summary(),
+                
+                // Toggle coverage collection, and show the last coverage
+                (_, KeyCode::Char('%')) => self.set_coverage_mode(!self.coverage_mode),
+                (_, KeyCode::Char('V')) => self.show_coverage(),
+                
+                // Cycle the list sort mode
+                (_, KeyCode::Char('s')) => self.cycle_sort_mode(),
+                
+                // Filter to tests changed since a git ref
+                (_, KeyCode::Char('c')) => self.prompt_changed_since(),
+                
+                // Filter to tests that depend on a source file
+                (_, KeyCode::Char('M')) => self.prompt_related_source(),
+                
+                // Audit skipped, todo, and focused tests across the project
+                (_, KeyCode::Char('A')) => self.run_audit(),
+                
+                // Per-directory statistics
+                (_, KeyCode::Char('D')) => self.show_directory_stats(),
+                
+                // Slowest tests and files
+                (_, KeyCode::Char('T')) => self.show_leaderboard(),
+                
+                // Switch Jest config
+                (_, KeyCode::Char('C')) => self.show_config_picker(),
+                
+                // Narrow the list to one project of a multi-project config
+                (_, KeyCode::Char('P')) => self.show_project_picker(),
+                
+                // Switch to another package of a monorepo
+                (_, KeyCode::Char('W')) => self.show_workspace_picker(),
+                
+                // Inspect the resolved configuration
+                (_, KeyCode::Char('I')) => self.show_config_inspector(),
+                
+                // Switch or save layout presets
+                (_, KeyCode::Char('L')) => self.show_layout_picker(),
+                
+                // Custom actions menu
+                (_, KeyCode::Char('a')) => self.show_action_menu(),
+                
+                // Flags to include in the following runs
+                (_, KeyCode::Char('o')) => self.run_options = Some(0),
+                
+                // Compare durations against another revision
+                (_, KeyCode::Char('B')) => self.show_benchmark(),
+                
+                // Return to a run going on in the background
+                (_, KeyCode::Char('t')) if self.running_tab().is_some() => self.show_running(),
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::TestDetail => match (key.modifiers, key.code) {
+                // Clear the search before anything else
+                (_, KeyCode::Esc) if self.detail_search.is_some() => self.detail_search = None,
+                
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Go back (left arrow)
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Return to a run going on in the background
+                (_, KeyCode::Char('t')) if self.running_tab().is_some() => self.show_running(),
+                
+                // Show the command without running it (Shift+Enter or d)
+                (KeyModifiers::SHIFT, KeyCode::Enter) | (_, KeyCode::Char('d')) => self.show_dry_run(),
+                
+                // Cycle through the covered imports shown next to the test file
+                (_, KeyCode::Char('c')) if self.detail_source.is_none() => self.next_covered_import(),
+                
+                // Filter the list to tests that depend on the source file
+                (_, KeyCode::Char('M')) => self.prompt_related_source(),
+                
+                // Scroll through the file
+                (_, KeyCode::Up | KeyCode::Char('k')) => self.scroll_detail(-1),
+                (_, KeyCode::Down | KeyCode::Char('j')) => self.scroll_detail(1),
+                (_, KeyCode::PageUp) => self.page_detail(-1),
+                (_, KeyCode::PageDown) => self.page_detail(1),
+                (_, KeyCode::Home) => self.detail_scroll = 0,
+                (_, KeyCode::End) => self.scroll_detail(isize::MAX),
+                
+                // Jump to a line
+                (_, KeyCode::Char('g')) => self.prompt_goto_line(),
+                
+                // Browse the file's snapshots, starting at the cursor test's
+                (_, KeyCode::Char('p')) => self.show_snapshots(),
+                
+                // Search the file, and move between matches
+                (_, KeyCode::Char('/')) => self.open_detail_search(),
+                (_, KeyCode::Char('n')) => self.step_detail_search(true),
+                (_, KeyCode::Char('N')) => self.step_detail_search(false),
+                
+                // Run the test under the cursor, or the file outside of one (enter/return)
+                (_, KeyCode::Enter) => {
+                    let _ = self.run_detail_test();
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::TestRunning => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Go back (left arrow)
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // View individual test results (right arrow)
+                (_, KeyCode::Right) if !self.test_loading => {
+                    self.view_test_results();
+                },
+                
+                // Show the command that was run
+                (_, KeyCode::Char('d')) => self.show_dry_run(),
+                
+                // Switch to a run still going in another tab
+                (_, KeyCode::Char('t')) if !self.test_loading && self.running_tab().is_some() => self.show_running(),
+                
+                // Follow new output again while running, re-run only the failing tests after
+                (_, KeyCode::Char('f')) if self.test_loading => self.follow(),
+                (_, KeyCode::Char('f')) => self.rerun_failed(),
+                
+                // Re-run with snapshots updated
+                (_, KeyCode::Char('u')) if !self.test_loading => self.update_snapshots(),
+                
+                // Save the output to a file, as plain text or with its colors
+                (_, KeyCode::Char('s')) => self.export_output(false),
+                (_, KeyCode::Char('S')) => self.export_output(true),
+                
+                // Open the failing location (or the frame jumped to) in the editor
+                (_, KeyCode::Char('o')) => self.open_failure_in_editor(),
+                
+                // Jump between stack frames pointing into the project
+                (_, KeyCode::Char('n')) => self.jump_to_frame(true),
+                (_, KeyCode::Char('p')) => self.jump_to_frame(false),
+                
+                // Fold or unfold library stack frames
+                (_, KeyCode::Char('z')) => self.toggle_frame_folds(),
+                
+                // Show only the failures, or everything
+                (_, KeyCode::Char('F')) => self.toggle_failures_only(),
+                
+                // Group the run's failures by error message
+                (_, KeyCode::Char('G')) => self.show_failure_groups(),
+                
+                // Show the frame jumped to in the file viewer
+                (_, KeyCode::Enter) if self.selected_output_frame().is_some() => {
+                    if let Err(e) = self.open_selected_frame() {
+                        self.status_message = Some(format!("Could not open file: {}", e));
+                    }
+                },
+                
+                // Copy command to clipboard (Enter)
+                (_, KeyCode::Enter) if !self.test_loading => {
+                    // Parse tests first to see if we have any
+                    self.parse_test_results();
+                    
+                    // If we have tests, view them, otherwise copy command
+                    if !self.individual_tests.is_empty() {
+                        self.view_test_results();
+                    } else {
+                        self.copy_command_to_clipboard();
+                    }
+                },
+                
+                // Scrolling for terminal output
+                (_, KeyCode::Up) => self.scroll_up(1),
+                (_, KeyCode::Down) => self.scroll_down(1),
+                (_, KeyCode::PageUp) => self.scroll_up(10),
+                (_, KeyCode::PageDown) => self.scroll_down(10),
+                (_, KeyCode::Home) => {
+                    self.terminal_scroll = 0;
+                    self.follow_output = false;
+                },
+                (_, KeyCode::End) => self.follow(),
+                
+                // Switch between the tabs of runs started while another was going
+                (_, KeyCode::Char('[')) => self.cycle_tab(false),
+                (_, KeyCode::Char(']')) => self.cycle_tab(true),
+                (_, KeyCode::Char('x')) => self.close_tab(),
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::TestResults => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Collapse and expand describe blocks
+                (_, KeyCode::Left) if self.selected_describe().is_some_and(|(_, collapsed)| !collapsed) => {
+                    self.set_describe_collapsed(true);
+                },
+                (_, KeyCode::Right) if self.selected_describe().is_some_and(|(_, collapsed)| collapsed) => {
+                    self.set_describe_collapsed(false);
+                },
+                (_, KeyCode::Enter | KeyCode::Char(' ')) if let Some((_, collapsed)) = self.selected_describe() => {
+                    self.set_describe_collapsed(!collapsed);
+                },
+                
+                // Back to test output view (left arrow)
+                (_, KeyCode::Left) => {
+                    // Go back to the summary we came from, or the running view if we
+                    // came from running a test, otherwise go back to the list
+                    if let Some(view) = self.results_return_view.take() {
+                        self.view = view;
+                    } else if self.test_run_output.trim().is_empty() {
+                        self.view = AppView::TestList;
+                    } else {
+                        self.view = AppView::TestRunning;
+                    }
+                },
+                
+                // Show the command without running it (Shift+Enter or d)
+                (KeyModifiers::SHIFT, KeyCode::Enter) | (_, KeyCode::Char('d')) => self.show_dry_run(),
+                
+                // Run individual test (right arrow or Enter)
+                (_, KeyCode::Right | KeyCode::Enter) if self.selected_test().is_some() => {
+                    let _ = self.run_individual_test();
+                },
+                
+                // Custom actions menu
+                (_, KeyCode::Char('a')) => self.show_action_menu(),
+                
+                // Open the selected test's failing location in the editor
+                (_, KeyCode::Char('o')) => self.open_failure_in_editor(),
+                
+                // Look up who last changed the selected failing test
+                (_, KeyCode::Char('b')) => self.blame_selected_test(),
+                
+                // Copy the selected test's error message, stack trace, or full name
+                (_, KeyCode::Char('y')) => self.copy_selected_failure(false),
+                (_, KeyCode::Char('Y')) => self.copy_selected_failure(true),
+                (_, KeyCode::Char('N')) => self.copy_selected_test_name(),
+                
+                // Copy the exact command that runs the selected test
+                (_, KeyCode::Char('c')) => self.copy_pending_command(),
+                
+                // Switch between a unified and a side-by-side expected/received diff
+                (_, KeyCode::Char('v')) => self.side_by_side_diff = !self.side_by_side_diff,
+                
+                // Re-run only the failing tests
+                (_, KeyCode::Char('f')) => self.rerun_failed(),
+                
+                // Return to a run going on in the background
+                (_, KeyCode::Char('t')) if self.running_tab().is_some() => self.show_running(),
+                
+                // Resize the test list
+                (_, KeyCode::Char('<')) => self.resize_results_split(-10),
+                (_, KeyCode::Char('>')) => self.resize_results_split(10),
+                
+                // Switch between the tabs of runs started while another was going
+                (_, KeyCode::Char('[')) => self.cycle_tab(false),
+                (_, KeyCode::Char(']')) => self.cycle_tab(true),
+                (_, KeyCode::Char('x')) => self.close_tab(),
+                
+                // Navigation of test results
+                (_, KeyCode::Up | KeyCode::Char('k')) if !self.individual_tests.is_empty() => {
+                    self.selected_test_index = self.selected_test_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) if !self.individual_tests.is_empty() => {
+                    self.selected_test_index = (self.selected_test_index + 1)
+                        .min(self.test_rows().len().saturating_sub(1));
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
             
-            // Calculate time since last render
-            let now = std::time::Instant::now();
-            let elapsed = now.duration_since(last_render);
+            AppView::Audit => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the test list
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Open the file containing the selected entry
+                (_, KeyCode::Right | KeyCode::Enter) => self.open_audit_entry(),
+                
+                // Navigation of audit entries
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_audit_index = self.selected_audit_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    self.selected_audit_index = (self.selected_audit_index + 1)
+                        .min(self.audit_entries.len().saturating_sub(1));
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
             
-            // If we're in loading state or enough time has passed, redraw
-            if self.test_loading || elapsed > std::time::Duration::from_millis(100) {
-                // Draw the UI
-                terminal.draw(|frame| self.render(frame))?;
-                last_render = now;
-            }
+            AppView::ConfigPicker => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Keep the current config
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Use the highlighted config
+                (_, KeyCode::Right | KeyCode::Enter) => {
+                    if let Err(e) = self.select_config() {
+                        self.status_message = Some(format!("Could not load config: {}", e));
+                    }
+                },
+                
+                // Navigation of configs
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_config_index = self.selected_config_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    self.selected_config_index = (self.selected_config_index + 1)
+                        .min(self.config_files.len().saturating_sub(1));
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
             
-            // Use a shorter timeout while loading to keep animation smooth
-            let poll_timeout = if self.test_loading {
-                std::time::Duration::from_millis(16) // ~60fps for smooth animation
-            } else {
-                std::time::Duration::from_millis(100)
-            };
+            AppView::ProjectPicker => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Keep the current project
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // List and run the highlighted project
+                (_, KeyCode::Right | KeyCode::Enter) => self.select_project(),
+                
+                // Navigation of projects
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_project_index = self.selected_project_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    self.selected_project_index = (self.selected_project_index + 1).min(self.projects.len());
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
             
-            // Handle user input with a timeout
-            if event::poll(poll_timeout)? {
-                self.handle_crossterm_events()?;
-            }
-        }
-        
-        Ok(())
-    }
-
-    /// Reads the crossterm events and updates the state of [`App`].
-    ///
-    /// If your application needs to perform work in between handling events, you can use the
-    /// [`event::poll`] function to check if there are any events available with a timeout.
-    fn handle_crossterm_events(&mut self) -> Result<()> {
-        match event::read()? {
-            // it's important to check KeyEventKind::Press to avoid handling key release events
-            Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
-            Event::Mouse(_) => {}
-            Event::Resize(_, _) => {}
-            _ => {}
-        }
-        Ok(())
-    }
-
-    /// Handles the key events and updates the state of [`App`].
-    fn on_key_event(&mut self, key: KeyEvent) {
-        match self.view {
-            AppView::TestList => match (key.modifiers, key.code) {
+            AppView::WorkspacePicker => match (key.modifiers, key.code) {
                 // Exit application
                 (_, KeyCode::Esc | KeyCode::Char('q'))
                 | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
                 
-                // Navigation keys
-                (_, KeyCode::Up | KeyCode::Char('k')) => self.previous(),
-                (_, KeyCode::Down | KeyCode::Char('j')) => self.next(),
+                // Keep the current package
+                (_, KeyCode::Left) => self.go_back(),
                 
-                // Page up/down for faster navigation
-                (_, KeyCode::PageUp) => {
-                    for _ in 0..10 {
-                        self.previous();
-                    }
+                // Search the highlighted package
+                (_, KeyCode::Right | KeyCode::Enter) => {
+                    let result = self.select_workspace();
+                    self.report_discovery(result);
                 },
-                (_, KeyCode::PageDown) => {
-                    for _ in 0..10 {
-                        self.next();
-                    }
+                
+                // Navigation of packages
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_workspace_index = self.selected_workspace_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    let count = self.monorepo.as_ref().map_or(0, |monorepo| monorepo.packages.len());
+                    self.selected_workspace_index = (self.selected_workspace_index + 1).min(count);
                 },
                 
-                // Home/End to jump to beginning/end
-                (_, KeyCode::Home) => {
-                    self.selected_index = 0;
-                    self.scroll_offset = 0;
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::Benchmark => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the test list (a running benchmark keeps going)
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Start another benchmark once this one is done
+                (_, KeyCode::Char('B')) => self.show_benchmark(),
+                
+                // Navigation of benchmarked files
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_benchmark_index = self.selected_benchmark_index.saturating_sub(1);
                 },
-                (_, KeyCode::End) => {
-                    if !self.tests.is_empty() {
-                        self.selected_index = self.tests.len() - 1;
-                    }
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    let count = self.benchmark.as_ref().map_or(0, |benchmark| benchmark.entries.len());
+                    self.selected_benchmark_index = (self.selected_benchmark_index + 1)
+                        .min(count.saturating_sub(1));
                 },
                 
-                // View test file content (Ctrl+Right arrow)
-                (KeyModifiers::CONTROL, KeyCode::Right) => {
-                    if !self.tests.is_empty() {
-                        let _ = self.load_test_content();
-                    }
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::LayoutPicker => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Keep the current layout
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Apply the highlighted preset
+                (_, KeyCode::Right | KeyCode::Enter) => self.select_layout(),
+                
+                // Save the current layout, or delete the highlighted preset
+                (_, KeyCode::Char('s')) => self.prompt_save_layout(),
+                (_, KeyCode::Char('x')) => self.delete_selected_layout(),
+                
+                // Navigation of presets
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_layout_index = self.selected_layout_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    self.selected_layout_index = (self.selected_layout_index + 1)
+                        .min(self.layout_entries.len().saturating_sub(1));
                 },
                 
-                // View file content and parse tests (right arrow)
-                (_, KeyCode::Right) => {
-                    if !self.tests.is_empty() {
-                        // First, load the test file content to parse
-                        let _ = self.load_and_parse_individual_tests();
-                        
-                        // If we found tests, show the test results view
-                        if !self.individual_tests.is_empty() {
-                            self.view = AppView::TestResults;
-                        }
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::ConfigInspector => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the test list
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Scrolling
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.inspector_scroll = self.inspector_scroll.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => self.inspector_scroll += 1,
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::DirectoryStats => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the test list
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Jump to the selected directory in the list
+                (_, KeyCode::Right | KeyCode::Enter) => self.jump_to_directory(),
+                
+                // Navigation of directories
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_directory_index = self.selected_directory_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    self.selected_directory_index = (self.selected_directory_index + 1)
+                        .min(self.directory_stats.len().saturating_sub(1));
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::Leaderboard => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the test list
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Open the selected test or file
+                (_, KeyCode::Right | KeyCode::Enter) => self.open_leaderboard_entry(),
+                
+                // Switch between tests and files
+                (_, KeyCode::Tab) => {
+                    self.leaderboard.toggle_kind();
+                    self.selected_leaderboard_index = 0;
+                },
+                
+                // Cycle the sort order
+                (_, KeyCode::Char('s')) => {
+                    self.leaderboard.cycle_sort();
+                    self.selected_leaderboard_index = 0;
+                },
+                
+                // Navigation of entries
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_leaderboard_index = self.selected_leaderboard_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    self.selected_leaderboard_index = (self.selected_leaderboard_index + 1)
+                        .min(self.leaderboard.entries().len().saturating_sub(1));
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::FailureGroups => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the view the groups were opened from
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Open the group's first failure
+                (_, KeyCode::Right | KeyCode::Enter) => self.open_failure_group(),
+                
+                // Navigation of groups
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_failure_group_index = self.selected_failure_group_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    self.selected_failure_group_index = (self.selected_failure_group_index + 1)
+                        .min(self.failure_groups.groups.len().saturating_sub(1));
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::RunHistory => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the test list
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Mark the selected run to compare against
+                (_, KeyCode::Char(' ')) => self.toggle_history_base(),
+                
+                // Navigation of runs
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_history_index = self.selected_history_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    self.selected_history_index = (self.selected_history_index + 1).min(self.run_history().len().saturating_sub(1));
+                },
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::Snapshots => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the view the snapshots were opened from
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // Jump to the test that wrote the selected snapshot
+                (_, KeyCode::Right | KeyCode::Enter) => self.open_snapshot_test(),
+                
+                // Navigation of files and entries
+                (_, KeyCode::Up | KeyCode::Char('k')) => self.select_snapshot_row(self.selected_snapshot_index.saturating_sub(1)),
+                (_, KeyCode::Down | KeyCode::Char('j')) => self.select_snapshot_row(self.selected_snapshot_index + 1),
+                
+                // Scroll the selected file
+                (_, KeyCode::PageUp) => self.scroll_snapshot(-10),
+                (_, KeyCode::PageDown) => self.scroll_snapshot(10),
+                
+                // Ignore other keys
+                _ => {}
+            },
+            
+            AppView::Coverage => match (key.modifiers, key.code) {
+                // Exit application
+                (_, KeyCode::Esc | KeyCode::Char('q'))
+                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
+                
+                // Back to the test list
+                (_, KeyCode::Left) => self.go_back(),
+                
+                // View the selected source file
+                (_, KeyCode::Right | KeyCode::Enter) => {
+                    if let Err(e) = self.open_coverage_file() {
+                        self.status_message = Some(format!("Could not open file: {}", e));
                     }
                 },
                 
-                // Run test (enter/return)
-                (_, KeyCode::Enter) => {
-                    if !self.tests.is_empty() {
-                        let _ = self.run_test();
-                    }
+                // Toggle coverage collection
+                (_, KeyCode::Char('%')) => self.set_coverage_mode(!self.coverage_mode),
+                
+                // Filter the list to tests that depend on the selected file
+                (_, KeyCode::Char('M')) => self.prompt_related_source(),
+                
+                // Compare against the coverage recorded on a base ref
+                (_, KeyCode::Char('b')) => self.prompt_coverage_base(),
+                
+                // Return to a run going on in the background
+                (_, KeyCode::Char('t')) if self.running_tab().is_some() => self.show_running(),
+                
+                // Navigation of covered files
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_coverage_index = self.selected_coverage_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    let file_count = self.coverage.as_ref().map_or(0, |coverage| coverage.files.len());
+                    self.selected_coverage_index = (self.selected_coverage_index + 1).min(file_count.saturating_sub(1));
                 },
                 
                 // Ignore other keys
                 _ => {}
             },
             
-            AppView::TestDetail => match (key.modifiers, key.code) {
+            AppView::CoverageDiff => match (key.modifiers, key.code) {
                 // Exit application
                 (_, KeyCode::Esc | KeyCode::Char('q'))
                 | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
                 
-                // Go back (left arrow)
+                // Back to the coverage view
                 (_, KeyCode::Left) => self.go_back(),
                 
-                // Run test (enter/return)
-                (_, KeyCode::Enter) => {
-                    let _ = self.run_test();
+                // View the selected source file
+                (_, KeyCode::Right | KeyCode::Enter) => {
+                    if let Err(e) = self.open_coverage_delta_file() {
+                        self.status_message = Some(format!("Could not open file: {}", e));
+                    }
+                },
+                
+                // Compare against a different base
+                (_, KeyCode::Char('b')) => self.prompt_coverage_base(),
+                
+                // Navigation of changed files
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_coverage_delta_index = self.selected_coverage_delta_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    let file_count = self.coverage_delta.as_ref().map_or(0, |delta| delta.files.len());
+                    self.selected_coverage_delta_index = (self.selected_coverage_delta_index + 1).min(file_count.saturating_sub(1));
                 },
                 
                 // Ignore other keys
                 _ => {}
             },
             
-            AppView::TestRunning => match (key.modifiers, key.code) {
+            AppView::Queue => match (key.modifiers, key.code) {
                 // Exit application
                 (_, KeyCode::Esc | KeyCode::Char('q'))
                 | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
                 
-                // Go back (left arrow)
+                // Back to the test list
                 (_, KeyCode::Left) => self.go_back(),
                 
-                // View individual test results (right arrow)
-                (_, KeyCode::Right) => {
-                    if !self.test_loading {
-                        self.view_test_results();
-                    }
-                },
+                // Drill into the selected file's results
+                (_, KeyCode::Right | KeyCode::Enter) => self.open_queue_entry(),
                 
-                // Copy command to clipboard (Enter)
-                (_, KeyCode::Enter) => {
-                    if !self.test_loading {
-                        // Parse tests first to see if we have any
-                        self.parse_test_results();
-                        
-                        // If we have tests, view them, otherwise copy command
-                        if !self.individual_tests.is_empty() {
-                            self.view_test_results();
-                        } else {
-                            let _ = self.copy_command_to_clipboard();
-                        }
-                    }
-                },
+                // Group the queued files' failures by error message
+                (_, KeyCode::Char('G')) => self.show_failure_groups(),
                 
-                // Scrolling for terminal output
-                (_, KeyCode::Up) => self.scroll_up(1),
-                (_, KeyCode::Down) => self.scroll_down(1),
-                (_, KeyCode::PageUp) => self.scroll_up(10),
-                (_, KeyCode::PageDown) => self.scroll_down(10),
-                (_, KeyCode::Home) => self.terminal_scroll = 0,
-                (_, KeyCode::End) => {
-                    // Set scroll position to show the last page of output with last line at bottom
-                    let approx_visible_lines = 20; // Reasonable estimate for most terminals
-                    let line_count = self.test_run_output.lines().count();
-                    self.terminal_scroll = line_count.saturating_sub(approx_visible_lines).max(0);
+                // Return to a run going on in the background
+                (_, KeyCode::Char('t')) if self.running_tab().is_some() => self.show_running(),
+                
+                // Navigation of queued files
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    self.selected_queue_index = self.selected_queue_index.saturating_sub(1);
+                },
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    let file_count = self.queue.as_ref().map_or(0, |queue| queue.entries.len());
+                    self.selected_queue_index = (self.selected_queue_index + 1).min(file_count.saturating_sub(1));
                 },
                 
                 // Ignore other keys
                 _ => {}
             },
             
-            AppView::TestResults => match (key.modifiers, key.code) {
+            AppView::RunSummary => match (key.modifiers, key.code) {
                 // Exit application
                 (_, KeyCode::Esc | KeyCode::Char('q'))
                 | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
                 
-                // Back to test output view (left arrow)
-                (_, KeyCode::Left) => {
-                    // If we came from running a test, go back to running view
-                    // otherwise go back to the list
-                    if self.test_run_output.trim().is_empty() {
-                        self.view = AppView::TestList;
-                    } else {
-                        self.view = AppView::TestRunning;
-                    }
-                },
+                // Back to the test list
+                (_, KeyCode::Left) => self.go_back(),
                 
-                // Run individual test (right arrow or Enter)
-                (_, KeyCode::Right | KeyCode::Enter) => {
-                    if !self.individual_tests.is_empty() {
-                        let _ = self.run_individual_test();
-                    }
-                },
+                // Drill into the selected file's results
+                (_, KeyCode::Right | KeyCode::Enter) => self.open_summary_file(),
                 
-                // Navigation of test results
+                // Run every listed file again
+                (_, KeyCode::Char('R')) => self.run_all(),
+                
+                // Group the run's failures by error message
+                (_, KeyCode::Char('G')) => self.show_failure_groups(),
+                
+                // Return to a run going on in the background
+                (_, KeyCode::Char('t')) if self.running_tab().is_some() => self.show_running(),
+                
+                // Navigation of files
                 (_, KeyCode::Up | KeyCode::Char('k')) => {
-                    if !self.individual_tests.is_empty() {
-                        self.selected_test_index = self.selected_test_index.saturating_sub(1);
-                    }
+                    self.selected_summary_index = self.selected_summary_index.saturating_sub(1);
                 },
                 (_, KeyCode::Down | KeyCode::Char('j')) => {
-                    if !self.individual_tests.is_empty() {
-                        self.selected_test_index = (self.selected_test_index + 1)
-                            .min(self.individual_tests.len().saturating_sub(1));
-                    }
+                    let file_count = self.run_summary.as_ref().map_or(0, |summary| summary.files.len());
+                    self.selected_summary_index = (self.selected_summary_index + 1).min(file_count.saturating_sub(1));
                 },
                 
                 // Ignore other keys
@@ -848,23 +5552,86 @@ impl App {
         }
     }
 
-    /// Set running to false to quit the application.
+    /// Set running to false to quit the application, first asking what to do
+    /// with any runs still going.
     pub fn quit(&mut self) {
-        self.running = false;
+        if self.runs_going() > 0 {
+            self.confirm_quit = true;
+        } else {
+            self.running = false;
+        }
+    }
+    
+    /// Handle a key press while asking whether to quit with runs still going
+    fn on_confirm_quit_key(&mut self, key: KeyEvent) {
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter)
+            | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.running = false,
+            (_, KeyCode::Char('d') | KeyCode::Char('D')) => {
+                self.detached = true;
+                self.running = false;
+            },
+            (_, KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc) => self.confirm_quit = false,
+            _ => {}
+        }
+    }
+    
+    /// After quitting with `d`, wait for the runs that were going to finish,
+    /// printing how each one went. Runs queued behind them are run too.
+    pub fn finish_detached_runs(&mut self) {
+        let going = self.runs_going();
+        println!(
+            "Waiting for {} to finish (Ctrl+C stops waiting)...",
+            if going == 1 { "1 run".to_string() } else { format!("{} runs", going) }
+        );
+        
+        let mut reported = self.session.runs.len();
+        loop {
+            self.check_test_results();
+            self.check_queue();
+            for run in &self.session.runs[reported..] {
+                println!("{}", self.detached_run_line(run));
+            }
+            reported = self.session.runs.len();
+            if self.runs_going() == 0 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    }
+    
+    /// How a run finished, for printing after a detached quit
+    fn detached_run_line(&self, run: &SessionRun) -> String {
+        let duration = self.config.format.duration(run.duration_ms);
+        match (&run.error, run.summary) {
+            (Some(error), _) => format!("✗ {}: {}", run.label, error),
+            (None, Some(summary)) if summary.is_success() => {
+                format!("✓ {}: {} passed in {}", run.label, summary.passed, duration)
+            },
+            (None, Some(summary)) => {
+                format!("✗ {}: {} failed, {} passed in {}", run.label, summary.failed, summary.passed, duration)
+            },
+            (None, None) => format!("? {}: finished in {} without a summary", run.label, duration),
+        }
     }
     
     /// Renders the user interface
     pub fn render(&mut self, frame: &mut Frame) {
-        use crate::widgets::{HeaderWidget, TestListWidget, TestDetailWidget, TestTerminalWidget, TestResultsWidget, HelpBarWidget, SpinnerWidget};
+        use crate::widgets::{HeaderWidget, PromptWidget, TestListWidget, TestDetailWidget, TestTerminalWidget, TestResultsWidget, SpinnerWidget, FocusWarningWidget, AuditWidget, DirectoryStatsWidget, LeaderboardWidget, ConfigPickerWidget, ProjectPickerWidget, WorkspacePickerWidget, ImportsWidget, ConfigInspectorWidget, DryRunWidget, MissingRunnerWidget, LayoutPickerWidget, ActionMenuWidget, RunOptionsWidget, BenchmarkWidget, OnboardingWidget, RunPanelWidget, RunTabsWidget, CommandLineWidget, RunSummaryWidget, QueueWidget, CoverageWidget, StatusFooterWidget, BatchProgressWidget, ToastsWidget, LogViewerWidget, ConfirmQuitWidget, FailureGroupsWidget, SnapshotsWidget, CoverageDiffWidget, RunHistoryWidget};
         
         let area = frame.area();
+        let theme = self.theme;
 
-        // Split the screen vertically: header (3 lines), main content, help bar (1 line)
+        // Runs keep going when the user leaves the output view, so show their progress
+        let show_run_panel = self.test_loading && self.view != AppView::TestRunning;
+        
+        // Split the screen vertically: header (3 lines), main content, run panel, help bar (1 line)
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),     // Header
                 Constraint::Min(0),        // Main content
+                Constraint::Length(if show_run_panel { 3 } else { 0 }), // Background run
                 Constraint::Length(1),     // Help bar
             ])
             .split(area);
@@ -877,36 +5644,62 @@ impl App {
 
         // Determine the appropriate title and subtitle based on the current view
         let (title, subtitle) = match self.view {
-            AppView::TestList => (
-                "Surely You Jest".to_string(),
-                format!(
-                    "Tests in: {} (Found: {}) [Patterns: {}]", 
-                    self.search_path, 
-                    self.tests.len(),
-                    self.test_matches.join(", ")
-                )
-            ),
-            AppView::TestDetail => {
-                let test_name = if !self.tests.is_empty() {
-                    &self.tests[self.selected_index]
-                } else {
-                    "Unknown Test"
+            AppView::TestList => {
+                let found = match &self.list_filter {
+                    Some(filter) => format!(
+                        "Showing: {} of {}, {}",
+                        self.tests.len(),
+                        self.all_tests.len(),
+                        filter.description()
+                    ),
+                    None => format!("Found: {}", self.tests.len()),
                 };
                 (
-                    "Test File".to_string(),
-                    format!("{}", test_name)
+                    "Surely You Jest".to_string(),
+                    format!(
+                        "Tests in: {} ({}) [Patterns: {}]", 
+                        self.search_path, 
+                        found,
+                        self.test_matches.join(", ")
+                    )
                 )
             },
-            AppView::TestRunning => {
+            AppView::TestDetail => {
                 let test_name = if !self.tests.is_empty() {
                     &self.tests[self.selected_index]
                 } else {
                     "Unknown Test"
                 };
-                (
-                    "Test Results".to_string(),
-                    format!("Running: {}", test_name)
-                )
+                match (&self.detail_source, self.detail_highlight) {
+                    (Some(source), Some(line)) => (
+                        "Source File".to_string(),
+                        format!("{}:{}", source, line)
+                    ),
+                    (Some(source), None) => (
+                        "Source File".to_string(),
+                        format!("{} (imported by {})", source, test_name)
+                    ),
+                    (None, _) => (
+                        "Test File".to_string(),
+                        match self.detail_cursor_test() {
+                            Some(test) => format!("{} › {}", test_name, test.full_name()),
+                            None => test_name.to_string(),
+                        }
+                    ),
+                }
+            },
+            AppView::TestRunning => {
+                let test_name = self.run_label().unwrap_or_else(|| "Unknown Test".to_string());
+                match &self.running_action {
+                    Some(action) => (
+                        "Custom Action".to_string(),
+                        action.clone()
+                    ),
+                    None => (
+                        "Test Results".to_string(),
+                        format!("Running: {}", test_name)
+                    ),
+                }
             },
             AppView::TestResults => {
                 let test_name = if !self.tests.is_empty() {
@@ -918,89 +5711,617 @@ impl App {
                     "Individual Tests".to_string(),
                     format!("File: {}", test_name)
                 )
-            }
+            },
+            AppView::Audit => (
+                "Test Audit".to_string(),
+                format!("Skipped, todo, and focused tests in: {}", self.search_path)
+            ),
+            AppView::ConfigInspector => (
+                "Resolved Configuration".to_string(),
+                format!("How tests are discovered and run in: {}", self.search_path)
+            ),
+            AppView::ConfigPicker => (
+                "Jest Config".to_string(),
+                format!("{} config files found in: {}", self.config_files.len(), self.search_path)
+            ),
+            AppView::ProjectPicker => (
+                "Jest Projects".to_string(),
+                format!("{} projects declared in: {}", self.projects.len(), self.search_path)
+            ),
+            AppView::WorkspacePicker => (
+                "Workspaces".to_string(),
+                match &self.monorepo {
+                    Some(monorepo) => format!("{} packages in: {}", monorepo.packages.len(), monorepo.root.display()),
+                    None => String::new(),
+                }
+            ),
+            AppView::DirectoryStats => (
+                "Directory Stats".to_string(),
+                format!("Last known results by directory in: {}", self.search_path)
+            ),
+            AppView::Leaderboard => (
+                "Slowest Tests".to_string(),
+                format!("{} tests timed this session, {} files with run history", self.leaderboard.tests.len(), self.leaderboard.files.len())
+            ),
+            AppView::FailureGroups => (
+                "Failure Groups".to_string(),
+                match self.failure_groups_return_view {
+                    AppView::RunSummary => "Failures of the run of every listed file, grouped by error".to_string(),
+                    AppView::Queue => "Failures of the queued files, grouped by error".to_string(),
+                    _ => format!("Failures of {}, grouped by error", self.run_label().unwrap_or_else(|| "the last run".to_string())),
+                }
+            ),
+            AppView::RunHistory => (
+                "Run History".to_string(),
+                format!("Recorded runs of {}", self.tests.get(self.selected_index).map_or("", String::as_str))
+            ),
+            AppView::Snapshots => (
+                "Snapshots".to_string(),
+                format!("Snapshot files of {}", self.tests.get(self.selected_index).map_or("", String::as_str))
+            ),
+            AppView::Benchmark => (
+                "Benchmark".to_string(),
+                match &self.benchmark {
+                    Some(benchmark) if !benchmark.finished => {
+                        let (done, total) = benchmark.progress();
+                        format!("Running {} of {} runs against {}", done + 1, total, benchmark.baseline)
+                    },
+                    Some(benchmark) => format!("Finished comparing against {}", benchmark.baseline),
+                    None => String::new(),
+                }
+            ),
+            AppView::RunSummary => (
+                "Run Summary".to_string(),
+                match &self.run_summary {
+                    Some(summary) => format!(
+                        "{} files, {} failed, in {}",
+                        summary.files.len(),
+                        summary.files_failed(),
+                        self.config.format.duration(summary.duration_ms)
+                    ),
+                    None => String::new(),
+                }
+            ),
+            AppView::Coverage => (
+                "Coverage".to_string(),
+                match self.coverage.as_ref().and_then(|coverage| coverage.total.lines.pct) {
+                    Some(pct) => format!("{:.1}% of lines covered in: {}", pct, self.search_path),
+                    None => self.search_path.clone(),
+                }
+            ),
+            AppView::CoverageDiff => (
+                "Coverage Diff".to_string(),
+                match &self.coverage_delta {
+                    Some(delta) => format!(
+                        "Last coverage run vs {} ({}), recorded {}",
+                        delta.base_ref,
+                        &delta.base_commit[..8.min(delta.base_commit.len())],
+                        self.config.format.timestamp(delta.base_timestamp)
+                    ),
+                    None => self.search_path.clone(),
+                }
+            ),
+            AppView::Queue => (
+                "Run Queue".to_string(),
+                match &self.queue {
+                    Some(queue) => {
+                        let (done, total) = queue.progress();
+                        format!("{} of {} files finished, {} running", done, total, queue.running())
+                    },
+                    None => String::new(),
+                }
+            ),
+            AppView::LayoutPicker => (
+                "Layouts".to_string(),
+                format!("{} layout presets for: {}", self.layout_entries.len(), self.search_path)
+            ),
         };
 
+        // Label file-specific views with the file's project
+        let display_name = match self.view {
+            AppView::TestDetail | AppView::TestRunning | AppView::TestResults => self.tests
+                .get(self.selected_index)
+                .and_then(|test_file| self.display_names.get(test_file)),
+            _ => None,
+        };
+        
+        // Chart how long the file's recent runs took, once there's a trend to see
+        let trend = match self.view {
+            AppView::TestDetail if self.detail_source.is_none() => self.tests.get(self.selected_index),
+            AppView::TestRunning | AppView::TestResults => self.tests.get(self.selected_index),
+            _ => None,
+        }
+        .map(|test_file| self.history.durations(test_file))
+        .filter(|durations| durations.len() > 1)
+        .map(|durations| {
+            let latest = self.config.format.duration(durations.last().copied().unwrap_or_default());
+            (durations, latest)
+        });
+        
+        // Flag patterns that matched nothing below the list header
+        let pattern_warnings = self.pattern_warnings();
+        let warning = (self.view == AppView::TestList && !pattern_warnings.is_empty())
+            .then(|| format!("Unproductive testMatch patterns: {}", pattern_warnings.join("; ")));
+        
+        // Status messages take over the subtitle until the next key press
+        let subtitle = match &self.status_message {
+            Some(message) => message.clone(),
+            None => subtitle,
+        };
+        
         // Render the header widget at the top
         frame.render_widget(
             HeaderWidget {
                 title,
                 subtitle,
+                display_name,
+                trend,
+                warning,
+                recording: self.macros.recording_register(),
+                watching: self.watcher.is_some(),
+                coverage: self.coverage_mode,
+                running: self.background_run(),
+                scanning: self.discovery.as_ref().map(|discovery| discovery.found),
+                theme,
             },
             chunks[0],
         );
 
+        // Warn about focused tests above the file-specific views
+        let mut content_area = chunks[1];
+        let focused = self.selected_focused_tests();
+        let showing_test_file = match self.view {
+            AppView::TestDetail => self.detail_source.is_none(),
+            AppView::TestResults => true,
+            _ => false,
+        };
+        if showing_test_file && !focused.is_empty() {
+            let warning_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3), // Focus warning
+                    Constraint::Min(0),    // View content
+                ])
+                .split(content_area);
+            frame.render_widget(FocusWarningWidget::new(focused).theme(theme), warning_chunks[0]);
+            content_area = warning_chunks[1];
+        }
+        
+        // List the runs' tabs above their output and results once there's more than one
+        if matches!(self.view, AppView::TestRunning | AppView::TestResults) && self.run_tabs.len() > 1 {
+            let [tabs_area, rest] = Layout::vertical([
+                Constraint::Length(1), // Tab bar
+                Constraint::Min(0),    // View content
+            ]).areas(content_area);
+            let tabs = self.run_tab_summaries();
+            frame.render_widget(RunTabsWidget::new(&tabs, self.active_tab).theme(theme), tabs_area);
+            content_area = rest;
+        }
+        
         // Render appropriate content based on the current view
         match self.view {
+            // Guide the user through adjusting discovery when nothing was found
+            AppView::TestList if self.all_tests.is_empty() && self.discovery.is_none() => {
+                let widget = OnboardingWidget::new(
+                    &self.search_path,
+                    self.config_path.as_deref(),
+                    &self.pattern_diagnostics
+                )
+                .theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
             AppView::TestList => {
+                // Make room for the imports pane if it's open
+                let list_area = if self.show_imports {
+                    let panes = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([
+                            Constraint::Percentage(65), // Test list
+                            Constraint::Percentage(35), // Imports
+                        ])
+                        .split(chunks[1]);
+                    let selected_import = self.imports_focused.then_some(self.selected_import_index);
+                    let imports = ImportsWidget::new(self.selected_imports(), selected_import).theme(theme);
+                    frame.render_widget(imports, panes[1]);
+                    panes[0]
+                } else {
+                    chunks[1]
+                };
+                
                 let widget = TestListWidget::new(
                     &self.tests,
                     self.selected_index,
                     self.scroll_offset
-                );
-                frame.render_widget(widget, chunks[1]);
+                )
+                .focused_tests(&self.focused_tests)
+                .display_names(&self.display_names)
+                .history(&self.history)
+                .sort_mode(self.sort_mode)
+                .search(self.search.as_ref())
+                .marked(&self.marked_files)
+                .format(&self.config.format)
+                .theme(theme);
+                frame.render_widget(widget, list_area);
             },
             AppView::TestDetail => {
-                let widget = TestDetailWidget::new(&self.current_test_content);
-                frame.render_widget(widget, chunks[1]);
+                // The search input takes the last line
+                self.detail_page = (content_area.height as usize).saturating_sub(usize::from(self.detail_search.is_some()));
+                
+                // Mark line coverage on a source file opened on its own
+                let source_hits = self.detail_source
+                    .as_ref()
+                    .and_then(|source| self.coverage.as_ref()?.line_hits.get(source));
+                let test_marks = self.detail_test_marks();
+                let mut widget = TestDetailWidget::new(&self.current_test_content)
+                    .theme(theme)
+                    .scroll(self.detail_scroll)
+                    .highlight_line(self.detail_highlight)
+                    .test_marks(&test_marks);
+                if let Some(search) = &self.detail_search {
+                    widget = widget.search(search);
+                }
+                if let Some(line_hits) = source_hits {
+                    widget = widget.line_hits(line_hits);
+                }
+                
+                // Show the covered import next to the test file that imports it
+                let covered = self.detail_coverage
+                    .get(self.detail_coverage_index)
+                    .and_then(|(source, content)| {
+                        let line_hits = self.coverage.as_ref()?.line_hits.get(source)?;
+                        Some((source, content, line_hits))
+                    });
+                match covered {
+                    Some((source, content, line_hits)) => {
+                        let [test_area, source_area] = Layout::horizontal([
+                            Constraint::Percentage(50),
+                            Constraint::Percentage(50),
+                        ]).areas(content_area);
+                        frame.render_widget(widget, test_area);
+                        
+                        let run = line_hits.values().filter(|&&hits| hits > 0).count();
+                        let mut title = format!(" {} - {}/{} lines run ", source, run, line_hits.len());
+                        if self.detail_coverage.len() > 1 {
+                            title.push_str(&format!("[{}/{}, c: next] ", self.detail_coverage_index + 1, self.detail_coverage.len()));
+                        }
+                        let block = Block::default().title(title).borders(Borders::LEFT);
+                        frame.render_widget(TestDetailWidget::new(content).theme(theme).line_hits(line_hits), block.inner(source_area));
+                        frame.render_widget(block, source_area);
+                    },
+                    None => frame.render_widget(widget, content_area),
+                }
             },
             AppView::TestRunning => {
-                // Get command for the currently selected test
-                let test_file = if !self.tests.is_empty() {
-                    &self.tests[self.selected_index]
-                } else {
-                    ""
-                };
-                let command = format!("cd {} && npx jest {} --no-cache", self.search_path, test_file);
+                // Get command for the current run
+                let command = self.last_command
+                    .as_ref()
+                    .map(JestCommand::shell_command)
+                    .unwrap_or_default();
                 
-                if self.test_loading {
-                    // Show spinner when test is loading
-                    let test_name = if !self.tests.is_empty() {
-                        &self.tests[self.selected_index]
-                    } else {
-                        "test"
-                    };
-                    let spinner = SpinnerWidget::new(format!("Running {}...", test_name))
-                        .style(crate::widgets::spinner::SpinnerStyle::Dot);
+                // Keep a spinner above the output while it's still streaming in,
+                // or a progress bar when it's running every listed file
+                let mut output_area = content_area;
+                if self.test_loading && let Some(total) = self.running_all {
+                    let (passed, failed) = output_parser::count_finished_files(&self.test_run_output);
+                    let (elapsed_ms, _) = self.run_timing();
+                    let progress = BatchProgressWidget::new(passed + failed, total, passed, failed)
+                        .elapsed(self.config.format.duration(elapsed_ms))
+                        .theme(theme);
                     
-                    // Center the spinner in the content area
-                    let spinner_area = Layout::default()
+                    let running_chunks = Layout::default()
                         .direction(Direction::Vertical)
                         .constraints([
-                            Constraint::Percentage(40),
-                            Constraint::Length(3),
-                            Constraint::Percentage(40),
+                            Constraint::Length(3), // Progress bar
+                            Constraint::Min(0),    // Output so far
                         ])
-                        .split(chunks[1])[1];
+                        .split(content_area);
                     
-                    frame.render_widget(spinner, spinner_area);
-                } else {
-                    // Show test results when loading is complete
-                    let widget = TestTerminalWidget::new(
-                        &command,
-                        &self.test_run_output,
-                        self.terminal_scroll,
-                        self.copied_command.is_some()
-                    );
-                    frame.render_widget(widget, chunks[1]);
+                    frame.render_widget(progress, running_chunks[0]);
+                    output_area = running_chunks[1];
+                } else if self.test_loading {
+                    let test_name = self.run_label().unwrap_or_else(|| "test".to_string());
+                    let (elapsed_ms, estimate_ms) = self.run_timing();
+                    let elapsed = self.config.format.elapsed(elapsed_ms, estimate_ms);
+                    let progress = estimate_ms.map(|estimate_ms| elapsed_ms as f64 / estimate_ms.max(1) as f64);
+                    let spinner = SpinnerWidget::new(format!("Running {}... {}", test_name, elapsed))
+                        .style(crate::widgets::spinner::SpinnerStyle::Dot)
+                        .progress(progress)
+                        .theme(theme);
+                    
+                    let running_chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([
+                            Constraint::Length(if progress.is_some() { 4 } else { 3 }), // Spinner
+                            Constraint::Min(0),    // Output so far
+                        ])
+                        .split(content_area);
+                    
+                    frame.render_widget(spinner, running_chunks[0]);
+                    output_area = running_chunks[1];
                 }
+                
+                let widget = TestTerminalWidget::new(
+                    &command,
+                    &self.test_run_output,
+                    self.terminal_scroll
+                )
+                .snapshots(self.last_results.as_ref().map(|results| results.snapshot))
+                .highlight_line(self.selected_output_frame().map(|frame| frame.output_line))
+                .rows(self.output_rows(), self.failures_only)
+                .following(self.test_loading.then_some(self.follow_output))
+                .theme(theme);
+                frame.render_widget(widget, output_area);
             },
             AppView::TestResults => {
                 let widget = TestResultsWidget::new(
                     &self.individual_tests,
                     self.selected_test_index
-                );
+                )
+                .collapsed(&self.collapsed_describes)
+                .blame(self.selected_test_blame())
+                .format(&self.config.format)
+                .split(self.results_split)
+                .side_by_side_diff(self.side_by_side_diff)
+                .theme(theme);
+                frame.render_widget(widget, content_area);
+            },
+            AppView::Audit => {
+                let widget = AuditWidget::new(&self.audit_entries, self.selected_audit_index).theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::ConfigInspector => {
+                let widget = ConfigInspectorWidget::new(&self.config_report, self.inspector_scroll).theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::ConfigPicker => {
+                let widget = ConfigPickerWidget::new(
+                    &self.config_files,
+                    self.selected_config_index,
+                    self.config_path.as_ref()
+                )
+                .theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::ProjectPicker => {
+                let active = match &self.list_filter {
+                    Some(ListFilter::Project(label)) => Some(label.as_str()),
+                    _ => None,
+                };
+                let widget = ProjectPickerWidget::new(
+                    &self.projects,
+                    self.selected_project_index,
+                    active,
+                    self.all_tests.len()
+                )
+                .theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::WorkspacePicker => {
+                if let Some(monorepo) = &self.monorepo {
+                    let search_path = Path::new(&self.search_path).canonicalize().ok();
+                    let widget = WorkspacePickerWidget::new(
+                        monorepo,
+                        self.selected_workspace_index,
+                        search_path.as_deref()
+                    )
+                    .theme(theme);
+                    frame.render_widget(widget, chunks[1]);
+                }
+            },
+            AppView::DirectoryStats => {
+                let widget = DirectoryStatsWidget::new(&self.directory_stats, self.selected_directory_index)
+                    .format(&self.config.format)
+                    .theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::Leaderboard => {
+                let widget = LeaderboardWidget::new(&self.leaderboard, self.selected_leaderboard_index)
+                    .format(&self.config.format)
+                    .theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::FailureGroups => {
+                let widget = FailureGroupsWidget::new(&self.failure_groups, self.selected_failure_group_index)
+                    .theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::RunHistory => {
+                let comparison = self.run_comparison();
+                let widget = RunHistoryWidget::new(self.run_history(), self.selected_history_index)
+                    .comparison(self.compared_runs(), comparison.as_ref())
+                    .format(&self.config.format)
+                    .theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::Snapshots => {
+                let widget = SnapshotsWidget::new(&self.snapshots, self.selected_snapshot_index, self.snapshot_scroll)
+                    .theme(theme);
+                frame.render_widget(widget, chunks[1]);
+            },
+            AppView::Benchmark => {
+                if let Some(benchmark) = &self.benchmark {
+                    let widget = BenchmarkWidget::new(benchmark, self.selected_benchmark_index)
+                        .format(&self.config.format)
+                        .theme(theme);
+                    frame.render_widget(widget, chunks[1]);
+                }
+            },
+            AppView::LayoutPicker => {
+                let widget = LayoutPickerWidget::new(
+                    &self.layout_entries,
+                    self.selected_layout_index,
+                    self.layouts.active.as_deref()
+                )
+                .theme(theme);
                 frame.render_widget(widget, chunks[1]);
+            },
+            AppView::Coverage => {
+                if let Some(coverage) = &self.coverage {
+                    let widget = CoverageWidget::new(coverage, self.selected_coverage_index).theme(theme);
+                    frame.render_widget(widget, chunks[1]);
+                }
+            },
+            AppView::CoverageDiff => {
+                if let Some(delta) = &self.coverage_delta {
+                    let widget = CoverageDiffWidget::new(delta, self.selected_coverage_delta_index).theme(theme);
+                    frame.render_widget(widget, chunks[1]);
+                }
+            },
+            AppView::Queue => {
+                if let Some(queue) = &self.queue {
+                    let (done, total) = queue.progress();
+                    let count = |passed| queue.entries.iter().filter(|entry| entry.passed() == Some(passed)).count();
+                    let progress = BatchProgressWidget::new(done, total, count(true), count(false)).theme(theme);
+                    
+                    let queue_chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([
+                            Constraint::Length(3), // Progress bar
+                            Constraint::Min(0),    // Queued files
+                        ])
+                        .split(chunks[1]);
+                    
+                    let widget = QueueWidget::new(queue, self.selected_queue_index)
+                        .format(&self.config.format)
+                        .theme(theme);
+                    frame.render_widget(progress, queue_chunks[0]);
+                    frame.render_widget(widget, queue_chunks[1]);
+                }
+            },
+            AppView::RunSummary => {
+                if let Some(summary) = &self.run_summary {
+                    let widget = RunSummaryWidget::new(summary, self.selected_summary_index)
+                        .format(&self.config.format)
+                        .theme(theme);
+                    frame.render_widget(widget, chunks[1]);
+                }
             }
         }
         
-        // Render the appropriate help bar for the current view
-        let help_bar = match self.view {
-            AppView::TestList => HelpBarWidget::for_test_list(),
-            AppView::TestDetail => HelpBarWidget::for_test_detail(),
-            AppView::TestRunning => HelpBarWidget::for_test_terminal(),
-            AppView::TestResults => HelpBarWidget::for_test_results(),
-        };
-        frame.render_widget(help_bar, chunks[2]);
+        if show_run_panel {
+            let label = self.run_label().unwrap_or_else(|| "test".to_string());
+            let (elapsed_ms, estimate_ms) = self.run_timing();
+            let widget = RunPanelWidget::new(&label, &self.test_run_output, elapsed_ms)
+                .estimate(estimate_ms)
+                .format(&self.config.format)
+                .theme(theme);
+            frame.render_widget(widget, chunks[2]);
+        }
+        
+        // Render the appropriate help bar for the current view, with the
+        // session's totals at its right end
+        let help_bar = self.help_bar().theme(theme);
+        match &self.command_line {
+            Some(line) => frame.render_widget(CommandLineWidget::new(line).theme(theme), chunks[3]),
+            None => {
+                let footer = StatusFooterWidget::new(self.session.stats(), self.background_run())
+                    .format(&self.config.format)
+                    .theme(theme);
+                let bottom = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Min(0), Constraint::Length(footer.width())])
+                    .split(chunks[3]);
+                frame.render_widget(help_bar, bottom[0]);
+                frame.render_widget(footer, bottom[1]);
+            },
+        }
+        
+        // Render any open popup over everything else
+        if let Some(command) = &self.dry_run {
+            frame.render_widget(DryRunWidget::new(command).theme(theme), area);
+        }
+        if let Some(reason) = &self.missing_runner {
+            let install_command = self.install_command();
+            let widget = MissingRunnerWidget::new(self.runner.label(), reason, &install_command)
+                .theme(theme);
+            frame.render_widget(widget, area);
+        }
+        if let Some(selected) = self.action_menu {
+            frame.render_widget(ActionMenuWidget::new(&self.config.actions, selected).theme(theme), area);
+        }
+        if let Some(selected) = self.run_options {
+            let options = self.run_option_states();
+            frame.render_widget(RunOptionsWidget::new(&options, selected).extra_args(&self.extra_args).theme(theme), area);
+        }
+        if let Some(prompt) = &self.prompt {
+            frame.render_widget(PromptWidget::new(prompt).theme(theme), area);
+        }
+        if let Some(scroll) = self.help_overlay {
+            let controls = self.help_bar().controls;
+            frame.render_widget(HelpOverlayWidget::new(&controls, scroll).theme(theme), area);
+        }
+        if self.confirm_quit {
+            let runs = self.runs_going();
+            let label = self.running_tab()
+                .filter(|_| runs == 1)
+                .map(|index| self.run_tab_summaries()[index].0.clone());
+            frame.render_widget(ConfirmQuitWidget::new(runs, label.as_deref()).theme(theme), area);
+        }
+        if let Some(scroll) = self.log_viewer {
+            let lines = self.logs.lines();
+            frame.render_widget(LogViewerWidget::new(&lines, scroll).path(self.logs.path.as_deref()).theme(theme), area);
+        }
+        
+        // Toasts sit inside the bottom border of the view, above the help bar,
+        // over any popup
+        let toasts_area = Rect { height: area.height.saturating_sub(2), ..area };
+        frame.render_widget(ToastsWidget::new(self.toasts.visible().collect()).theme(theme), toasts_area);
+    }
+}
+
+/// A test's describe names and its own name joined with spaces, the way Jest
+/// matches `--testNamePattern` against them
+fn full_test_name(ancestors: &[String], name: &str) -> String {
+    ancestors.iter().map(String::as_str).chain([name]).collect::<Vec<_>>().join(" ")
+}
+
+/// The results of one file's tests, for the individual results view
+fn file_test_infos(file: &FileResult) -> Vec<TestInfo> {
+    let mut tests = Vec::new();
+    
+    // A file that couldn't run at all has no tests, only a message
+    if file.failed_to_run() {
+        let name = format!("{} failed to run", file.name);
+        tests.push(TestInfo {
+            full_name: name.clone(),
+            name,
+            line: None,
+            file: Some(file.name.clone()),
+            ancestors: Vec::new(),
+            status: TestStatus::Failed,
+            error: Some(file.message.clone()),
+            duration: None,
+        });
     }
+    
+    tests.extend(file.assertion_results
+        .iter()
+        .map(|test| TestInfo {
+            name: test.title.clone(),
+            full_name: test.full_name.clone(),
+            line: test.location.map(|location| location.line),
+            file: Some(file.name.clone()),
+            ancestors: test.ancestor_titles.clone(),
+            status: TestStatus::from_assertion(test.status),
+            error: (!test.failure_messages.is_empty()).then(|| test.failure_messages.join("\n")),
+            duration: test.duration.map(|ms| ms.round() as u64),
+        }));
+    
+    tests
+}
+
+/// Copy text to the clipboard by piping it to `command`, or pbcopy (macOS) if
+/// none is configured
+fn copy_to_clipboard(text: &str, command: Option<&str>) -> io::Result<()> {
+    let mut words = command.unwrap_or("pbcopy").split_whitespace();
+    let program = words.next().ok_or_else(|| io::Error::other("clipboard command is empty"))?;
+    Command::new(program)
+        .args(words)
+        .stdin(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            child.stdin.as_mut().unwrap().write_all(text.as_bytes())?;
+            child.wait().map(|_| ())
+        })
 }
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::{collections::BTreeMap, path::Path};
+use crate::app::history::History;
+
+/// Aggregate statistics for the test files in one directory
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryStats {
+    /// Directory path relative to the search path
+    pub directory: String,
+    /// Number of test files in the directory
+    pub files: usize,
+    /// Number of files that have been run at least once
+    pub files_run: usize,
+    /// Files whose last run passed
+    pub files_passed: usize,
+    /// Files whose last run failed
+    pub files_failed: usize,
+    /// Passing tests across each file's last run
+    pub tests_passed: usize,
+    /// Failing tests across each file's last run
+    pub tests_failed: usize,
+    /// Sum of each file's last run duration in ms
+    pub total_duration_ms: u64,
+}
+
+/// Roll up the last known results of every test file by directory
+pub fn directory_stats(tests: &[String], history: &History) -> Vec<DirectoryStats> {
+    let mut by_directory: BTreeMap<String, DirectoryStats> = BTreeMap::new();
+    
+    for test_file in tests {
+        let directory = Path::new(test_file)
+            .parent()
+            .map(|p| p.display().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        
+        let stats = by_directory.entry(directory.clone()).or_insert_with(|| DirectoryStats {
+            directory,
+            ..DirectoryStats::default()
+        });
+        stats.files += 1;
+        
+        let Some(record) = history.last_run(test_file) else {
+            continue;
+        };
+        
+        stats.files_run += 1;
+        stats.total_duration_ms += record.duration_ms;
+        
+        match record.passed() {
+            Some(true) => stats.files_passed += 1,
+            Some(false) => stats.files_failed += 1,
+            None => {},
+        }
+        if let Some(summary) = record.summary {
+            stats.tests_passed += summary.passed;
+            stats.tests_failed += summary.failed;
+        }
+    }
+    
+    by_directory.into_values().collect()
+}
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+use crate::app::state::{TestInfo, TestStatus};
+
+/// What a row of the results tree shows
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeRowKind {
+    /// A describe block, identified by the names of it and its enclosing blocks
+    Describe {
+        path: Vec<String>,
+        /// Whether the block's contents are hidden
+        collapsed: bool,
+        /// Number of tests inside the block, at any depth
+        tests: usize,
+        /// The tests' statuses combined: failed if any failed, otherwise passed if any passed
+        status: TestStatus,
+    },
+    /// The test at this index of the test list
+    Test(usize),
+}
+
+/// A visible row of the results tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeRow {
+    /// Nesting depth, 0 for top-level blocks and tests
+    pub depth: usize,
+    pub kind: TreeRowKind,
+}
+
+/// Lay tests out as a tree of their describe blocks, in the order they're listed,
+/// leaving out the contents of collapsed blocks
+pub fn tree_rows(tests: &[TestInfo], collapsed: &HashSet<Vec<String>>) -> Vec<TreeRow> {
+    let mut rows = Vec::new();
+    let mut open: &[String] = &[];
+
+    for (index, test) in tests.iter().enumerate() {
+        let ancestors = test.ancestors.as_slice();
+
+        // Start a row for each block the previous test wasn't already in
+        let shared = open.iter().zip(ancestors).take_while(|(a, b)| a == b).count();
+        for depth in shared..ancestors.len() {
+            let path = &ancestors[..=depth];
+            if is_hidden(&path[..depth], collapsed) {
+                break;
+            }
+
+            let inside = tests.iter().filter(|test| test.ancestors.starts_with(path));
+            let (count, status) = inside.fold((0, TestStatus::Todo), |(count, status), test| (count + 1, status.combine(test.status)));
+            rows.push(TreeRow {
+                depth,
+                kind: TreeRowKind::Describe {
+                    path: path.to_vec(),
+                    collapsed: collapsed.contains(path),
+                    tests: count,
+                    status,
+                },
+            });
+        }
+
+        if !is_hidden(ancestors, collapsed) {
+            rows.push(TreeRow { depth: ancestors.len(), kind: TreeRowKind::Test(index) });
+        }
+        open = ancestors;
+    }
+
+    rows
+}
+
+/// Whether whatever is inside the blocks of `path` is hidden by one of them being collapsed
+fn is_hidden(path: &[String], collapsed: &HashSet<Vec<String>>) -> bool {
+    (1..=path.len()).any(|len| collapsed.contains(&path[..len]))
+}
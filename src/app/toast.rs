@@ -0,0 +1,63 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How long a toast stays up once it's shown
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+/// Most toasts shown at once; the rest wait for one to go
+pub const MAX_VISIBLE_TOASTS: usize = 3;
+
+/// What a toast is about, which decides its color and icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    /// Something worked, e.g. a copy or an export
+    Success,
+    /// Something worth knowing
+    Info,
+    /// Something failed, but the app carries on
+    Error,
+}
+
+/// A short message shown in a corner for a few seconds
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub kind: ToastKind,
+    pub message: String,
+    /// When the toast was first shown, starting its countdown
+    shown_at: Option<Instant>,
+}
+
+/// Toasts waiting to be shown or showing, oldest first
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Queue a toast. One repeating a toast that's still queued or showing
+    /// restarts that toast's countdown instead of stacking up.
+    pub fn push(&mut self, kind: ToastKind, message: impl Into<String>) {
+        let message = message.into();
+        if let Some(toast) = self.queue.iter_mut().find(|toast| toast.kind == kind && toast.message == message) {
+            toast.shown_at = toast.shown_at.map(|_| Instant::now());
+            return;
+        }
+        self.queue.push_back(Toast { kind, message, shown_at: None });
+    }
+
+    /// Drop toasts whose time is up, and start the countdown of those that
+    /// have just come into view
+    pub fn tick(&mut self, now: Instant) {
+        self.queue.retain(|toast| toast.shown_at.is_none_or(|shown_at| now.duration_since(shown_at) < TOAST_DURATION));
+        for toast in self.queue.iter_mut().take(MAX_VISIBLE_TOASTS) {
+            toast.shown_at.get_or_insert(now);
+        }
+    }
+
+    /// The toasts being shown, oldest first
+    pub fn visible(&self) -> impl Iterator<Item = &Toast> {
+        self.queue.iter().take(MAX_VISIBLE_TOASTS)
+    }
+}
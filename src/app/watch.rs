@@ -0,0 +1,161 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{gitignore::{Gitignore, GitignoreBuilder}, WalkBuilder};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+use crate::app::session::ExportOptions;
+
+/// How long changes must settle before they trigger a re-run, so that an
+/// editor saving several files (or one file several times) causes a single run
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Directories whose contents never trigger a re-run
+const IGNORED_DIRS: &[&str] = &["node_modules", ".git", "coverage", "dist", "__snapshots__"];
+
+/// File extensions that can affect test results
+const WATCHED_EXTENSIONS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts", "json"];
+
+/// Paths under the watched directory that never trigger a re-run: anything
+/// `.gitignore`d, and the files the app itself writes there
+struct Ignored {
+    root: PathBuf,
+    /// One matcher per `.gitignore` (and `.git/info/exclude`), each rooted at
+    /// its own directory
+    gitignores: Vec<Gitignore>,
+    /// Session exports and saved output, relative to `root`
+    exports: GlobSet,
+}
+
+impl Ignored {
+    fn new(root: &Path, exports: &ExportOptions) -> Self {
+        let mut files: Vec<PathBuf> = WalkBuilder::new(root)
+            .hidden(false)
+            .require_git(false)
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_some_and(|file_type| file_type.is_dir())
+                    || !IGNORED_DIRS.iter().any(|dir| entry.file_name() == *dir)
+            })
+            .build()
+            .flatten()
+            .filter(|entry| entry.file_name() == ".gitignore")
+            .map(|entry| entry.into_path())
+            .collect();
+        files.push(root.join(".git").join("info").join("exclude"));
+
+        let gitignores = files
+            .into_iter()
+            .filter(|file| file.is_file())
+            .filter_map(|file| {
+                let dir = if file.ends_with(".gitignore") { file.parent()? } else { root };
+                let mut builder = GitignoreBuilder::new(dir);
+                builder.add(&file);
+                builder.build().ok()
+            })
+            .collect();
+
+        let mut builder = GlobSetBuilder::new();
+        for template in [&exports.path, &exports.output] {
+            if let Ok(glob) = Glob::new(&template.replace("{timestamp}", "*")) {
+                builder.add(glob);
+            }
+        }
+
+        Self {
+            root: root.to_path_buf(),
+            gitignores,
+            exports: builder.build().unwrap_or_else(|_| GlobSet::empty()),
+        }
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        let exported = path.strip_prefix(&self.root).is_ok_and(|relative| self.exports.is_match(relative));
+        let gitignored = self.gitignores.iter().any(|gitignore| {
+            path.starts_with(gitignore.path()) && gitignore.matched_path_or_any_parents(path, false).is_ignore()
+        });
+
+        exported || gitignored
+    }
+}
+
+/// Whether a change to this path should trigger a re-run
+fn is_relevant(path: &Path, ignored: &Ignored) -> bool {
+    let in_ignored_dir = path.components().any(|component| {
+        IGNORED_DIRS.iter().any(|dir| component.as_os_str() == *dir)
+    });
+    let watched = path
+        .extension()
+        .is_some_and(|ext| WATCHED_EXTENSIONS.iter().any(|watched| ext == *watched));
+
+    !in_ignored_dir && watched && !ignored.contains(path)
+}
+
+/// Watches the project directory for changes to source and test files
+pub struct FileWatcher {
+    /// Kept alive for as long as the watch should last
+    _watcher: RecommendedWatcher,
+    /// Relevant paths reported by the watcher
+    receiver: mpsc::Receiver<PathBuf>,
+    /// Changed paths waiting for changes to settle
+    pending: Vec<PathBuf>,
+    /// When the most recent change arrived
+    last_change: Option<Instant>,
+}
+
+impl std::fmt::Debug for FileWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileWatcher")
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileWatcher {
+    /// Start watching a directory and everything below it, except what's
+    /// `.gitignore`d and the app's own exports
+    pub fn start(root: &Path, exports: &ExportOptions) -> notify::Result<Self> {
+        let (tx, receiver) = mpsc::channel();
+        let ignored = Ignored::new(root, exports);
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                return;
+            }
+            for path in event.paths.into_iter().filter(|path| is_relevant(path, &ignored)) {
+                let _ = tx.send(path);
+            }
+        })?;
+        watcher.watch(root, RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+            pending: Vec::new(),
+            last_change: None,
+        })
+    }
+
+    /// The files changed since the last call, once changes have settled
+    pub fn poll(&mut self) -> Option<Vec<PathBuf>> {
+        for path in self.receiver.try_iter() {
+            if !self.pending.contains(&path) {
+                self.pending.push(path);
+            }
+            self.last_change = Some(Instant::now());
+        }
+
+        let settled = self.last_change.is_some_and(|last_change| last_change.elapsed() >= DEBOUNCE);
+        if !settled {
+            return None;
+        }
+
+        self.last_change = None;
+        Some(std::mem::take(&mut self.pending))
+    }
+}
@@ -0,0 +1,205 @@
+use clap::{Args, Parser, Subcommand};
+use color_eyre::{Result, eyre::eyre};
+use serde_json::json;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::{ExitCode, Stdio},
+    time::Instant,
+};
+use tracing::info;
+use crate::{
+    app::{config::Config, run_summary::RunAllSummary},
+    runner::{backend::Runner, command::JestCommand, json_results, project::Project},
+};
+
+/// A terminal UI to navigate, run, and debug Jest tests
+#[derive(Debug, Parser)]
+#[command(name = "syj", version, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    /// Directory to search for tests when opening the UI
+    pub path: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+/// Commands that work without the UI, for scripts and CI
+#[derive(Debug, Subcommand)]
+pub enum CliCommand {
+    /// Print the test files that would be shown in the UI
+    List(ListArgs),
+    /// Run test files and exit with a status reflecting the result
+    Run(RunArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Directory to search for tests
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Print a JSON document instead of one file per line
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Test files to run, relative to the project directory (every discovered file if none are given)
+    pub files: Vec<String>,
+
+    /// Only run tests whose full name matches this regular expression
+    #[arg(short = 't', long)]
+    pub pattern: Option<String>,
+
+    /// Project directory to run from
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+
+    /// Print a JSON summary to stdout; the runner's own output goes to stderr
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Exit status when some tests failed
+const EXIT_FAILED: u8 = 1;
+/// Exit status when the tests couldn't be run at all
+const EXIT_ERROR: u8 = 2;
+
+/// Run a headless command, reporting errors as a plain message and exit status
+pub fn execute(command: CliCommand) -> ExitCode {
+    let result = match command {
+        CliCommand::List(args) => list(&args),
+        CliCommand::Run(args) => run(&args),
+    };
+
+    result.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        ExitCode::from(EXIT_ERROR)
+    })
+}
+
+/// Check a path given on the command line is a directory
+pub fn require_dir(path: &Path) -> Result<()> {
+    if !path.is_dir() {
+        return Err(eyre!("The specified path does not exist or is not a directory: {}", path.display()));
+    }
+    Ok(())
+}
+
+/// Load the user and project config, warning about files that couldn't be used
+fn load_config(project_dir: &Path) -> Config {
+    let (config, errors) = Config::load(project_dir);
+    for error in errors {
+        eprintln!("Ignoring config {}", error);
+    }
+    config
+}
+
+fn list(args: &ListArgs) -> Result<ExitCode> {
+    require_dir(&args.path)?;
+    let config = load_config(&args.path);
+    let project = Project::discover(&args.path, &config.discovery.test_match, config.discovery.backend)?;
+    if let Some(error) = &project.discovery_error {
+        eprintln!("Could not list tests with Jest, using test patterns instead: {}", error);
+    }
+
+    if args.json {
+        let document = json!({
+            "runner": project.runner.label(),
+            "config": project.config_files.first(),
+            "testMatch": project.test_matches,
+            "testRegex": project.path_filters.test_regex.iter().map(|regex| regex.as_str()).collect::<Vec<_>>(),
+            "ignorePatterns": project.path_filters.ignore_patterns(),
+            "tests": project.tests,
+        });
+        println!("{}", serde_json::to_string_pretty(&document)?);
+    } else {
+        for test in &project.tests {
+            println!("{}", test);
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run(args: &RunArgs) -> Result<ExitCode> {
+    require_dir(&args.root)?;
+    let config = load_config(&args.root);
+    let project = Project::discover(&args.root, &config.discovery.test_match, config.discovery.backend)?;
+    let files = if args.files.is_empty() { &project.tests } else { &args.files };
+    if files.is_empty() {
+        return Err(eyre!("No test files found in {}", args.root.display()));
+    }
+
+    let root = args.root.display().to_string();
+    let results_path = json_results::results_path();
+    let command = run_command(&root, &project, &config, files, args.pattern.as_deref())
+        .json_results(&results_path);
+
+    // Keep stdout for the JSON summary when one was asked for
+    let stdout = if args.json { Stdio::from(io::stderr()) } else { Stdio::inherit() };
+    let started_at = Instant::now();
+    info!(cwd = %command.cwd.display(), command = %command.display(), "Starting run");
+    let status = command.to_command().stdout(stdout).status()
+        .map_err(|e| eyre!("Failed to run `{}`: {}", command.display(), e))?;
+    let duration_ms = started_at.elapsed().as_millis() as u64;
+    info!(%status, duration_ms, "Run finished");
+
+    let Ok(results) = json_results::take_results(&results_path) else {
+        eprintln!("{} exited without writing results ({})", project.runner.label(), status);
+        return Ok(ExitCode::from(EXIT_ERROR));
+    };
+    let summary = RunAllSummary::from_results(&results, &args.root, duration_ms);
+    let success = status.success() && summary.files_failed() == 0 && summary.totals.is_success();
+
+    if args.json {
+        let document = json!({
+            "success": success,
+            "runner": project.runner.label(),
+            "command": command.display(),
+            "durationMs": summary.duration_ms,
+            "totals": summary.totals,
+            "files": summary.files,
+        });
+        println!("{}", serde_json::to_string_pretty(&document)?);
+    } else {
+        let totals = summary.totals;
+        eprintln!(
+            "{}: {} passed, {} failed, {} skipped in {} files",
+            if success { "PASS" } else { "FAIL" },
+            totals.passed,
+            totals.failed,
+            totals.skipped,
+            summary.files.len()
+        );
+    }
+
+    Ok(if success { ExitCode::SUCCESS } else { ExitCode::from(EXIT_FAILED) })
+}
+
+/// The command for a headless run, built the way the UI builds its commands
+fn run_command(root: &str, project: &Project, config: &Config, files: &[String], pattern: Option<&str>) -> JestCommand {
+    let mut command = config.runner.base_command(root, project.runner);
+    for file in files {
+        command = command.arg(file);
+    }
+    command = command.exact_paths();
+    if project.runner == Runner::Jest {
+        command = command.arg("--no-cache");
+    }
+
+    // Only pin the config when there was a choice to make
+    if project.config_files.len() > 1 {
+        command = command.arg("--config").arg(project.config_files[0].to_string_lossy());
+    }
+    for arg in &config.runner.args {
+        command = command.arg(arg);
+    }
+    if let Some(pattern) = pattern {
+        command = command.arg("--testNamePattern").arg(pattern);
+    }
+
+    command
+}
@@ -0,0 +1,197 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Run a git command in the given directory and return its stdout without trailing whitespace
+fn git(dir: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("git {}: {}", args.join(" "), stderr.trim())));
+    }
+    
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+/// The root directory of the repository containing `dir`
+pub fn repo_root(dir: &Path) -> io::Result<PathBuf> {
+    git(dir, &["rev-parse", "--show-toplevel"]).map(PathBuf::from)
+}
+
+/// Best guess at the repository's default branch, e.g. "origin/main"
+pub fn default_branch(dir: &Path) -> Option<String> {
+    // The remote HEAD is the most reliable source when it exists
+    if let Ok(head) = git(dir, &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+        return Some(head);
+    }
+    
+    // Otherwise fall back to common local branch names
+    ["main", "master"]
+        .iter()
+        .find(|branch| git(dir, &["rev-parse", "--verify", "--quiet", branch]).is_ok())
+        .map(|branch| branch.to_string())
+}
+
+/// Full hash of the commit a ref points to
+pub fn resolve_commit(dir: &Path, git_ref: &str) -> io::Result<String> {
+    git(dir, &["rev-parse", "--verify", &format!("{}^{{commit}}", git_ref)])
+}
+
+/// Full hash of the best common ancestor of two refs
+pub fn merge_base(dir: &Path, a: &str, b: &str) -> io::Result<String> {
+    git(dir, &["merge-base", a, b])
+}
+
+/// Absolute paths of files changed between `git_ref` and the working tree,
+/// including untracked files
+pub fn changed_files(dir: &Path, git_ref: &str) -> io::Result<Vec<PathBuf>> {
+    let root = repo_root(dir)?;
+    
+    let diffed = git(dir, &["diff", "--name-only", git_ref])?;
+    let untracked = git(dir, &["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+    
+    let mut files: Vec<PathBuf> = diffed
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.is_empty())
+        .map(|line| root.join(line))
+        .collect();
+    
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Time of the latest commit touching each of the given files (absolute paths
+/// under `dir`), in seconds since the Unix epoch, keyed by absolute path.
+/// Files that were never committed are left out.
+pub fn last_commit_times(dir: &Path, files: &[PathBuf]) -> io::Result<HashMap<PathBuf, u64>> {
+    let root = repo_root(dir)?;
+    let mut wanted: HashSet<&PathBuf> = files.iter().collect();
+    
+    // Walk the log of the directory newest first, where the first time a file
+    // shows up is its latest commit, stopping once every file has shown up
+    let mut child = Command::new("git")
+        .args(["log", "--format=%x00%ct", "--name-only", "--no-merges", "--", "."])
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let stdout = child.stdout.take().ok_or_else(|| io::Error::other("git log has no output"))?;
+    
+    let mut times = HashMap::new();
+    let mut current_time = 0;
+    for line in BufReader::new(stdout).lines() {
+        if wanted.is_empty() {
+            break;
+        }
+        let line = line?;
+        if let Some(timestamp) = line.strip_prefix('\0') {
+            current_time = timestamp.trim().parse().unwrap_or(0);
+        } else if !line.is_empty() {
+            let path = root.join(&line);
+            if wanted.remove(&path) {
+                times.insert(path, current_time);
+            }
+        }
+    }
+    
+    // Stop the walk if it was cut short; being killed isn't a failure
+    let _ = child.kill();
+    let status = child.wait()?;
+    if !status.success() && status.code().is_some() {
+        return Err(io::Error::other(format!("git log: {}", status)));
+    }
+    
+    Ok(times)
+}
+
+/// Absolute paths of files with uncommitted changes or that are untracked
+pub fn dirty_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let root = repo_root(dir)?;
+    let status = git(dir, &["status", "--porcelain", "--untracked-files=all"])?;
+    
+    Ok(status
+        .lines()
+        .filter_map(|line| line.get(3..))
+        .map(|path| {
+            // Renames are reported as "old -> new"
+            let path = path.rsplit(" -> ").next().unwrap_or(path);
+            root.join(path.trim_matches('"'))
+        })
+        .collect())
+}
+
+/// Who last changed a range of lines, from `git blame`
+#[derive(Debug, Clone)]
+pub struct BlameInfo {
+    /// Abbreviated commit hash
+    pub commit: String,
+    /// Author name
+    pub author: String,
+    /// Author time in seconds since the Unix epoch
+    pub time: u64,
+    /// First line of the commit message
+    pub summary: String,
+}
+
+/// Find the most recent change to lines `start..=end` (1-based) of a file
+pub fn blame_lines(file: &Path, start: usize, end: usize) -> io::Result<Option<BlameInfo>> {
+    let dir = file.parent().unwrap_or(Path::new("."));
+    let range = format!("{},{}", start, end);
+    let file_arg = file.to_string_lossy();
+    let output = git(dir, &["blame", "--porcelain", "-L", &range, "--", &file_arg])?;
+    
+    let mut latest: Option<BlameInfo> = None;
+    let mut current: Option<BlameInfo> = None;
+    
+    for line in output.lines() {
+        if let Some(author) = line.strip_prefix("author ") {
+            if let Some(info) = current.as_mut() {
+                info.author = author.to_string();
+            }
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            if let Some(info) = current.as_mut() {
+                info.time = time.parse().unwrap_or(0);
+            }
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            if let Some(info) = current.as_mut() {
+                info.summary = summary.to_string();
+            }
+        } else if line.starts_with('\t') {
+            // The content line ends each entry; keep whichever change is newest
+            if let Some(info) = current.take()
+                && latest.as_ref().is_none_or(|l| info.time > l.time) {
+                latest = Some(info);
+            }
+        } else if let Some(hash) = line.split(' ').next().filter(|h| h.len() == 40) {
+            current = Some(BlameInfo {
+                commit: hash[..8].to_string(),
+                author: String::new(),
+                time: 0,
+                summary: String::new(),
+            });
+        }
+    }
+    
+    Ok(latest)
+}
+
+/// Check out `git_ref` into a new detached worktree at `path`
+pub fn add_worktree(dir: &Path, git_ref: &str, path: &Path) -> io::Result<()> {
+    let path_arg = path.to_string_lossy();
+    git(dir, &["worktree", "add", "--detach", &path_arg, git_ref]).map(|_| ())
+}
+
+/// Remove a worktree, discarding any changes made in it
+pub fn remove_worktree(dir: &Path, path: &Path) -> io::Result<()> {
+    let path_arg = path.to_string_lossy();
+    git(dir, &["worktree", "remove", "--force", &path_arg]).map(|_| ())
+}
@@ -1,2 +0,0 @@
-pub mod config_finder;
-pub mod test_runner;
\ No newline at end of file
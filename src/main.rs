@@ -1,63 +1,89 @@
+use clap::Parser;
 use color_eyre::Result;
-use std::{env, path::PathBuf};
+use std::{path::PathBuf, process::ExitCode};
 
 mod app;
-mod jest;
+mod cli;
+mod git;
+mod runner;
 mod widgets;
 
-use app::App;
-use jest::config_finder;
+use app::{App, config::Config};
+use cli::Cli;
+use runner::{project::Project, workspace::Monorepo};
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     color_eyre::install()?;
+    let logs = app::logging::init();
+
+    let cli = Cli::parse();
+    if let Some(command) = cli.command {
+        return Ok(cli::execute(command));
+    }
 
     // Get path to directory containing tests from CLI args
-    let path = env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| {
-        eprintln!("Usage: cargo run -- <path-to-tests-directory>");
-        std::process::exit(1);
-    });
-
-    if !path.exists() || !path.is_dir() {
-        eprintln!("The specified path does not exist or is not a directory: {}", path.display());
-        std::process::exit(1);
+    let path = cli.path.unwrap_or_else(|| PathBuf::from("."));
+    if let Err(err) = cli::require_dir(&path) {
+        eprintln!("{}", err);
+        return Ok(ExitCode::FAILURE);
     }
 
-    // Try to find and read Jest config file
-    let test_matches = match config_finder::find_jest_config_file(&path)? {
+    // Load user and project settings; problems with them are shown in the UI
+    let (config, config_errors) = Config::load(&path);
+
+    // Work out whether this is a Jest or Vitest project; its tests are found
+    // once the UI is up
+    let project = Project::detect(&path, &config.discovery.test_match)?;
+    let label = project.runner.label();
+    for config_path in &project.config_files {
+        println!("Found {} configuration at {}", label, config_path.display());
+    }
+    match project.config_files.first() {
         Some(config_path) => {
-            println!("Using Jest configuration from {}", config_path.display());
-            config_finder::extract_test_matches(&config_path)?
+            println!("Using {} configuration from {}", label, config_path.display());
+            println!("Found test patterns: {:?}", project.test_matches);
         },
-        None => {
-            println!("Using default test patterns");
-            // Fallback to default patterns if no config found
-            vec![
-                "**/*.test.js".to_string(),
-                "**/*.test.ts".to_string(),
-                "**/*.test.tsx".to_string(),
-                "**/*.test.jsx".to_string(),
-                "**/*.spec.js".to_string(),
-                "**/*.spec.ts".to_string(),
-                "**/*.spec.tsx".to_string(),
-                "**/*.spec.jsx".to_string(),
-                "**/__tests__/**/*.js".to_string(),
-                "**/__tests__/**/*.ts".to_string(),
-            ]
-        }
-    };
-    
-    let tests = config_finder::find_matching_tests(&test_matches, &path)?;
+        None => println!("No {} configuration file found, using default test patterns", label),
+    }
+    let monorepo = Monorepo::detect(&path);
+    if let Some(monorepo) = &monorepo {
+        println!("Found {} monorepo with {} packages", monorepo.tool, monorepo.packages.len());
+    }
     let path_str = path.display().to_string();
+    tracing::info!(
+        path = %path_str,
+        runner = label,
+        configs = ?project.config_files,
+        monorepo = monorepo.as_ref().map(|monorepo| monorepo.tool),
+        "Starting"
+    );
 
     // Initialize the terminal
     let terminal = ratatui::init();
-    
+
     // Create and run the application
-    let result = App::new(path_str, test_matches, tests).run(terminal);
-    
+    let mut app = App::new(path_str, project.test_matches, Vec::new())
+        .with_runner(project.runner)
+        .with_path_filters(project.path_filters)
+        .with_projects(project.projects)
+        .with_configs(project.config_files)
+        .with_monorepo(monorepo)
+        .with_logs(logs)
+        .with_user_config(config, config_errors)
+        .with_discovery();
+    let result = app.run(terminal);
+
     // Restore terminal state
     ratatui::restore();
-    
+
+    // Runs left going with `d` finish without the UI
+    if result.is_ok() && app.detached {
+        app.finish_detached_runs();
+    }
+
+    // Don't leave runs going in the background after quitting
+    runner::processes::stop_all();
+
     // Return the result
-    result
-}
\ No newline at end of file
+    result.map(|()| ExitCode::SUCCESS)
+}
@@ -0,0 +1,13 @@
+use regex::Regex;
+use std::borrow::Cow;
+
+/// Matches ANSI control sequences (colors, cursor movement) and OSC sequences (e.g. hyperlinks)
+pub const ESCAPE_PATTERN: &str = r"\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)";
+
+/// Remove ANSI escape sequences, such as colors, from runner output
+pub fn strip(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+    Regex::new(ESCAPE_PATTERN).unwrap().replace_all(text, "")
+}
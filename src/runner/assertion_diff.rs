@@ -0,0 +1,162 @@
+use regex::Regex;
+use crate::runner::pretty_value;
+
+/// The values a failed assertion compared, line by line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedReceived {
+    pub expected: Vec<String>,
+    pub received: Vec<String>,
+}
+
+impl ExpectedReceived {
+    /// Find the compared values in a failure message: either the annotated diff
+    /// printed for objects and long strings (`- Expected` / `+ Received`, then
+    /// `-`, `+`, and context lines), or the `Expected: ...` / `Received: ...` lines
+    /// printed for everything else, with object dumps spread over several lines
+    pub fn from_failure(message: &str) -> Option<Self> {
+        Self::from_annotated_diff(message).or_else(|| Self::from_value_lines(message))
+    }
+    
+    fn from_annotated_diff(message: &str) -> Option<Self> {
+        let lines: Vec<&str> = message.lines().collect();
+        let header = lines.windows(2).position(|pair| {
+            pair[0].trim_start().starts_with("- Expected") && pair[1].trim_start().starts_with("+ Received")
+        })?;
+        
+        let mut values = Self { expected: Vec::new(), received: Vec::new() };
+        let body = lines[header + 2..].iter().skip_while(|line| line.trim().is_empty());
+        for line in body {
+            // The diff ends at a blank line or where the stack trace starts
+            if line.trim().is_empty() || line.trim_start().starts_with("at ") {
+                break;
+            }
+            let content = line.get(2..).unwrap_or("").to_string();
+            match line.chars().next() {
+                Some('-') => values.expected.push(content),
+                Some('+') => values.received.push(content),
+                _ => {
+                    values.expected.push(content.clone());
+                    values.received.push(content);
+                },
+            }
+        }
+        
+        (!values.expected.is_empty() || !values.received.is_empty()).then_some(values)
+    }
+    
+    fn from_value_lines(message: &str) -> Option<Self> {
+        // e.g. "Expected: 3", "Expected substring: ..." or "Received number of calls: 0"
+        let value = |label: &str| {
+            let regex = Regex::new(&format!(r"(?m)^\s*{}(?: [a-z ]+)?:\s?(.*)$", label)).unwrap();
+            regex.captures(message).map(|cap| cap[1].trim_end().to_string())
+        };
+        let lines = |value: String| pretty_value::pretty_lines(&value).unwrap_or_else(|| vec![value]);
+        
+        Some(Self {
+            expected: lines(value("Expected")?),
+            received: lines(value("Received")?),
+        })
+    }
+}
+
+/// One step of turning one sequence into another
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change<T> {
+    Same(T),
+    Removed(T),
+    Added(T),
+}
+
+/// The shortest way of turning `old` into `new`, from their longest common
+/// subsequence. Inputs too long to compare are treated as entirely different.
+pub fn diff<T: PartialEq + Copy>(old: &[T], new: &[T]) -> Vec<Change<T>> {
+    const MAX_CELLS: usize = 1_000_000;
+    if old.len().saturating_mul(new.len()) > MAX_CELLS {
+        return old.iter().map(|&item| Change::Removed(item))
+            .chain(new.iter().map(|&item| Change::Added(item)))
+            .collect();
+    }
+    
+    // common[i * width + j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let width = new.len() + 1;
+    let mut common = vec![0usize; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i * width + j] = if old[i] == new[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+    
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            changes.push(Change::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if common[(i + 1) * width + j] >= common[i * width + j + 1] {
+            changes.push(Change::Removed(old[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Added(new[j]));
+            j += 1;
+        }
+    }
+    changes.extend(old[i..].iter().map(|&item| Change::Removed(item)));
+    changes.extend(new[j..].iter().map(|&item| Change::Added(item)));
+    changes
+}
+
+/// A row of a line diff, with changed lines paired up with what replaced them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffRow<'a> {
+    Same(&'a str),
+    Changed {
+        expected: Option<&'a str>,
+        received: Option<&'a str>,
+    },
+}
+
+/// Diff the expected and received values by line, pairing each run of removed
+/// lines with the run of added lines after it
+pub fn diff_rows(values: &ExpectedReceived) -> Vec<DiffRow<'_>> {
+    let expected: Vec<&str> = values.expected.iter().map(String::as_str).collect();
+    let received: Vec<&str> = values.received.iter().map(String::as_str).collect();
+    
+    let mut rows = Vec::new();
+    let mut removed: Vec<&str> = Vec::new();
+    let mut added: Vec<&str> = Vec::new();
+    for change in diff(&expected, &received) {
+        match change {
+            Change::Same(line) => {
+                pair_changed(&mut removed, &mut added, &mut rows);
+                rows.push(DiffRow::Same(line));
+            },
+            Change::Removed(line) => {
+                // A removal after additions starts a new run
+                if !added.is_empty() {
+                    pair_changed(&mut removed, &mut added, &mut rows);
+                }
+                removed.push(line);
+            },
+            Change::Added(line) => added.push(line),
+        }
+    }
+    pair_changed(&mut removed, &mut added, &mut rows);
+    rows
+}
+
+/// Move runs of removed and added lines into rows, side by side
+fn pair_changed<'a>(removed: &mut Vec<&'a str>, added: &mut Vec<&'a str>, rows: &mut Vec<DiffRow<'a>>) {
+    for index in 0..removed.len().max(added.len()) {
+        rows.push(DiffRow::Changed {
+            expected: removed.get(index).copied(),
+            received: added.get(index).copied(),
+        });
+    }
+    removed.clear();
+    added.clear();
+}
@@ -0,0 +1,79 @@
+use std::{env, path::Path};
+use crate::runner::config_finder;
+
+/// The test runner a project uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Runner {
+    #[default]
+    Jest,
+    Vitest,
+}
+
+impl Runner {
+    /// Detect the runner for a project directory.
+    ///
+    /// Projects with a Jest config use Jest; otherwise a `vitest.config.*`
+    /// next to the tests (or in a parent) means Vitest. Jest is the default.
+    pub fn detect(project_dir: &Path) -> Self {
+        let has_config = |files: std::io::Result<Vec<_>>| files.is_ok_and(|files| !files.is_empty());
+
+        if has_config(config_finder::find_jest_config_files(project_dir)) {
+            Runner::Jest
+        } else if has_config(config_finder::find_vitest_config_files(project_dir)) {
+            Runner::Vitest
+        } else {
+            Runner::Jest
+        }
+    }
+
+    /// Name of the runner as shown to the user
+    pub fn label(self) -> &'static str {
+        match self {
+            Runner::Jest => "Jest",
+            Runner::Vitest => "Vitest",
+        }
+    }
+
+    /// The runner's executable and the arguments that start a single run,
+    /// e.g. `vitest run` rather than Vitest's default watch mode
+    pub fn program_args(self) -> &'static [&'static str] {
+        match self {
+            Runner::Jest => &["jest"],
+            Runner::Vitest => &["vitest", "run"],
+        }
+    }
+
+    /// Whether the runner is installed for a project: in the `node_modules` of
+    /// its directory or a parent, through Yarn Plug'n'Play (which can't be
+    /// checked without Node, so it's trusted), or globally on the `PATH`
+    pub fn is_installed(self, project_dir: &Path) -> bool {
+        let program = self.program_args()[0];
+        let start = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+        let local = start.ancestors().any(|dir| {
+            dir.join("node_modules").join(program).join("package.json").is_file()
+                || dir.join("node_modules").join(".bin").join(program).exists()
+                || dir.join(".pnp.cjs").is_file()
+                || dir.join(".pnp.js").is_file()
+        });
+        
+        local || env::var_os("PATH").is_some_and(|path| {
+            env::split_paths(&path).any(|dir| dir.join(program).is_file())
+        })
+    }
+
+    /// Find the runner's config files for the given directory
+    pub fn find_config_files(self, dir: &Path) -> std::io::Result<Vec<std::path::PathBuf>> {
+        match self {
+            Runner::Jest => config_finder::find_jest_config_files(dir),
+            Runner::Vitest => config_finder::find_vitest_config_files(dir),
+        }
+    }
+
+    /// The test patterns the runner uses when none are configured
+    pub fn default_test_matches(self) -> Vec<String> {
+        match self {
+            Runner::Jest => config_finder::default_test_matches(),
+            Runner::Vitest => config_finder::default_vitest_includes(),
+        }
+    }
+}
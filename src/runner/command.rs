@@ -0,0 +1,239 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::runner::{backend::Runner, package_manager::PackageManager};
+
+/// Quote an argument for display in a POSIX shell command, if it needs it
+pub fn shell_quote(arg: &str) -> String {
+    let is_safe = !arg.is_empty()
+        && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:@%+,".contains(c));
+
+    if is_safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Split a line into arguments the way a POSIX shell would, honoring single
+/// and double quotes and backslash escapes
+pub fn shell_split(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = line.chars();
+    
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err("unterminated ' quote".to_string()),
+                    }
+                }
+            },
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        // Inside double quotes, backslashes only escape these
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => arg.push(c),
+                            Some(c) => {
+                                arg.push('\\');
+                                arg.push(c);
+                            },
+                            None => return Err("unterminated \" quote".to_string()),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err("unterminated \" quote".to_string()),
+                    }
+                }
+            },
+            '\\' => {
+                let escaped = chars.next().ok_or_else(|| "trailing backslash".to_string())?;
+                current.get_or_insert_with(String::new).push(escaped);
+            },
+            c if c.is_whitespace() => args.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    
+    args.extend(current);
+    Ok(args)
+}
+
+/// A Jest invocation: the program, its arguments, and where to run it
+#[derive(Debug, Clone)]
+pub struct JestCommand {
+    /// Executable to spawn
+    pub program: String,
+    /// Arguments passed to the program
+    pub args: Vec<String>,
+    /// Directory to run the command from
+    pub cwd: PathBuf,
+    /// Extra environment variables set for the process
+    pub env: Vec<(String, String)>,
+    /// Which runner the command invokes, deciding how options are spelled
+    pub runner: Runner,
+}
+
+impl JestCommand {
+    /// Create a new test runner command run from the given project directory, using
+    /// the project's package manager (e.g. `npx jest`, `yarn jest`, `pnpm exec vitest run`)
+    pub fn new(project_dir: &str, runner: Runner) -> Self {
+        Self::with_package_manager(project_dir, PackageManager::detect(Path::new(project_dir)), runner)
+    }
+    
+    /// Create a new test runner command run through the given package manager
+    pub fn with_package_manager(project_dir: &str, package_manager: PackageManager, runner: Runner) -> Self {
+        let (program, exec_args) = package_manager.exec();
+        let args = exec_args
+            .iter()
+            .chain(runner.program_args())
+            .map(|arg| arg.to_string())
+            .collect();
+        
+        Self {
+            program: program.to_string(),
+            args,
+            cwd: PathBuf::from(project_dir),
+            env: Vec::new(),
+            runner,
+        }
+    }
+
+    /// Create a command that runs the package.json `test` script, which is
+    /// expected to start the runner. Watch mode, which scripts like
+    /// `react-scripts test` turn on outside CI, is turned off.
+    pub fn test_script(project_dir: &str, runner: Runner) -> Self {
+        let (program, script_args) = PackageManager::detect(Path::new(project_dir)).run_test_script();
+        let no_watch = match runner {
+            Runner::Jest => "--watchAll=false",
+            Runner::Vitest => "--run",
+        };
+        let args = script_args
+            .iter()
+            .chain([&no_watch])
+            .map(|arg| arg.to_string())
+            .collect();
+        
+        Self {
+            program: program.to_string(),
+            args,
+            cwd: PathBuf::from(project_dir),
+            env: Vec::new(),
+            runner,
+        }
+    }
+    
+    /// Create a command that runs a shell script from the given project directory
+    pub fn shell(project_dir: &str, script: &str) -> Self {
+        Self {
+            program: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            cwd: PathBuf::from(project_dir),
+            env: Vec::new(),
+            runner: Runner::default(),
+        }
+    }
+    
+    /// Append an argument
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+    
+    /// Set an environment variable for the process
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+    
+    /// Have the runner color its output even though it isn't writing to a terminal
+    pub fn colors(self) -> Self {
+        match self.runner {
+            Runner::Jest => self.arg("--colors"),
+            // Vitest's color library only listens to the environment
+            Runner::Vitest => self.env("FORCE_COLOR", "1"),
+        }
+    }
+    
+    /// Have the runner also write structured JSON results for the run to a file
+    pub fn json_results(self, path: &Path) -> Self {
+        match self.runner {
+            Runner::Jest => self
+                .arg("--json")
+                .arg("--testLocationInResults")
+                .arg(format!("--outputFile={}", path.display())),
+            // Keep the default reporter so output still streams in
+            Runner::Vitest => self
+                .arg("--reporter=default")
+                .arg("--reporter=json")
+                .arg(format!("--outputFile.json={}", path.display()))
+                .arg("--includeTaskLocation"),
+        }
+    }
+    
+    /// Collect coverage and write a `coverage-summary.json` and per-line `coverage-final.json` for it
+    pub fn coverage(self) -> Self {
+        match self.runner {
+            Runner::Jest => self
+                .arg("--coverage")
+                .arg("--coverageReporters=json-summary")
+                .arg("--coverageReporters=json"),
+            Runner::Vitest => self
+                .arg("--coverage.enabled")
+                .arg("--coverage.reporter=json-summary")
+                .arg("--coverage.reporter=json"),
+        }
+    }
+    
+    /// Rewrite failing snapshots and remove obsolete ones
+    pub fn update_snapshots(self) -> Self {
+        let flag = match self.runner {
+            Runner::Jest => "--updateSnapshot",
+            Runner::Vitest => "--update",
+        };
+        if self.args.iter().any(|arg| arg == flag) {
+            return self;
+        }
+        self.arg(flag)
+    }
+    
+    /// Treat file arguments as exact paths rather than patterns, where the
+    /// runner supports it (Vitest always filters by path)
+    pub fn exact_paths(self) -> Self {
+        match self.runner {
+            Runner::Jest => self.arg("--runTestsByPath"),
+            Runner::Vitest => self,
+        }
+    }
+
+    /// Build a [`Command`] ready to be spawned
+    pub fn to_command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args).current_dir(&self.cwd);
+        command.envs(self.env.iter().map(|(key, value)| (key, value)));
+        command
+    }
+
+    /// The command line as it would be typed in a shell
+    pub fn display(&self) -> String {
+        let env = self.env
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, shell_quote(value)));
+        let command = std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .map(shell_quote);
+        
+        env.chain(command).collect::<Vec<_>>().join(" ")
+    }
+
+    /// A self-contained shell command that changes into the project directory first
+    pub fn shell_command(&self) -> String {
+        format!("cd {} && {}", shell_quote(&self.cwd.to_string_lossy()), self.display())
+    }
+}
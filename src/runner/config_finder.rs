@@ -0,0 +1,914 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
+use std::io;
+use std::fs;
+use globset::{GlobBuilder, GlobMatcher};
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde_json::Value;
+
+/// Whether a file name looks like a dedicated Jest config file,
+/// e.g. `jest.config.js`, `jest.e2e.config.ts`, or `.jestrc.json`
+fn is_jest_config_filename(filename: &str) -> bool {
+    let config_regex = Regex::new(r"^(jest(\.[\w-]+)?\.config\.(js|ts|mjs|cjs|json)|\.jestrc(\.js|\.json)?)$").unwrap();
+    config_regex.is_match(filename)
+}
+
+/// Whether a file name looks like a Vitest config file,
+/// e.g. `vitest.config.ts` or `vitest.unit.config.mts`
+fn is_vitest_config_filename(filename: &str) -> bool {
+    let config_regex = Regex::new(r"^vitest(\.[\w-]+)?\.config\.(js|ts|mjs|cjs|mts|cts)$").unwrap();
+    config_regex.is_match(filename)
+}
+
+/// Whether a config file belongs to Vitest rather than Jest
+pub fn is_vitest_config(config_path: &Path) -> bool {
+    config_path
+        .file_name()
+        .is_some_and(|name| is_vitest_config_filename(&name.to_string_lossy()))
+}
+
+/// Lists the dedicated Jest config files in a directory, sorted so that
+/// `jest.config.*` comes before variants like `jest.e2e.config.*`
+fn config_files_in(dir: &Path) -> Vec<PathBuf> {
+    named_files_in(dir, is_jest_config_filename, "jest.config.")
+}
+
+/// Lists the files in a directory whose names pass the check, sorted so that
+/// those starting with `preferred` come first
+fn named_files_in(dir: &Path, is_match: fn(&str) -> bool, preferred: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    
+    let mut configs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.file_name()
+                .map(|name| is_match(&name.to_string_lossy()))
+                .unwrap_or(false)
+        })
+        .collect();
+    
+    configs.sort_by_key(|path| {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        (!name.starts_with(preferred), name)
+    });
+    configs
+}
+
+/// Finds all Jest configuration files for the given directory.
+///
+/// Looks in the directory itself (including a `"jest"` object in package.json),
+/// then in up to 3 parent directories, returning the configs from the first
+/// directory that has any. The first entry is the default config, so dedicated
+/// config files come before package.json.
+pub fn find_jest_config_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    // Look for dedicated Jest config files
+    let mut configs = config_files_in(dir);
+    
+    // Also check package.json (common for Jest config). Only a top-level
+    // `"jest"` object counts, not e.g. a `"jest"` devDependency.
+    let package_json = dir.join("package.json");
+    let has_jest_key = fs::read_to_string(&package_json)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .is_some_and(|package| package.get("jest").is_some_and(Value::is_object));
+    if has_jest_key {
+        configs.push(package_json);
+    }
+    
+    if !configs.is_empty() {
+        return Ok(configs);
+    }
+    
+    // Look for Jest config in parent directory (up to 3 levels)
+    for parent in dir.ancestors().skip(1).take(3) {
+        let configs = config_files_in(parent);
+        if !configs.is_empty() {
+            return Ok(configs);
+        }
+    }
+    
+    Ok(configs)
+}
+
+/// Finds the Vitest config files for the given directory, looking in the
+/// directory itself and then up to 3 parent directories like Jest configs
+pub fn find_vitest_config_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let configs = dir
+        .ancestors()
+        .take(4)
+        .map(|dir| named_files_in(dir, is_vitest_config_filename, "vitest.config."))
+        .find(|configs| !configs.is_empty())
+        .unwrap_or_default();
+    
+    Ok(configs)
+}
+
+/// The test patterns Vitest uses when no `include` is configured
+/// (`**/*.{test,spec}.?(c|m)[jt]s?(x)`, expanded for the glob matcher)
+pub fn default_vitest_includes() -> Vec<String> {
+    let mut patterns = Vec::new();
+    for kind in ["test", "spec"] {
+        for ext in ["js", "ts", "jsx", "tsx", "mjs", "mts", "cjs", "cts"] {
+            patterns.push(format!("**/*.{}.{}", kind, ext));
+        }
+    }
+    patterns
+}
+
+/// The test patterns Jest uses when no testMatch is configured
+pub fn default_test_matches() -> Vec<String> {
+    vec![
+        "**/*.test.js".to_string(),
+        "**/*.test.ts".to_string(),
+        "**/*.test.tsx".to_string(),
+        "**/*.test.jsx".to_string(),
+        "**/*.spec.js".to_string(),
+        "**/*.spec.ts".to_string(),
+        "**/*.spec.tsx".to_string(),
+        "**/*.spec.jsx".to_string(),
+        "**/__tests__/**/*.js".to_string(),
+        "**/__tests__/**/*.ts".to_string(),
+    ]
+}
+
+/// Loads a config module with Node, calling it if it exports a function and
+/// awaiting it if it's a promise, and prints the result as JSON.
+/// The config path is passed as the script's first argument.
+const EVALUATE_SCRIPT: &str = r#"
+const configPath = process.argv[1];
+(async () => {
+    let config;
+    try {
+        config = require(configPath);
+    } catch (error) {
+        if (error.code !== 'ERR_REQUIRE_ESM') throw error;
+        config = await import(require('url').pathToFileURL(configPath));
+    }
+    if (config && (config.__esModule || config[Symbol.toStringTag] === 'Module')) config = config.default;
+    if (typeof config === 'function') config = config();
+    config = await config;
+    if (configPath.endsWith('package.json')) config = config.jest;
+    process.stdout.write(JSON.stringify(config ?? {}));
+})().catch(() => process.exit(1));
+"#;
+
+/// A config evaluated with Node, and the config file's modification time then
+type EvaluatedConfig = (SystemTime, Option<Value>);
+
+/// Modules that let Node load TypeScript configs, tried in order
+const TYPESCRIPT_LOADERS: [&str; 2] = ["ts-node/register", "esbuild-register"];
+
+/// Evaluates a Jest configuration file with Node and returns the config object.
+///
+/// This sees the config the way Jest does, including values that are computed,
+/// spread from shared presets, or returned from an (async) function. TypeScript
+/// configs are loaded through ts-node, or esbuild-register when ts-node isn't
+/// installed. Returns `None` when Node isn't available or the config fails to
+/// load, so callers can fall back to reading the file.
+///
+/// Results are remembered until the config file is modified, since several
+/// options are read from the same config.
+pub fn evaluate_config(config_path: &Path) -> Option<Value> {
+    static EVALUATED: OnceLock<Mutex<HashMap<PathBuf, EvaluatedConfig>>> = OnceLock::new();
+    
+    let config_path = config_path.canonicalize().ok()?;
+    let modified = fs::metadata(&config_path).and_then(|metadata| metadata.modified()).ok()?;
+    let cache = EVALUATED.get_or_init(Default::default);
+    if let Some((evaluated_at, config)) = cache.lock().ok()?.get(&config_path)
+        && *evaluated_at == modified {
+        return config.clone();
+    }
+    
+    let config = evaluate_with_node(&config_path);
+    cache.lock().ok()?.insert(config_path, (modified, config.clone()));
+    config
+}
+
+/// Runs the config through Node, trying each TypeScript loader in turn for
+/// TypeScript configs
+fn evaluate_with_node(config_path: &Path) -> Option<Value> {
+    let config_dir = config_path.parent()?;
+    let is_typescript = config_path
+        .extension()
+        .is_some_and(|extension| matches!(extension.to_str(), Some("ts" | "mts" | "cts")));
+    
+    let loaders: Vec<Option<&str>> = if is_typescript {
+        TYPESCRIPT_LOADERS.iter().copied().map(Some).collect()
+    } else {
+        vec![None]
+    };
+    
+    loaders.into_iter().find_map(|loader| {
+        let mut command = Command::new("node");
+        if let Some(loader) = loader {
+            command.args(["-r", loader]);
+        }
+        let output = command
+            .args(["-e", EVALUATE_SCRIPT])
+            .arg(config_path)
+            .current_dir(config_dir)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        
+        if !output.status.success() {
+            return None;
+        }
+        serde_json::from_slice::<Value>(&output.stdout)
+            .ok()
+            .filter(Value::is_object)
+    })
+}
+
+/// Reads a config option that's either a string or a list of strings
+fn string_list(value: Option<&Value>) -> Option<Vec<String>> {
+    match value? {
+        Value::String(single) => Some(vec![single.clone()]),
+        Value::Array(items) => Some(items.iter().filter_map(|item| item.as_str().map(str::to_string)).collect()),
+        _ => None,
+    }
+}
+
+/// Resolves a `rootDir` value against the config file's directory, using
+/// Jest's default (the config file's directory) when it's not set
+fn resolve_root_dir(config_path: &Path, root_dir: Option<&str>) -> PathBuf {
+    let config_dir = config_path.parent().unwrap_or(Path::new("."));
+    let root_dir = root_dir
+        .map(|root_dir| config_dir.join(root_dir))
+        .unwrap_or_else(|| config_dir.to_path_buf());
+    
+    root_dir.canonicalize().unwrap_or(root_dir)
+}
+
+/// Extracts the `rootDir` option from a Jest configuration file.
+///
+/// Relative values are resolved against the config file's directory, and
+/// Jest's default (the config file's directory) is used when it's not set.
+pub fn extract_root_dir(config_path: &Path) -> PathBuf {
+    if !is_vitest_config(config_path)
+        && let Some(config) = evaluate_config(config_path) {
+        return resolve_root_dir(config_path, config.get("rootDir").and_then(Value::as_str));
+    }
+    
+    let root_dir_regex = Regex::new(r#"["']?rootDir["']?\s*:\s*["']([^"']+)["']"#).unwrap();
+    let root_dir = fs::read_to_string(config_path)
+        .ok()
+        .and_then(|content| root_dir_regex.captures(&content).map(|c| c[1].to_string()));
+    
+    resolve_root_dir(config_path, root_dir.as_deref())
+}
+
+/// Replaces the `<rootDir>` token in patterns with the resolved root directory
+pub fn substitute_root_dir(patterns: Vec<String>, root_dir: &Path) -> Vec<String> {
+    let root = slash_path(root_dir);
+    patterns
+        .into_iter()
+        .map(|pattern| pattern.replace("<rootDir>", &root))
+        .collect()
+}
+
+/// Extracts testMatch patterns from a Jest configuration file.
+///
+/// The config is evaluated with Node when possible, and read as text
+/// otherwise. Any `<rootDir>` tokens are replaced with the config's resolved
+/// root directory.
+pub fn extract_test_matches(config_path: &Path) -> io::Result<Vec<String>> {
+    if !is_vitest_config(config_path)
+        && let Some(config) = evaluate_config(config_path) {
+        let patterns = string_list(config.get("testMatch")).unwrap_or_else(default_test_matches);
+        let root_dir = resolve_root_dir(config_path, config.get("rootDir").and_then(Value::as_str));
+        return Ok(substitute_root_dir(patterns, &root_dir));
+    }
+    
+    let patterns = extract_raw_test_matches(config_path)?;
+    Ok(substitute_root_dir(patterns, &extract_root_dir(config_path)))
+}
+
+/// Extracts testMatch patterns from a Jest configuration file (or `include`
+/// patterns from a Vitest one) as written, for when it can't be evaluated.
+fn extract_raw_test_matches(config_path: &Path) -> io::Result<Vec<String>> {
+    if is_vitest_config(config_path) {
+        return extract_vitest_includes(config_path);
+    }
+    
+    let content = fs::read_to_string(config_path)?;
+    
+    // Extract testMatch array using regex
+    // This is a simple extraction - a real implementation might use a JS parser
+    let test_match_regex = Regex::new(r#"testMatch\s*:?\s*\[\s*(["'][^"']+["'](?:\s*,\s*["'][^"']+["'])*)\s*\]"#)
+        .map_err(io::Error::other)?;
+    
+    if let Some(captures) = test_match_regex.captures(&content)
+        && let Some(patterns_match) = captures.get(1) {
+        let patterns_str = patterns_match.as_str();
+        
+        // Split by comma and extract the patterns
+        let patterns: Vec<String> = patterns_str
+            .split(',')
+            .map(|s| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+            .collect();
+        
+        return Ok(patterns);
+    }
+    
+    // Alternative pattern - check for testMatch: [values]
+    // This is for different formatting styles
+    let alt_regex = Regex::new(r#"["']testMatch["']\s*:?\s*\[\s*(["'][^"']+["'](?:\s*,\s*["'][^"']+["'])*)\s*\]"#)
+        .map_err(io::Error::other)?;
+    
+    if let Some(captures) = alt_regex.captures(&content)
+        && let Some(patterns_match) = captures.get(1) {
+        let patterns_str = patterns_match.as_str();
+        
+        // Split by comma and extract the patterns
+        let patterns: Vec<String> = patterns_str
+            .split(',')
+            .map(|s| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+            .collect();
+        
+        return Ok(patterns);
+    }
+    
+    // If all else fails, return default patterns
+    Ok(default_test_matches())
+}
+
+/// Extracts the `test.include` patterns from a Vitest configuration file
+fn extract_vitest_includes(config_path: &Path) -> io::Result<Vec<String>> {
+    let content = fs::read_to_string(config_path)?;
+    
+    let include_regex = Regex::new(r#"include\s*:\s*\[\s*(["'][^"']+["'](?:\s*,\s*["'][^"']+["'])*)\s*,?\s*\]"#)
+        .map_err(io::Error::other)?;
+    
+    let patterns = include_regex
+        .captures(&content)
+        .and_then(|captures| captures.get(1))
+        .map(|patterns_match| {
+            patterns_match
+                .as_str()
+                .split(',')
+                .map(|s| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+                .collect()
+        })
+        .unwrap_or_else(default_vitest_includes);
+    
+    Ok(patterns)
+}
+
+/// The config options that decide which files count as tests besides testMatch
+#[derive(Debug, Clone)]
+pub struct PathFilters {
+    /// `testRegex` patterns; when any are set, Jest uses them instead of testMatch
+    pub test_regex: Vec<Regex>,
+    /// `testPathIgnorePatterns`, with `<rootDir>` replaced
+    pub test_path_ignore: Vec<Regex>,
+    /// `modulePathIgnorePatterns`, with `<rootDir>` replaced
+    pub module_path_ignore: Vec<Regex>,
+    /// Directories searched for tests (`roots`, which defaults to `rootDir`);
+    /// empty without a config, meaning the project directory
+    pub roots: Vec<PathBuf>,
+}
+
+impl Default for PathFilters {
+    /// Jest's defaults: no testRegex, and only `node_modules` ignored
+    fn default() -> Self {
+        Self {
+            test_regex: Vec::new(),
+            test_path_ignore: vec![Regex::new("/node_modules/").unwrap()],
+            module_path_ignore: Vec::new(),
+            roots: Vec::new(),
+        }
+    }
+}
+
+impl PathFilters {
+    /// Whether an absolute test file path is excluded by an ignore pattern
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let path_str = slash_path(path);
+        in_node_modules(path)
+            || self.test_path_ignore.iter().chain(&self.module_path_ignore).any(|regex| regex.is_match(&path_str))
+    }
+    
+    /// The directories to search for tests in a project
+    pub fn search_roots(&self, project_root: &Path) -> Vec<PathBuf> {
+        if self.roots.is_empty() {
+            vec![project_root.to_path_buf()]
+        } else {
+            self.roots.clone()
+        }
+    }
+
+    /// Every ignore pattern as configured, for display
+    pub fn ignore_patterns(&self) -> Vec<String> {
+        let mut patterns = vec!["/node_modules/".to_string()];
+        for regex in self.test_path_ignore.iter().chain(&self.module_path_ignore) {
+            if !patterns.iter().any(|pattern| pattern == regex.as_str()) {
+                patterns.push(regex.as_str().to_string());
+            }
+        }
+        patterns
+    }
+}
+
+/// Extracts `testRegex`, `testPathIgnorePatterns`, `modulePathIgnorePatterns`,
+/// and `roots` from a Jest configuration file.
+///
+/// Patterns the regex engine can't compile (e.g. ones using lookaround) are
+/// skipped, and options that aren't set keep Jest's defaults.
+pub fn extract_path_filters(config_path: &Path) -> PathFilters {
+    if is_vitest_config(config_path) {
+        return PathFilters::default();
+    }
+    
+    let config = evaluate_config(config_path);
+    let content = match config {
+        Some(_) => String::new(),
+        None => fs::read_to_string(config_path).unwrap_or_default(),
+    };
+    let options = |name: &str| match &config {
+        Some(config) => string_list(config.get(name)),
+        None => extract_string_option(&content, name),
+    };
+    path_filters_from(&options, extract_root_dir(config_path))
+}
+
+/// Builds path filters from a config's options, looked up by name
+fn path_filters_from(options: &dyn Fn(&str) -> Option<Vec<String>>, root_dir: PathBuf) -> PathFilters {
+    let mut filters = PathFilters::default();
+    let compile = |patterns: Vec<String>| -> Vec<Regex> {
+        substitute_root_dir(patterns, &root_dir)
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect()
+    };
+    
+    if let Some(patterns) = options("testRegex") {
+        filters.test_regex = compile(patterns);
+    }
+    if let Some(patterns) = options("testPathIgnorePatterns") {
+        filters.test_path_ignore = compile(patterns);
+    }
+    if let Some(patterns) = options("modulePathIgnorePatterns") {
+        filters.module_path_ignore = compile(patterns);
+    }
+    filters.roots = match options("roots") {
+        Some(roots) => substitute_root_dir(roots, &root_dir)
+            .into_iter()
+            .map(|root| {
+                let root = root_dir.join(root);
+                root.canonicalize().unwrap_or(root)
+            })
+            .collect(),
+        None => vec![root_dir],
+    };
+    filters
+}
+
+/// Reads an option that's a string or an array of strings from a config's
+/// source text, undoing JavaScript's backslash escapes
+fn extract_string_option(content: &str, name: &str) -> Option<Vec<String>> {
+    let option_regex = Regex::new(&format!(
+        r#"["']?{}["']?\s*:\s*(\[[^\]]*\]|"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*')"#,
+        name
+    )).unwrap();
+    let string_regex = Regex::new(r#""((?:[^"\\]|\\.)*)"|'((?:[^'\\]|\\.)*)'"#).unwrap();
+    
+    let value = option_regex.captures(content)?.get(1)?.as_str();
+    let strings = string_regex
+        .captures_iter(value)
+        .filter_map(|captures| captures.get(1).or_else(|| captures.get(2)))
+        .map(|string| string.as_str().replace("\\\\", "\\"))
+        .collect();
+    Some(strings)
+}
+
+/// Directories never searched for tests: dependencies, VCS data, and build
+/// and coverage output
+const SKIPPED_DIRS: [&str; 4] = ["node_modules", ".git", "dist", "coverage"];
+
+/// Whether a path is inside a `node_modules` directory
+fn in_node_modules(path: &Path) -> bool {
+    path.components().any(|component| component.as_os_str() == "node_modules")
+}
+
+/// A path as a string with `/` separators, the way Jest's patterns are written
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace(std::path::MAIN_SEPARATOR, "/")
+}
+
+/// Lists every file under a directory, skipping the `SKIPPED_DIRS` and
+/// anything `.gitignore`d
+fn files_under(dir: &Path) -> Vec<PathBuf> {
+    walk_files(dir).collect()
+}
+
+/// Walks the files under a directory as they're found, skipping the same
+/// directories as `files_under`
+fn walk_files(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    WalkBuilder::new(dir)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_some_and(|file_type| file_type.is_dir())
+                || !SKIPPED_DIRS.iter().any(|skipped| entry.file_name() == *skipped)
+        })
+        .build()
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|file_type| file_type.is_file()))
+        .map(|entry| entry.into_path())
+}
+
+/// A Jest project's `displayName`, used to label its test files
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayName {
+    /// The label text
+    pub name: String,
+    /// Configured color name (e.g. "blue"), if any
+    pub color: Option<String>,
+}
+
+/// Extracts the `displayName` option from a Jest configuration file.
+///
+/// Supports both the string form and the `{ name, color }` object form.
+pub fn extract_display_name(config_path: &Path) -> Option<DisplayName> {
+    let content = fs::read_to_string(config_path).ok()?;
+    
+    let object_regex = Regex::new(r#"["']?displayName["']?\s*:\s*\{([^}]*)\}"#).unwrap();
+    let field_regex = |field: &str| {
+        Regex::new(&format!(r#"["']?{}["']?\s*:\s*["']([^"']+)["']"#, field)).unwrap()
+    };
+    
+    if let Some(captures) = object_regex.captures(&content) {
+        let fields = &captures[1];
+        let name = field_regex("name").captures(fields)?[1].to_string();
+        let color = field_regex("color").captures(fields).map(|c| c[1].to_string());
+        return Some(DisplayName { name, color });
+    }
+    
+    field_regex("displayName").captures(&content).map(|captures| DisplayName {
+        name: captures[1].to_string(),
+        color: None,
+    })
+}
+
+/// Reads a `displayName` option from an evaluated config, in either form
+fn display_name_from(config: &Value) -> Option<DisplayName> {
+    match config.get("displayName")? {
+        Value::String(name) => Some(DisplayName { name: name.clone(), color: None }),
+        Value::Object(fields) => Some(DisplayName {
+            name: fields.get("name")?.as_str()?.to_string(),
+            color: fields.get("color").and_then(Value::as_str).map(str::to_string),
+        }),
+        _ => None,
+    }
+}
+
+/// One of the projects a multi-project Jest config declares with `projects`
+#[derive(Debug, Clone)]
+pub struct JestProject {
+    /// The project's `displayName`, which `--selectProjects` selects it by
+    pub display_name: Option<DisplayName>,
+    /// The project's resolved `rootDir`
+    pub root_dir: PathBuf,
+    /// Patterns the project's test files are matched with
+    pub test_matches: Vec<String>,
+    /// The project's testRegex, ignore patterns, and roots
+    pub filters: PathFilters,
+    /// The project's test files, relative to the search directory; filled in
+    /// by `find_project_tests`
+    pub tests: Vec<String>,
+}
+
+impl JestProject {
+    /// Reads a project from its own config file
+    fn from_config_file(config_path: &Path) -> Self {
+        Self {
+            display_name: extract_display_name(config_path),
+            root_dir: extract_root_dir(config_path),
+            test_matches: extract_test_matches(config_path).unwrap_or_else(|_| default_test_matches()),
+            filters: extract_path_filters(config_path),
+            tests: Vec::new(),
+        }
+    }
+
+    /// Reads a project from a directory, using its Jest config (or
+    /// package.json) if it has one and Jest's defaults otherwise
+    fn from_directory(dir: &Path) -> Self {
+        let package_json = dir.join("package.json");
+        match config_files_in(dir).into_iter().next().or(package_json.exists().then_some(package_json)) {
+            Some(config_path) => Self::from_config_file(&config_path),
+            None => {
+                let root_dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+                Self {
+                    display_name: None,
+                    test_matches: default_test_matches(),
+                    filters: PathFilters { roots: vec![root_dir.clone()], ..PathFilters::default() },
+                    root_dir,
+                    tests: Vec::new(),
+                }
+            },
+        }
+    }
+
+    /// Reads a project written inline in the parent config, whose `rootDir`
+    /// is resolved against the parent config's directory and defaults to the
+    /// parent's `rootDir`
+    fn from_inline(config: &Value, config_dir: &Path, parent_root_dir: &Path) -> Self {
+        let root_dir = match config.get("rootDir").and_then(Value::as_str) {
+            Some(root_dir) => {
+                let root_dir = config_dir.join(root_dir);
+                root_dir.canonicalize().unwrap_or(root_dir)
+            },
+            None => parent_root_dir.to_path_buf(),
+        };
+        let patterns = string_list(config.get("testMatch")).unwrap_or_else(default_test_matches);
+
+        Self {
+            display_name: display_name_from(config),
+            test_matches: substitute_root_dir(patterns, &root_dir),
+            filters: path_filters_from(&|name| string_list(config.get(name)), root_dir.clone()),
+            root_dir,
+            tests: Vec::new(),
+        }
+    }
+
+    /// The project's name, or its root directory when it has no `displayName`
+    pub fn label(&self) -> String {
+        match &self.display_name {
+            Some(display_name) => display_name.name.clone(),
+            None => self.root_dir.display().to_string(),
+        }
+    }
+}
+
+/// Reads the `projects` a Jest config declares, if any.
+///
+/// Each entry can be a glob of project directories or config files (e.g.
+/// `<rootDir>/packages/*`) or an inline config object.
+pub fn extract_projects(config_path: &Path) -> Vec<JestProject> {
+    if is_vitest_config(config_path) {
+        return Vec::new();
+    }
+
+    let entries: Vec<Value> = match evaluate_config(config_path) {
+        Some(config) => config.get("projects").and_then(Value::as_array).cloned().unwrap_or_default(),
+        None => fs::read_to_string(config_path)
+            .ok()
+            .and_then(|content| extract_string_option(&content, "projects"))
+            .unwrap_or_default()
+            .into_iter()
+            .map(Value::String)
+            .collect(),
+    };
+    let config_dir = config_path.parent().unwrap_or(Path::new("."));
+    let root_dir = extract_root_dir(config_path);
+
+    let mut projects = Vec::new();
+    for entry in &entries {
+        match entry {
+            Value::String(pattern) => {
+                let pattern = pattern.replace("<rootDir>", &root_dir.to_string_lossy());
+                let full_pattern = root_dir.join(pattern).to_string_lossy().to_string();
+                let Ok(paths) = glob::glob(&full_pattern) else {
+                    continue;
+                };
+                for path in paths.flatten() {
+                    if path.is_dir() {
+                        projects.push(JestProject::from_directory(&path));
+                    } else if path.is_file() {
+                        projects.push(JestProject::from_config_file(&path));
+                    }
+                }
+            },
+            Value::Object(_) => projects.push(JestProject::from_inline(entry, config_dir, &root_dir)),
+            _ => {},
+        }
+    }
+    projects
+}
+
+/// Finds each project's test files, returning every project's tests together
+pub fn find_project_tests(projects: &mut [JestProject], project_root: &Path) -> io::Result<Vec<String>> {
+    let mut tests = BTreeSet::new();
+    for project in projects.iter_mut() {
+        project.tests = find_matching_tests(&project.test_matches, &project.filters, project_root)?;
+        tests.extend(project.tests.iter().cloned());
+    }
+    Ok(tests.into_iter().collect())
+}
+
+/// Finds the `displayName` of the project each test file belongs to.
+///
+/// A file belongs to the nearest directory at or above it (within the project
+/// root) whose Jest config declares a `displayName`, which covers the common
+/// `projects: ["<rootDir>/packages/*"]` layout.
+pub fn find_display_names(tests: &[String], project_root: &Path) -> HashMap<String, DisplayName> {
+    let mut by_directory: HashMap<PathBuf, Option<DisplayName>> = HashMap::new();
+    let mut display_names = HashMap::new();
+    
+    for test_file in tests {
+        let full_path = project_root.join(test_file);
+        let Some(test_dir) = full_path.parent() else {
+            continue;
+        };
+        
+        for dir in test_dir.ancestors() {
+            let display_name = by_directory
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| {
+                    let package_json = dir.join("package.json");
+                    config_files_in(dir)
+                        .into_iter()
+                        .chain(package_json.exists().then_some(package_json))
+                        .find_map(|config| extract_display_name(&config))
+                })
+                .clone();
+            
+            if let Some(display_name) = display_name {
+                display_names.insert(test_file.clone(), display_name);
+                break;
+            }
+            
+            // Don't look above the project root
+            if dir == project_root {
+                break;
+            }
+        }
+    }
+    
+    display_names
+}
+
+/// How many files a single testMatch pattern matched during discovery
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternDiagnostic {
+    /// The pattern as configured
+    pub pattern: String,
+    /// Files matched that were kept
+    pub matched: usize,
+    /// Files matched that were dropped because they live in ignored paths
+    pub ignored: usize,
+}
+
+impl PatternDiagnostic {
+    /// A warning for patterns that contribute no test files, if this is one
+    pub fn warning(&self) -> Option<String> {
+        match (self.matched, self.ignored) {
+            (0, 0) => Some(format!("'{}' matched no files", self.pattern)),
+            (0, ignored) => Some(format!("'{}' only matched {} ignored files", self.pattern, ignored)),
+            _ => None,
+        }
+    }
+}
+
+/// Counts the files each pattern (or each testRegex, when those are set)
+/// matches on its own, including ignored ones.
+///
+/// A typo'd pattern silently shortens the test list, so this is used to flag
+/// patterns that contribute nothing.
+pub fn pattern_diagnostics(test_matches: &[String], filters: &PathFilters, project_root: &Path) -> Vec<PatternDiagnostic> {
+    let canonical_root = project_root.canonicalize().unwrap_or_else(|_| project_root.to_path_buf());
+    let walked = walk_roots(&filters.search_roots(&canonical_root));
+    let count = |pattern: String, paths: &mut dyn Iterator<Item = &PathBuf>| {
+        let (mut matched, mut ignored) = (0, 0);
+        for path in paths {
+            if filters.is_ignored(path) {
+                ignored += 1;
+            } else {
+                matched += 1;
+            }
+        }
+        PatternDiagnostic { pattern, matched, ignored }
+    };
+
+    if !filters.test_regex.is_empty() {
+        let files = files_under_roots(&walked);
+        return filters.test_regex
+            .iter()
+            .map(|regex| {
+                let mut paths = files.iter().filter(|path| regex.is_match(&slash_path(path)));
+                count(regex.as_str().to_string(), &mut paths)
+            })
+            .collect();
+    }
+
+    test_matches
+        .iter()
+        .map(|pattern| {
+            let files = glob_under_roots(pattern, &walked).unwrap_or_default();
+            count(pattern.clone(), &mut files.iter())
+        })
+        .collect()
+}
+
+/// Matches a pattern against the files under each search root, resolving
+/// relative patterns against each root, without duplicates
+fn glob_under_roots(pattern: &str, walked: &[(PathBuf, Vec<PathBuf>)]) -> Result<BTreeSet<PathBuf>, globset::Error> {
+    let mut files = BTreeSet::new();
+    for (root, root_files) in walked {
+        let matcher = root_matcher(pattern, root)?;
+        files.extend(root_files.iter().filter(|file| matcher.is_match(slash_path(file))).cloned());
+    }
+    Ok(files)
+}
+
+/// Compiles a pattern resolved against a search root
+fn root_matcher(pattern: &str, root: &Path) -> Result<GlobMatcher, globset::Error> {
+    // Absolute patterns (e.g. from `<rootDir>`) match the same under every root
+    let full_pattern = if Path::new(pattern).is_absolute() {
+        slash_path(Path::new(pattern))
+    } else {
+        format!("{}/{}", slash_path(root), pattern.trim_start_matches("./"))
+    };
+    Ok(GlobBuilder::new(&full_pattern)
+        .literal_separator(true)
+        .build()?
+        .compile_matcher())
+}
+
+/// Lists the files under each search root, with the root they were found in
+fn walk_roots(roots: &[PathBuf]) -> Vec<(PathBuf, Vec<PathBuf>)> {
+    roots
+        .iter()
+        .map(|root| (root.clone(), files_under(root)))
+        .collect()
+}
+
+/// Every file under the search roots, without duplicates
+fn files_under_roots(walked: &[(PathBuf, Vec<PathBuf>)]) -> BTreeSet<PathBuf> {
+    walked.iter().flat_map(|(_, files)| files.iter().cloned()).collect()
+}
+
+/// Finds test files matching the given patterns in the config's roots, or
+/// in the specified directory when there's no config.
+///
+/// When the config sets `testRegex`, files are matched against it instead of
+/// the patterns, the way Jest does. Files in ignored paths are left out.
+/// Paths are relative to the directory, or absolute for files outside it.
+pub fn find_matching_tests(
+    test_matches: &[String],
+    filters: &PathFilters,
+    project_root: &Path,
+) -> io::Result<Vec<String>> {
+    scan_matching_tests(test_matches, filters, project_root, &AtomicBool::new(false), &mut |_| {})
+}
+
+/// Finds the same test files as `find_matching_tests`, calling `on_found`
+/// with each one as the walk comes across it. Stops early when `cancelled`
+/// is set, returning the files found until then.
+pub fn scan_matching_tests(
+    test_matches: &[String],
+    filters: &PathFilters,
+    project_root: &Path,
+    cancelled: &AtomicBool,
+    on_found: &mut dyn FnMut(&str),
+) -> io::Result<Vec<String>> {
+    let canonical_root = project_root.canonicalize()?;
+    let mut found = BTreeSet::new();
+
+    for root in filters.search_roots(&canonical_root) {
+        // Invalid patterns match nothing; the pattern diagnostics explain why
+        let matchers: Vec<GlobMatcher> = test_matches
+            .iter()
+            .filter_map(|pattern| root_matcher(pattern, &root).ok())
+            .collect();
+
+        for path in walk_files(&root) {
+            if cancelled.load(Ordering::Relaxed) {
+                return Ok(found.into_iter().collect());
+            }
+
+            let path_str = slash_path(&path);
+            let is_test = if filters.test_regex.is_empty() {
+                matchers.iter().any(|matcher| matcher.is_match(&path_str))
+            } else {
+                filters.test_regex.iter().any(|regex| regex.is_match(&path_str))
+            };
+            // Skip files in node_modules and other ignored paths
+            if !is_test || filters.is_ignored(&path) {
+                continue;
+            }
+
+            // Make the path relative to the search directory when it's inside it
+            let relative = path.strip_prefix(&canonical_root).unwrap_or(&path).display().to_string();
+            if !found.contains(&relative) {
+                on_found(&relative);
+                found.insert(relative);
+            }
+        }
+    }
+
+    // Sorted alphabetically for better readability
+    Ok(found.into_iter().collect())
+}
\ No newline at end of file
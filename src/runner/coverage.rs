@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::{BTreeMap, HashMap}, fs, io, path::{Path, PathBuf}};
+
+/// Where Jest's `json-summary` coverage reporter writes, with the default `coverageDirectory`
+pub fn summary_path(project_dir: &Path) -> PathBuf {
+    project_dir.join("coverage").join("coverage-summary.json")
+}
+
+/// Where Jest's `json` coverage reporter writes, with the default `coverageDirectory`
+pub fn final_path(project_dir: &Path) -> PathBuf {
+    project_dir.join("coverage").join("coverage-final.json")
+}
+
+/// How many times each executable line of a file ran, by 1-based line number
+pub type LineHits = BTreeMap<usize, u64>;
+
+/// Coverage of one kind of code (statements, branches, ...)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Metric {
+    pub total: usize,
+    pub covered: usize,
+    /// Percentage covered; Jest reports "Unknown" when there's nothing to cover
+    #[serde(default, deserialize_with = "percentage")]
+    pub pct: Option<f64>,
+}
+
+/// Coverage of a single source file, or of the whole run
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct FileCoverage {
+    pub statements: Metric,
+    pub branches: Metric,
+    pub functions: Metric,
+    pub lines: Metric,
+}
+
+/// A parsed `coverage-summary.json`
+#[derive(Debug, Clone, Default)]
+pub struct CoverageSummary {
+    /// Coverage across every file
+    pub total: FileCoverage,
+    /// Per-file coverage, with paths relative to the project directory, sorted by path
+    pub files: Vec<(String, FileCoverage)>,
+    /// Per-line hits, keyed like `files`, if the run also wrote `coverage-final.json`
+    pub line_hits: HashMap<String, LineHits>,
+}
+
+impl CoverageSummary {
+    /// Read a coverage summary, making file paths relative to the project directory
+    pub fn load(path: &Path, project_dir: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let mut entries: HashMap<String, FileCoverage> = serde_json::from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let total = entries.remove("total").unwrap_or_default();
+        let root = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+        let mut files: Vec<(String, FileCoverage)> = entries
+            .into_iter()
+            .map(|(file, coverage)| (relative_to(&root, file), coverage))
+            .collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(Self { total, files, line_hits: HashMap::new() })
+    }
+
+    /// Read per-line hits from a `coverage-final.json`, keying files the same way as `load`
+    pub fn load_line_hits(&mut self, path: &Path, project_dir: &Path) -> io::Result<()> {
+        let content = fs::read_to_string(path)?;
+        let entries: HashMap<String, FinalFileCoverage> = serde_json::from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let root = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+        self.line_hits = entries
+            .into_iter()
+            .map(|(file, coverage)| (relative_to(&root, file), coverage.line_hits()))
+            .collect();
+        Ok(())
+    }
+}
+
+/// Where a statement starts or ends in a source file
+#[derive(Debug, Deserialize)]
+struct Position {
+    line: usize,
+}
+
+/// The source range of a statement
+#[derive(Debug, Deserialize)]
+struct Location {
+    start: Position,
+}
+
+/// One file's entry in an Istanbul `coverage-final.json`
+#[derive(Debug, Deserialize)]
+struct FinalFileCoverage {
+    #[serde(rename = "statementMap")]
+    statement_map: HashMap<String, Location>,
+    /// Execution count of each statement in `statement_map`
+    s: HashMap<String, u64>,
+}
+
+impl FinalFileCoverage {
+    /// Count a line as run as often as the most-run statement starting on it,
+    /// the way Istanbul's own line coverage does
+    fn line_hits(&self) -> LineHits {
+        let mut hits = LineHits::new();
+        for (id, location) in &self.statement_map {
+            let count = self.s.get(id).copied().unwrap_or(0);
+            let line = hits.entry(location.start.line).or_insert(0);
+            *line = (*line).max(count);
+        }
+        hits
+    }
+}
+
+/// Make a reported file path relative to the project directory, if it's inside it
+fn relative_to(root: &Path, file: String) -> String {
+    Path::new(&file)
+        .strip_prefix(root)
+        .map(|relative| relative.display().to_string())
+        .unwrap_or(file)
+}
+
+/// Accept both numbers and Jest's "Unknown" for percentages
+fn percentage<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    Ok(serde_json::Value::deserialize(deserializer)?.as_f64())
+}
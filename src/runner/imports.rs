@@ -0,0 +1,61 @@
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Extensions tried when resolving an import without one, in Jest's default order
+const RESOLVE_EXTENSIONS: [&str; 7] = ["js", "mjs", "cjs", "jsx", "ts", "tsx", "json"];
+
+/// A local module imported by a test file
+#[derive(Debug, Clone)]
+pub struct LocalImport {
+    /// The specifier as written, e.g. "../src/Button"
+    pub specifier: String,
+    /// The file it resolves to, if it could be found
+    pub resolved: Option<PathBuf>,
+}
+
+/// Finds the relative import/require specifiers in the given file content
+pub fn find_local_specifiers(content: &str) -> Vec<String> {
+    let import_regex = Regex::new(
+        r#"(?:\bfrom\s*|\bimport\s*\(?\s*|\brequire\s*\(\s*|\bjest\.mock\s*\(\s*)['"](\.{1,2}/[^'"]*|\.{1,2})['"]"#
+    ).unwrap();
+    
+    let mut specifiers: Vec<String> = Vec::new();
+    for cap in import_regex.captures_iter(content) {
+        let specifier = cap[1].to_string();
+        if !specifiers.contains(&specifier) {
+            specifiers.push(specifier);
+        }
+    }
+    specifiers
+}
+
+/// Resolves an import specifier relative to the importing file, the way Node would
+pub fn resolve_import(importer: &Path, specifier: &str) -> Option<PathBuf> {
+    let base = importer.parent()?.join(specifier);
+    
+    if base.is_file() {
+        return Some(base);
+    }
+    
+    // Try adding each extension, then index files inside a directory
+    RESOLVE_EXTENSIONS
+        .iter()
+        .map(|ext| PathBuf::from(format!("{}.{}", base.display(), ext)))
+        .chain(RESOLVE_EXTENSIONS.iter().map(|ext| base.join(format!("index.{}", ext))))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Finds and resolves the local modules imported by a test file
+pub fn find_local_imports(test_path: &Path) -> Vec<LocalImport> {
+    let Ok(content) = std::fs::read_to_string(test_path) else {
+        return Vec::new();
+    };
+    
+    find_local_specifiers(&content)
+        .into_iter()
+        .map(|specifier| LocalImport {
+            resolved: resolve_import(test_path, &specifier),
+            specifier,
+        })
+        .collect()
+}
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{fs, io, path::{Path, PathBuf}};
+use crate::runner::{ansi, output_parser::RunSummary};
+
+/// Where Jest is asked to write its JSON results for runs started by this process
+pub fn results_path() -> PathBuf {
+    std::env::temp_dir().join(format!("syj-results-{}.json", std::process::id()))
+}
+
+/// Where Jest writes JSON results for one of several concurrent runs
+pub fn numbered_results_path(number: usize) -> PathBuf {
+    std::env::temp_dir().join(format!("syj-results-{}-{}.json", std::process::id(), number))
+}
+
+/// Where Jest writes JSON results for runs started in a run tab other than the first
+pub fn tab_results_path(tab: usize) -> PathBuf {
+    std::env::temp_dir().join(format!("syj-results-{}-tab{}.json", std::process::id(), tab))
+}
+
+/// Read and remove the results file Jest wrote for a run.
+///
+/// Removing it means a run that dies before writing results can't be
+/// mistaken for the previous one.
+pub fn take_results(path: &Path) -> io::Result<JsonResults> {
+    let content = fs::read_to_string(path)?;
+    let _ = fs::remove_file(path);
+    serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// The outcome of a single test as reported by Jest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssertionStatus {
+    Passed,
+    Failed,
+    Skipped,
+    Pending,
+    Todo,
+    Disabled,
+    Focused,
+}
+
+/// Where a test is declared, from `--testLocationInResults`
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Location {
+    pub line: usize,
+}
+
+/// A single test case from Jest's JSON results
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResult {
+    /// The test's own name
+    pub title: String,
+    /// Describe names and the test name joined with spaces, as matched by `--testNamePattern`
+    pub full_name: String,
+    /// Names of the enclosing describe blocks, outermost first
+    #[serde(default)]
+    pub ancestor_titles: Vec<String>,
+    pub status: AssertionStatus,
+    /// Duration in ms, when Jest measured it
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// Formatted failure messages, including stack traces
+    #[serde(default, deserialize_with = "messages_without_colors")]
+    pub failure_messages: Vec<String>,
+    #[serde(default)]
+    pub location: Option<Location>,
+}
+
+/// When Jest started and finished running a file, in ms since the Unix epoch
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct PerfStats {
+    #[serde(default)]
+    pub start: f64,
+    #[serde(default)]
+    pub end: f64,
+}
+
+/// The results for one test file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileResult {
+    /// Absolute path of the test file
+    pub name: String,
+    /// Error shown when the file failed without running its tests (e.g. a syntax error)
+    #[serde(default, deserialize_with = "message_without_colors")]
+    pub message: String,
+    #[serde(default)]
+    pub assertion_results: Vec<AssertionResult>,
+    #[serde(default)]
+    pub perf_stats: PerfStats,
+    /// When Vitest started running the file, which it reports instead of `perfStats`
+    #[serde(default)]
+    pub start_time: Option<f64>,
+    /// When Vitest finished running the file
+    #[serde(default)]
+    pub end_time: Option<f64>,
+}
+
+impl FileResult {
+    /// Whether the file failed without running its tests (e.g. a syntax error)
+    pub fn failed_to_run(&self) -> bool {
+        self.assertion_results.is_empty() && !self.message.is_empty()
+    }
+    
+    /// Test counts for this file
+    pub fn summary(&self) -> RunSummary {
+        let mut summary = RunSummary {
+            total: self.assertion_results.len(),
+            ..RunSummary::default()
+        };
+        for test in &self.assertion_results {
+            match test.status {
+                AssertionStatus::Passed => summary.passed += 1,
+                AssertionStatus::Failed => summary.failed += 1,
+                AssertionStatus::Todo => summary.todo += 1,
+                _ => summary.skipped += 1,
+            }
+        }
+        summary
+    }
+
+    /// How long the runner spent on this file
+    pub fn duration_ms(&self) -> u64 {
+        let (start, end) = match (self.start_time, self.end_time) {
+            (Some(start), Some(end)) => (start, end),
+            _ => (self.perf_stats.start, self.perf_stats.end),
+        };
+        (end - start).max(0.0) as u64
+    }
+}
+
+/// Snapshot counts for a whole run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SnapshotSummary {
+    /// Snapshots written for the first time
+    pub added: usize,
+    /// Snapshots rewritten by `--updateSnapshot`
+    pub updated: usize,
+    /// Snapshots that didn't match what the tests produced
+    pub unmatched: usize,
+    /// Obsolete snapshots that no test checked
+    pub unchecked: usize,
+}
+
+impl SnapshotSummary {
+    /// Whether failing or obsolete snapshots could be fixed by updating them
+    pub fn needs_update(&self) -> bool {
+        self.unmatched > 0 || self.unchecked > 0
+    }
+    
+    /// A short description of what happened to snapshots, if anything did
+    pub fn label(&self) -> Option<String> {
+        let counts = [
+            (self.added, "written"),
+            (self.updated, "updated"),
+            (self.unmatched, "failed"),
+            (self.unchecked, "obsolete"),
+        ];
+        let parts: Vec<String> = counts
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, label)| format!("{} {}", count, label))
+            .collect();
+        
+        (!parts.is_empty()).then(|| format!("Snapshots: {}", parts.join(", ")))
+    }
+}
+
+/// Jest's `--json` output for a whole run (Vitest's JSON reporter has the same shape)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonResults {
+    pub num_passed_tests: usize,
+    pub num_failed_tests: usize,
+    pub num_pending_tests: usize,
+    pub num_todo_tests: usize,
+    pub num_total_tests: usize,
+    #[serde(default)]
+    pub test_results: Vec<FileResult>,
+    #[serde(default)]
+    pub snapshot: SnapshotSummary,
+}
+
+impl JsonResults {
+    /// The run's test counts
+    pub fn summary(&self) -> RunSummary {
+        RunSummary {
+            passed: self.num_passed_tests,
+            failed: self.num_failed_tests,
+            skipped: self.num_pending_tests,
+            todo: self.num_todo_tests,
+            total: self.num_total_tests,
+        }
+    }
+}
+
+/// Read a message without the color codes runs started with `--colors` add to it
+fn message_without_colors<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(ansi::strip(&String::deserialize(deserializer)?).into_owned())
+}
+
+/// Read messages without the color codes runs started with `--colors` add to them
+fn messages_without_colors<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let messages = Vec::<String>::deserialize(deserializer)?;
+    Ok(messages.iter().map(|message| ansi::strip(message).into_owned()).collect())
+}
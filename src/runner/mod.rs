@@ -0,0 +1,20 @@
+pub mod ansi;
+pub mod assertion_diff;
+pub mod backend;
+pub mod command;
+pub mod config_finder;
+pub mod coverage;
+pub mod imports;
+pub mod json_results;
+pub mod output_parser;
+pub mod package_manager;
+pub mod pretty_value;
+pub mod processes;
+pub mod project;
+pub mod related;
+pub mod snapshots;
+pub mod stack_trace;
+pub mod test_script;
+pub mod test_parser;
+pub mod test_runner;
+pub mod workspace;
\ No newline at end of file
@@ -0,0 +1,78 @@
+use regex::Regex;
+use crate::runner::ansi;
+use serde::{Deserialize, Serialize};
+
+/// Test counts from the summary Jest prints at the end of a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// Number of passing tests
+    pub passed: usize,
+    /// Number of failing tests
+    pub failed: usize,
+    /// Number of skipped tests
+    pub skipped: usize,
+    /// Number of todo tests
+    pub todo: usize,
+    /// Total number of tests
+    pub total: usize,
+}
+
+impl RunSummary {
+    /// Whether the run had no failing tests
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Parse the `Tests: 1 failed, 3 passed, 4 total` summary line from Jest output,
+/// or Vitest's `Tests  1 failed | 3 passed (4)`.
+pub fn parse_summary(output: &str) -> Option<RunSummary> {
+    let output = ansi::strip(output);
+    // Vitest puts the total in parentheses at the end of the line
+    let count_regex = Regex::new(r"(\d+) (passed|failed|skipped|todo|total)|\((\d+)\)$").unwrap();
+    
+    let line = output
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("Tests:") || line.starts_with("Tests  "))?;
+    
+    let mut summary = RunSummary::default();
+    for cap in count_regex.captures_iter(line) {
+        if let Some(total) = cap.get(3) {
+            summary.total = total.as_str().parse().unwrap_or(0);
+            continue;
+        }
+        let count = cap[1].parse().unwrap_or(0);
+        match &cap[2] {
+            "passed" => summary.passed = count,
+            "failed" => summary.failed = count,
+            "skipped" => summary.skipped = count,
+            "todo" => summary.todo = count,
+            _ => summary.total = count,
+        }
+    }
+    
+    Some(summary)
+}
+
+/// Count the test files that have finished so far in a multi-file run, as
+/// `(passed, failed)`, from Jest's `PASS`/`FAIL` lines or Vitest's per-file
+/// `✓ file (3 tests)` / `❯ file (3 tests | 1 failed)` lines.
+pub fn count_finished_files(output: &str) -> (usize, usize) {
+    let output = ansi::strip(output);
+    let vitest_file_regex = Regex::new(r"^([✓❯×]) \S+ \(\d+ tests?").unwrap();
+    
+    output.lines().map(str::trim).fold((0, 0), |(passed, failed), line| {
+        if line.starts_with("PASS ") {
+            return (passed + 1, failed);
+        }
+        if line.starts_with("FAIL ") {
+            return (passed, failed + 1);
+        }
+        match vitest_file_regex.captures(line) {
+            Some(cap) if &cap[1] == "✓" => (passed + 1, failed),
+            Some(_) => (passed, failed + 1),
+            None => (passed, failed),
+        }
+    })
+}
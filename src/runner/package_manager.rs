@@ -0,0 +1,85 @@
+use std::path::Path;
+
+/// The package manager a project uses, which decides how Jest is invoked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PackageManager {
+    /// npm, run through `npx`
+    #[default]
+    Npm,
+    /// Yarn (classic or berry)
+    Yarn,
+    /// pnpm
+    Pnpm,
+    /// Bun
+    Bun,
+}
+
+/// Lockfiles that identify each package manager, in order of precedence
+const LOCKFILES: &[(&str, PackageManager)] = &[
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("yarn.lock", PackageManager::Yarn),
+    ("bun.lockb", PackageManager::Bun),
+    ("bun.lock", PackageManager::Bun),
+    ("package-lock.json", PackageManager::Npm),
+];
+
+impl PackageManager {
+    /// Detect the package manager from the lockfile in the project directory
+    /// or the nearest parent that has one (the workspace root in monorepos),
+    /// falling back to npm
+    pub fn detect(project_dir: &Path) -> Self {
+        let start = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+
+        start
+            .ancestors()
+            .find_map(|dir| {
+                LOCKFILES
+                    .iter()
+                    .find(|(lockfile, _)| dir.join(lockfile).is_file())
+                    .map(|(_, manager)| *manager)
+            })
+            .unwrap_or_default()
+    }
+
+    /// The program and leading arguments that run a package's binary, e.g. `pnpm exec`
+    pub fn exec(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            PackageManager::Npm => ("npx", &[]),
+            PackageManager::Yarn => ("yarn", &[]),
+            PackageManager::Pnpm => ("pnpm", &["exec"]),
+            PackageManager::Bun => ("bunx", &[]),
+        }
+    }
+
+    /// The program and arguments that run the package.json `test` script,
+    /// passing on any arguments that follow
+    pub fn run_test_script(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            PackageManager::Npm => ("npm", &["test", "--"]),
+            PackageManager::Yarn => ("yarn", &["test"]),
+            PackageManager::Pnpm => ("pnpm", &["test"]),
+            // `bun test` is Bun's own test runner rather than the script
+            PackageManager::Bun => ("bun", &["run", "test"]),
+        }
+    }
+
+    /// The command that adds a package as a dev dependency, e.g. `npm i -D jest`
+    pub fn add_dev_command(self, package: &str) -> String {
+        match self {
+            PackageManager::Npm => format!("npm i -D {}", package),
+            PackageManager::Yarn => format!("yarn add -D {}", package),
+            PackageManager::Pnpm => format!("pnpm add -D {}", package),
+            PackageManager::Bun => format!("bun add -d {}", package),
+        }
+    }
+
+    /// Name of the package manager
+    pub fn label(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Bun => "bun",
+        }
+    }
+}
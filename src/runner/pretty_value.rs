@@ -0,0 +1,187 @@
+/// One-line values shorter than this read fine as they are
+const MIN_PRETTY_LENGTH: usize = 40;
+
+/// Spaces per nesting level
+const INDENT: &str = "  ";
+
+/// What a piece of a formatted value is, for coloring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// An object key, quoted or not
+    Key,
+    /// A quoted string
+    String,
+    /// A number
+    Number,
+    /// `true`, `false`, `null`, `undefined`, or `NaN`
+    Literal,
+    /// Brackets, commas, and colons
+    Punctuation,
+    /// Anything else, like type names (`Object`, `Map`) and whitespace
+    Other,
+}
+
+/// A line being built, with the object it's directly inside (for lining up
+/// keys) and the width of its key and colon, if it's a `key: value` line
+struct PrettyLine {
+    depth: usize,
+    text: String,
+    parent: Option<usize>,
+    key_width: Option<usize>,
+}
+
+/// Re-format a one-line object or array dump, as printed in `Expected:` and
+/// `Received:` lines, over several indented lines with the values of each
+/// object's keys lined up.
+///
+/// Returns `None` for values that aren't worth it: short ones, ones without
+/// an object or array, and ones whose brackets don't balance (e.g. a dump
+/// the runner cut short).
+pub fn pretty_lines(value: &str) -> Option<Vec<String>> {
+    let value = value.trim();
+    if value.len() < MIN_PRETTY_LENGTH || !value.contains(['{', '[']) {
+        return None;
+    }
+
+    let chars: Vec<char> = value.chars().collect();
+    let mut lines = vec![PrettyLine { depth: 0, text: String::new(), parent: None, key_width: None }];
+    // Each open bracket, with an id for the objects among them
+    let mut open: Vec<(char, Option<usize>)> = Vec::new();
+    let mut objects = 0;
+    let mut index = 0;
+
+    let new_line = |lines: &mut Vec<PrettyLine>, open: &[(char, Option<usize>)]| {
+        lines.push(PrettyLine {
+            depth: open.len(),
+            text: String::new(),
+            parent: open.last().and_then(|(_, object)| *object),
+            key_width: None,
+        });
+    };
+
+    while index < chars.len() {
+        let c = chars[index];
+        match c {
+            '"' | '\'' => {
+                let end = string_end(&chars, index)?;
+                lines.last_mut()?.text.extend(&chars[index..=end]);
+                index = end;
+            },
+            '{' | '[' => {
+                lines.last_mut()?.text.push(c);
+                let close = if c == '{' { '}' } else { ']' };
+                let next = chars[index + 1..].iter().position(|c| !c.is_whitespace()).map(|offset| index + 1 + offset);
+                if let Some(next) = next.filter(|&next| chars[next] == close) {
+                    // Leave empty objects and arrays on the line
+                    lines.last_mut()?.text.push(close);
+                    index = next;
+                } else {
+                    let object = (c == '{').then(|| {
+                        objects += 1;
+                        objects
+                    });
+                    open.push((c, object));
+                    new_line(&mut lines, &open);
+                }
+            },
+            '}' | ']' => {
+                let (opened, _) = open.pop()?;
+                if (opened == '{') != (c == '}') {
+                    return None;
+                }
+                if lines.last()?.text.trim().is_empty() {
+                    lines.pop();
+                }
+                new_line(&mut lines, &open);
+                lines.last_mut()?.text.push(c);
+            },
+            ',' if !open.is_empty() => {
+                lines.last_mut()?.text.push(',');
+                new_line(&mut lines, &open);
+            },
+            ':' if open.last().is_some_and(|(opened, _)| *opened == '{') && lines.last()?.key_width.is_none() => {
+                let line = lines.last_mut()?;
+                line.text.push(':');
+                line.key_width = Some(line.text.chars().count());
+            },
+            c if c.is_whitespace() && lines.last()?.text.is_empty() => {},
+            c => lines.last_mut()?.text.push(c),
+        }
+        index += 1;
+    }
+    if !open.is_empty() {
+        return None;
+    }
+
+    // Line up the values of each object's keys
+    let mut key_widths: std::collections::HashMap<usize, usize> = std::collections::HashMap::new();
+    for line in &lines {
+        if let (Some(parent), Some(key_width)) = (line.parent, line.key_width) {
+            let width = key_widths.entry(parent).or_default();
+            *width = (*width).max(key_width);
+        }
+    }
+    let formatted = lines
+        .into_iter()
+        .filter(|line| !line.text.trim().is_empty())
+        .map(|line| {
+            let indent = INDENT.repeat(line.depth);
+            match (line.parent.and_then(|parent| key_widths.get(&parent)), line.key_width) {
+                (Some(&width), Some(key_width)) => {
+                    let split = line.text.char_indices().nth(key_width).map_or(line.text.len(), |(index, _)| index);
+                    let (key, value) = line.text.split_at(split);
+                    format!("{}{:<width$} {}", indent, key, value.trim_start())
+                },
+                _ => format!("{}{}", indent, line.text),
+            }
+        })
+        .collect();
+    Some(formatted)
+}
+
+/// Index of the quote closing the string starting at `start`
+fn string_end(chars: &[char], start: usize) -> Option<usize> {
+    let quote = chars[start];
+    let mut index = start + 1;
+    while index < chars.len() {
+        match chars[index] {
+            '\\' => index += 1,
+            c if c == quote => return Some(index),
+            _ => {},
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Split a line of a formatted value into pieces to color
+pub fn tokens(line: &str) -> Vec<(TokenKind, &str)> {
+    let is_word = |c: char| c.is_alphanumeric() || matches!(c, '_' | '$' | '-' | '.');
+    let chars: Vec<char> = line.chars().collect();
+    let offsets: Vec<usize> = line.char_indices().map(|(offset, _)| offset).chain(std::iter::once(line.len())).collect();
+
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let end = match chars[index] {
+            '"' | '\'' => string_end(&chars, index).map_or(chars.len(), |end| end + 1),
+            c if is_word(c) => chars[index..].iter().position(|&c| !is_word(c)).map_or(chars.len(), |length| index + length),
+            c if c.is_whitespace() => chars[index..].iter().position(|c| !c.is_whitespace()).map_or(chars.len(), |length| index + length),
+            _ => index + 1,
+        };
+        let text = &line[offsets[index]..offsets[end]];
+        let key = chars.get(end) == Some(&':');
+        let kind = match chars[index] {
+            '"' | '\'' if key => TokenKind::Key,
+            '"' | '\'' => TokenKind::String,
+            '{' | '}' | '[' | ']' | ',' | ':' => TokenKind::Punctuation,
+            c if is_word(c) && key => TokenKind::Key,
+            c if is_word(c) && matches!(text, "true" | "false" | "null" | "undefined" | "NaN") => TokenKind::Literal,
+            c if is_word(c) && text.parse::<f64>().is_ok() => TokenKind::Number,
+            _ => TokenKind::Other,
+        };
+        tokens.push((kind, text));
+        index = end;
+    }
+    tokens
+}
@@ -0,0 +1,111 @@
+use std::{
+    collections::HashSet,
+    io,
+    process::{Child, Command, Output, Stdio},
+    sync::{Mutex, OnceLock},
+    thread,
+    time::Duration,
+};
+use tracing::{debug, info};
+
+/// How long stopped runs get to exit on their own before they're killed
+const STOP_GRACE: Duration = Duration::from_millis(300);
+
+/// Process ids of the runs still going. On Unix each is also the id of the
+/// process group holding the run and everything it started (Jest's workers,
+/// Node processes started by npm, and so on).
+fn running() -> &'static Mutex<HashSet<u32>> {
+    static RUNNING: OnceLock<Mutex<HashSet<u32>>> = OnceLock::new();
+    RUNNING.get_or_init(Default::default)
+}
+
+/// Start a command so that it, and any processes it starts, can be stopped
+/// when the app quits.
+///
+/// On Unix the command gets a process group of its own, so stopping it reaches
+/// the runner's workers too; its stdin is closed, since the terminal's input
+/// belongs to the UI. Call [`finished`] once it has exited.
+pub fn spawn(command: &mut Command) -> io::Result<Child> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let child = command.stdin(Stdio::null()).spawn()?;
+    if let Ok(mut running) = running().lock() {
+        running.insert(child.id());
+    }
+    Ok(child)
+}
+
+/// Stop tracking a process started with [`spawn`] once it has exited
+pub fn finished(child: &Child) {
+    if let Ok(mut running) = running().lock() {
+        running.remove(&child.id());
+    }
+}
+
+/// Run a command to completion, collecting its output, the way
+/// [`Command::output`] does, while letting [`stop_all`] stop it
+pub fn output(command: &mut Command) -> io::Result<Output> {
+    let child = spawn(command.stdout(Stdio::piped()).stderr(Stdio::piped()))?;
+    let pid = child.id();
+    let output = child.wait_with_output();
+    if let Ok(mut running) = running().lock() {
+        running.remove(&pid);
+    }
+    output
+}
+
+/// Stop every run that's still going, along with the processes it started.
+///
+/// Runs are asked to stop first, and killed if they're still there after a
+/// moment.
+pub fn stop_all() {
+    let pids: Vec<u32> = match running().lock() {
+        Ok(mut running) => running.drain().collect(),
+        Err(_) => return,
+    };
+    if pids.is_empty() {
+        return;
+    }
+
+    info!(?pids, "Stopping runs still going");
+    for &pid in &pids {
+        signal(pid, "TERM");
+    }
+    thread::sleep(STOP_GRACE);
+    for &pid in &pids {
+        signal(pid, "KILL");
+    }
+}
+
+/// Send a signal to a run's process group. Groups that have already exited
+/// are skipped by `kill` with an error, which is ignored.
+#[cfg(unix)]
+fn signal(pid: u32, signal: &str) {
+    let result = Command::new("kill")
+        .arg(format!("-{}", signal))
+        .arg("--")
+        .arg(format!("-{}", pid))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    debug!(pid, signal, ?result, "Signalled run");
+}
+
+/// Kill a run and every process it started. Windows has no signals to ask a
+/// run to stop, so only the forced kill does anything.
+#[cfg(windows)]
+fn signal(pid: u32, signal: &str) {
+    if signal != "KILL" {
+        return;
+    }
+    let result = Command::new("taskkill")
+        .args(["/T", "/F", "/PID"])
+        .arg(pid.to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    debug!(pid, ?result, "Killed run");
+}
@@ -0,0 +1,138 @@
+use serde::Deserialize;
+use std::{io, path::{Path, PathBuf}};
+use tracing::debug;
+use crate::runner::{backend::Runner, command::JestCommand, config_finder::{self, JestProject, PathFilters}, processes};
+
+/// How test files are found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscoveryBackend {
+    /// Match files against the config's patterns
+    #[default]
+    Glob,
+    /// Ask Jest for the files it would run (`jest --listTests`), which matches
+    /// its own resolution exactly but takes as long as starting Jest
+    Jest,
+}
+
+/// The tests found in a project directory, and how they were found
+#[derive(Debug, Clone)]
+pub struct Project {
+    /// Runner the project uses
+    pub runner: Runner,
+    /// The runner's config files, the one in use first
+    pub config_files: Vec<PathBuf>,
+    /// Patterns test files were matched with
+    pub test_matches: Vec<String>,
+    /// testRegex and ignore patterns from the config
+    pub path_filters: PathFilters,
+    /// Projects declared with the config's `projects` option, each with its tests
+    pub projects: Vec<JestProject>,
+    /// Matching test files, relative to the directory
+    pub tests: Vec<String>,
+    /// Why asking Jest for the test files failed, when the Jest backend was
+    /// chosen and the files were found with patterns instead
+    pub discovery_error: Option<String>,
+}
+
+impl Project {
+    /// Detect the runner and read its config in a directory, without
+    /// searching it for test files yet.
+    ///
+    /// Without a runner config, tests will be matched with
+    /// `default_test_matches`, or the runner's own defaults when that's empty.
+    pub fn detect(path: &Path, default_test_matches: &[String]) -> io::Result<Self> {
+        let runner = Runner::detect(path);
+        let config_files = runner.find_config_files(path)?;
+        let test_matches = match config_files.first() {
+            Some(config_path) => config_finder::extract_test_matches(config_path)?,
+            // Fallback to default patterns if no config found
+            None if default_test_matches.is_empty() => runner.default_test_matches(),
+            None => default_test_matches.to_vec(),
+        };
+        let path_filters = config_files
+            .first()
+            .map(|config_path| config_finder::extract_path_filters(config_path))
+            .unwrap_or_default();
+        let projects = config_files
+            .first()
+            .map(|config_path| config_finder::extract_projects(config_path))
+            .unwrap_or_default();
+
+        Ok(Self {
+            runner,
+            config_files,
+            test_matches,
+            path_filters,
+            projects,
+            tests: Vec::new(),
+            discovery_error: None,
+        })
+    }
+
+    /// Detect the runner, read its config, and find the test files in a directory.
+    ///
+    /// Without a runner config, tests are matched with `default_test_matches`,
+    /// or the runner's own defaults when that's empty. With the Jest backend,
+    /// Jest's own list of test files is used when it can be had.
+    pub fn discover(path: &Path, default_test_matches: &[String], backend: DiscoveryBackend) -> io::Result<Self> {
+        let Self { runner, config_files, test_matches, path_filters, mut projects, .. } = Self::detect(path, default_test_matches)?;
+        // A multi-project config's tests are whatever its projects match
+        let mut tests = if projects.is_empty() {
+            config_finder::find_matching_tests(&test_matches, &path_filters, path)?
+        } else {
+            config_finder::find_project_tests(&mut projects, path)?
+        };
+
+        let mut discovery_error = None;
+        if backend == DiscoveryBackend::Jest && runner == Runner::Jest {
+            let mut command = JestCommand::new(&path.display().to_string(), runner);
+            // Only pin the config when there was a choice to make
+            if config_files.len() > 1 {
+                command = command.arg("--config").arg(config_files[0].to_string_lossy());
+            }
+            match list_tests(command) {
+                Ok(listed) => tests = listed,
+                Err(e) => discovery_error = Some(e.to_string()),
+            }
+        }
+
+        Ok(Self {
+            runner,
+            config_files,
+            test_matches,
+            path_filters,
+            projects,
+            tests,
+            discovery_error,
+        })
+    }
+}
+
+/// Ask Jest for every test file it would run (`--listTests --json`).
+/// Returns the files relative to the command's directory, or absolute when
+/// they're outside it, sorted.
+pub fn list_tests(command: JestCommand) -> io::Result<Vec<String>> {
+    let command = command.arg("--listTests").arg("--json");
+    debug!(cwd = %command.cwd.display(), command = %command.display(), "Listing tests");
+    let output = processes::output(&mut command.to_command())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("{}: {}", command.display(), stderr.trim())));
+    }
+
+    // Anything the config prints comes before the JSON list
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = stdout.rfind("\n[").map_or(stdout.as_ref(), |start| &stdout[start + 1..]);
+    let files: Vec<PathBuf> = serde_json::from_str(json.trim())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("unexpected --listTests output: {}", e)))?;
+
+    let root = command.cwd.canonicalize().unwrap_or_else(|_| command.cwd.clone());
+    let mut tests: Vec<String> = files
+        .iter()
+        .map(|file| file.strip_prefix(&root).unwrap_or(file).display().to_string())
+        .collect();
+    tests.sort();
+    Ok(tests)
+}
@@ -0,0 +1,83 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use crate::runner::{command::JestCommand, processes};
+
+/// The file name up to its first dot, e.g. "Button" for "Button.test.tsx"
+fn base_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy())
+        .and_then(|name| name.split('.').next().map(str::to_string))
+        .filter(|name| !name.is_empty())
+}
+
+/// Whether the test content imports or requires a module with the given base name
+fn imports_module(content: &str, module: &str) -> bool {
+    ["'", "\""].iter().any(|quote| {
+        content.contains(&format!("/{}{}", module, quote))
+            || content.contains(&format!("/{}.", module))
+    })
+}
+
+/// Find test files affected by a set of changed files.
+///
+/// A test is affected when it was changed itself, when it shares a base name
+/// with a changed source file (`Button.tsx` -> `Button.test.tsx`), or when it
+/// imports a changed module by path.
+pub fn find_related_tests(changed: &[PathBuf], tests: &[String], search_path: &Path) -> Vec<String> {
+    let root = search_path.canonicalize().unwrap_or_else(|_| search_path.to_path_buf());
+    
+    let changed_bases: Vec<String> = changed.iter().filter_map(|path| base_name(path)).collect();
+    
+    tests
+        .iter()
+        .filter(|test_file| {
+            let full_path = root.join(test_file);
+            
+            // The test file itself changed
+            if changed.contains(&full_path) {
+                return true;
+            }
+            
+            // A source file with the same base name changed
+            if base_name(&full_path).is_some_and(|base| changed_bases.contains(&base)) {
+                return true;
+            }
+            
+            // The test imports one of the changed modules
+            fs::read_to_string(&full_path)
+                .map(|content| changed_bases.iter().any(|base| imports_module(&content, base)))
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Ask Jest which test files depend on the given source files, following its
+/// module graph (`--listTests --findRelatedTests`). Returns the test files
+/// relative to the command's directory.
+pub fn list_related_tests(command: JestCommand, sources: &[PathBuf]) -> io::Result<Vec<String>> {
+    let command = sources
+        .iter()
+        .fold(command.arg("--listTests").arg("--findRelatedTests"), |command, source| {
+            command.arg(source.to_string_lossy())
+        });
+    let output = processes::output(&mut command.to_command())?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("{}: {}", command.display(), stderr.trim())));
+    }
+    
+    let root = command.cwd.canonicalize().unwrap_or_else(|_| command.cwd.clone());
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            Path::new(line.trim())
+                .strip_prefix(&root)
+                .ok()
+                .map(|relative| relative.display().to_string())
+        })
+        .collect())
+}
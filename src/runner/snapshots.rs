@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+/// One snapshot in a `.snap` file, e.g. ``exports[`math adds 1`] = `...`;``
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotEntry {
+    /// The snapshot's key: the test's full name, any hint passed to
+    /// `toMatchSnapshot` after a colon, and a count
+    pub name: String,
+    /// 1-based line the entry starts on
+    pub line: usize,
+}
+
+impl SnapshotEntry {
+    /// Whether the entry was written by the test with the given full name
+    /// (describe names and the test name joined with spaces)
+    pub fn belongs_to(&self, full_name: &str) -> bool {
+        // Drop the count Jest appends to tell a test's snapshots apart
+        let name = match self.name.rsplit_once(' ') {
+            Some((name, count)) if !count.is_empty() && count.chars().all(|c| c.is_ascii_digit()) => name,
+            _ => self.name.as_str(),
+        };
+        name == full_name || name.strip_prefix(full_name).is_some_and(|rest| rest.starts_with(": "))
+    }
+}
+
+/// A snapshot file next to a test file, with the entries in it
+#[derive(Debug, Clone)]
+pub struct SnapshotFile {
+    pub path: PathBuf,
+    pub content: String,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+/// Finds the entries in the given snapshot file content, in file order
+pub fn parse_entries(content: &str) -> Vec<SnapshotEntry> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let key = line.strip_prefix("exports[`")?;
+            let end = key.find("`] = ")?;
+            Some(SnapshotEntry {
+                name: key[..end].replace("\\`", "`").replace("\\\\", "\\"),
+                line: index + 1,
+            })
+        })
+        .collect()
+}
+
+/// Finds the snapshot files written for a test file: the one Jest and Vitest
+/// write to `__snapshots__/<file name>.snap`, followed by any others there
+/// named after the same module (e.g. left behind when the test file was
+/// renamed from `.js` to `.ts`)
+pub fn find_snapshot_files(test_file: &Path) -> Vec<SnapshotFile> {
+    let (Some(dir), Some(file_name)) = (test_file.parent(), test_file.file_name().and_then(|name| name.to_str())) else {
+        return Vec::new();
+    };
+    let snapshot_dir = dir.join("__snapshots__");
+    let Ok(read_dir) = std::fs::read_dir(&snapshot_dir) else {
+        return Vec::new();
+    };
+
+    let own = format!("{}.snap", file_name);
+    let module = file_name.split('.').next().unwrap_or(file_name);
+    let mut paths: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name().and_then(|name| name.to_str()).is_some_and(|name| {
+                name.ends_with(".snap") && name.split('.').next() == Some(module)
+            })
+        })
+        .collect();
+    paths.sort_by_key(|path| (path.file_name().and_then(|name| name.to_str()) != Some(own.as_str()), path.clone()));
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            let entries = parse_entries(&content);
+            Some(SnapshotFile { path, content, entries })
+        })
+        .collect()
+}
+
+/// Rows of the snapshot browser: each file, followed by its entries, as
+/// indexes into the files and their entries
+pub fn rows(files: &[SnapshotFile]) -> Vec<(usize, Option<usize>)> {
+    files
+        .iter()
+        .enumerate()
+        .flat_map(|(file, snapshot)| {
+            std::iter::once((file, None)).chain((0..snapshot.entries.len()).map(move |entry| (file, Some(entry))))
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use crate::runner::ansi;
+
+/// A stack frame pointing into a file of the project
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackFrame {
+    /// Absolute path of the file
+    pub path: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column number
+    pub column: usize,
+    /// 0-based line of the searched text the frame was found on
+    pub output_line: usize,
+}
+
+/// Find the `file:line:column` locations in runner output that point at files
+/// inside the project (and outside `node_modules`), in order and without repeats.
+///
+/// Handles Node's `at fn (file:line:col)` and `at file:line:col` frames as well
+/// as Vitest's `❯ file:line:col`, with paths relative to the project or absolute.
+pub fn project_frames(output: &str, project_dir: &Path) -> Vec<StackFrame> {
+    let location_regex = Regex::new(r#"(?:file://)?([^\s()'"❯]+):(\d+):(\d+)"#).unwrap();
+    let root = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+    
+    let output = ansi::strip(output);
+    let mut frames: Vec<StackFrame> = Vec::new();
+    for (output_line, text) in output.lines().enumerate() {
+        for cap in location_regex.captures_iter(text) {
+            let file = Path::new(&cap[1]);
+            if file.components().any(|component| component.as_os_str() == "node_modules") {
+                continue;
+            }
+            
+            let Ok(path) = root.join(file).canonicalize() else {
+                continue;
+            };
+            if !path.starts_with(&root) || !path.is_file() {
+                continue;
+            }
+            
+            let line = cap[2].parse().unwrap_or(1);
+            let column = cap[3].parse().unwrap_or(1);
+            let seen = frames
+                .iter()
+                .any(|frame| frame.path == path && frame.line == line && frame.column == column);
+            if !seen {
+                frames.push(StackFrame { path, line, column, output_line });
+            }
+        }
+    }
+    
+    frames
+}
+
+/// Split a test's failure into its message and its stack trace, which starts
+/// at the first `at ...` (or Vitest `❯ ...`) frame. Colors are stripped from both.
+pub fn split_failure(failure: &str) -> (String, String) {
+    let failure = ansi::strip(failure);
+    let lines: Vec<&str> = failure.lines().collect();
+    let stack_start = lines
+        .iter()
+        .position(|line| {
+            let line = line.trim_start();
+            line.starts_with("at ") || line.starts_with("❯ ")
+        })
+        .unwrap_or(lines.len());
+    
+    let message = lines[..stack_start].join("\n").trim_end().to_string();
+    let stack = lines[stack_start..].iter().map(|line| line.trim()).collect::<Vec<_>>().join("\n");
+    (message, stack)
+}
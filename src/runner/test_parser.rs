@@ -0,0 +1,263 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// A modified test declaration, capturing the call, the modifier, and the
+/// test description
+static MODIFIER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"\b((?:describe|it|test)\.(only|skip|todo)|x(?:describe|it|test)|f(?:describe|it))\s*\(\s*(?:['"`](.+?)['"`])?"#
+    ).unwrap()
+});
+
+/// An `it`/`test` declaration, capturing the `x`/`f` prefix, the modifier, and
+/// the test description
+static TEST_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\b([xf])?(?:test|it)(?:\.(skip|todo|only))?\s*\(\s*['"`](.+?)['"`]"#).unwrap()
+});
+
+/// A `describe` declaration, capturing the `x`/`f` prefix, the modifier, and
+/// the describe description
+static DESCRIBE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\b([xf])?describe(?:\.(skip|only))?\s*\(\s*['"`](.+?)['"`]"#).unwrap()
+});
+
+/// The kind of modifier applied to a test declaration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModifierKind {
+    /// Focused test (`.only`, `fit`, `fdescribe`) - every other test in the file is skipped
+    Only,
+    /// Skipped test (`.skip`, `xit`, `xdescribe`, `xtest`)
+    Skip,
+    /// Placeholder test (`.todo`)
+    Todo,
+}
+
+impl ModifierKind {
+    /// Short label used when listing modifiers
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModifierKind::Only => "only",
+            ModifierKind::Skip => "skip",
+            ModifierKind::Todo => "todo",
+        }
+    }
+}
+
+/// A modified test declaration (e.g. `test.only`, `it.skip`) found in a test file
+#[derive(Debug, Clone)]
+pub struct TestModifier {
+    /// 1-based line number of the declaration
+    pub line: usize,
+    /// What the modifier does
+    pub kind: ModifierKind,
+    /// The call as written, e.g. "it.only"
+    pub declaration: String,
+    /// The test description, if it could be extracted
+    pub name: Option<String>,
+}
+
+/// Finds all `.only`, `.skip`, and `.todo` style declarations (and their `f`/`x`
+/// prefixed aliases) in the given file content.
+pub fn find_test_modifiers(content: &str) -> Vec<TestModifier> {
+    let mut modifiers = Vec::new();
+    let mut in_comment_block = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        // Skip anything inside comments
+        if line.starts_with("/*") {
+            in_comment_block = true;
+        }
+        if line.contains("*/") {
+            in_comment_block = false;
+            continue;
+        }
+        if in_comment_block || line.starts_with("//") {
+            continue;
+        }
+
+        for cap in MODIFIER_REGEX.captures_iter(line) {
+            let kind = match cap.get(2).map(|m| m.as_str()) {
+                Some("only") => ModifierKind::Only,
+                Some("todo") => ModifierKind::Todo,
+                None if cap[1].starts_with('f') => ModifierKind::Only,
+                _ => ModifierKind::Skip,
+            };
+
+            modifiers.push(TestModifier {
+                line: idx + 1,
+                kind,
+                declaration: cap[1].to_string(),
+                name: cap.get(3).map(|m| m.as_str().to_string()),
+            });
+        }
+    }
+
+    modifiers
+}
+
+/// Finds all focused test declarations in the given file content.
+///
+/// Focused tests silently skip every other test in the suite, so they are
+/// worth surfacing before the file is run or committed.
+pub fn find_focused_tests(content: &str) -> Vec<TestModifier> {
+    find_test_modifiers(content)
+        .into_iter()
+        .filter(|modifier| modifier.kind == ModifierKind::Only)
+        .collect()
+}
+
+/// Find the lines spanned by the test or describe block declared on the given
+/// 1-based line.
+///
+/// Returns 1-based `(start, end)` line numbers. The end of the block is found by
+/// balancing parentheses from the declaration, which is good enough for
+/// typical test files but not a full JavaScript parser.
+pub fn find_test_block(content: &str, line: usize) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = line.checked_sub(1).filter(|&start| start < lines.len())?;
+    
+    Some((line, block_end(&lines, start)))
+}
+
+/// 1-based line the block declared on the 0-based `start` line ends on, found
+/// by balancing parentheses. Unbalanced blocks run to the end of the file.
+fn block_end(lines: &[&str], start: usize) -> usize {
+    let mut depth = 0i32;
+    let mut opened = false;
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        for c in line.chars() {
+            match c {
+                '(' => {
+                    depth += 1;
+                    opened = true;
+                },
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        
+        if opened && depth <= 0 {
+            return idx + 1;
+        }
+    }
+    
+    lines.len()
+}
+
+/// Whether a block declares a group of tests or a single test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    /// A `describe` block
+    Describe,
+    /// An `it`/`test` block
+    Test,
+}
+
+/// A `describe`/`it`/`test` declaration and the lines its block spans
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestBlock {
+    /// What the block declares
+    pub kind: BlockKind,
+    /// Names of the enclosing describe blocks, outermost first
+    pub ancestors: Vec<String>,
+    /// The test or describe description
+    pub name: String,
+    /// Whether the block is focused, skipped, or a todo. Blocks inside a
+    /// skipped describe are skipped whatever their own modifier.
+    pub modifier: Option<ModifierKind>,
+    /// 1-based line the test is declared on
+    pub start: usize,
+    /// 1-based line its block ends on
+    pub end: usize,
+}
+
+impl TestBlock {
+    /// Describe names and the block's own name joined the way Jest reports them
+    pub fn full_name(&self) -> String {
+        self.ancestors
+            .iter()
+            .chain(std::iter::once(&self.name))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Finds every `describe`/`it`/`test` declaration in the given file content
+/// (including skipped, todo, and focused ones), in the order they're declared,
+/// with the describe blocks around each.
+pub fn find_test_blocks(content: &str) -> Vec<TestBlock> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut blocks = Vec::new();
+    
+    // Describe blocks enclosing the current line, with the parenthesis depth each
+    // was opened at and whether it skips its tests
+    let mut describes: Vec<(String, i32, bool)> = Vec::new();
+    let mut depth = 0i32;
+    let mut in_comment_block = false;
+    
+    for (idx, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        
+        // Skip anything inside comments
+        if line.starts_with("/*") {
+            in_comment_block = true;
+        }
+        if line.contains("*/") {
+            in_comment_block = false;
+            continue;
+        }
+        if in_comment_block || line.starts_with("//") {
+            continue;
+        }
+        
+        // Leave describe blocks that were closed on earlier lines
+        while describes.last().is_some_and(|(_, opened_at, _)| depth <= *opened_at) {
+            describes.pop();
+        }
+        
+        for cap in DESCRIBE_REGEX.captures_iter(line) {
+            let modifier = block_modifier(&cap, &describes);
+            blocks.push(TestBlock {
+                kind: BlockKind::Describe,
+                ancestors: describes.iter().map(|(name, _, _)| name.clone()).collect(),
+                name: cap[3].trim().to_string(),
+                modifier,
+                start: idx + 1,
+                end: block_end(&lines, idx),
+            });
+            describes.push((cap[3].trim().to_string(), depth, modifier == Some(ModifierKind::Skip)));
+        }
+        
+        for cap in TEST_REGEX.captures_iter(line) {
+            blocks.push(TestBlock {
+                kind: BlockKind::Test,
+                ancestors: describes.iter().map(|(name, _, _)| name.clone()).collect(),
+                name: cap[3].trim().to_string(),
+                modifier: block_modifier(&cap, &describes),
+                start: idx + 1,
+                end: block_end(&lines, idx),
+            });
+        }
+        
+        depth += line.matches('(').count() as i32 - line.matches(')').count() as i32;
+    }
+    
+    blocks
+}
+
+/// The modifier of a block matched by `TEST_REGEX` or `DESCRIBE_REGEX`, inside
+/// the given describe blocks
+fn block_modifier(cap: &regex::Captures, describes: &[(String, i32, bool)]) -> Option<ModifierKind> {
+    let prefix = cap.get(1).map(|m| m.as_str());
+    match cap.get(2).map(|m| m.as_str()) {
+        Some("todo") => Some(ModifierKind::Todo),
+        Some("skip") => Some(ModifierKind::Skip),
+        _ if prefix == Some("x") || describes.iter().any(|(_, _, skipped)| *skipped) => Some(ModifierKind::Skip),
+        Some("only") => Some(ModifierKind::Only),
+        _ if prefix == Some("f") => Some(ModifierKind::Only),
+        _ => None,
+    }
+}
@@ -0,0 +1,97 @@
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::thread;
+use tracing::{debug, error, info};
+use crate::runner::{command::JestCommand, processes};
+
+/// Runs a Jest command and returns the stdout and stderr output
+pub fn run_jest_test(command: &JestCommand) -> io::Result<(String, String)> {
+    // Execute the command from the project directory
+    debug!(cwd = %command.cwd.display(), command = %command.display(), "Running");
+    let output = processes::output(&mut command.to_command())?;
+    debug!(status = %output.status, "Run finished");
+
+    // Extract stdout and stderr
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    Ok((stdout, stderr))
+}
+
+/// Result of a test run
+pub enum TestResult {
+    /// Test is still running
+    Running,
+    /// A line of output from the running process, including its newline
+    OutputChunk(String),
+    /// Test has completed, with its complete stdout and stderr
+    Completed(io::Result<(String, String)>),
+}
+
+/// Forward each line read from a process stream as an output chunk,
+/// returning everything that was read once the stream closes
+fn stream_lines(stream: impl Read, tx: mpsc::Sender<TestResult>) -> String {
+    let mut reader = BufReader::new(stream);
+    let mut collected = String::new();
+    let mut line = Vec::new();
+
+    // Read raw bytes so invalid UTF-8 doesn't end the stream early
+    while let Ok(read) = reader.read_until(b'\n', &mut line) {
+        if read == 0 {
+            break;
+        }
+        let text = String::from_utf8_lossy(&line).to_string();
+        collected.push_str(&text);
+        let _ = tx.send(TestResult::OutputChunk(text));
+        line.clear();
+    }
+
+    collected
+}
+
+/// Spawn a command and stream its stdout and stderr line by line until it exits
+fn run_streaming(command: &JestCommand, tx: &mpsc::Sender<TestResult>) -> io::Result<(String, String)> {
+    info!(cwd = %command.cwd.display(), command = %command.display(), env = ?command.env, "Starting run");
+    let mut child = processes::spawn(command.to_command().stdout(Stdio::piped()).stderr(Stdio::piped()))
+        .inspect_err(|e| error!(program = %command.program, "Could not start run: {}", e))?;
+
+    // Read both streams at once so neither fills its pipe and blocks the process
+    let stdout = child.stdout.take().map(|stdout| {
+        let tx = tx.clone();
+        thread::spawn(move || stream_lines(stdout, tx))
+    });
+    let stderr = child.stderr.take().map(|stderr| {
+        let tx = tx.clone();
+        thread::spawn(move || stream_lines(stderr, tx))
+    });
+
+    let stdout = stdout.and_then(|handle| handle.join().ok()).unwrap_or_default();
+    let stderr = stderr.and_then(|handle| handle.join().ok()).unwrap_or_default();
+    let status = child.wait();
+    processes::finished(&child);
+    let status = status?;
+    info!(%status, pid = child.id(), "Run finished");
+
+    Ok((stdout, stderr))
+}
+
+/// Starts an async test run and returns a channel to receive updates
+pub fn start_async_test(command: JestCommand) -> mpsc::Receiver<TestResult> {
+    // Create a synchronous channel
+    let (tx, rx) = mpsc::channel();
+
+    // Spawn a standard thread to run the test in the background
+    thread::spawn(move || {
+        // Send a Running message right away
+        let _ = tx.send(TestResult::Running);
+
+        // Run the test, streaming its output as it arrives
+        let result = run_streaming(&command, &tx);
+
+        // Send the completed result
+        let _ = tx.send(TestResult::Completed(result));
+    });
+
+    rx
+}
@@ -0,0 +1,104 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::{fs, path::Path};
+use crate::runner::{backend::Runner, command::shell_split};
+
+/// Tools whose `test` command starts Jest with their own config, e.g.
+/// `react-scripts test` in Create React App projects
+const JEST_WRAPPERS: &[&str] = &["react-scripts", "craco", "react-app-rewired", "rescripts"];
+
+/// When tests are run through the package.json `test` script instead of
+/// invoking the runner directly
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestScriptMode {
+    /// When the script runs the runner in a way a direct invocation wouldn't,
+    /// e.g. `react-scripts test` or `jest --config custom.js`
+    #[default]
+    Auto,
+    /// Whenever there's a `test` script
+    Always,
+    /// Never
+    Never,
+}
+
+impl TestScriptMode {
+    /// Whether runs in the project directory should go through its `test` script
+    pub fn use_script(self, project_dir: &Path, runner: Runner) -> bool {
+        match self {
+            TestScriptMode::Auto => test_script(project_dir).is_some_and(|script| wraps_runner(&script, runner)),
+            TestScriptMode::Always => test_script(project_dir).is_some(),
+            TestScriptMode::Never => false,
+        }
+    }
+}
+
+/// The `test` script in a directory's package.json
+pub fn test_script(project_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(project_dir.join("package.json")).ok()?;
+    let package_json: Value = serde_json::from_str(&content).ok()?;
+    package_json.get("scripts")?.get("test")?.as_str().map(str::to_string)
+}
+
+/// The last command of a script chained with `&&`, `||`, `;`, or `|`, which
+/// is the one `npm test -- <args>` passes the arguments to
+fn last_command(script: &str) -> &str {
+    let mut start = 0;
+    let mut quote = None;
+    let mut chars = script.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        let separator = match (quote, c) {
+            (Some(open), c) => {
+                if c == open {
+                    quote = None;
+                }
+                false
+            },
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                false
+            },
+            (None, '\\') => {
+                chars.next();
+                false
+            },
+            (None, ';' | '|') => true,
+            (None, '&') => chars.peek().is_some_and(|&(_, next)| next == '&'),
+            _ => false,
+        };
+        if separator {
+            // `&&` and `||` take up two characters
+            chars.next_if(|&(_, next)| c != ';' && next == c);
+            start = chars.peek().map_or(script.len(), |&(next, _)| next);
+        }
+    }
+    script[start..].trim()
+}
+
+/// Whether a script starts the runner with something a direct invocation
+/// would miss: extra flags, environment, or a wrapper tool. A script that's
+/// just the runner (or doesn't run it at all, like npm's placeholder) doesn't.
+///
+/// Only the last command of a chained script counts, since that's where
+/// arguments go: `jest && eslint .` would pass the file and `--json` to
+/// eslint, so the runner is run directly instead.
+pub fn wraps_runner(script: &str, runner: Runner) -> bool {
+    let script = last_command(script);
+    let words = shell_split(script)
+        .unwrap_or_else(|_| script.split_whitespace().map(str::to_string).collect());
+    // `vitest` alone runs the same as `vitest run` once watch mode is off
+    let program_args = runner.program_args();
+    let direct = [program_args, &program_args[..1]];
+    if direct.iter().any(|args| words.iter().map(String::as_str).eq(args.iter().copied())) {
+        return false;
+    }
+    
+    let program = |word: &String| word.rsplit('/').next().unwrap_or(word).to_string();
+    words.iter().enumerate().any(|(index, word)| match program(word).as_str() {
+        "jest" => runner == Runner::Jest,
+        "vitest" => runner == Runner::Vitest,
+        wrapper => runner == Runner::Jest
+            && JEST_WRAPPERS.contains(&wrapper)
+            && words.get(index + 1).is_some_and(|next| next == "test"),
+    })
+}
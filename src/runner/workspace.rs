@@ -0,0 +1,172 @@
+use serde_json::Value;
+use std::{fs, path::{Path, PathBuf}};
+use crate::runner::backend::Runner;
+
+/// One package of a monorepo
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspacePackage {
+    /// The package's name from its package.json (or Nx project.json), or its directory
+    pub name: String,
+    /// The package directory, relative to the monorepo root
+    pub dir: PathBuf,
+    /// The package's own Jest (or Vitest) config, if it has one
+    pub config: Option<PathBuf>,
+}
+
+/// A monorepo's packages, found from its workspace configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monorepo {
+    /// What manages the workspaces, e.g. "pnpm workspaces" or "Nx"
+    pub tool: &'static str,
+    /// The monorepo root
+    pub root: PathBuf,
+    /// Packages sorted by directory
+    pub packages: Vec<WorkspacePackage>,
+}
+
+impl Monorepo {
+    /// Detect a monorepo rooted at a directory from package.json `workspaces`,
+    /// pnpm-workspace.yaml, or nx.json (Turborepo uses one of the first two).
+    /// Returns `None` when the directory doesn't declare any packages.
+    pub fn detect(root: &Path) -> Option<Self> {
+        let root = root.canonicalize().ok()?;
+
+        let mut patterns = package_json_workspaces(&root);
+        patterns.extend(pnpm_workspace_packages(&root));
+        patterns.extend(nx_project_dirs(&root));
+
+        let (include, exclude): (Vec<String>, Vec<String>) = patterns
+            .into_iter()
+            .partition(|pattern| !pattern.starts_with('!'));
+        let excluded: Vec<glob::Pattern> = exclude
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern.trim_start_matches('!').trim_start_matches("./")).ok())
+            .collect();
+
+        let mut packages: Vec<WorkspacePackage> = Vec::new();
+        for pattern in &include {
+            let full_pattern = root.join(pattern.trim_start_matches("./")).to_string_lossy().to_string();
+            let Ok(paths) = glob::glob(&full_pattern) else {
+                continue;
+            };
+            for dir in paths.flatten() {
+                let Ok(relative) = dir.strip_prefix(&root) else {
+                    continue;
+                };
+                let is_package = dir.join("package.json").is_file() || dir.join("project.json").is_file();
+                if !is_package
+                    || relative.as_os_str().is_empty()
+                    || relative.components().any(|component| component.as_os_str() == "node_modules")
+                    || excluded.iter().any(|exclude| exclude.matches_path(relative))
+                    || packages.iter().any(|package| package.dir == relative) {
+                    continue;
+                }
+                packages.push(WorkspacePackage {
+                    name: package_name(&dir).unwrap_or_else(|| relative.display().to_string()),
+                    dir: relative.to_path_buf(),
+                    config: Runner::detect(&dir)
+                        .find_config_files(&dir)
+                        .ok()
+                        .and_then(|configs| configs.into_iter().next()),
+                });
+            }
+        }
+
+        if packages.is_empty() {
+            return None;
+        }
+        packages.sort_by(|a, b| a.dir.cmp(&b.dir));
+
+        let tool = if root.join("turbo.json").is_file() {
+            "Turborepo"
+        } else if root.join("nx.json").is_file() {
+            "Nx"
+        } else if root.join("pnpm-workspace.yaml").is_file() {
+            "pnpm workspaces"
+        } else {
+            "workspaces"
+        };
+        Some(Self { tool, root, packages })
+    }
+}
+
+/// Read a JSON file, if it exists and parses
+fn read_json(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// The `name` in a directory's package.json, or in its project.json for Nx
+/// projects without one
+fn package_name(dir: &Path) -> Option<String> {
+    ["package.json", "project.json"]
+        .iter()
+        .find_map(|file| read_json(&dir.join(file))?.get("name")?.as_str().map(str::to_string))
+}
+
+/// The package.json `workspaces` globs, in either the array form or Yarn's
+/// `{ packages: [...] }` form
+fn package_json_workspaces(root: &Path) -> Vec<String> {
+    let Some(package_json) = read_json(&root.join("package.json")) else {
+        return Vec::new();
+    };
+    let workspaces = match package_json.get("workspaces") {
+        Some(Value::Object(fields)) => fields.get("packages"),
+        workspaces => workspaces,
+    };
+
+    workspaces
+        .and_then(Value::as_array)
+        .map(|patterns| patterns.iter().filter_map(|pattern| pattern.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// The `packages` globs in pnpm-workspace.yaml, read line by line since
+/// they're a plain list
+fn pnpm_workspace_packages(root: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(root.join("pnpm-workspace.yaml")) else {
+        return Vec::new();
+    };
+
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if in_packages && let Some(item) = trimmed.strip_prefix('-') {
+            let item = item.split(" #").next().unwrap_or_default().trim();
+            patterns.push(item.trim_matches(|c| c == '"' || c == '\'').to_string());
+        }
+    }
+    patterns
+}
+
+/// Where Nx projects live: the `workspaceLayout` directories in nx.json
+/// (`apps` and `libs` by default), plus `packages`
+fn nx_project_dirs(root: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(root.join("nx.json")) else {
+        return Vec::new();
+    };
+    let layout = serde_json::from_str::<Value>(&content)
+        .ok()
+        .and_then(|nx| nx.get("workspaceLayout").cloned());
+    let layout_dir = |key: &str, default: &str| {
+        layout
+            .as_ref()
+            .and_then(|layout| layout.get(key))
+            .and_then(Value::as_str)
+            .unwrap_or(default)
+            .to_string()
+    };
+
+    [layout_dir("appsDir", "apps"), layout_dir("libsDir", "libs"), "packages".to_string()]
+        .into_iter()
+        .map(|dir| format!("{}/*", dir))
+        .collect()
+}
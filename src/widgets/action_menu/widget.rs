@@ -0,0 +1,83 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use crate::app::actions::CustomAction;
+use crate::widgets::theme::Theme;
+
+/// Popup menu of the user's custom actions
+pub struct ActionMenuWidget<'a> {
+    /// Actions to choose from
+    pub actions: &'a [CustomAction],
+    /// Currently highlighted action
+    pub selected_index: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> ActionMenuWidget<'a> {
+    /// Create a new action menu widget
+    pub fn new(actions: &'a [CustomAction], selected_index: usize) -> Self {
+        Self {
+            actions,
+            selected_index,
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Area of the popup, centered in the given area and sized to the actions
+    fn popup_area(&self, area: Rect) -> Rect {
+        let width = area.width.saturating_sub(4).min(80);
+        let height = (self.actions.len() as u16 + 2).min(area.height);
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a> Widget for ActionMenuWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = self.popup_area(area);
+        
+        // Clear whatever is underneath the popup
+        Clear.render(popup, buf);
+        
+        let mut text = Text::default();
+        for (idx, action) in self.actions.iter().enumerate() {
+            let is_selected = idx == self.selected_index;
+            let selector = if is_selected { "▶ " } else { "  " };
+            
+            let style = if is_selected {
+                self.theme.selected()
+            } else {
+                Style::default()
+            };
+            
+            text.lines.push(Line::from(vec![
+                Span::styled(format!("{}{}", selector, action.name), style),
+                Span::styled(format!("  {}", action.command), Style::default().fg(self.theme.muted)),
+            ]));
+        }
+        
+        // Keep the selection in view when there are more actions than rows
+        let visible_rows = popup.height.saturating_sub(2) as usize;
+        let scroll = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+        
+        Paragraph::new(text)
+            .scroll((scroll as u16, 0))
+            .block(Block::default()
+                .title(" Actions (Enter: Run, Esc: Close) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.accent)))
+            .render(popup, buf);
+    }
+}
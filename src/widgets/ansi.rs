@@ -0,0 +1,105 @@
+use ratatui::prelude::*;
+use regex::Regex;
+use crate::runner::ansi::ESCAPE_PATTERN;
+
+/// Turn a line of output containing ANSI color codes into styled spans. Colors
+/// are assumed not to carry over from earlier lines, which chalk guarantees by
+/// closing styles at line breaks; other escape sequences are dropped.
+pub fn ansi_line(text: &str) -> Line<'static> {
+    let escape_regex = Regex::new(ESCAPE_PATTERN).unwrap();
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    let mut last_end = 0;
+    
+    for escape in escape_regex.find_iter(text) {
+        if escape.start() > last_end {
+            spans.push(Span::styled(text[last_end..escape.start()].to_string(), style));
+        }
+        if let Some(params) = escape.as_str().strip_prefix("\x1b[").and_then(|rest| rest.strip_suffix('m')) {
+            style = apply_sgr(style, params);
+        }
+        last_end = escape.end();
+    }
+    if last_end < text.len() {
+        spans.push(Span::styled(text[last_end..].to_string(), style));
+    }
+    
+    Line::from(spans)
+}
+
+/// Apply a Select Graphic Rendition sequence's parameters, e.g. "1;31", to a style
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    let codes: Vec<u16> = params.split(';').map(|code| code.parse().unwrap_or(0)).collect();
+    let mut codes = codes.iter().copied();
+    
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(basic_color(code - 30)),
+            90..=97 => style.fg(bright_color(code - 90)),
+            40..=47 => style.bg(basic_color(code - 40)),
+            100..=107 => style.bg(bright_color(code - 100)),
+            38 => match extended_color(&mut codes) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            48 => match extended_color(&mut codes) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            39 => style.fg(Color::Reset),
+            49 => style.bg(Color::Reset),
+            _ => style,
+        };
+    }
+    
+    style
+}
+
+/// The color of a `38;5;n` or `38;2;r;g;b` sequence, after the 38 (or 48)
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match codes.next()? {
+        5 => Some(Color::Indexed(codes.next()? as u8)),
+        2 => Some(Color::Rgb(codes.next()? as u8, codes.next()? as u8, codes.next()? as u8)),
+        _ => None,
+    }
+}
+
+/// One of the eight standard terminal colors
+fn basic_color(index: u16) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::Gray,
+    }
+}
+
+/// One of the eight bright terminal colors
+fn bright_color(index: u16) -> Color {
+    match index {
+        0 => Color::DarkGray,
+        1 => Color::LightRed,
+        2 => Color::LightGreen,
+        3 => Color::LightYellow,
+        4 => Color::LightBlue,
+        5 => Color::LightMagenta,
+        6 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
@@ -0,0 +1,103 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::app::state::AuditEntry;
+use crate::runner::test_parser::ModifierKind;
+use crate::widgets::theme::Theme;
+
+/// Widget listing every skipped, todo, and focused test across the project
+pub struct AuditWidget<'a> {
+    /// Audit entries to display
+    pub entries: &'a [AuditEntry],
+    /// Currently selected entry
+    pub selected_index: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> AuditWidget<'a> {
+    /// Create a new audit widget
+    pub fn new(entries: &'a [AuditEntry], selected_index: usize) -> Self {
+        Self {
+            entries,
+            selected_index,
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Color used for each modifier kind
+    fn kind_color(&self, kind: ModifierKind) -> Color {
+        match kind {
+            ModifierKind::Only => self.theme.warning,
+            ModifierKind::Skip => self.theme.highlight,
+            ModifierKind::Todo => self.theme.info,
+        }
+    }
+}
+
+impl<'a> Widget for AuditWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let count = |kind| self.entries.iter().filter(|e| e.modifier.kind == kind).count();
+        let title = format!(
+            "Test Audit ({} only, {} skip, {} todo)",
+            count(ModifierKind::Only),
+            count(ModifierKind::Skip),
+            count(ModifierKind::Todo)
+        );
+        
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL);
+        
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        
+        if self.entries.is_empty() {
+            Paragraph::new("No skipped, todo, or focused tests found. Nice and tidy!")
+                .render(inner_area, buf);
+            return;
+        }
+        
+        // Keep the selection in view
+        let visible_items = inner_area.height as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_items.saturating_sub(1));
+        
+        let mut text = Text::default();
+        
+        for (idx, entry) in self.entries.iter().enumerate().skip(scroll_offset).take(visible_items) {
+            let is_selected = idx == self.selected_index;
+            let selector = if is_selected { "▶ " } else { "  " };
+            let color = self.kind_color(entry.modifier.kind);
+            
+            let name = entry.modifier.name.as_deref().unwrap_or("");
+            let mut line = Line::from(vec![
+                Span::raw(selector),
+                Span::styled(
+                    format!("[{}]", entry.modifier.kind.label()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD)
+                ),
+                Span::raw(format!(" {}:{} ", entry.file, entry.modifier.line)),
+                Span::styled(
+                    format!("{} {}", entry.modifier.declaration, name),
+                    Style::default().fg(self.theme.muted)
+                ),
+            ]);
+            
+            if is_selected {
+                line = line.style(Style::default().bg(self.theme.selected_bg).add_modifier(Modifier::BOLD));
+            }
+            
+            text.lines.push(line);
+        }
+        
+        Paragraph::new(text)
+            .render(inner_area, buf);
+    }
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Gauge, Widget},
+};
+use crate::widgets::theme::Theme;
+
+/// Progress of a run over several files: how many have finished, and how
+/// many of those passed and failed
+pub struct BatchProgressWidget {
+    /// Files finished so far
+    pub done: usize,
+    /// Files in the run
+    pub total: usize,
+    /// Finished files whose tests all passed
+    pub passed: usize,
+    /// Finished files with failures, or that couldn't run
+    pub failed: usize,
+    /// How long the run has been going, already formatted
+    pub elapsed: String,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl BatchProgressWidget {
+    /// Create a new batch progress widget
+    pub fn new(done: usize, total: usize, passed: usize, failed: usize) -> Self {
+        Self {
+            done,
+            total,
+            passed,
+            failed,
+            elapsed: String::new(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Show how long the run has been going
+    pub fn elapsed(mut self, elapsed: String) -> Self {
+        self.elapsed = elapsed;
+        self
+    }
+}
+
+impl Widget for BatchProgressWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let ratio = match self.total {
+            0 => 0.0,
+            total => self.done.min(total) as f64 / total as f64,
+        };
+        // Red once anything has failed, so a bad run stands out at a glance
+        let color = if self.failed > 0 { self.theme.failed } else { self.theme.passed };
+
+        let mut label = format!(
+            "{} of {} files │ ✓ {} passed ✗ {} failed",
+            self.done.min(self.total),
+            self.total,
+            self.passed,
+            self.failed
+        );
+        if !self.elapsed.is_empty() {
+            label.push_str(&format!(" │ {}", self.elapsed));
+        }
+
+        Gauge::default()
+            .block(Block::default()
+                .title(" Progress ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.info)))
+            .gauge_style(Style::default().fg(color).bg(self.theme.dim))
+            .ratio(ratio)
+            .label(Span::styled(label, Style::default().fg(self.theme.selected_fg).add_modifier(Modifier::BOLD)))
+            .render(area, buf);
+    }
+}
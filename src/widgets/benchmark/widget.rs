@@ -0,0 +1,161 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Row, Table, Widget},
+};
+use crate::app::{
+    benchmark::{Benchmark, RunOutcome, Side},
+    format::FormatOptions,
+};
+use crate::widgets::theme::Theme;
+
+/// Widget comparing test file durations between a baseline and the working tree
+pub struct BenchmarkWidget<'a> {
+    /// The benchmark to show
+    pub benchmark: &'a Benchmark,
+    /// Currently selected file
+    pub selected_index: usize,
+    /// How durations are formatted
+    pub format: FormatOptions,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> BenchmarkWidget<'a> {
+    /// Create a new benchmark widget
+    pub fn new(benchmark: &'a Benchmark, selected_index: usize) -> Self {
+        Self {
+            benchmark,
+            selected_index,
+            format: FormatOptions::default(),
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Format durations using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+    
+    /// A run's duration, marked if the run failed
+    fn outcome_cell(&self, outcome: Option<RunOutcome>, running: bool) -> String {
+        match outcome {
+            Some(outcome) if outcome.passed => self.format.duration(outcome.duration_ms),
+            Some(outcome) => format!("{} ✗", self.format.duration(outcome.duration_ms)),
+            None if running => "running…".to_string(),
+            None => "-".to_string(),
+        }
+    }
+    
+    /// A change in duration with its sign and percentage
+    fn delta_cell(&self, baseline_ms: u64, delta_ms: i64) -> String {
+        let sign = if delta_ms > 0 { "+" } else if delta_ms < 0 { "-" } else { "±" };
+        let percent = if baseline_ms > 0 {
+            format!(" ({}{:.0}%)", sign, (delta_ms.unsigned_abs() as f64 / baseline_ms as f64) * 100.0)
+        } else {
+            String::new()
+        };
+        format!("{}{}{}", sign, self.format.duration(delta_ms.unsigned_abs()), percent)
+    }
+    
+    fn delta_style(&self, delta_ms: i64) -> Style {
+        if delta_ms > 0 {
+            Style::default().fg(self.theme.failed)
+        } else if delta_ms < 0 {
+            Style::default().fg(self.theme.passed)
+        } else {
+            Style::default()
+        }
+    }
+}
+
+impl<'a> Widget for BenchmarkWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let benchmark = self.benchmark;
+        let block = Block::default()
+            .title(format!("Benchmark: {} vs working tree", benchmark.baseline))
+            .borders(Borders::ALL);
+        
+        if let Some(error) = &benchmark.error {
+            Paragraph::new(format!("Benchmark failed: {}", error))
+                .style(Style::default().fg(self.theme.failed))
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+        
+        // Keep the selection in view (subtract borders, header, and total rows)
+        let visible_rows = area.height.saturating_sub(4) as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+        
+        let header = Row::new(vec!["File", "Baseline", "Current", "Delta"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        
+        let mut rows: Vec<Row> = benchmark.entries
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, entry)| {
+                let running = |side| benchmark.running == Some((idx, side));
+                let delta = entry.delta_ms();
+                
+                let style = delta.map(|delta| self.delta_style(delta)).unwrap_or_default();
+                let style = if idx == self.selected_index {
+                    style.bg(self.theme.selected_bg).add_modifier(Modifier::BOLD)
+                } else {
+                    style
+                };
+                
+                Row::new(vec![
+                    entry.file.clone(),
+                    self.outcome_cell(entry.baseline, running(Side::Baseline)),
+                    self.outcome_cell(entry.current, running(Side::Current)),
+                    match (entry.baseline, delta) {
+                        (Some(baseline), Some(delta)) => self.delta_cell(baseline.duration_ms, delta),
+                        _ => String::new(),
+                    },
+                ])
+                .style(style)
+            })
+            .collect();
+        
+        // Totals over files that have run on both sides
+        let (baseline_total, current_total) = benchmark.totals();
+        let total_delta = current_total as i64 - baseline_total as i64;
+        let (done, total) = benchmark.progress();
+        let label = if benchmark.finished {
+            "Total".to_string()
+        } else {
+            format!("Total so far ({}/{} runs)", done, total)
+        };
+        rows.push(
+            Row::new(vec![
+                label,
+                self.format.duration(baseline_total),
+                self.format.duration(current_total),
+                self.delta_cell(baseline_total, total_delta),
+            ])
+            .style(self.delta_style(total_delta).add_modifier(Modifier::BOLD))
+        );
+        
+        let widths = [
+            Constraint::Min(30),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(20),
+        ];
+        
+        Widget::render(
+            Table::new(rows, widths).header(header).block(block),
+            area,
+            buf,
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Widget},
+};
+use crate::app::command_line::CommandLine;
+use crate::widgets::theme::Theme;
+
+/// The `:` command line, shown in place of the help bar while it's open
+pub struct CommandLineWidget<'a> {
+    /// The command line being edited
+    pub line: &'a CommandLine,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> CommandLineWidget<'a> {
+    /// Create a new command line widget
+    pub fn new(line: &'a CommandLine) -> Self {
+        Self { line, theme: Theme::default() }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for CommandLineWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![
+            Span::styled(":", self.theme.key()),
+            Span::raw(self.line.input.clone()),
+            Span::styled("█", Style::default().fg(self.theme.accent)),
+        ];
+        
+        // List the candidates being cycled through with Tab
+        if self.line.is_completing() {
+            spans.push(Span::raw("   "));
+            for (i, completion) in self.line.completions.iter().enumerate() {
+                let candidate = completion.rsplit(' ').next().unwrap_or(completion);
+                let style = if i == self.line.completion_index {
+                    self.theme.badge()
+                } else {
+                    Style::default().fg(self.theme.muted)
+                };
+                spans.push(Span::styled(candidate.to_string(), style));
+                spans.push(Span::raw(" "));
+            }
+        }
+        
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+}
@@ -0,0 +1,2 @@
+mod widget;
+pub use widget::ConfigInspectorWidget;
\ No newline at end of file
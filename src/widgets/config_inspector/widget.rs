@@ -0,0 +1,115 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+use crate::app::inspector::ConfigReport;
+use crate::widgets::theme::Theme;
+
+/// Widget showing the resolved configuration used for discovery and runs
+pub struct ConfigInspectorWidget<'a> {
+    /// The resolved configuration
+    pub report: &'a ConfigReport,
+    /// Scroll position in the report
+    pub scroll: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> ConfigInspectorWidget<'a> {
+    /// Create a new config inspector widget
+    pub fn new(report: &'a ConfigReport, scroll: usize) -> Self {
+        Self { report, scroll, theme: Theme::default() }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// A bold section heading
+    fn heading(&self, text: &str) -> Line<'static> {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default().fg(self.theme.info).add_modifier(Modifier::BOLD)
+        ))
+    }
+}
+
+impl<'a> Widget for ConfigInspectorWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let report = self.report;
+        let mut text = Text::default();
+        
+        // Config file
+        text.lines.push(self.heading("Config file"));
+        match &report.config_path {
+            Some(path) => text.lines.push(Line::from(format!("  {}", path.display()))),
+            None => text.lines.push(Line::from("  (none found - using Jest defaults)")),
+        }
+        for other in report.config_files.iter().filter(|c| Some(*c) != report.config_path.as_ref()) {
+            text.lines.push(Line::from(Span::styled(
+                format!("  also found: {}", other.display()),
+                Style::default().fg(self.theme.muted)
+            )));
+        }
+        text.lines.push(Line::from(""));
+        
+        // Root
+        text.lines.push(self.heading("Root directory"));
+        text.lines.push(Line::from(format!("  {}", report.root)));
+        if let Some(root_dir) = &report.root_dir {
+            text.lines.push(Line::from(Span::styled(
+                format!("  <rootDir>: {}", root_dir.display()),
+                Style::default().fg(self.theme.muted)
+            )));
+        }
+        for root in &report.roots {
+            text.lines.push(Line::from(Span::styled(
+                format!("  root: {}", root.display()),
+                Style::default().fg(self.theme.muted)
+            )));
+        }
+        text.lines.push(Line::from(""));
+        
+        // Patterns and their match counts
+        let option = if report.uses_test_regex { "testRegex" } else { "testMatch" };
+        text.lines.push(self.heading(&format!("{} ({} files total)", option, report.total_files)));
+        for diagnostic in &report.patterns {
+            let style = if diagnostic.matched == 0 {
+                Style::default().fg(self.theme.warning)
+            } else {
+                Style::default()
+            };
+            let ignored = if diagnostic.ignored > 0 {
+                format!(" ({} ignored)", diagnostic.ignored)
+            } else {
+                String::new()
+            };
+            text.lines.push(Line::from(Span::styled(
+                format!("  {:>5}  {}{}", diagnostic.matched, diagnostic.pattern, ignored),
+                style
+            )));
+        }
+        text.lines.push(Line::from(""));
+        
+        // Ignored paths
+        text.lines.push(self.heading("Ignored"));
+        for pattern in &report.ignore_patterns {
+            text.lines.push(Line::from(format!("  {}", pattern)));
+        }
+        text.lines.push(Line::from(""));
+        
+        // Command
+        text.lines.push(self.heading(&format!("Run command ({} via {})", report.runner, report.package_manager)));
+        text.lines.push(Line::from(format!("  {}", report.command_template)));
+        
+        Paragraph::new(text)
+            .block(Block::default()
+                .title("Resolved Configuration")
+                .borders(Borders::ALL))
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll as u16, 0))
+            .render(area, buf);
+    }
+}
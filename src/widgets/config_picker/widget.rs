@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::widgets::theme::Theme;
+
+/// Widget for choosing which Jest config file to use
+pub struct ConfigPickerWidget<'a> {
+    /// Config files to choose from
+    pub configs: &'a [PathBuf],
+    /// Currently highlighted config
+    pub selected_index: usize,
+    /// Config currently in use, if any
+    pub active: Option<&'a PathBuf>,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> ConfigPickerWidget<'a> {
+    /// Create a new config picker widget
+    pub fn new(configs: &'a [PathBuf], selected_index: usize, active: Option<&'a PathBuf>) -> Self {
+        Self {
+            configs,
+            selected_index,
+            active,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for ConfigPickerWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("Choose a Jest Config")
+            .borders(Borders::ALL);
+        
+        let mut text = Text::default();
+        
+        for (idx, config) in self.configs.iter().enumerate() {
+            let is_selected = idx == self.selected_index;
+            let selector = if is_selected { "▶ " } else { "  " };
+            let active_marker = if self.active == Some(config) { " (active)" } else { "" };
+            
+            let line_text = format!("{}{}{}", selector, config.display(), active_marker);
+            let style = if is_selected {
+                self.theme.selected()
+            } else {
+                Style::default()
+            };
+            
+            text.lines.push(Line::from(Span::styled(line_text, style)));
+        }
+        
+        Paragraph::new(text)
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,78 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+use crate::widgets::theme::Theme;
+
+/// Popup asking whether to quit while runs are still going
+pub struct ConfirmQuitWidget<'a> {
+    /// How many runs are still going
+    pub runs: usize,
+    /// What the run is running, when there's only one
+    pub label: Option<&'a str>,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> ConfirmQuitWidget<'a> {
+    /// Create a new quit confirmation widget
+    pub fn new(runs: usize, label: Option<&'a str>) -> Self {
+        Self { runs, label, theme: Theme::default() }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Area of the popup, centered in the given area
+    fn popup_area(area: Rect) -> Rect {
+        let width = area.width.saturating_sub(4).min(64);
+        let height = area.height.min(9);
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+
+    /// A key and what it does
+    fn choice(&self, key: &str, description: &str) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("  {:<6}", key), self.theme.key()),
+            Span::raw(description.to_string()),
+        ])
+    }
+}
+
+impl<'a> Widget for ConfirmQuitWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = Self::popup_area(area);
+
+        // Clear whatever is underneath the popup
+        Clear.render(popup, buf);
+
+        let question = match (self.runs, self.label) {
+            (1, Some(label)) => format!("{} is still running - quit and stop it?", label),
+            (1, None) => "A test is still running - quit and stop it?".to_string(),
+            (runs, _) => format!("{} runs are still going - quit and stop them?", runs),
+        };
+        let text = Text::from(vec![
+            Line::from(question),
+            Line::from(""),
+            self.choice("y", "Quit and stop the runs"),
+            self.choice("d", "Quit, and let the runs finish without the UI"),
+            self.choice("n/Esc", "Keep going"),
+        ]);
+
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(Block::default()
+                .title(" Quit? ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.warning)))
+            .render(popup, buf);
+    }
+}
@@ -0,0 +1,118 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Row, Table, Widget},
+};
+use crate::runner::coverage::{CoverageSummary, FileCoverage, Metric};
+use crate::widgets::theme::Theme;
+
+/// Coverage at or above this percentage is shown in green
+const GOOD_COVERAGE: f64 = 80.0;
+/// Coverage below this percentage is shown in red, and in yellow above it
+const POOR_COVERAGE: f64 = 50.0;
+
+/// Widget showing per-file coverage from the last coverage run
+pub struct CoverageWidget<'a> {
+    /// The coverage being shown
+    pub summary: &'a CoverageSummary,
+    /// Currently selected file
+    pub selected_index: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> CoverageWidget<'a> {
+    /// Create a new coverage widget
+    pub fn new(summary: &'a CoverageSummary, selected_index: usize) -> Self {
+        Self {
+            summary,
+            selected_index,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+/// A percentage cell colored by how good the coverage is
+fn metric_cell(metric: &Metric, theme: &Theme) -> Cell<'static> {
+    match metric.pct {
+        Some(pct) => {
+            let color = if pct >= GOOD_COVERAGE {
+                theme.passed
+            } else if pct >= POOR_COVERAGE {
+                theme.warning
+            } else {
+                theme.failed
+            };
+            Cell::from(format!("{:.1}%", pct)).style(Style::default().fg(color))
+        },
+        None => Cell::from("–").style(Style::default().fg(theme.dim)),
+    }
+}
+
+/// Cells for each kind of coverage of a file
+fn metric_cells(coverage: &FileCoverage, theme: &Theme) -> [Cell<'static>; 4] {
+    [
+        metric_cell(&coverage.statements, theme),
+        metric_cell(&coverage.branches, theme),
+        metric_cell(&coverage.functions, theme),
+        metric_cell(&coverage.lines, theme),
+    ]
+}
+
+impl<'a> Widget for CoverageWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(format!(
+                "Coverage: {} of {} lines covered in {} files",
+                self.summary.total.lines.covered,
+                self.summary.total.lines.total,
+                self.summary.files.len()
+            ))
+            .borders(Borders::ALL);
+
+        // Keep the selection in view (subtract borders, header, and total rows)
+        let visible_rows = area.height.saturating_sub(4) as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+
+        let header = Row::new(vec!["File", "Stmts", "Branch", "Funcs", "Lines"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let total = Row::new(
+            std::iter::once(Cell::from("All files")).chain(metric_cells(&self.summary.total, &self.theme))
+        )
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let files = self.summary.files
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, (file, coverage))| {
+                let row = Row::new(std::iter::once(Cell::from(file.clone())).chain(metric_cells(coverage, &self.theme)));
+                if idx == self.selected_index {
+                    row.style(Style::default().bg(self.theme.selected_bg).add_modifier(Modifier::BOLD))
+                } else {
+                    row
+                }
+            });
+
+        let widths = [
+            Constraint::Min(30),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ];
+
+        Widget::render(
+            Table::new(std::iter::once(total).chain(files), widths).header(header).block(block),
+            area,
+            buf,
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Widget},
+};
+use crate::app::coverage_history::{CoverageDelta, FileDelta};
+use crate::runner::coverage::FileCoverage;
+use crate::widgets::theme::Theme;
+
+/// Widget showing how each file's line coverage changed since a base ref
+pub struct CoverageDiffWidget<'a> {
+    /// The comparison being shown
+    pub delta: &'a CoverageDelta,
+    /// Currently selected file
+    pub selected_index: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> CoverageDiffWidget<'a> {
+    /// Create a new coverage diff widget
+    pub fn new(delta: &'a CoverageDelta, selected_index: usize) -> Self {
+        Self {
+            delta,
+            selected_index,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+/// A side's line coverage, e.g. "82.5% (33/40)"
+fn lines_cell(coverage: Option<&FileCoverage>, theme: &Theme) -> Cell<'static> {
+    match coverage {
+        Some(coverage) => {
+            let pct = coverage.lines.pct.map_or("–".to_string(), |pct| format!("{:.1}%", pct));
+            Cell::from(format!("{} ({}/{})", pct, coverage.lines.covered, coverage.lines.total))
+        },
+        None => Cell::from("–").style(Style::default().fg(theme.dim)),
+    }
+}
+
+/// The change in line coverage, red when it dropped and green when it rose
+fn delta_cell(delta: Option<f64>, theme: &Theme) -> Cell<'static> {
+    match delta {
+        Some(delta) if delta < 0.0 => Cell::from(format!("{:.1}", delta)).style(Style::default().fg(theme.failed)),
+        Some(delta) => Cell::from(format!("+{:.1}", delta)).style(Style::default().fg(theme.passed)),
+        None => Cell::from("–").style(Style::default().fg(theme.dim)),
+    }
+}
+
+/// Cells for a file's coverage on both sides and the change between them
+fn file_cells(file: &FileDelta, theme: &Theme) -> [Cell<'static>; 3] {
+    let change = match (file.base, file.current) {
+        (None, Some(_)) => Cell::from("new").style(Style::default().fg(theme.muted)),
+        (Some(_), None) => Cell::from("removed").style(Style::default().fg(theme.muted)),
+        _ => delta_cell(file.delta(), theme),
+    };
+    [lines_cell(file.base.as_ref(), theme), lines_cell(file.current.as_ref(), theme), change]
+}
+
+impl<'a> Widget for CoverageDiffWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(format!(
+                "Coverage vs {}: {} files changed, {} lost coverage",
+                self.delta.base_ref,
+                self.delta.files.len(),
+                self.delta.regression_count()
+            ))
+            .borders(Borders::ALL);
+
+        if self.delta.files.is_empty() {
+            Paragraph::new("No file's line coverage changed.")
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        // Keep the selection in view (subtract borders, header, and total rows)
+        let visible_rows = area.height.saturating_sub(4) as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+
+        let header = Row::new(vec!["File", "Base lines", "Current lines", "Change"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let total = Row::new(vec![
+            Cell::from("All files"),
+            lines_cell(Some(&self.delta.base_total), &self.theme),
+            lines_cell(Some(&self.delta.current_total), &self.theme),
+            delta_cell(self.delta.total_delta(), &self.theme),
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let files = self.delta.files
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, file)| {
+                let row = Row::new(std::iter::once(Cell::from(file.file.clone())).chain(file_cells(file, &self.theme)));
+                if idx == self.selected_index {
+                    row.style(Style::default().bg(self.theme.selected_bg).add_modifier(Modifier::BOLD))
+                } else {
+                    row
+                }
+            });
+
+        let widths = [
+            Constraint::Min(30),
+            Constraint::Length(20),
+            Constraint::Length(20),
+            Constraint::Length(8),
+        ];
+
+        Widget::render(
+            Table::new(std::iter::once(total).chain(files), widths).header(header).block(block),
+            area,
+            buf,
+        );
+    }
+}
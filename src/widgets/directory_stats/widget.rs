@@ -0,0 +1,114 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Row, Table, Widget},
+};
+use crate::app::{format::FormatOptions, stats::DirectoryStats};
+use crate::widgets::theme::Theme;
+
+/// Widget showing a per-directory health map of the test suite
+pub struct DirectoryStatsWidget<'a> {
+    /// Stats for each directory
+    pub stats: &'a [DirectoryStats],
+    /// Currently selected directory
+    pub selected_index: usize,
+    /// How durations are formatted
+    pub format: FormatOptions,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> DirectoryStatsWidget<'a> {
+    /// Create a new directory stats widget
+    pub fn new(stats: &'a [DirectoryStats], selected_index: usize) -> Self {
+        Self {
+            stats,
+            selected_index,
+            format: FormatOptions::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Format durations and timestamps using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+}
+
+impl<'a> Widget for DirectoryStatsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("Directory Stats")
+            .borders(Borders::ALL);
+        
+        if self.stats.is_empty() {
+            Paragraph::new("No test files found.")
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+        
+        // Keep the selection in view (subtract borders and header row)
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+        
+        let header = Row::new(vec!["Directory", "Files", "Run", "✓ Files", "✗ Files", "✓ Tests", "✗ Tests", "Duration"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        
+        let rows = self.stats
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, stats)| {
+                let style = if stats.files_failed > 0 {
+                    Style::default().fg(self.theme.failed)
+                } else if stats.files_run == 0 {
+                    Style::default().fg(self.theme.muted)
+                } else {
+                    Style::default().fg(self.theme.passed)
+                };
+                
+                let style = if idx == self.selected_index {
+                    style.bg(self.theme.selected_bg).add_modifier(Modifier::BOLD)
+                } else {
+                    style
+                };
+                
+                Row::new(vec![
+                    stats.directory.clone(),
+                    stats.files.to_string(),
+                    stats.files_run.to_string(),
+                    stats.files_passed.to_string(),
+                    stats.files_failed.to_string(),
+                    stats.tests_passed.to_string(),
+                    stats.tests_failed.to_string(),
+                    self.format.duration(stats.total_duration_ms),
+                ])
+                .style(style)
+            });
+        
+        let widths = [
+            Constraint::Min(20),
+            Constraint::Length(6),
+            Constraint::Length(5),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(10),
+        ];
+        
+        Widget::render(
+            Table::new(rows, widths).header(header).block(block),
+            area,
+            buf,
+        );
+    }
+}
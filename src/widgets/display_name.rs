@@ -0,0 +1,15 @@
+use ratatui::prelude::*;
+use crate::runner::config_finder::DisplayName;
+
+/// Render a project's displayName as an inverse label, like Jest does
+pub fn display_name_span(display_name: &DisplayName) -> Span<'static> {
+    let color = display_name.color
+        .as_deref()
+        .and_then(|color| color.parse::<Color>().ok())
+        .unwrap_or(Color::White);
+    
+    Span::styled(
+        format!(" {} ", display_name.name),
+        Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD),
+    )
+}
@@ -0,0 +1,82 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+use crate::runner::command::JestCommand;
+use crate::widgets::theme::Theme;
+
+/// Popup showing the exact command an action would run, without running it
+pub struct DryRunWidget<'a> {
+    /// The command that would be run
+    pub command: &'a JestCommand,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> DryRunWidget<'a> {
+    /// Create a new dry-run widget
+    pub fn new(command: &'a JestCommand) -> Self {
+        Self { command, theme: Theme::default() }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Area of the popup, centered in the given area
+    fn popup_area(area: Rect) -> Rect {
+        let width = area.width.saturating_sub(4).min(100);
+        let height = area.height.min(14);
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+
+    fn label(&self, text: &str) -> Line<'static> {
+        Line::from(Span::styled(text.to_string(), Style::default().fg(self.theme.info).add_modifier(Modifier::BOLD)))
+    }
+}
+
+impl<'a> Widget for DryRunWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = Self::popup_area(area);
+
+        // Clear whatever is underneath the popup
+        Clear.render(popup, buf);
+
+        let mut text = Text::default();
+
+        text.lines.push(self.label("Directory"));
+        text.lines.push(Line::from(format!("  {}", self.command.cwd.display())));
+
+        text.lines.push(self.label("Environment"));
+        if self.command.env.is_empty() {
+            text.lines.push(Line::from(Span::styled("  (inherited, nothing added)", Style::default().fg(self.theme.muted))));
+        }
+        for (key, value) in &self.command.env {
+            text.lines.push(Line::from(format!("  {}={}", key, value)));
+        }
+
+        text.lines.push(self.label("Command"));
+        text.lines.push(Line::from(format!("  {}", self.command.display())));
+        text.lines.push(Line::from(""));
+
+        text.lines.push(Line::from(Span::styled(
+            "y: copy to clipboard, any other key: close",
+            Style::default().fg(self.theme.muted)
+        )));
+
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(Block::default()
+                .title(" Dry run - nothing was executed ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.accent)))
+            .render(popup, buf);
+    }
+}
@@ -0,0 +1,112 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+use crate::app::failure_groups::FailureGroups;
+use crate::widgets::theme::Theme;
+
+/// Widget listing a run's failures grouped by error message, with the
+/// selected group's message and failures below
+pub struct FailureGroupsWidget<'a> {
+    /// Groups to list
+    pub groups: &'a FailureGroups,
+    /// Currently selected group
+    pub selected_index: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> FailureGroupsWidget<'a> {
+    /// Create a new failure groups widget
+    pub fn new(groups: &'a FailureGroups, selected_index: usize) -> Self {
+        Self {
+            groups,
+            selected_index,
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for FailureGroupsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(format!(
+                "Failure Groups ({} failures, {} distinct errors)",
+                self.groups.failure_count(),
+                self.groups.groups.len()
+            ))
+            .borders(Borders::ALL);
+        
+        if self.groups.groups.is_empty() {
+            Paragraph::new("No failures in this run.")
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+        
+        let [list_area, details_area] = Layout::vertical([
+            Constraint::Percentage(40),
+            Constraint::Percentage(60),
+        ]).areas(area);
+        
+        // Keep the selection in view
+        let inner = block.inner(list_area);
+        block.render(list_area, buf);
+        let visible_rows = inner.height as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+        
+        let lines: Vec<Line> = self.groups.groups
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, group)| {
+                let first_line = group.message.lines().find(|line| !line.trim().is_empty()).unwrap_or("(no message)");
+                let line = Line::from(vec![
+                    Span::styled(format!("{:>4} ", group.tests.len()), Style::default().fg(self.theme.failed)),
+                    Span::raw(first_line.trim().to_string()),
+                ]);
+                if idx == self.selected_index {
+                    line.style(Style::default().bg(self.theme.selected_bg).add_modifier(Modifier::BOLD))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+        
+        // The selected group's full message and where it failed
+        let Some(group) = self.groups.groups.get(self.selected_index) else {
+            return;
+        };
+        let mut text = Text::default();
+        for line in group.message.lines() {
+            text.lines.push(Line::from(Span::styled(line.to_string(), Style::default().fg(self.theme.warning))));
+        }
+        text.lines.push(Line::from(""));
+        for test in &group.tests {
+            let location = match test.line {
+                Some(line) => format!("{}:{}", test.file, line),
+                None => test.file.clone(),
+            };
+            text.lines.push(Line::from(vec![
+                Span::styled("✗ ", Style::default().fg(self.theme.failed)),
+                Span::raw(test.test.clone().unwrap_or_else(|| "Test file failed to run".to_string())),
+                Span::styled(format!("  {}", location), Style::default().fg(self.theme.muted)),
+            ]));
+        }
+        
+        Paragraph::new(text)
+            .block(Block::default()
+                .title(format!(" {} failures with this error ", group.tests.len()))
+                .borders(Borders::ALL))
+            .wrap(Wrap { trim: false })
+            .render(details_area, buf);
+    }
+}
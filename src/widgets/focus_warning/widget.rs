@@ -0,0 +1,54 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::runner::test_parser::TestModifier;
+use crate::widgets::theme::Theme;
+
+/// Widget for warning that the current file contains focused (`.only`, `fit`, `fdescribe`) tests
+pub struct FocusWarningWidget<'a> {
+    /// Focused declarations found in the file
+    pub focused: &'a [TestModifier],
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> FocusWarningWidget<'a> {
+    /// Create a new focus warning widget
+    pub fn new(focused: &'a [TestModifier]) -> Self {
+        Self { focused, theme: Theme::default() }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for FocusWarningWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let locations = self.focused
+            .iter()
+            .map(|f| format!("{} (line {})", f.declaration, f.line))
+            .collect::<Vec<_>>()
+            .join(", ");
+        
+        let line = Line::from(vec![
+            Span::styled(
+                " ⚠ FOCUSED TESTS ",
+                Style::default().fg(self.theme.badge_fg).bg(self.theme.warning).add_modifier(Modifier::BOLD)
+            ),
+            Span::styled(
+                format!(" {} — all other tests in this file will be skipped", locations),
+                Style::default().fg(self.theme.warning)
+            ),
+        ]);
+        
+        Paragraph::new(line)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.warning)))
+            .render(area, buf);
+    }
+}
@@ -1,19 +1,102 @@
 use ratatui::prelude::*;
 use ratatui::widgets::{Widget, Paragraph};
+use crate::runner::config_finder::DisplayName;
+use crate::widgets::{display_name::display_name_span, sparkline::sparkline_span, spinner::{SpinnerStyle, SpinnerWidget}, theme::Theme};
 
-pub struct HeaderWidget {
+pub struct HeaderWidget<'a> {
     pub title: String,
     pub subtitle: String,
+    /// Project label for the file being shown, if any
+    pub display_name: Option<&'a DisplayName>,
+    /// Recent run durations of the file being shown, oldest first, and the
+    /// latest one formatted
+    pub trend: Option<(Vec<u64>, String)>,
+    /// Warning shown on the line below the title, if any
+    pub warning: Option<String>,
+    /// Register of the macro being recorded, if any
+    pub recording: Option<char>,
+    /// Whether watch mode is re-running tests on file changes
+    pub watching: bool,
+    /// Whether runs collect coverage
+    pub coverage: bool,
+    /// Label of a run going on while its output isn't shown, and how many
+    /// other runs are going
+    pub running: Option<(String, usize)>,
+    /// How many test files discovery has found so far, while it's going
+    pub scanning: Option<usize>,
+    /// Colors to draw with
+    pub theme: Theme,
 }
 
-impl Widget for HeaderWidget {
+impl<'a> Widget for HeaderWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let line = Line::from(vec![
+        let mut spans = vec![
             Span::styled(self.title, Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(" - "),
-            Span::styled(self.subtitle, Style::default().add_modifier(Modifier::ITALIC)),
-        ]);
+        ];
+        
+        if let Some(display_name) = self.display_name {
+            spans.push(display_name_span(display_name));
+            spans.push(Span::raw(" "));
+        }
+        
+        spans.push(Span::styled(self.subtitle, Style::default().add_modifier(Modifier::ITALIC)));
+        
+        if let Some((durations, latest)) = self.trend {
+            spans.push(Span::raw(" "));
+            spans.push(sparkline_span(&durations, &self.theme));
+            spans.push(Span::styled(format!(" {}", latest), Style::default().fg(self.theme.muted)));
+        }
+        
+        if let Some(register) = self.recording {
+            spans.push(Span::styled(
+                format!(" [recording @{}]", register),
+                Style::default().fg(self.theme.failed).add_modifier(Modifier::BOLD)
+            ));
+        }
 
-        Paragraph::new(line).render(area, buf);
+        if self.watching {
+            spans.push(Span::styled(
+                " [watching]",
+                Style::default().fg(self.theme.passed).add_modifier(Modifier::BOLD)
+            ));
+        }
+
+        if self.coverage {
+            spans.push(Span::styled(
+                " [coverage]",
+                Style::default().fg(self.theme.highlight).add_modifier(Modifier::BOLD)
+            ));
+        }
+
+        if let Some((label, others)) = self.running {
+            let spinner = SpinnerWidget::default().style(SpinnerStyle::Dot);
+            let others = match others {
+                0 => String::new(),
+                count => format!(" +{}", count),
+            };
+            spans.push(Span::styled(
+                format!(" [{} {}{} (t)]", spinner.current_frame(), label, others),
+                Style::default().fg(self.theme.info).add_modifier(Modifier::BOLD)
+            ));
+        }
+
+        if let Some(found) = self.scanning {
+            let spinner = SpinnerWidget::default().style(SpinnerStyle::Dot);
+            spans.push(Span::styled(
+                format!(" [{} scanning… {} files (Esc)]", spinner.current_frame(), found),
+                Style::default().fg(self.theme.info).add_modifier(Modifier::BOLD)
+            ));
+        }
+
+        let mut lines = vec![Line::from(spans)];
+        if let Some(warning) = self.warning {
+            lines.push(Line::from(Span::styled(
+                format!("⚠ {}", warning),
+                Style::default().fg(self.theme.warning)
+            )));
+        }
+
+        Paragraph::new(lines).render(area, buf);
     }
-}
\ No newline at end of file
+}
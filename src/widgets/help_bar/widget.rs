@@ -2,17 +2,26 @@ use ratatui::{
     prelude::*,
     widgets::{Paragraph, Widget},
 };
+use crate::widgets::theme::Theme;
 
 /// Widget for displaying keyboard control help at the bottom of the screen
 pub struct HelpBarWidget<'a> {
     /// Controls to display [("key", "description"), ...]
     pub controls: Vec<(&'a str, &'a str)>,
+    /// Colors to draw with
+    pub theme: Theme,
 }
 
 impl<'a> HelpBarWidget<'a> {
     /// Create a new help bar widget with the given controls
     pub fn new(controls: Vec<(&'a str, &'a str)>) -> Self {
-        Self { controls }
+        Self { controls, theme: Theme::default() }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
     }
     
     /// Create a help bar for test list view
@@ -23,6 +32,51 @@ impl<'a> HelpBarWidget<'a> {
             ("Ctrl+→", "View File"),
             ("→", "View Tests"),
             ("Enter", "Run Test"),
+            ("o", "Run Options"),
+            ("Space", "Mark"),
+            ("Q", "Queue"),
+            ("R", "Run All"),
+            ("S", "Summary"),
+            ("%", "Coverage Mode"),
+            ("V", "Coverage"),
+            ("d", "Dry Run"),
+            ("/", "Search"),
+            ("r", "Refresh"),
+            ("s", "Sort"),
+            ("F", "Failed Only"),
+            ("c", "Changed"),
+            ("M", "Related to Source"),
+            ("i/Tab", "Imports"),
+            ("p", "Snapshots"),
+            ("H", "Run History"),
+            ("X", "Clear Results"),
+            ("A", "Audit"),
+            ("D", "Dir Stats"),
+            ("T", "Slowest"),
+            ("C", "Config"),
+            ("P", "Projects"),
+            ("W", "Workspaces"),
+            ("I", "Inspect"),
+            ("L", "Layouts"),
+            ("a", "Actions"),
+            ("B", "Benchmark"),
+            ("w", "Watch"),
+            ("t", "Show Run"),
+            (":", "Command"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the test list when no tests were found
+    pub fn for_onboarding() -> Self {
+        Self::new(vec![
+            ("r", "Search Again"),
+            ("R", "Change Root"),
+            ("e", "Edit Patterns"),
+            ("u", "Use Jest Defaults"),
+            ("C", "Config"),
+            ("?", "Help"),
             ("q", "Quit"),
         ])
     }
@@ -30,8 +84,18 @@ impl<'a> HelpBarWidget<'a> {
     /// Create a help bar for test detail view
     pub fn for_test_detail() -> Self {
         Self::new(vec![
-            ("←", "Back to List"),
+            ("←", "Back"),
+            ("↑/↓", "Scroll"),
+            ("PgUp/PgDn", "Page Up/Down"),
+            ("Home/End", "Top/Bottom"),
+            ("g", "Go to Line"),
+            ("/", "Search"),
+            ("n/N", "Next/Previous Match"),
             ("Enter", "Run Test"),
+            ("d", "Dry Run"),
+            ("c", "Next Covered Source"),
+            ("p", "Snapshots"),
+            ("?", "Help"),
             ("q", "Quit"),
         ])
     }
@@ -45,6 +109,18 @@ impl<'a> HelpBarWidget<'a> {
             ("PgUp/PgDn", "Scroll Faster"),
             ("Home/End", "Top/Bottom"),
             ("Enter", "View Tests/Copy"),
+            ("d", "Show Command"),
+            ("f", "Follow / Re-run Failed"),
+            ("u", "Update Snapshots"),
+            ("s/S", "Save Output"),
+            ("n/p", "Stack Frames"),
+            ("z", "Fold Library Frames"),
+            ("F", "Failures Only"),
+            ("G", "Group Failures"),
+            ("o", "Open in Editor"),
+            ("[/]", "Switch Tab"),
+            ("x", "Close Tab"),
+            ("?", "Help"),
             ("q", "Quit"),
         ])
     }
@@ -55,6 +131,206 @@ impl<'a> HelpBarWidget<'a> {
             ("←", "Back to Output"),
             ("↑/↓", "Select Test"),
             ("→/Enter", "Run Selected Test"),
+            ("Space", "Fold Describe"),
+            ("d", "Dry Run"),
+            ("f", "Re-run Failed"),
+            ("o", "Open in Editor"),
+            ("b", "Git Blame"),
+            ("y/Y/N", "Copy Error/Stack/Name"),
+            ("c", "Copy Command"),
+            ("v", "Diff Layout"),
+            ("</>", "Resize/Hide Details"),
+            ("a", "Actions"),
+            ("[/]", "Switch Tab"),
+            ("x", "Close Tab"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the skipped/focused test audit view
+    pub fn for_audit() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Select"),
+            ("Enter", "Open File"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the directory stats view
+    pub fn for_directory_stats() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Select"),
+            ("Enter", "Jump to Directory"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the slowest tests view
+    pub fn for_leaderboard() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Select"),
+            ("Enter", "Open"),
+            ("Tab", "Tests/Files"),
+            ("s", "Sort"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the failure groups view
+    pub fn for_failure_groups() -> Self {
+        Self::new(vec![
+            ("←", "Back"),
+            ("↑/↓", "Select"),
+            ("Enter", "Open First Failure"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the run history view
+    pub fn for_run_history() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Select Run"),
+            ("Space", "Mark Run to Compare"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the snapshots view
+    pub fn for_snapshots() -> Self {
+        Self::new(vec![
+            ("←", "Back"),
+            ("↑/↓", "Select"),
+            ("PgUp/PgDn", "Scroll File"),
+            ("Enter", "Go to Test"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the run queue view
+    pub fn for_queue() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Select"),
+            ("→/Enter", "View Results"),
+            ("G", "Group Failures"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the coverage view
+    pub fn for_coverage() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Select"),
+            ("→/Enter", "View Source"),
+            ("M", "Related Tests"),
+            ("b", "Compare to Base"),
+            ("%", "Coverage Mode"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the coverage diff view
+    pub fn for_coverage_diff() -> Self {
+        Self::new(vec![
+            ("←", "Back to Coverage"),
+            ("↑/↓", "Select"),
+            ("→/Enter", "View Source"),
+            ("b", "Change Base"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the run summary view
+    pub fn for_run_summary() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Select"),
+            ("→/Enter", "View Results"),
+            ("R", "Run All Again"),
+            ("G", "Group Failures"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the config picker view
+    pub fn for_config_picker() -> Self {
+        Self::new(vec![
+            ("↑/↓", "Select"),
+            ("Enter", "Use Config"),
+            ("←", "Back to List"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the project picker view
+    pub fn for_project_picker() -> Self {
+        Self::new(vec![
+            ("↑/↓", "Select"),
+            ("Enter", "Use Project"),
+            ("←", "Back to List"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the workspace picker view
+    pub fn for_workspace_picker() -> Self {
+        Self::new(vec![
+            ("↑/↓", "Select"),
+            ("Enter", "Use Package"),
+            ("←", "Back to List"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the layout picker view
+    pub fn for_layout_picker() -> Self {
+        Self::new(vec![
+            ("↑/↓", "Select"),
+            ("Enter", "Apply Layout"),
+            ("s", "Save Current"),
+            ("x", "Delete"),
+            ("←", "Back to List"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the benchmark view
+    pub fn for_benchmark() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Select"),
+            ("B", "New Benchmark"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ])
+    }
+    
+    /// Create a help bar for the config inspector view
+    pub fn for_config_inspector() -> Self {
+        Self::new(vec![
+            ("←", "Back to List"),
+            ("↑/↓", "Scroll"),
+            ("?", "Help"),
             ("q", "Quit"),
         ])
     }
@@ -74,7 +350,7 @@ impl<'a> Widget for HelpBarWidget<'a> {
             // Add key with highlighting
             spans.push(Span::styled(
                 key.to_string(),
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                self.theme.key()
             ));
             
             // Add description
@@ -83,7 +359,7 @@ impl<'a> Widget for HelpBarWidget<'a> {
         
         // Create and render the paragraph
         Paragraph::new(Line::from(spans))
-            .style(Style::default().fg(Color::Gray))
+            .style(Style::default().fg(self.theme.muted))
             .render(area, buf);
     }
 }
\ No newline at end of file
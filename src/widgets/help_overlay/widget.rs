@@ -0,0 +1,120 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use crate::widgets::theme::Theme;
+
+/// Keys that work the same in every view
+const GLOBAL_KEYS: &[(&str, &str)] = &[
+    ("?", "Show or hide this help"),
+    (":", "Command line"),
+    ("w", "Toggle watch mode"),
+    ("Ctrl+E", "Export the session"),
+    ("m<reg>", "Record a macro into a register, m again to stop"),
+    ("@<reg>", "Replay a macro, @@ replays the last one"),
+    ("!", "Open a shell in the project, exit comes back"),
+    ("Ctrl+Z", "Suspend to the shell, fg comes back"),
+    ("Ctrl+C", "Quit"),
+];
+
+/// Popup listing every key for the current view, and the keys that work everywhere
+pub struct HelpOverlayWidget<'a> {
+    /// Keys for the current view [("key", "description"), ...]
+    pub controls: &'a [(&'a str, &'a str)],
+    /// Number of lines scrolled past
+    pub scroll: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> HelpOverlayWidget<'a> {
+    /// Create a new help overlay widget
+    pub fn new(controls: &'a [(&'a str, &'a str)], scroll: usize) -> Self {
+        Self {
+            controls,
+            scroll,
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// The view's keys, leaving out the ones listed with the global keys
+    fn view_controls(&self) -> impl Iterator<Item = &(&'a str, &'a str)> {
+        self.controls
+            .iter()
+            .filter(|(key, _)| !GLOBAL_KEYS.iter().any(|(global, _)| global == key))
+    }
+    
+    /// Number of lines the help takes, to keep scrolling within it
+    pub fn line_count(&self) -> usize {
+        // Two headings and the blank line between the sections
+        self.view_controls().count() + GLOBAL_KEYS.len() + 3
+    }
+    
+    /// Area of the popup, centered in the given area and sized to the help
+    fn popup_area(&self, area: Rect) -> Rect {
+        let width = area.width.saturating_sub(4).min(70);
+        let height = (self.line_count() as u16 + 2).min(area.height.saturating_sub(2));
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+    
+    /// A bold section heading
+    fn heading(&self, text: &str) -> Line<'static> {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default().fg(self.theme.info).add_modifier(Modifier::BOLD)
+        ))
+    }
+    
+    /// A key and what it does, with the descriptions lined up
+    fn key_line(&self, key: &str, description: &str, key_width: usize) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("  {:<key_width$}  ", key), self.theme.key()),
+            Span::raw(description.to_string()),
+        ])
+    }
+}
+
+impl<'a> Widget for HelpOverlayWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = self.popup_area(area);
+        
+        // Clear whatever is underneath the popup
+        Clear.render(popup, buf);
+        
+        let key_width = self.view_controls()
+            .chain(GLOBAL_KEYS)
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0);
+        
+        let mut text = Text::default();
+        text.lines.push(self.heading("This view"));
+        for (key, description) in self.view_controls() {
+            text.lines.push(self.key_line(key, description, key_width));
+        }
+        text.lines.push(Line::from(""));
+        text.lines.push(self.heading("Everywhere"));
+        for (key, description) in GLOBAL_KEYS {
+            text.lines.push(self.key_line(key, description, key_width));
+        }
+        
+        Paragraph::new(text)
+            .scroll((self.scroll as u16, 0))
+            .block(Block::default()
+                .title(" Keys (↑/↓: Scroll, Esc: Close) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.accent)))
+            .render(popup, buf);
+    }
+}
@@ -0,0 +1,75 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::runner::imports::LocalImport;
+use crate::widgets::theme::Theme;
+
+/// Side pane listing the local modules a test file imports
+pub struct ImportsWidget<'a> {
+    /// Imports of the highlighted test file
+    pub imports: &'a [LocalImport],
+    /// Selected import, when the pane has focus
+    pub selected_index: Option<usize>,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> ImportsWidget<'a> {
+    /// Create a new imports widget
+    pub fn new(imports: &'a [LocalImport], selected_index: Option<usize>) -> Self {
+        Self {
+            imports,
+            selected_index,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for ImportsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_color = if self.selected_index.is_some() { self.theme.accent } else { Color::Reset };
+        let block = Block::default()
+            .title("Imports")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+        
+        if self.imports.is_empty() {
+            Paragraph::new("No local imports.")
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+        
+        let mut text = Text::default();
+        for (idx, import) in self.imports.iter().enumerate() {
+            let is_selected = self.selected_index == Some(idx);
+            let selector = if is_selected { "▶ " } else { "  " };
+            
+            let mut style = if import.resolved.is_some() {
+                Style::default()
+            } else {
+                // Unresolved imports (aliases, missing files) are dimmed
+                Style::default().fg(self.theme.dim)
+            };
+            if is_selected {
+                style = style.patch(self.theme.selected());
+            }
+            
+            text.lines.push(Line::from(Span::styled(
+                format!("{}{}", selector, import.specifier),
+                style
+            )));
+        }
+        
+        Paragraph::new(text)
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,74 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::app::layout::LayoutPreset;
+use crate::widgets::theme::Theme;
+
+/// Widget for choosing a named layout preset
+pub struct LayoutPickerWidget<'a> {
+    /// Presets to choose from, by name
+    pub presets: &'a [(String, LayoutPreset)],
+    /// Currently highlighted preset
+    pub selected_index: usize,
+    /// Name of the preset currently in use, if any
+    pub active: Option<&'a str>,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> LayoutPickerWidget<'a> {
+    /// Create a new layout picker widget
+    pub fn new(presets: &'a [(String, LayoutPreset)], selected_index: usize, active: Option<&'a str>) -> Self {
+        Self {
+            presets,
+            selected_index,
+            active,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for LayoutPickerWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("Choose a Layout")
+            .borders(Borders::ALL);
+        
+        if self.presets.is_empty() {
+            Paragraph::new("No layouts saved yet. Press s to save the current layout.")
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+        
+        let mut text = Text::default();
+        
+        for (idx, (name, preset)) in self.presets.iter().enumerate() {
+            let is_selected = idx == self.selected_index;
+            let selector = if is_selected { "▶ " } else { "  " };
+            let active_marker = if self.active == Some(name.as_str()) { " (active)" } else { "" };
+            
+            let style = if is_selected {
+                self.theme.selected()
+            } else {
+                Style::default()
+            };
+            
+            text.lines.push(Line::from(vec![
+                Span::styled(format!("{}{}{}", selector, name, active_marker), style),
+                Span::styled(format!("  {}", preset.summary()), Style::default().fg(self.theme.muted)),
+            ]));
+        }
+        
+        Paragraph::new(text)
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,118 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Row, Table, Widget},
+};
+use crate::app::{
+    format::FormatOptions,
+    leaderboard::{Leaderboard, LeaderboardKind},
+};
+use crate::widgets::theme::Theme;
+
+/// Widget ranking the slowest tests or test files
+pub struct LeaderboardWidget<'a> {
+    /// Timings to rank
+    pub leaderboard: &'a Leaderboard,
+    /// Currently selected entry
+    pub selected_index: usize,
+    /// How durations are formatted
+    pub format: FormatOptions,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> LeaderboardWidget<'a> {
+    /// Create a new leaderboard widget
+    pub fn new(leaderboard: &'a Leaderboard, selected_index: usize) -> Self {
+        Self {
+            leaderboard,
+            selected_index,
+            format: FormatOptions::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Format durations using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+}
+
+impl<'a> Widget for LeaderboardWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (title, name_column, empty) = match self.leaderboard.kind {
+            LeaderboardKind::Tests => ("Slowest Tests", "Test", "No test timings yet. Run some tests to time them."),
+            LeaderboardKind::Files => ("Slowest Files", "File", "No run history for the listed files yet."),
+        };
+        let block = Block::default()
+            .title(format!("{} (by {})", title, self.leaderboard.sort.label()))
+            .borders(Borders::ALL);
+
+        let entries = self.leaderboard.entries();
+        if entries.is_empty() {
+            Paragraph::new(empty)
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        // Keep the selection in view (subtract borders and header row)
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+
+        let header = Row::new(vec!["#", name_column, "Slowest", "Average", "Runs", "Total"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows = entries
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, entry)| {
+                let name = match &entry.test {
+                    Some(test) => Line::from(vec![
+                        Span::raw(test.clone()),
+                        Span::styled(format!("  {}", entry.file), Style::default().fg(self.theme.muted)),
+                    ]),
+                    None => Line::from(entry.file.clone()),
+                };
+
+                let style = if idx == self.selected_index {
+                    Style::default().bg(self.theme.selected_bg).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+
+                Row::new(vec![
+                    Line::from(format!("{}", idx + 1)).style(Style::default().fg(self.theme.muted)),
+                    name,
+                    Line::from(self.format.duration(entry.max_ms)).style(Style::default().fg(self.theme.warning)),
+                    Line::from(self.format.duration(entry.average_ms())),
+                    Line::from(entry.runs.to_string()),
+                    Line::from(self.format.duration(entry.total_ms)),
+                ])
+                .style(style)
+            });
+
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Min(20),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(10),
+        ];
+
+        Widget::render(
+            Table::new(rows, widths).header(header).block(block),
+            area,
+            buf,
+        );
+    }
+}
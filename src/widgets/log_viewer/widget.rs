@@ -0,0 +1,107 @@
+use std::path::Path;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use crate::widgets::theme::Theme;
+
+/// Popup tailing the latest log lines, for diagnosing discovery and runs
+pub struct LogViewerWidget<'a> {
+    /// Latest log lines, oldest first
+    pub lines: &'a [String],
+    /// Number of lines scrolled back from the newest
+    pub scroll: usize,
+    /// File the log is written to, if it could be opened
+    pub path: Option<&'a Path>,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> LogViewerWidget<'a> {
+    /// Create a new log viewer widget
+    pub fn new(lines: &'a [String], scroll: usize) -> Self {
+        Self {
+            lines,
+            scroll,
+            path: None,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Show the file the log is written to
+    pub fn path(mut self, path: Option<&'a Path>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Area of the popup, filling most of the given area
+    fn popup_area(area: Rect) -> Rect {
+        let width = area.width.saturating_sub(4);
+        let height = area.height.saturating_sub(2);
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+
+    /// A log line, colored by its level
+    fn log_line(&self, line: &str) -> Line<'static> {
+        let color = if line.contains(" ERROR ") {
+            self.theme.failed
+        } else if line.contains(" WARN ") {
+            self.theme.warning
+        } else if line.contains(" DEBUG ") || line.contains(" TRACE ") {
+            self.theme.muted
+        } else {
+            return Line::from(line.to_string());
+        };
+        Line::from(Span::styled(line.to_string(), Style::default().fg(color)))
+    }
+}
+
+impl<'a> Widget for LogViewerWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = Self::popup_area(area);
+
+        // Clear whatever is underneath the popup
+        Clear.render(popup, buf);
+
+        let title = match self.path {
+            Some(path) => format!(" Log - {} ", path.display()),
+            None => " Log (not written to a file) ".to_string(),
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(" ↑/↓: Scroll  End: Newest  Esc/F12: Close ").right_aligned())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        if self.lines.is_empty() {
+            Paragraph::new(Span::styled(
+                "Nothing logged yet (SYJ_LOG=debug logs more)",
+                Style::default().fg(self.theme.muted)
+            ))
+                .block(block)
+                .render(popup, buf);
+            return;
+        }
+
+        // Show the newest lines, or those just before the ones scrolled past
+        let visible = popup.height.saturating_sub(2) as usize;
+        let end = self.lines.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(visible);
+        let text: Vec<Line> = self.lines[start..end].iter().map(|line| self.log_line(line)).collect();
+
+        Paragraph::new(text)
+            .block(block)
+            .render(popup, buf);
+    }
+}
@@ -0,0 +1,2 @@
+mod widget;
+pub use widget::MissingRunnerWidget;
\ No newline at end of file
@@ -0,0 +1,79 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+use crate::widgets::theme::Theme;
+
+/// Popup explaining that the test runner couldn't be found, and how to install it
+pub struct MissingRunnerWidget<'a> {
+    /// Name of the runner, e.g. "Jest"
+    pub runner: &'a str,
+    /// What was looked for, or why the run couldn't start
+    pub reason: &'a str,
+    /// Command that installs the runner as a dev dependency
+    pub install_command: &'a str,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> MissingRunnerWidget<'a> {
+    /// Create a new missing runner widget
+    pub fn new(runner: &'a str, reason: &'a str, install_command: &'a str) -> Self {
+        Self {
+            runner,
+            reason,
+            install_command,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Area of the popup, centered in the given area
+    fn popup_area(area: Rect) -> Rect {
+        let width = area.width.saturating_sub(4).min(80);
+        let height = area.height.min(12);
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a> Widget for MissingRunnerWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = Self::popup_area(area);
+
+        // Clear whatever is underneath the popup
+        Clear.render(popup, buf);
+
+        let text = Text::from(vec![
+            Line::from(self.reason.to_string()),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Install it as a dev dependency of the project:",
+                Style::default().fg(self.theme.info).add_modifier(Modifier::BOLD)
+            )),
+            Line::from(format!("  {}", self.install_command)),
+            Line::from(""),
+            Line::from(Span::styled(
+                "y: copy the install command, any other key: close",
+                Style::default().fg(self.theme.muted)
+            )),
+        ]);
+
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(Block::default()
+                .title(format!(" Can't run {} ", self.runner))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.failed)))
+            .render(popup, buf);
+    }
+}
@@ -1,6 +1,13 @@
 // Basic widgets
+pub mod ansi;
+pub mod display_name;
+pub mod scrollbar;
+pub mod sparkline;
 pub mod header;
+pub mod command_line;
+pub mod prompt;
 pub mod spinner;
+pub mod theme;
 
 // View-specific widgets
 pub mod test_list;
@@ -8,12 +15,76 @@ pub mod test_detail;
 pub mod test_terminal;
 pub mod test_results;
 pub mod help_bar;
+pub mod help_overlay;
+pub mod focus_warning;
+pub mod audit;
+pub mod directory_stats;
+pub mod leaderboard;
+pub mod config_picker;
+pub mod project_picker;
+pub mod workspace_picker;
+pub mod imports;
+pub mod config_inspector;
+pub mod dry_run;
+pub mod missing_runner;
+pub mod action_menu;
+pub mod layout_picker;
+pub mod benchmark;
+pub mod onboarding;
+pub mod run_panel;
+pub mod run_tabs;
+pub mod run_options;
+pub mod run_summary;
+pub mod queue;
+pub mod coverage;
+pub mod status_footer;
+pub mod batch_progress;
+pub mod toasts;
+pub mod log_viewer;
+pub mod confirm_quit;
+pub mod failure_groups;
+pub mod snapshots;
+pub mod coverage_diff;
+pub mod run_history;
 
 // Re-export widgets for easy access
 pub use header::HeaderWidget;
+pub use prompt::PromptWidget;
+pub use command_line::CommandLineWidget;
 pub use test_list::TestListWidget;
 pub use test_detail::TestDetailWidget;
 pub use test_terminal::TestTerminalWidget;
 pub use test_results::TestResultsWidget;
 pub use help_bar::HelpBarWidget;
-pub use spinner::SpinnerWidget;
\ No newline at end of file
+pub use help_overlay::HelpOverlayWidget;
+pub use spinner::SpinnerWidget;
+pub use focus_warning::FocusWarningWidget;
+pub use audit::AuditWidget;
+pub use directory_stats::DirectoryStatsWidget;
+pub use leaderboard::LeaderboardWidget;
+pub use config_picker::ConfigPickerWidget;
+pub use project_picker::ProjectPickerWidget;
+pub use workspace_picker::WorkspacePickerWidget;
+pub use imports::ImportsWidget;
+pub use config_inspector::ConfigInspectorWidget;
+pub use dry_run::DryRunWidget;
+pub use toasts::ToastsWidget;
+pub use log_viewer::LogViewerWidget;
+pub use confirm_quit::ConfirmQuitWidget;
+pub use failure_groups::FailureGroupsWidget;
+pub use snapshots::SnapshotsWidget;
+pub use coverage_diff::CoverageDiffWidget;
+pub use run_history::RunHistoryWidget;
+pub use missing_runner::MissingRunnerWidget;
+pub use action_menu::ActionMenuWidget;
+pub use layout_picker::LayoutPickerWidget;
+pub use benchmark::BenchmarkWidget;
+pub use onboarding::OnboardingWidget;
+pub use run_panel::RunPanelWidget;
+pub use run_tabs::RunTabsWidget;
+pub use run_options::RunOptionsWidget;
+pub use run_summary::RunSummaryWidget;
+pub use queue::QueueWidget;
+pub use coverage::CoverageWidget;
+pub use status_footer::StatusFooterWidget;
+pub use batch_progress::BatchProgressWidget;
\ No newline at end of file
@@ -0,0 +1,97 @@
+use std::path::Path;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+use crate::runner::config_finder::PatternDiagnostic;
+use crate::widgets::theme::Theme;
+
+/// Shown in place of the test list when discovery found nothing, explaining
+/// what was searched and how to adjust it
+pub struct OnboardingWidget<'a> {
+    /// Directory that was searched
+    pub search_path: &'a str,
+    /// Jest config the patterns came from, if any
+    pub config_path: Option<&'a Path>,
+    /// What each pattern matched
+    pub diagnostics: &'a [PatternDiagnostic],
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> OnboardingWidget<'a> {
+    /// Create a new onboarding widget
+    pub fn new(search_path: &'a str, config_path: Option<&'a Path>, diagnostics: &'a [PatternDiagnostic]) -> Self {
+        Self {
+            search_path,
+            config_path,
+            diagnostics,
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    fn heading(&self, text: &str) -> Line<'static> {
+        Line::from(Span::styled(text.to_string(), Style::default().fg(self.theme.info).add_modifier(Modifier::BOLD)))
+    }
+    
+    fn key_hint(&self, key: &str, description: &str) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("  {:<3}", key), self.theme.key()),
+            Span::raw(description.to_string()),
+        ])
+    }
+}
+
+impl<'a> Widget for OnboardingWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut text = Text::default();
+        
+        text.lines.push(Line::from(Span::styled(
+            "No test files found",
+            Style::default().fg(self.theme.warning).add_modifier(Modifier::BOLD)
+        )));
+        text.lines.push(Line::from(""));
+        
+        text.lines.push(self.heading("Searched in"));
+        text.lines.push(Line::from(format!("  {}", self.search_path)));
+        text.lines.push(Line::from(""));
+        
+        text.lines.push(self.heading("Using patterns from"));
+        text.lines.push(Line::from(match self.config_path {
+            Some(config) => format!("  {}", config.display()),
+            None => "  Jest's defaults (no config file found)".to_string(),
+        }));
+        text.lines.push(Line::from(""));
+        
+        text.lines.push(self.heading("Patterns"));
+        for diagnostic in self.diagnostics {
+            let note = match diagnostic.ignored {
+                0 => "no matches".to_string(),
+                ignored => format!("{} matches, all in ignored paths", ignored),
+            };
+            text.lines.push(Line::from(vec![
+                Span::raw(format!("  {}", diagnostic.pattern)),
+                Span::styled(format!("  ({})", note), Style::default().fg(self.theme.muted)),
+            ]));
+        }
+        text.lines.push(Line::from(""));
+        
+        text.lines.push(self.heading("Try"));
+        text.lines.push(self.key_hint("R", "Search a different directory (e.g. the project root)"));
+        text.lines.push(self.key_hint("e", "Edit the patterns"));
+        text.lines.push(self.key_hint("u", "Ignore the config and search with Jest's default patterns"));
+        text.lines.push(self.key_hint("C", "Use a different Jest config"));
+        text.lines.push(self.key_hint("r", "Search again, e.g. after adding tests"));
+        
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().title("Test Files").borders(Borders::ALL))
+            .render(area, buf);
+    }
+}
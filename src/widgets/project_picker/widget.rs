@@ -0,0 +1,94 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::runner::config_finder::JestProject;
+use crate::widgets::{display_name::display_name_span, theme::Theme};
+
+/// Widget for choosing which of a multi-project config's projects to list and run
+pub struct ProjectPickerWidget<'a> {
+    /// Projects to choose from, listed after an "All projects" entry
+    pub projects: &'a [JestProject],
+    /// Currently highlighted entry, where 0 is "All projects"
+    pub selected_index: usize,
+    /// Label of the project currently selected, if any
+    pub active: Option<&'a str>,
+    /// Total number of test files across every project
+    pub total_files: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> ProjectPickerWidget<'a> {
+    /// Create a new project picker widget
+    pub fn new(projects: &'a [JestProject], selected_index: usize, active: Option<&'a str>, total_files: usize) -> Self {
+        Self {
+            projects,
+            selected_index,
+            active,
+            total_files,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// One entry of the list, with its selector and active marker
+    fn entry(&self, index: usize, label: Vec<Span<'static>>, detail: String, active: bool) -> Line<'static> {
+        let is_selected = index == self.selected_index;
+        let selector = if is_selected { "▶ " } else { "  " };
+        let active_marker = if active { " (active)" } else { "" };
+        let style = if is_selected {
+            self.theme.selected()
+        } else {
+            Style::default()
+        };
+        
+        let mut spans = vec![Span::styled(selector, style)];
+        spans.extend(label);
+        spans.push(Span::styled(format!("  {}{}", detail, active_marker), Style::default().fg(self.theme.muted)));
+        Line::from(spans)
+    }
+}
+
+impl<'a> Widget for ProjectPickerWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("Choose a Jest Project")
+            .borders(Borders::ALL);
+        
+        let mut text = Text::default();
+        
+        let all_style = if self.selected_index == 0 { self.theme.selected() } else { Style::default() };
+        text.lines.push(self.entry(
+            0,
+            vec![Span::styled("All projects", all_style)],
+            format!("{} files", self.total_files),
+            self.active.is_none(),
+        ));
+        
+        for (idx, project) in self.projects.iter().enumerate() {
+            let label = match &project.display_name {
+                Some(display_name) => display_name_span(display_name),
+                None => {
+                    let style = if idx + 1 == self.selected_index { self.theme.selected() } else { Style::default() };
+                    Span::styled(project.label(), style)
+                },
+            };
+            let detail = match &project.display_name {
+                Some(_) => format!("{} files in {}", project.tests.len(), project.root_dir.display()),
+                None => format!("{} files", project.tests.len()),
+            };
+            let active = self.active == Some(project.label().as_str());
+            text.lines.push(self.entry(idx + 1, vec![label], detail, active));
+        }
+        
+        Paragraph::new(text)
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,59 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use crate::app::prompt::Prompt;
+use crate::widgets::theme::Theme;
+
+/// Popup widget for entering a line of text
+pub struct PromptWidget<'a> {
+    /// The prompt being edited
+    pub prompt: &'a Prompt,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> PromptWidget<'a> {
+    /// Create a new prompt widget
+    pub fn new(prompt: &'a Prompt) -> Self {
+        Self { prompt, theme: Theme::default() }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Area of the popup, centered horizontally near the top of the given area
+    pub fn popup_area(area: Rect) -> Rect {
+        let width = area.width.saturating_sub(4).min(70);
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + area.height / 3,
+            width,
+            height: 3.min(area.height),
+        }
+    }
+}
+
+impl<'a> Widget for PromptWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = Self::popup_area(area);
+        
+        // Clear whatever is underneath the popup
+        Clear.render(popup, buf);
+        
+        let line = Line::from(vec![
+            Span::raw(self.prompt.input.clone()),
+            Span::styled("█", Style::default().fg(self.theme.accent)),
+        ]);
+        
+        Paragraph::new(line)
+            .block(Block::default()
+                .title(format!(" {} (Enter: OK, Esc: Cancel) ", self.prompt.kind.title()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.accent)))
+            .render(popup, buf);
+    }
+}
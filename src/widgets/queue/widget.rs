@@ -0,0 +1,114 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Row, Table, Widget},
+};
+use crate::app::{format::FormatOptions, queue::RunQueue};
+use crate::widgets::theme::Theme;
+
+/// Widget showing the progress and results of queued test files
+pub struct QueueWidget<'a> {
+    /// The queue being shown
+    pub queue: &'a RunQueue,
+    /// Currently selected file
+    pub selected_index: usize,
+    /// How durations are formatted
+    pub format: FormatOptions,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> QueueWidget<'a> {
+    /// Create a new queue widget
+    pub fn new(queue: &'a RunQueue, selected_index: usize) -> Self {
+        Self {
+            queue,
+            selected_index,
+            format: FormatOptions::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Format durations using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+}
+
+impl<'a> Widget for QueueWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (done, total) = self.queue.progress();
+        let block = Block::default()
+            .title(format!(
+                "Run Queue: {} of {} finished ({} at a time)",
+                done,
+                total,
+                self.queue.concurrency
+            ))
+            .borders(Borders::ALL);
+
+        // Keep the selection in view (subtract borders and header row)
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+
+        let header = Row::new(vec!["", "File", "Status", "✓", "✗", "Duration"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows = self.queue.entries
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, entry)| {
+                let (icon, status, style) = match (entry.passed(), entry.started_at) {
+                    (Some(true), _) => ("✓", "passed", Style::default().fg(self.theme.passed)),
+                    (Some(false), _) if entry.error.is_some() => ("✗", "error", Style::default().fg(self.theme.failed)),
+                    (Some(false), _) => ("✗", "failed", Style::default().fg(self.theme.failed)),
+                    (None, Some(_)) => ("▶", "running", Style::default().fg(self.theme.warning)),
+                    (None, None) => ("·", "waiting", Style::default().fg(self.theme.muted)),
+                };
+
+                let style = if idx == self.selected_index {
+                    style.bg(self.theme.selected_bg).add_modifier(Modifier::BOLD)
+                } else {
+                    style
+                };
+
+                // Running files show their elapsed time so far
+                let duration_ms = entry.duration_ms
+                    .or_else(|| entry.started_at.map(|started_at| started_at.elapsed().as_millis() as u64));
+                let summary = entry.summary();
+
+                Row::new(vec![
+                    icon.to_string(),
+                    entry.file.clone(),
+                    status.to_string(),
+                    summary.map(|summary| summary.passed.to_string()).unwrap_or_default(),
+                    summary.map(|summary| summary.failed.to_string()).unwrap_or_default(),
+                    duration_ms.map(|ms| self.format.duration(ms)).unwrap_or_default(),
+                ])
+                .style(style)
+            });
+
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Min(20),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(10),
+        ];
+
+        Widget::render(
+            Table::new(rows, widths).header(header).block(block),
+            area,
+            buf,
+        );
+    }
+}
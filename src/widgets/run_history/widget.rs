@@ -0,0 +1,165 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+use crate::app::{
+    format::FormatOptions,
+    history::RunRecord,
+    run_compare::{RunComparison, TestChange},
+};
+use crate::widgets::theme::Theme;
+
+/// Widget listing a test file's recorded runs, newest first, with what
+/// changed between the two runs being compared below
+pub struct RunHistoryWidget<'a> {
+    /// Recorded runs, oldest first
+    pub runs: &'a [RunRecord],
+    /// Selected row; rows list the runs newest first
+    pub selected_index: usize,
+    /// The earlier and later run compared, as indexes into `runs`
+    pub compared: Option<(usize, usize)>,
+    /// What changed between them
+    pub comparison: Option<&'a RunComparison>,
+    /// How durations and timestamps are formatted
+    pub format: FormatOptions,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> RunHistoryWidget<'a> {
+    /// Create a new run history widget
+    pub fn new(runs: &'a [RunRecord], selected_index: usize) -> Self {
+        Self {
+            runs,
+            selected_index,
+            compared: None,
+            comparison: None,
+            format: FormatOptions::default(),
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Show what changed between two runs, given as indexes into the runs
+    pub fn comparison(mut self, compared: Option<(usize, usize)>, comparison: Option<&'a RunComparison>) -> Self {
+        self.compared = compared;
+        self.comparison = comparison;
+        self
+    }
+    
+    /// Format durations and timestamps with the given options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// A run's outcome, e.g. "12 passed, 1 failed"
+    fn outcome(&self, run: &RunRecord) -> Span<'static> {
+        match (run.failed_to_run, run.summary) {
+            (true, _) => Span::styled("failed to run", Style::default().fg(self.theme.failed)),
+            (false, Some(summary)) if summary.failed > 0 => Span::styled(
+                format!("{} passed, {} failed", summary.passed, summary.failed),
+                Style::default().fg(self.theme.failed),
+            ),
+            (false, Some(summary)) => Span::styled(format!("{} passed", summary.passed), Style::default().fg(self.theme.passed)),
+            (false, None) => Span::styled("unknown", Style::default().fg(self.theme.muted)),
+        }
+    }
+    
+    /// Color a change is shown in
+    fn change_style(&self, change: TestChange) -> Style {
+        match change {
+            TestChange::NewlyFailing | TestChange::Slower => Style::default().fg(self.theme.failed),
+            TestChange::NewlyPassing | TestChange::Faster => Style::default().fg(self.theme.passed),
+            TestChange::Added | TestChange::Removed => Style::default().fg(self.theme.muted),
+        }
+    }
+}
+
+impl<'a> Widget for RunHistoryWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [list_area, comparison_area] = Layout::vertical([
+            Constraint::Percentage(40),
+            Constraint::Percentage(60),
+        ]).areas(area);
+        
+        let block = Block::default()
+            .title(format!("Recorded Runs ({}, Space marks the run to compare against)", self.runs.len()))
+            .borders(Borders::ALL);
+        let inner = block.inner(list_area);
+        block.render(list_area, buf);
+        
+        // Keep the selection in view
+        let visible_rows = inner.height as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+        let lines: Vec<Line> = self.runs
+            .iter()
+            .enumerate()
+            .rev()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(row, (index, run))| {
+                let tag = match self.compared {
+                    Some((before, _)) if before == index => "before",
+                    Some((_, after)) if after == index => "after ",
+                    _ => "      ",
+                };
+                let line = Line::from(vec![
+                    Span::styled(format!("{} ", tag), Style::default().fg(self.theme.accent)),
+                    Span::raw(format!("{:<16}", self.format.timestamp(run.timestamp))),
+                    Span::styled(format!("{:>10}  ", self.format.duration(run.duration_ms)), Style::default().fg(self.theme.muted)),
+                    self.outcome(run),
+                ]);
+                if row == self.selected_index {
+                    line.style(self.theme.selected())
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+        
+        // What changed between the compared runs
+        let block = Block::default()
+            .title(match self.comparison {
+                Some(comparison) => format!(" Changed: {}, unchanged: {} ", comparison.changes.len(), comparison.unchanged),
+                None => " Changes ".to_string(),
+            })
+            .borders(Borders::ALL);
+        let text = match self.comparison {
+            None => Text::from("Only one run is recorded for this file, so there's nothing to compare yet."),
+            Some(comparison) if comparison.missing_results => {
+                Text::from("One of these runs was recorded without per-test results, so only its counts and duration are known.")
+            },
+            Some(comparison) if comparison.changes.is_empty() => Text::from("No test newly failed, newly passed, or changed duration noticeably."),
+            Some(comparison) => comparison.changes
+                .iter()
+                .map(|test| {
+                    let mut spans = vec![
+                        Span::styled(format!("{:<14}", test.change.label()), self.change_style(test.change)),
+                        Span::raw(test.name().to_string()),
+                    ];
+                    let durations = test.before.as_ref().and_then(|test| test.duration_ms)
+                        .zip(test.after.as_ref().and_then(|test| test.duration_ms));
+                    if let Some((before, after)) = durations.filter(|_| matches!(test.change, TestChange::Slower | TestChange::Faster)) {
+                        spans.push(Span::styled(
+                            format!("  {} → {}", self.format.duration(before), self.format.duration(after)),
+                            Style::default().fg(self.theme.muted),
+                        ));
+                    }
+                    Line::from(spans)
+                })
+                .collect(),
+        };
+        Paragraph::new(text)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(comparison_area, buf);
+    }
+}
@@ -0,0 +1,102 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use crate::app::run_options::RunOption;
+use crate::runner::command::shell_quote;
+use crate::widgets::theme::Theme;
+
+/// Popup of Jest flags to include in the following runs
+pub struct RunOptionsWidget<'a> {
+    /// Flags to choose from, and whether each is on
+    pub options: &'a [(RunOption, bool)],
+    /// Currently highlighted flag
+    pub selected_index: usize,
+    /// Arguments typed in for the following runs
+    pub extra_args: &'a [String],
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> RunOptionsWidget<'a> {
+    /// Create a new run options widget
+    pub fn new(options: &'a [(RunOption, bool)], selected_index: usize) -> Self {
+        Self {
+            options,
+            selected_index,
+            extra_args: &[],
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Show the arguments typed in for the following runs
+    pub fn extra_args(mut self, extra_args: &'a [String]) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Area of the popup, centered in the given area and sized to the options
+    fn popup_area(&self, area: Rect) -> Rect {
+        let width = area.width.saturating_sub(4).min(70);
+        // One line per flag, the extra arguments, and the borders
+        let height = (self.options.len() as u16 + 3).min(area.height);
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a> Widget for RunOptionsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = self.popup_area(area);
+        
+        // Clear whatever is underneath the popup
+        Clear.render(popup, buf);
+        
+        let mut text = Text::default();
+        for (idx, (option, enabled)) in self.options.iter().enumerate() {
+            let is_selected = idx == self.selected_index;
+            let selector = if is_selected { "▶ " } else { "  " };
+            let checkbox = if *enabled { "[x]" } else { "[ ]" };
+            
+            let style = if is_selected {
+                self.theme.selected()
+            } else if *enabled {
+                Style::default().fg(self.theme.passed)
+            } else {
+                Style::default()
+            };
+            
+            text.lines.push(Line::from(vec![
+                Span::styled(format!("{}{} --{}", selector, checkbox, option.flag), style),
+                Span::styled(format!("  {}", option.description), Style::default().fg(self.theme.muted)),
+            ]));
+        }
+        
+        let extra_args = match self.extra_args {
+            [] => "none".to_string(),
+            args => args.iter().map(|arg| shell_quote(arg)).collect::<Vec<_>>().join(" "),
+        };
+        text.lines.push(Line::from(vec![
+            Span::styled("  Extra arguments: ", Style::default().fg(self.theme.info)),
+            Span::raw(extra_args),
+            Span::styled("  (e: edit)", Style::default().fg(self.theme.muted)),
+        ]));
+        
+        Paragraph::new(text)
+            .block(Block::default()
+                .title(" Run Options (Space: Toggle, Enter: Run, Esc: Close) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.accent)))
+            .render(popup, buf);
+    }
+}
@@ -0,0 +1,94 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::app::format::FormatOptions;
+use crate::runner::ansi;
+use crate::widgets::spinner::{SpinnerStyle, SpinnerWidget};
+use crate::widgets::theme::Theme;
+
+/// Compact status of a run going on while the user is in another view
+pub struct RunPanelWidget<'a> {
+    /// What is running (test file or action name)
+    pub label: &'a str,
+    /// Output received from the run so far
+    pub output: &'a str,
+    /// Time since the run started, in ms
+    pub elapsed_ms: u64,
+    /// How long runs of the file usually take, in ms, if they've run before
+    pub estimate_ms: Option<u64>,
+    /// How the elapsed time is formatted
+    pub format: FormatOptions,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> RunPanelWidget<'a> {
+    /// Create a new run panel widget
+    pub fn new(label: &'a str, output: &'a str, elapsed_ms: u64) -> Self {
+        Self {
+            label,
+            output,
+            elapsed_ms,
+            estimate_ms: None,
+            format: FormatOptions::default(),
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Show how long runs of the file usually take next to the elapsed time
+    pub fn estimate(mut self, estimate_ms: Option<u64>) -> Self {
+        self.estimate_ms = estimate_ms;
+        self
+    }
+    
+    /// Format the elapsed time using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+}
+
+impl<'a> Widget for RunPanelWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let spinner = SpinnerWidget::default().style(SpinnerStyle::Dot);
+        
+        // Tally the Jest result markers seen so far
+        let output = ansi::strip(self.output);
+        let lines = output.lines().map(str::trim);
+        let (passed, failed) = lines.clone().fold((0, 0), |(passed, failed), line| {
+            if line.starts_with('✓') {
+                (passed + 1, failed)
+            } else if line.starts_with('✕') {
+                (passed, failed + 1)
+            } else {
+                (passed, failed)
+            }
+        });
+        let latest = lines.rev().find(|line| !line.is_empty()).unwrap_or("");
+        
+        let line = Line::from(vec![
+            Span::styled(format!("{} ", spinner.current_frame()), Style::default().fg(self.theme.info)),
+            Span::styled(self.label.to_string(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!(" │ {} │ ", self.format.elapsed(self.elapsed_ms, self.estimate_ms))),
+            Span::styled(format!("✓ {}", passed), Style::default().fg(self.theme.passed)),
+            Span::raw(" "),
+            Span::styled(format!("✗ {}", failed), Style::default().fg(self.theme.failed)),
+            Span::raw(" │ "),
+            Span::styled(latest.to_string(), Style::default().fg(self.theme.muted)),
+        ]);
+        
+        Paragraph::new(line)
+            .block(Block::default()
+                .title(" Running in background (t: show) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.info)))
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,113 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Row, Table, Widget},
+};
+use crate::app::{format::FormatOptions, run_summary::RunAllSummary};
+use crate::widgets::theme::Theme;
+
+/// Widget showing per-file results of a run of every listed file
+pub struct RunSummaryWidget<'a> {
+    /// The summarized run
+    pub summary: &'a RunAllSummary,
+    /// Currently selected file
+    pub selected_index: usize,
+    /// How durations are formatted
+    pub format: FormatOptions,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> RunSummaryWidget<'a> {
+    /// Create a new run summary widget
+    pub fn new(summary: &'a RunAllSummary, selected_index: usize) -> Self {
+        Self {
+            summary,
+            selected_index,
+            format: FormatOptions::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Format durations using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+}
+
+impl<'a> Widget for RunSummaryWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let totals = &self.summary.totals;
+        let block = Block::default()
+            .title(format!(
+                "Run Summary: {} passed, {} failed, {} skipped of {} tests",
+                totals.passed,
+                totals.failed,
+                totals.skipped + totals.todo,
+                totals.total
+            ))
+            .borders(Borders::ALL);
+        
+        // Keep the selection in view (subtract borders and header row)
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+        
+        let header = Row::new(vec!["", "File", "✓", "✗", "Skipped", "Duration"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        
+        let rows = self.summary.files
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, outcome)| {
+                let (icon, style) = if outcome.passed() {
+                    ("✓", Style::default().fg(self.theme.passed))
+                } else {
+                    ("✗", Style::default().fg(self.theme.failed))
+                };
+                
+                let style = if idx == self.selected_index {
+                    style.bg(self.theme.selected_bg).add_modifier(Modifier::BOLD)
+                } else {
+                    style
+                };
+                
+                let file = match outcome.error {
+                    Some(_) => format!("{} (failed to run)", outcome.file),
+                    None => outcome.file.clone(),
+                };
+                
+                Row::new(vec![
+                    icon.to_string(),
+                    file,
+                    outcome.summary.passed.to_string(),
+                    outcome.summary.failed.to_string(),
+                    (outcome.summary.skipped + outcome.summary.todo).to_string(),
+                    self.format.duration(outcome.duration_ms),
+                ])
+                .style(style)
+            });
+        
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Min(20),
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(10),
+        ];
+        
+        Widget::render(
+            Table::new(rows, widths).header(header).block(block),
+            area,
+            buf,
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Tabs, Widget},
+};
+use crate::widgets::spinner::{SpinnerStyle, SpinnerWidget};
+use crate::widgets::theme::Theme;
+
+/// How the run in a tab is going, or how it went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunTabState {
+    Running,
+    Passed,
+    Failed,
+    /// Finished without test counts, like a custom action
+    Finished,
+}
+
+/// Bar listing every run tab, with the shown one highlighted
+pub struct RunTabsWidget<'a> {
+    /// Label and state of each tab
+    tabs: &'a [(String, RunTabState)],
+    /// Index of the shown tab
+    active: usize,
+    /// Colors to draw with
+    theme: Theme,
+}
+
+impl<'a> RunTabsWidget<'a> {
+    /// Create a new tab bar
+    pub fn new(tabs: &'a [(String, RunTabState)], active: usize) -> Self {
+        Self {
+            tabs,
+            active,
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for RunTabsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let spinner = SpinnerWidget::default().style(SpinnerStyle::Dot);
+        let titles = self.tabs.iter().enumerate().map(|(i, (label, state))| {
+            let (symbol, color) = match state {
+                RunTabState::Running => (spinner.current_frame(), self.theme.info),
+                RunTabState::Passed => ("✓", self.theme.passed),
+                RunTabState::Failed => ("✗", self.theme.failed),
+                RunTabState::Finished => ("•", self.theme.muted),
+            };
+            Line::from(vec![
+                Span::raw(format!("{} ", i + 1)),
+                Span::styled(symbol.to_string(), Style::default().fg(color)),
+                Span::raw(format!(" {}", label)),
+            ])
+        });
+        
+        Tabs::new(titles)
+            .select(self.active)
+            .style(Style::default().fg(self.theme.muted))
+            .highlight_style(self.theme.selected())
+            .divider("│")
+            .render(area, buf);
+    }
+}
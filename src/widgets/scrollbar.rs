@@ -0,0 +1,31 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget},
+};
+use crate::widgets::theme::Theme;
+
+/// The right border of a bordered block's area, between its corners, where
+/// its scrollbar goes
+pub fn border_track(area: Rect) -> Rect {
+    area.inner(Margin { vertical: 1, horizontal: 0 })
+}
+
+/// Draw a scrollbar down the right edge of `area` when `total` rows don't fit
+/// in the `visible` rows shown, `offset` rows in
+pub fn render_scrollbar(area: Rect, buf: &mut Buffer, theme: &Theme, total: usize, visible: usize, offset: usize) {
+    if total <= visible {
+        return;
+    }
+
+    // Every offset that keeps the view full is a position, so the thumb's
+    // size is the visible share of the content
+    let mut state = ScrollbarState::new(total - visible + 1)
+        .viewport_content_length(visible)
+        .position(offset);
+    Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(theme.dim))
+        .thumb_style(Style::default().fg(theme.accent))
+        .render(area, buf, &mut state);
+}
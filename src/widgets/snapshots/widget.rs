@@ -0,0 +1,129 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::runner::{pretty_value, snapshots::{self, SnapshotFile}};
+use crate::widgets::theme::Theme;
+
+/// Widget listing the snapshot files of a test file and their entries, with
+/// the selected file's content next to them
+pub struct SnapshotsWidget<'a> {
+    /// Snapshot files to list
+    pub files: &'a [SnapshotFile],
+    /// Selected row: a file or one of its entries
+    pub selected_index: usize,
+    /// First line of the file shown in the content pane
+    pub scroll: usize,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> SnapshotsWidget<'a> {
+    /// Create a new snapshots widget
+    pub fn new(files: &'a [SnapshotFile], selected_index: usize, scroll: usize) -> Self {
+        Self {
+            files,
+            selected_index,
+            scroll,
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for SnapshotsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [list_area, content_area] = Layout::horizontal([
+            Constraint::Percentage(35),
+            Constraint::Percentage(65),
+        ]).areas(area);
+        
+        let rows = snapshots::rows(self.files);
+        let entry_count: usize = self.files.iter().map(|file| file.entries.len()).sum();
+        let block = Block::default()
+            .title(format!("Snapshots ({} files, {} entries)", self.files.len(), entry_count))
+            .borders(Borders::ALL);
+        let inner = block.inner(list_area);
+        block.render(list_area, buf);
+        
+        // Keep the selection in view
+        let visible_rows = inner.height as usize;
+        let scroll_offset = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+        let lines: Vec<Line> = rows
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+            .map(|(idx, &(file, entry))| {
+                let snapshot = &self.files[file];
+                let line = match entry {
+                    None => Line::from(vec![
+                        Span::styled(
+                            snapshot.path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+                            Style::default().fg(self.theme.info).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(format!(" ({})", snapshot.entries.len()), Style::default().fg(self.theme.muted)),
+                    ]),
+                    Some(entry) => Line::from(format!("  {}", snapshot.entries[entry].name)),
+                };
+                if idx == self.selected_index {
+                    line.style(self.theme.selected())
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+        
+        // The selected file, with the selected entry marked in the gutter
+        let Some(&(file, entry)) = rows.get(self.selected_index) else {
+            return;
+        };
+        let snapshot = &self.files[file];
+        let marked = entry.map(|entry| {
+            let start = snapshot.entries[entry].line;
+            let end = snapshot.entries.get(entry + 1).map_or(usize::MAX, |next| next.line);
+            start..end
+        });
+        
+        // The file's path from the test file's directory, e.g. `__snapshots__/sum.test.js.snap`
+        let components: Vec<_> = snapshot.path.components().collect();
+        let name = components[components.len().saturating_sub(2)..].iter().collect::<std::path::PathBuf>();
+        let block = Block::default()
+            .title(format!(" {} ", name.display()))
+            .borders(Borders::ALL);
+        let inner = block.inner(content_area);
+        block.render(content_area, buf);
+        
+        let lines: Vec<Line> = snapshot.content
+            .lines()
+            .enumerate()
+            .skip(self.scroll)
+            .take(inner.height as usize)
+            .map(|(index, line)| {
+                let gutter = if marked.as_ref().is_some_and(|marked| marked.contains(&(index + 1))) {
+                    Span::styled("▌", Style::default().fg(self.theme.accent))
+                } else {
+                    Span::raw(" ")
+                };
+                let mut spans = vec![gutter];
+                if line.starts_with("//") {
+                    spans.push(Span::styled(line.to_string(), Style::default().fg(self.theme.muted)));
+                } else if line.starts_with("exports[`") {
+                    spans.push(Span::styled(line.to_string(), Style::default().fg(self.theme.accent).add_modifier(Modifier::BOLD)));
+                } else {
+                    spans.extend(pretty_value::tokens(line).into_iter().map(|(kind, text)| {
+                        Span::styled(text.to_string(), self.theme.token(kind))
+                    }));
+                }
+                Line::from(spans)
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
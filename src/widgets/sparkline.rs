@@ -0,0 +1,37 @@
+use ratatui::prelude::*;
+use crate::widgets::theme::Theme;
+
+/// Bars from shortest to tallest
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// How much slower than the average of the earlier runs the latest run has to
+/// be to count as a regression
+const REGRESSION_RATIO: f64 = 1.25;
+
+/// Render durations, oldest first, as a one-line bar chart scaled between the
+/// fastest and slowest of them. The chart turns the warning color when the
+/// latest run is well over the average of the ones before it.
+pub fn sparkline_span(durations: &[u64], theme: &Theme) -> Span<'static> {
+    let min = durations.iter().copied().min().unwrap_or(0);
+    let max = durations.iter().copied().max().unwrap_or(0);
+    let range = (max - min).max(1) as f64;
+    
+    let bars: String = durations
+        .iter()
+        .map(|&duration| {
+            let level = ((duration - min) as f64 / range * (BARS.len() - 1) as f64).round() as usize;
+            BARS[level]
+        })
+        .collect();
+    
+    let regressed = match durations.split_last() {
+        Some((&latest, earlier)) if !earlier.is_empty() => {
+            let average = earlier.iter().sum::<u64>() as f64 / earlier.len() as f64;
+            latest as f64 > average * REGRESSION_RATIO
+        },
+        _ => false,
+    };
+    let color = if regressed { theme.warning } else { theme.info };
+    
+    Span::styled(bars, Style::default().fg(color))
+}
@@ -1,13 +1,15 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Widget, Paragraph},
+    widgets::{Block, Borders, LineGauge, Widget, Paragraph},
 };
+use crate::widgets::theme::Theme;
 
 /// Animation styles for the spinner
 #[derive(Debug, Clone, Copy)]
 pub enum SpinnerStyle {
     Line,
     Dot,
+    #[allow(dead_code)]
     Box,
 }
 
@@ -17,6 +19,10 @@ pub struct SpinnerWidget {
     label: String,
     /// The animation style to use
     style: SpinnerStyle,
+    /// How far along the work is (0.0 to 1.0), shown as a bar below the label if known
+    progress: Option<f64>,
+    /// Colors to draw with
+    theme: Theme,
 }
 
 impl Default for SpinnerWidget {
@@ -24,6 +30,8 @@ impl Default for SpinnerWidget {
         Self {
             label: "Loading...".to_string(),
             style: SpinnerStyle::Line,
+            progress: None,
+            theme: Theme::default(),
         }
     }
 }
@@ -43,8 +51,20 @@ impl SpinnerWidget {
         self
     }
     
+    /// Show a progress bar below the label, which needs an extra line of height
+    pub fn progress(mut self, progress: Option<f64>) -> Self {
+        self.progress = progress;
+        self
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
     /// Get the current animation frame based on system time
-    fn current_frame(&self) -> &str {
+    pub fn current_frame(&self) -> &str {
         // Use the current time to determine the frame
         let frames = self.get_frames();
         let current_millis = std::time::SystemTime::now()
@@ -75,13 +95,28 @@ impl Widget for SpinnerWidget {
         let text = format!("{} {}", spinner_frame, self.label);
         
         // Render with a nice block
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.info))
+            .title(" Running Test ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+        
+        let [label_area, progress_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(if self.progress.is_some() { 1 } else { 0 }),
+        ]).areas(inner);
         Paragraph::new(text)
-            .block(Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(" Running Test "))
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::Cyan))
-            .render(area, buf);
+            .style(Style::default().fg(self.theme.info))
+            .render(label_area, buf);
+        
+        if let Some(progress) = self.progress {
+            LineGauge::default()
+                .ratio(progress.clamp(0.0, 1.0))
+                .filled_style(Style::default().fg(self.theme.info))
+                .unfilled_style(Style::default().fg(self.theme.dim))
+                .render(progress_area, buf);
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,92 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Widget},
+};
+use crate::app::{format::FormatOptions, session::SessionStats};
+use crate::widgets::spinner::{SpinnerStyle, SpinnerWidget};
+use crate::widgets::theme::Theme;
+
+/// Session totals shown at the right end of the help bar line
+pub struct StatusFooterWidget {
+    /// Totals across the runs finished so far
+    pub stats: SessionStats,
+    /// Label of the run going on, if any, and how many other runs are going
+    pub running: Option<(String, usize)>,
+    /// How the total runtime is formatted
+    pub format: FormatOptions,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl StatusFooterWidget {
+    /// Create a new status footer widget
+    pub fn new(stats: SessionStats, running: Option<(String, usize)>) -> Self {
+        Self {
+            stats,
+            running,
+            format: FormatOptions::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Format the total runtime using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+
+    /// Columns the footer needs
+    pub fn width(&self) -> u16 {
+        self.line().width() as u16
+    }
+
+    fn line(&self) -> Line<'static> {
+        // Set off from the help bar's controls, which run up to it
+        let mut spans = vec![Span::raw(" ┃ ")];
+
+        if let Some((label, others)) = &self.running {
+            let spinner = SpinnerWidget::default().style(SpinnerStyle::Dot);
+            let others = match others {
+                0 => String::new(),
+                count => format!(" +{}", count),
+            };
+            spans.push(Span::styled(
+                format!("{} {}{}", spinner.current_frame(), label, others),
+                Style::default().fg(self.theme.info)
+            ));
+            spans.push(Span::raw(" │ "));
+        }
+
+        if self.stats.files == 0 && self.stats.tests.total == 0 {
+            spans.push(Span::raw("No runs yet"));
+        } else {
+            let tests = self.stats.tests;
+            spans.extend([
+                Span::raw(format!("{} files │ ", self.stats.files)),
+                Span::styled(format!("✓ {}", tests.passed), Style::default().fg(self.theme.passed)),
+                Span::raw(" "),
+                Span::styled(format!("✗ {}", tests.failed), Style::default().fg(self.theme.failed)),
+                Span::raw(" "),
+                Span::styled(format!("○ {}", tests.skipped), Style::default().fg(self.theme.highlight)),
+                Span::raw(format!(" │ {}", self.format.duration(self.stats.duration_ms))),
+            ]);
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl Widget for StatusFooterWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.line())
+            .style(Style::default().fg(self.theme.muted))
+            .alignment(Alignment::Right)
+            .render(area, buf);
+    }
+}
@@ -2,26 +2,192 @@ use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
+use std::collections::HashMap;
+use crate::app::{search::FileSearch, state::TestStatus};
+use crate::runner::coverage::LineHits;
+use crate::widgets::{scrollbar::render_scrollbar, theme::Theme};
 
 /// Widget for displaying the content of a test file
 pub struct TestDetailWidget<'a> {
     /// Content to display
     pub content: &'a str,
+    /// Per-line coverage to mark in a gutter, if any
+    pub line_hits: Option<&'a LineHits>,
+    /// Status of the tests and describe blocks declared on each line, to mark
+    /// in a gutter
+    pub test_marks: Option<&'a HashMap<usize, TestStatus>>,
+    /// Search through the content, with its input shown on the last line
+    pub search: Option<&'a FileSearch>,
+    /// Number of lines scrolled past
+    pub scroll: usize,
+    /// 1-based line to highlight, if any
+    pub highlighted_line: Option<usize>,
+    /// Colors to draw with
+    pub theme: Theme,
 }
 
 impl<'a> TestDetailWidget<'a> {
     /// Create a new test detail widget
     pub fn new(content: &'a str) -> Self {
-        Self { content }
+        Self {
+            content,
+            line_hits: None,
+            test_marks: None,
+            search: None,
+            scroll: 0,
+            highlighted_line: None,
+            theme: Theme::default(),
+        }
+    }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Mark covered lines green and uncovered lines red in a gutter
+    pub fn line_hits(mut self, line_hits: &'a LineHits) -> Self {
+        self.line_hits = Some(line_hits);
+        self
+    }
+    
+    /// Mark test declarations with how they did in a gutter. Nothing is drawn
+    /// for a file without any.
+    pub fn test_marks(mut self, test_marks: &'a HashMap<usize, TestStatus>) -> Self {
+        self.test_marks = Some(test_marks).filter(|marks| !marks.is_empty());
+        self
+    }
+    
+    /// Show a search's input on the last line and highlight its matches,
+    /// the current one more strongly
+    pub fn search(mut self, search: &'a FileSearch) -> Self {
+        self.search = Some(search);
+        self
+    }
+    
+    /// Start the view this many lines into the file
+    pub fn scroll(mut self, scroll: usize) -> Self {
+        self.scroll = scroll;
+        self
+    }
+    
+    /// Highlight a line, e.g. the one a stack frame points at
+    pub fn highlight_line(mut self, line: Option<usize>) -> Self {
+        self.highlighted_line = line;
+        self
     }
 }
 
 impl<'a> Widget for TestDetailWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        // Keep the last line for the search input
+        let area = match self.search {
+            Some(search) => {
+                let input_area = Rect {
+                    y: area.bottom().saturating_sub(1),
+                    height: 1.min(area.height),
+                    ..area
+                };
+                render_search_input(search, &self.theme, input_area, buf);
+                Rect { height: area.height.saturating_sub(1), ..area }
+            },
+            None => area,
+        };
+        
+        let line_count = self.content.lines().count();
+        let width = line_count.to_string().len();
+        
+        // Give up the last column to a scrollbar when the file doesn't fit
+        let visible_rows = area.height as usize;
+        let text_area = if line_count > visible_rows {
+            render_scrollbar(area, buf, &self.theme, line_count, visible_rows, self.scroll);
+            Rect { width: area.width.saturating_sub(1), ..area }
+        } else {
+            area
+        };
+        let lines: Vec<Line> = self.content
+            .lines()
+            .enumerate()
+            .skip(self.scroll)
+            .take(area.height as usize)
+            .map(|(index, line)| {
+                let number = index + 1;
+                let mut spans = vec![Span::styled(format!("{:>width$} ", number), Style::default().fg(self.theme.dim))];
+                
+                // Lines without statements, like comments, get no coverage marker
+                if let Some(line_hits) = self.line_hits {
+                    let marker = match line_hits.get(&number) {
+                        Some(0) => Span::styled("▌", Style::default().fg(self.theme.failed)),
+                        Some(_) => Span::styled("▌", Style::default().fg(self.theme.passed)),
+                        None => Span::raw(" "),
+                    };
+                    spans.push(marker);
+                    spans.push(Span::raw(" "));
+                }
+                
+                if let Some(test_marks) = self.test_marks {
+                    let marker = match test_marks.get(&number) {
+                        Some(TestStatus::Passed) => Span::styled("✓", Style::default().fg(self.theme.passed)),
+                        Some(TestStatus::Failed) => Span::styled("✗", Style::default().fg(self.theme.failed)),
+                        Some(TestStatus::Skipped) => Span::styled("○", Style::default().fg(self.theme.muted)),
+                        Some(TestStatus::Todo) => Span::styled("✎", Style::default().fg(self.theme.muted)),
+                        Some(TestStatus::Pending) => Span::styled("·", Style::default().fg(self.theme.dim)),
+                        None => Span::raw(" "),
+                    };
+                    spans.push(marker);
+                    spans.push(Span::raw(" "));
+                }
+                
+                let style = if self.highlighted_line == Some(number) { self.theme.badge() } else { Style::default() };
+                
+                // Split the line around any search matches on it
+                let mut end = 0;
+                let matches = self.search.map(|search| search.matches.as_slice()).unwrap_or(&[]);
+                let current = self.search.and_then(FileSearch::current_match);
+                for found in matches.iter().filter(|found| found.line == number) {
+                    let (Some(before), Some(text)) = (line.get(end..found.start), line.get(found.start..found.end)) else {
+                        continue;
+                    };
+                    let match_style = if current == Some(*found) {
+                        Style::default().fg(self.theme.badge_fg).bg(self.theme.warning).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(self.theme.badge_fg).bg(self.theme.highlight)
+                    };
+                    spans.push(Span::styled(before, style));
+                    spans.push(Span::styled(text, match_style));
+                    end = found.end;
+                }
+                spans.push(Span::styled(&line[end..], style));
+                Line::from(spans)
+            })
+            .collect();
+        
         // Render the file content
-        Paragraph::new(self.content)
+        Paragraph::new(lines)
             .block(Block::default().borders(Borders::NONE))
             .wrap(Wrap { trim: false })
-            .render(area, buf);
+            .render(text_area, buf);
     }
-}
\ No newline at end of file
+}
+
+/// Draw what's been typed into a search, and where it's up to
+fn render_search_input(search: &FileSearch, theme: &Theme, area: Rect, buf: &mut Buffer) {
+    let mut spans = vec![
+        Span::styled("/", theme.key()),
+        Span::raw(search.query.clone()),
+    ];
+    if search.editing {
+        spans.push(Span::styled("█", Style::default().fg(theme.accent)));
+    }
+    let status = match search.matches.len() {
+        _ if search.query.is_empty() => String::new(),
+        0 => "  No matches".to_string(),
+        count => format!("  {} of {}", search.current + 1, count),
+    };
+    spans.push(Span::styled(status, Style::default().fg(theme.muted)));
+    if !search.editing {
+        spans.push(Span::styled("  (n/N: next/previous, / to edit, Esc to clear)", Style::default().fg(theme.dim)));
+    }
+    Paragraph::new(Line::from(spans)).render(area, buf);
+}
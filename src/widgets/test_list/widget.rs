@@ -1,7 +1,12 @@
+use std::collections::HashMap;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Paragraph, Widget},
 };
+use crate::app::{format::FormatOptions, history::{History, RunRecord}, search::Search, state::SortMode};
+use crate::runner::{config_finder::DisplayName, test_parser::TestModifier};
+use crate::widgets::{display_name::display_name_span, scrollbar::{border_track, render_scrollbar}};
+use crate::widgets::theme::Theme;
 
 /// Widget for displaying a scrollable list of test files
 pub struct TestListWidget<'a> {
@@ -11,6 +16,22 @@ pub struct TestListWidget<'a> {
     pub selected_index: usize,
     /// First visible item index
     pub scroll_offset: usize,
+    /// Files containing focused tests, flagged with a warning badge
+    pub focused_tests: Option<&'a HashMap<String, Vec<TestModifier>>>,
+    /// Run history used for the status badges and duration column
+    pub history: Option<&'a History>,
+    /// How the list is currently sorted
+    pub sort_mode: SortMode,
+    /// Project labels for files in multi-project setups
+    pub display_names: Option<&'a HashMap<String, DisplayName>>,
+    /// How durations are formatted
+    pub format: FormatOptions,
+    /// Fuzzy search narrowing the list, if any
+    pub search: Option<&'a Search>,
+    /// Files marked for a batch run
+    pub marked: &'a [String],
+    /// Colors to draw with
+    pub theme: Theme,
 }
 
 impl<'a> TestListWidget<'a> {
@@ -20,9 +41,65 @@ impl<'a> TestListWidget<'a> {
             tests,
             selected_index,
             scroll_offset,
+            focused_tests: None,
+            history: None,
+            sort_mode: SortMode::default(),
+            display_names: None,
+            format: FormatOptions::default(),
+            search: None,
+            marked: &[],
+            theme: Theme::default(),
         }
     }
     
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Flag files marked for a batch run
+    pub fn marked(mut self, marked: &'a [String]) -> Self {
+        self.marked = marked;
+        self
+    }
+    
+    /// Show the search input and highlight the characters each file matched
+    pub fn search(mut self, search: Option<&'a Search>) -> Self {
+        self.search = search;
+        self
+    }
+
+    /// Format durations and timestamps using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+    
+    /// Prefix files with their project's displayName
+    pub fn display_names(mut self, display_names: &'a HashMap<String, DisplayName>) -> Self {
+        self.display_names = Some(display_names);
+        self
+    }
+    
+    /// Show each file's last run status and duration, and its average duration, from history
+    pub fn history(mut self, history: &'a History) -> Self {
+        self.history = Some(history);
+        self
+    }
+    
+    /// Show the current sort mode in the title
+    pub fn sort_mode(mut self, sort_mode: SortMode) -> Self {
+        self.sort_mode = sort_mode;
+        self
+    }
+    
+    /// Flag files that contain focused tests
+    pub fn focused_tests(mut self, focused_tests: &'a HashMap<String, Vec<TestModifier>>) -> Self {
+        self.focused_tests = Some(focused_tests);
+        self
+    }
+    
     /// Calculate maximum visible items in the given area
     pub fn visible_items(&self, area: Rect) -> usize {
         area.height.saturating_sub(2) as usize // Subtract 2 for potential borders
@@ -47,29 +124,64 @@ impl<'a> TestListWidget<'a> {
 
 impl<'a> Widget for TestListWidget<'a> {
     fn render(mut self, area: Rect, buf: &mut Buffer) {
-        // Update the scroll position based on selection and visible area
-        let visible_items = self.visible_items(area);
+        // Update the scroll position based on selection and the rows left for
+        // files inside the borders and search input
+        let search_rows = usize::from(self.search.is_some());
+        let visible_items = self.visible_items(area).saturating_sub(search_rows);
         self.update_scroll(visible_items);
         
         // Create a block for the list
+        let title = match self.search {
+            Some(search) if !search.query.is_empty() => format!(
+                "Test Files ({} of {} matching, best first)",
+                self.tests.len(),
+                search.base.len()
+            ),
+            _ => format!("Test Files (sorted by {})", self.sort_mode.label()),
+        };
+        let title = match self.marked.len() {
+            0 => title,
+            count => format!("{} - {} marked, Enter queues them", title, count),
+        };
         let block = Block::default()
-            .title("Test Files")
+            .title(title)
             .borders(Borders::ALL);
         
         // Render the block first
-        let inner_area = block.inner(area);
+        let mut inner_area = block.inner(area);
         block.render(area, buf);
         
+        // Keep the last line for the search input
+        if let Some(search) = self.search {
+            let input_area = Rect {
+                y: inner_area.bottom().saturating_sub(1),
+                height: 1.min(inner_area.height),
+                ..inner_area
+            };
+            inner_area.height = inner_area.height.saturating_sub(1);
+            
+            let mut spans = vec![
+                Span::styled("/", self.theme.key()),
+                Span::raw(search.query.clone()),
+            ];
+            if search.editing {
+                spans.push(Span::styled("█", Style::default().fg(self.theme.accent)));
+            } else {
+                spans.push(Span::styled("  (/ to edit, Esc to clear)", Style::default().fg(self.theme.dim)));
+            }
+            Paragraph::new(Line::from(spans)).render(input_area, buf);
+        }
+        
         // If no tests, show a message and return
         if self.tests.is_empty() {
-            Paragraph::new("No test files found.")
+            let message = if self.search.is_some() { "No matching test files." } else { "No test files found." };
+            Paragraph::new(message)
                 .render(inner_area, buf);
             return;
         }
         
         // Calculate visible range
-        let visible_area_height = self.visible_items(inner_area);
-        let end_idx = (self.scroll_offset + visible_area_height).min(self.tests.len());
+        let end_idx = (self.scroll_offset + visible_items).min(self.tests.len());
         let visible_tests = &self.tests[self.scroll_offset..end_idx];
         
         // Create styled text for the list
@@ -82,41 +194,90 @@ impl<'a> Widget for TestListWidget<'a> {
             // Create the selector string (arrow or space) - keep it inside the box
             let selector = if is_selected { "▶ " } else { "  " };
             
-            // Create the test name with proper styling
-            let line_text = format!("{}{}", selector, line);
-            let styled_line = if is_selected {
-                // Highlight selected item with bold yellow on blue background
-                Span::styled(
-                    line_text,
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .bg(Color::Blue)
-                        .add_modifier(Modifier::BOLD)
-                )
+            // Label the file with its project, if it has one
+            let display_name = self.display_names.and_then(|names| names.get(line));
+            
+            // Highlight selected item with the theme's selection colors
+            let line_style = if is_selected {
+                self.theme.selected()
             } else {
-                // Regular item
-                Span::raw(line_text)
+                Style::default()
             };
             
+            let mut spans = vec![Span::styled(selector, line_style)];
+            
+            // Show how the file's last run went
+            if let Some(history) = self.history {
+                let (badge, color) = match history.last_run(line).and_then(RunRecord::passed) {
+                    Some(true) => ("✓ ", self.theme.passed),
+                    Some(false) => ("✗ ", self.theme.failed),
+                    None => ("– ", self.theme.dim),
+                };
+                spans.push(Span::styled(badge, line_style.fg(color)));
+            }
+            
+            // Make room for a mark on every line once any file is marked
+            if !self.marked.is_empty() {
+                let mark = if self.marked.contains(line) { "● " } else { "  " };
+                spans.push(Span::styled(mark, line_style.fg(self.theme.info)));
+            }
+            spans.extend(display_name.map(display_name_span));
+            if display_name.is_some() {
+                spans.push(Span::styled(" ", line_style));
+            }
+            
+            // Create the test name, highlighting the characters a search matched
+            match self.search.and_then(|search| search.matches.get(line)) {
+                Some(positions) => {
+                    let matched_style = line_style.fg(self.theme.highlight).add_modifier(Modifier::BOLD);
+                    for (index, c) in line.chars().enumerate() {
+                        let style = if positions.contains(&index) { matched_style } else { line_style };
+                        spans.push(Span::styled(c.to_string(), style));
+                    }
+                },
+                None => spans.push(Span::styled(line.to_string(), line_style)),
+            }
+            
+            // Add a warning badge for files with focused tests
+            if self.focused_tests.is_some_and(|focused| focused.contains_key(line)) {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    "⚠ focused",
+                    Style::default()
+                        .fg(self.theme.badge_fg)
+                        .bg(self.theme.warning)
+                        .add_modifier(Modifier::BOLD)
+                ));
+            }
+            
+            // Right-align the last run's duration and the average of previous runs
+            if let Some(history) = self.history
+                && let Some(last_run) = history.last_run(line)
+                && let Some(average) = history.average_duration(line) {
+                let duration = format!(
+                    "{} ~{}",
+                    self.format.duration(last_run.duration_ms),
+                    self.format.duration(average)
+                );
+                let used_width: usize = spans.iter().map(|span| span.width()).sum();
+                let padding = (inner_area.width as usize)
+                    .saturating_sub(used_width + duration.chars().count())
+                    .max(1);
+                spans.push(Span::raw(" ".repeat(padding)));
+                spans.push(Span::styled(duration, Style::default().fg(self.theme.dim)));
+            }
+            
             // Add the line to the text
-            text.lines.push(Line::from(styled_line));
-        }
-        
-        // Append scroll indicator if needed
-        if self.tests.len() > visible_area_height {
-            let scroll_info = format!(
-                "[{}/{}]", 
-                self.selected_index + 1, 
-                self.tests.len()
-            );
-            text.lines.push(Line::from(Span::styled(
-                scroll_info,
-                Style::default().fg(Color::Gray)
-            )));
+            text.lines.push(Line::from(spans));
         }
         
         // Render the text inside the block's inner area
         Paragraph::new(text)
             .render(inner_area, buf);
+        
+        // Show where the visible files are in the whole list, alongside them
+        // rather than the search input
+        let list_rows = Rect { height: area.height.saturating_sub(search_rows as u16), ..area };
+        render_scrollbar(border_track(list_rows), buf, &self.theme, self.tests.len(), visible_items, self.scroll_offset);
     }
 }
\ No newline at end of file
@@ -2,11 +2,37 @@ use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Paragraph, Wrap},
 };
-use crate::app::state::TestInfo;
+use regex::Regex;
+use std::collections::HashSet;
+use crate::app::{
+    format::FormatOptions,
+    layout::DEFAULT_RESULTS_SPLIT,
+    state::{FailureBlame, TestInfo, TestStatus},
+    test_tree::{self, TreeRowKind},
+};
+use crate::git::BlameInfo;
+use crate::runner::{
+    assertion_diff::{self, Change, DiffRow, ExpectedReceived},
+    pretty_value,
+};
+use crate::widgets::{scrollbar::{border_track, render_scrollbar}, theme::Theme};
 
 pub struct TestResultsWidget<'a> {
     pub tests: &'a [TestInfo],
+    /// Selected row of the tree of describe blocks and tests
     pub selected_index: usize,
+    /// Describe blocks whose contents are hidden
+    pub collapsed: Option<&'a HashSet<Vec<String>>>,
+    /// Last changes to the selected failing test, once looked up with git blame
+    pub blame: Option<&'a FailureBlame>,
+    /// How durations and timestamps are formatted
+    pub format: FormatOptions,
+    /// Width of the test list as a percentage
+    pub split: u16,
+    /// Whether expected and received values are shown in columns rather than one above the other
+    pub side_by_side_diff: bool,
+    /// Colors to draw with
+    pub theme: Theme,
 }
 
 impl<'a> TestResultsWidget<'a> {
@@ -14,8 +40,50 @@ impl<'a> TestResultsWidget<'a> {
         Self {
             tests,
             selected_index,
+            collapsed: None,
+            blame: None,
+            format: FormatOptions::default(),
+            split: DEFAULT_RESULTS_SPLIT,
+            side_by_side_diff: false,
+            theme: Theme::default(),
         }
     }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Show expected and received values in columns instead of a unified diff
+    pub fn side_by_side_diff(mut self, side_by_side: bool) -> Self {
+        self.side_by_side_diff = side_by_side;
+        self
+    }
+    
+    /// Set the width of the test list as a percentage, hiding the details at 100
+    pub fn split(mut self, split: u16) -> Self {
+        self.split = split;
+        self
+    }
+
+    /// Format durations and timestamps using the configured options
+    pub fn format(mut self, format: &FormatOptions) -> Self {
+        self.format = format.clone();
+        self
+    }
+    
+    /// Hide the contents of these describe blocks
+    pub fn collapsed(mut self, collapsed: &'a HashSet<Vec<String>>) -> Self {
+        self.collapsed = Some(collapsed);
+        self
+    }
+    
+    /// Show who last changed the selected test
+    pub fn blame(mut self, blame: Option<&'a FailureBlame>) -> Self {
+        self.blame = blame;
+        self
+    }
 }
 
 impl<'a> Widget for TestResultsWidget<'a> {
@@ -24,16 +92,18 @@ impl<'a> Widget for TestResultsWidget<'a> {
         let horizontal_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(40),
-                Constraint::Percentage(60),
+                Constraint::Percentage(self.split),
+                Constraint::Percentage(100 - self.split),
             ])
             .split(area);
         
         // Build a custom test list
         
-        // Create a block for the test list
+        // Create a block for the test list, saying how to get hidden details back
+        let details_hidden = self.split >= 100;
+        let title = if details_hidden { "Test Results (details hidden, < to show)" } else { "Test Results" };
         let block = Block::default()
-            .title("Test Results")
+            .title(title)
             .borders(Borders::ALL);
         
         // Render the block first and get inner area
@@ -42,89 +112,169 @@ impl<'a> Widget for TestResultsWidget<'a> {
         
         // Create a custom list with an internal selection indicator
         let mut list_text = Text::default();
+        let no_collapsed = HashSet::new();
+        let rows = test_tree::tree_rows(self.tests, self.collapsed.unwrap_or(&no_collapsed));
         
-        for (idx, test) in self.tests.iter().enumerate() {
+        for (idx, row) in rows.iter().enumerate() {
             let is_selected = idx == self.selected_index;
             
             // Create the selector string (arrow or space) - keep it inside the box
             let selector = if is_selected { "▶ " } else { "  " };
+            let indent = "  ".repeat(row.depth);
             
-            let status = if test.passed { "✅ " } else { "❌ " };
-            
-            let time_str = match test.duration {
-                Some(ms) => format!(" ({} ms)", ms),
-                None => String::new(),
+            // Describe blocks show whether they're expanded and how many tests they hold
+            let (line_text, status) = match &row.kind {
+                TreeRowKind::Describe { path, collapsed, tests, status } => {
+                    let arrow = if *collapsed { "▸ " } else { "▾ " };
+                    let name = path.last().map(String::as_str).unwrap_or_default();
+                    (format!("{}{}{}{} ({})", selector, indent, arrow, name, tests), *status)
+                },
+                TreeRowKind::Test(index) => {
+                    let test = &self.tests[*index];
+                    let time_str = match test.duration {
+                        Some(ms) => format!(" ({})", self.format.duration(ms)),
+                        None => String::new(),
+                    };
+                    (format!("{}{}{}{}{}", selector, indent, status_icon(test.status), test.name, time_str), test.status)
+                },
             };
             
-            // Create combined line text
-            let line_text = format!("{}{}{}{}", selector, status, test.name, time_str);
-            
-            // Style based on selection and pass/fail status
+            // Style based on selection and status
             let style = if is_selected {
-                Style::default()
-                    .fg(if test.passed { Color::Green } else { Color::Red })
-                    .bg(Color::Blue)
+                status_style(status, &self.theme)
+                    .bg(self.theme.selected_bg)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default()
-                    .fg(if test.passed { Color::Green } else { Color::Red })
+                status_style(status, &self.theme)
             };
             
             list_text.lines.push(Line::from(Span::styled(line_text, style)));
         }
         
+        // Keep the selection in view when there are more rows than fit
+        let visible_rows = inner_area.height as usize;
+        let scroll = self.selected_index.saturating_sub(visible_rows.saturating_sub(1));
+        
         // Render our custom list
         Paragraph::new(list_text)
+            .scroll((scroll as u16, 0))
             .render(inner_area, buf);
+        render_scrollbar(border_track(horizontal_chunks[0]), buf, &self.theme, rows.len(), visible_rows, scroll);
+        if details_hidden {
+            return;
+        }
+        
+        // Describe blocks get a short summary instead of test details
+        if let Some(TreeRowKind::Describe { path, collapsed, tests, status }) = rows.get(self.selected_index).map(|row| &row.kind) {
+            let text = format!(
+                "Describe: {}\nTests: {}\nStatus: {}\n\n{}",
+                path.join(" › "),
+                tests,
+                match status {
+                    TestStatus::Failed => "Some failed",
+                    TestStatus::Passed => "None failed",
+                    status => status.label(),
+                },
+                if *collapsed { "Enter or → expands the block" } else { "Enter or ← collapses the block" }
+            );
+            let title_style = status_style(*status, &self.theme);
+            
+            Paragraph::new(text)
+                .block(Block::default()
+                    .title("Describe Block")
+                    .title_style(title_style)
+                    .borders(Borders::ALL))
+                .wrap(Wrap { trim: false })
+                .render(horizontal_chunks[1], buf);
+            return;
+        }
         
         // Render the details of the selected test if any
-        if !self.tests.is_empty() && self.selected_index < self.tests.len() {
-            let selected_test = &self.tests[self.selected_index];
+        let selected_test = match rows.get(self.selected_index).map(|row| &row.kind) {
+            Some(TreeRowKind::Test(index)) => self.tests.get(*index),
+            _ => None,
+        };
+        if let Some(selected_test) = selected_test {
             
             // Create formatted test details
-            let status = if selected_test.passed {
-                "Passed"
-            } else {
-                "Failed"
-            };
+            let status = selected_test.status.label();
+            let failed = selected_test.status == TestStatus::Failed;
             
             let time = match selected_test.duration {
-                Some(ms) => format!("{} ms", ms),
+                Some(ms) => self.format.duration(ms),
                 None => "Unknown".to_string(),
             };
             
+            let location = match selected_test.line {
+                Some(line) => format!("\nLine: {}", line),
+                None => String::new(),
+            };
+            
             let header_text = format!(
-                "Name: {}\nStatus: {}\nDuration: {}", 
-                selected_test.name, 
+                "Name: {}\nStatus: {}\nDuration: {}{}", 
+                selected_test.full_name, 
                 status, 
-                time
+                time,
+                location
             );
             
-            let error_text = if let Some(ref error) = selected_test.error {
-                format!("\n\nError Details:\n{}", error)
-            } else {
-                String::new()
+            let describe = |label: &str, blame: &BlameInfo| format!(
+                "\n{}: {} by {}, {} — {}",
+                label,
+                blame.commit,
+                blame.author,
+                self.format.timestamp(blame.time),
+                blame.summary
+            );
+            let blame_text = match self.blame {
+                Some(blame) if failed => {
+                    let test = blame.test.as_ref().map(|blame| describe("Last changed", blame)).unwrap_or_default();
+                    let frame = blame.frame
+                        .as_ref()
+                        .map(|(location, blame)| describe(&format!("{} last changed", location), blame))
+                        .unwrap_or_default();
+                    format!("{}{}", test, frame)
+                },
+                None if failed => "\nLast changed: b looks it up with git blame".to_string(),
+                _ => String::new(),
             };
             
-            let full_text = format!("{}{}", header_text, error_text);
+            let mut full_text = Text::from(format!("{}{}", header_text, blame_text));
+            if let Some(ref error) = selected_test.error {
+                full_text.extend([Line::raw(""), Line::raw("Error Details:")]);
+                full_text.extend(error_lines(error, &self.theme));
+            }
             
-            // Create style based on pass/fail status
-            let title_style = if selected_test.passed {
-                Style::default().fg(Color::Green)
-            } else {
-                Style::default().fg(Color::Red)
-            };
+            // Create style based on the test's status
+            let title_style = status_style(selected_test.status, &self.theme);
+            
+            // Give the values a failed assertion compared a pane of their own
+            let values = selected_test.error
+                .as_deref()
+                .filter(|_| failed)
+                .and_then(ExpectedReceived::from_failure);
+            let mut detail_area = horizontal_chunks[1];
+            if let Some(values) = &values {
+                let rows = assertion_diff::diff_rows(values);
+                let diff_height = (rows.len() as u16 + 3).min(detail_area.height / 2);
+                let [top, bottom] = Layout::vertical([
+                    Constraint::Min(0),
+                    Constraint::Length(diff_height),
+                ]).areas(detail_area);
+                detail_area = top;
+                render_diff(&rows, self.side_by_side_diff, &self.theme, bottom, buf);
+            }
             
             // Render the details
             let detail_block = Block::default()
-                .title(format!("Test Details"))
+                .title("Test Details".to_string())
                 .title_style(title_style)
                 .borders(Borders::ALL);
             
             Paragraph::new(full_text)
                 .block(detail_block)
                 .wrap(Wrap { trim: false })
-                .render(horizontal_chunks[1], buf);
+                .render(detail_area, buf);
         } else {
             // No test selected
             let no_test_selected = Paragraph::new("No test selected")
@@ -136,4 +286,155 @@ impl<'a> Widget for TestResultsWidget<'a> {
             no_test_selected.render(horizontal_chunks[1], buf);
         }
     }
-}
\ No newline at end of file
+}
+
+/// Icon shown before a test, padded to the same width for every status
+fn status_icon(status: TestStatus) -> &'static str {
+    match status {
+        TestStatus::Passed => "✅ ",
+        TestStatus::Failed => "❌ ",
+        TestStatus::Skipped => "○  ",
+        TestStatus::Todo => "✎  ",
+        TestStatus::Pending => "·  ",
+    }
+}
+
+/// Style of a test or describe block with the given status
+fn status_style(status: TestStatus, theme: &Theme) -> Style {
+    let color = match status {
+        TestStatus::Passed => theme.passed,
+        TestStatus::Failed => theme.failed,
+        TestStatus::Skipped => theme.highlight,
+        TestStatus::Todo => theme.info,
+        TestStatus::Pending => theme.muted,
+    };
+    Style::default().fg(color)
+}
+
+/// Lines of a test's error, with object dumps on `Expected:` and `Received:`
+/// lines spread over several indented, colored lines
+fn error_lines(error: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let value_line = Regex::new(r"^(\s*(?:Expected|Received)(?: [a-z ]+)?:\s?)(.*)$").unwrap();
+    let mut lines = Vec::new();
+    for line in error.lines() {
+        let pretty = value_line
+            .captures(line)
+            .and_then(|cap| Some((cap[1].to_string(), pretty_value::pretty_lines(&cap[2])?)));
+        let Some((label, values)) = pretty else {
+            lines.push(Line::raw(line.to_string()));
+            continue;
+        };
+        
+        lines.push(Line::raw(label.trim_end().to_string()));
+        let indent = " ".repeat(label.len() - label.trim_start().len() + 2);
+        for value in &values {
+            let mut spans = vec![Span::raw(indent.clone())];
+            spans.extend(pretty_value::tokens(value).into_iter().map(|(kind, text)| {
+                Span::styled(text.to_string(), theme.token(kind))
+            }));
+            lines.push(Line::from(spans));
+        }
+    }
+    lines
+}
+
+/// Render the expected and received values of a failed assertion, highlighting what differs
+fn render_diff(rows: &[DiffRow], side_by_side: bool, theme: &Theme, area: Rect, buf: &mut Buffer) {
+    let mode = if side_by_side { "side by side" } else { "unified" };
+    let block = Block::default()
+        .title(format!("Expected / Received ({}, v to switch)", mode))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    block.render(area, buf);
+    
+    let expected_style = Style::default().fg(theme.failed);
+    let received_style = Style::default().fg(theme.passed);
+    
+    if side_by_side {
+        let [left, right] = Layout::horizontal([
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ]).areas(inner);
+        
+        let mut expected_lines = vec![Line::styled("Expected", expected_style.add_modifier(Modifier::BOLD))];
+        let mut received_lines = vec![Line::styled("Received", received_style.add_modifier(Modifier::BOLD))];
+        for row in rows {
+            match *row {
+                DiffRow::Same(line) => {
+                    expected_lines.push(Line::raw(line.to_string()));
+                    received_lines.push(Line::raw(line.to_string()));
+                },
+                DiffRow::Changed { expected, received } => {
+                    let (expected_spans, received_spans) = intraline_spans(expected, received, theme);
+                    expected_lines.push(Line::from(expected_spans));
+                    received_lines.push(Line::from(received_spans));
+                },
+            }
+        }
+        
+        // No wrapping, so the columns stay lined up
+        Paragraph::new(expected_lines).render(left, buf);
+        Paragraph::new(received_lines).render(right, buf);
+        return;
+    }
+    
+    let mut lines = vec![Line::from(vec![
+        Span::styled("- Expected  ", expected_style),
+        Span::styled("+ Received", received_style),
+    ])];
+    for row in rows {
+        match *row {
+            DiffRow::Same(line) => lines.push(Line::raw(format!("  {}", line))),
+            DiffRow::Changed { expected, received } => {
+                let (expected_spans, received_spans) = intraline_spans(expected, received, theme);
+                if expected.is_some() {
+                    lines.push(Line::from([vec![Span::styled("- ", expected_style)], expected_spans].concat()));
+                }
+                if received.is_some() {
+                    lines.push(Line::from([vec![Span::styled("+ ", received_style)], received_spans].concat()));
+                }
+            },
+        }
+    }
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .render(inner, buf);
+}
+
+/// Spans for a changed line and the line that replaced it, with the characters
+/// that differ between them highlighted
+fn intraline_spans(expected: Option<&str>, received: Option<&str>, theme: &Theme) -> (Vec<Span<'static>>, Vec<Span<'static>>) {
+    let expected_style = Style::default().fg(theme.failed);
+    let received_style = Style::default().fg(theme.passed);
+    
+    let (Some(expected), Some(received)) = (expected, received) else {
+        return (
+            expected.map(|line| vec![Span::styled(line.to_string(), expected_style)]).unwrap_or_default(),
+            received.map(|line| vec![Span::styled(line.to_string(), received_style)]).unwrap_or_default(),
+        );
+    };
+    
+    let expected_chars: Vec<char> = expected.chars().collect();
+    let received_chars: Vec<char> = received.chars().collect();
+    let mut expected_spans = Vec::new();
+    let mut received_spans = Vec::new();
+    for change in assertion_diff::diff(&expected_chars, &received_chars) {
+        match change {
+            Change::Same(c) => {
+                push_char(&mut expected_spans, c, expected_style);
+                push_char(&mut received_spans, c, received_style);
+            },
+            Change::Removed(c) => push_char(&mut expected_spans, c, expected_style.add_modifier(Modifier::REVERSED)),
+            Change::Added(c) => push_char(&mut received_spans, c, received_style.add_modifier(Modifier::REVERSED)),
+        }
+    }
+    (expected_spans, received_spans)
+}
+
+/// Add a character to the last span if it has the same style, or start a new span
+fn push_char(spans: &mut Vec<Span<'static>>, c: char, style: Style) {
+    match spans.last_mut() {
+        Some(span) if span.style == style => span.content.to_mut().push(c),
+        _ => spans.push(Span::styled(c.to_string(), style)),
+    }
+}
@@ -2,6 +2,11 @@ use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
+use crate::app::output_rows::{OutputRow, OutputRows};
+use crate::runner::json_results::SnapshotSummary;
+use crate::runner::ansi;
+use crate::widgets::ansi::ansi_line;
+use crate::widgets::{scrollbar::{border_track, render_scrollbar}, theme::Theme};
 
 /// Widget for displaying test execution output with terminal-like styling
 pub struct TestTerminalWidget<'a> {
@@ -9,22 +14,69 @@ pub struct TestTerminalWidget<'a> {
     pub command: &'a str,
     /// Output from the command
     pub output: &'a str,
-    /// Scroll position in the output
+    /// Scroll position in the output, in rows as shown
     pub scroll_position: usize,
-    /// Whether the command has been copied
-    pub command_copied: bool,
+    /// How the output's lines are shown
+    pub rows: Option<OutputRows>,
+    /// Whether only the failures are shown
+    pub failures_only: bool,
+    /// What the finished run did to snapshots
+    pub snapshots: Option<SnapshotSummary>,
+    /// Output line to highlight, e.g. a stack frame jumped to
+    pub highlighted_line: Option<usize>,
+    /// Whether the view is following streaming output, while a run is going
+    pub following: Option<bool>,
+    /// Colors to draw with
+    pub theme: Theme,
 }
 
 impl<'a> TestTerminalWidget<'a> {
     /// Create a new terminal widget
-    pub fn new(command: &'a str, output: &'a str, scroll_position: usize, command_copied: bool) -> Self {
+    pub fn new(command: &'a str, output: &'a str, scroll_position: usize) -> Self {
         Self {
             command,
             output,
             scroll_position,
-            command_copied,
+            rows: None,
+            failures_only: false,
+            snapshots: None,
+            highlighted_line: None,
+            following: None,
+            theme: Theme::default(),
         }
     }
+    
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// Show how many snapshots the run wrote, updated, or failed
+    pub fn snapshots(mut self, snapshots: Option<SnapshotSummary>) -> Self {
+        self.snapshots = snapshots;
+        self
+    }
+    
+    /// Highlight a line of the output
+    pub fn highlight_line(mut self, line: Option<usize>) -> Self {
+        self.highlighted_line = line;
+        self
+    }
+    
+    /// Show the output's lines as laid out, with library stack frames folded
+    /// or only the failures kept
+    pub fn rows(mut self, rows: OutputRows, failures_only: bool) -> Self {
+        self.rows = Some(rows);
+        self.failures_only = failures_only;
+        self
+    }
+    
+    /// Show whether new output is being followed, for a run that's still going
+    pub fn following(mut self, following: Option<bool>) -> Self {
+        self.following = following;
+        self
+    }
 }
 
 impl<'a> Widget for TestTerminalWidget<'a> {
@@ -38,18 +90,14 @@ impl<'a> Widget for TestTerminalWidget<'a> {
             ])
             .split(area);
             
-        // Render command area with copy status
-        let command_text = if self.command_copied {
-            format!("{} [Copied ✓]", self.command)
-        } else {
-            format!("{} [Press Enter to copy]", self.command)
-        };
+        // Render command area with how to copy it
+        let command_text = format!("{} [Press Enter to copy]", self.command);
         
         Paragraph::new(command_text)
             .block(Block::default()
                 .title(" Command ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Blue)))
+                .border_style(Style::default().fg(self.theme.border)))
             .render(chunks[0], buf);
             
         // Process and render terminal output
@@ -58,29 +106,62 @@ impl<'a> Widget for TestTerminalWidget<'a> {
         // Calculate visible range
         let visible_lines = chunks[1].height.saturating_sub(2) as usize; // Account for borders
         let lines: Vec<&str> = self.output.lines().collect();
+        let rows = match self.rows {
+            Some(rows) => rows.rows,
+            None => (0..lines.len()).map(OutputRow::Line).collect(),
+        };
+        if rows.is_empty() && self.failures_only {
+            text.lines.push(Line::from(Span::styled(
+                "No failures in the output (F shows all of it)",
+                Style::default().fg(self.theme.muted)
+            )));
+        }
         
-        let start_line = self.scroll_position.min(lines.len().saturating_sub(1));
-        let end_line = (start_line + visible_lines).min(lines.len());
+        // Runners asked for colors print them; other output is colored by keyword
+        let colored = self.output.contains('\x1b');
+        
+        let start_row = self.scroll_position.min(rows.len().saturating_sub(1));
+        let end_row = (start_row + visible_lines).min(rows.len());
         
         // Add each visible line with appropriate styling
-        for line in &lines[start_line..end_line] {
-            let line_str = *line; // Dereference to get &str
-            let styled_line = if line_str.contains("PASS") || line_str.contains("✓") {
-                Line::from(Span::styled(line_str, Style::default().fg(Color::Green)))
+        for row in &rows[start_row..end_row] {
+            let index = match row {
+                OutputRow::Line(index) => *index,
+                OutputRow::Fold(folded) => {
+                    let first = ansi::strip(lines[folded.start]);
+                    let indent = first.len() - first.trim_start().len();
+                    text.lines.push(Line::from(Span::styled(
+                        format!("{}⋯ {} library frames folded (z shows them)", " ".repeat(indent), folded.len()),
+                        Style::default().fg(self.theme.dim).add_modifier(Modifier::ITALIC)
+                    )));
+                    continue;
+                },
+            };
+            let line_str = lines[index];
+            let styled_line = if self.highlighted_line == Some(index) {
+                // The selected stack frame stands out from the rest of the trace
+                Line::from(Span::styled(
+                    ansi::strip(line_str),
+                    self.theme.badge().add_modifier(Modifier::BOLD)
+                ))
+            } else if colored {
+                ansi_line(line_str)
+            } else if line_str.contains("PASS") || line_str.contains("✓") {
+                Line::from(Span::styled(line_str, Style::default().fg(self.theme.passed)))
             } else if line_str.contains("FAIL") || line_str.contains("×") || line_str.contains("Error:") {
-                Line::from(Span::styled(line_str, Style::default().fg(Color::Red)))
+                Line::from(Span::styled(line_str, Style::default().fg(self.theme.failed)))
             } else if line_str.starts_with("    at ") || line_str.contains("Stack:") {
                 // Stack traces in dimmed white
-                Line::from(Span::styled(line_str, Style::default().fg(Color::Gray)))
+                Line::from(Span::styled(line_str, Style::default().fg(self.theme.muted)))
             } else if line_str.contains("Expected:") || line_str.contains("Received:") {
                 // Expected/Received in yellow
-                Line::from(Span::styled(line_str, Style::default().fg(Color::Yellow)))
+                Line::from(Span::styled(line_str, Style::default().fg(self.theme.warning)))
             } else if line_str.contains("console.log") || line_str.contains("console.info") {
                 // Console output in cyan
-                Line::from(Span::styled(line_str, Style::default().fg(Color::Cyan)))
+                Line::from(Span::styled(line_str, Style::default().fg(self.theme.info)))
             } else if line_str.contains("warning") || line_str.contains("Warning:") {
                 // Warnings in yellow
-                Line::from(Span::styled(line_str, Style::default().fg(Color::Yellow)))
+                Line::from(Span::styled(line_str, Style::default().fg(self.theme.warning)))
             } else {
                 // Default color
                 Line::from(line_str)
@@ -89,43 +170,34 @@ impl<'a> Widget for TestTerminalWidget<'a> {
             text.lines.push(styled_line);
         }
         
-        // Add scroll indicator if needed
-        if lines.len() > visible_lines {
-            let scroll_percentage = if lines.len() <= visible_lines {
-                100.0
+        // Note what happened to snapshots next to the title
+        let mut title = vec![Span::raw(" Terminal Output ")];
+        if self.failures_only {
+            title.push(Span::styled("[failures only, F shows all] ", Style::default().fg(self.theme.warning)));
+        }
+        match self.following {
+            Some(true) => title.push(Span::styled("[following] ", Style::default().fg(self.theme.passed))),
+            Some(false) => title.push(Span::styled("[paused, f to follow] ", Style::default().fg(self.theme.warning))),
+            None => {},
+        }
+        if let Some(snapshots) = self.snapshots
+            && let Some(label) = snapshots.label() {
+            let (hint, color) = if snapshots.needs_update() {
+                (" (u to update) ", self.theme.warning)
             } else {
-                (start_line as f64 / (lines.len().saturating_sub(visible_lines)) as f64) * 100.0
+                (" ", self.theme.passed)
             };
-            
-            let scroll_indicator = format!(
-                "Scroll: {:.0}% ({}/{} lines) [↑/↓: Navigate | PgUp/PgDn: Scroll faster]",
-                scroll_percentage,
-                start_line + 1,
-                lines.len()
-            );
-            
-            if end_line < lines.len() {
-                text.lines.push(Line::from(Span::styled(
-                    "↓ More lines below ↓",
-                    Style::default().fg(Color::Gray)
-                )));
-            }
-            
-            if text.lines.len() < visible_lines && end_line >= lines.len() {
-                text.lines.push(Line::from(Span::styled(
-                    scroll_indicator,
-                    Style::default().fg(Color::Gray)
-                )));
-            }
+            title.push(Span::styled(format!("─ {}{}", label, hint), Style::default().fg(color)));
         }
         
         // Render the terminal output
         Paragraph::new(text)
             .block(Block::default()
-                .title(" Terminal Output ")
+                .title(Line::from(title))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Blue)))
+                .border_style(Style::default().fg(self.theme.border)))
             .wrap(Wrap { trim: false })
             .render(chunks[1], buf);
+        render_scrollbar(border_track(chunks[1]), buf, &self.theme, rows.len(), visible_lines, start_row);
     }
 }
\ No newline at end of file
@@ -0,0 +1,172 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::{collections::BTreeMap, str::FromStr};
+use crate::runner::pretty_value::TokenKind;
+
+/// Colors every widget draws with, by what they mean rather than what they are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Passing tests and covered lines
+    pub passed: Color,
+    /// Failing tests and uncovered lines
+    pub failed: Color,
+    /// Things that need attention, like focused tests or a running file
+    pub warning: Color,
+    /// Keys, cursors, and the borders of popups
+    pub accent: Color,
+    /// Headings, spinners, and other informational text
+    pub info: Color,
+    /// Search matches and skipped tests
+    pub highlight: Color,
+    /// Secondary text, like hints and commands
+    pub muted: Color,
+    /// Text that should barely be noticed, like line numbers
+    pub dim: Color,
+    /// Borders of the main panes
+    pub border: Color,
+    /// Text of the selected row
+    pub selected_fg: Color,
+    /// Background of the selected row
+    pub selected_bg: Color,
+    /// Text drawn on an `accent` or `warning` background, like badges
+    pub badge_fg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        ThemeName::Dark.theme()
+    }
+}
+
+impl Theme {
+    /// Style of the selected row in a list
+    pub fn selected(&self) -> Style {
+        Style::default().fg(self.selected_fg).bg(self.selected_bg).add_modifier(Modifier::BOLD)
+    }
+
+    /// Style of a key in help text
+    pub fn key(&self) -> Style {
+        Style::default().fg(self.accent).add_modifier(Modifier::BOLD)
+    }
+
+    /// Style of a badge or highlighted line
+    pub fn badge(&self) -> Style {
+        Style::default().fg(self.badge_fg).bg(self.accent)
+    }
+
+    /// Style of a piece of a pretty-printed value
+    pub fn token(&self, kind: TokenKind) -> Style {
+        match kind {
+            TokenKind::Key => Style::default().fg(self.info),
+            TokenKind::String => Style::default().fg(self.passed),
+            TokenKind::Number | TokenKind::Literal => Style::default().fg(self.warning),
+            TokenKind::Punctuation => Style::default().fg(self.muted),
+            TokenKind::Other => Style::default(),
+        }
+    }
+
+    /// Replace the color of a role, by the role's field name
+    fn set(&mut self, role: &str, color: Color) -> Result<(), String> {
+        let field = match role {
+            "passed" => &mut self.passed,
+            "failed" => &mut self.failed,
+            "warning" => &mut self.warning,
+            "accent" => &mut self.accent,
+            "info" => &mut self.info,
+            "highlight" => &mut self.highlight,
+            "muted" => &mut self.muted,
+            "dim" => &mut self.dim,
+            "border" => &mut self.border,
+            "selected_fg" => &mut self.selected_fg,
+            "selected_bg" => &mut self.selected_bg,
+            "badge_fg" => &mut self.badge_fg,
+            _ => return Err(format!("unknown theme color '{}'", role)),
+        };
+        *field = color;
+        Ok(())
+    }
+}
+
+/// The built-in themes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    /// For dark terminal backgrounds
+    #[default]
+    Dark,
+    /// For light terminal backgrounds
+    Light,
+    /// Bright colors and an inverted selection
+    HighContrast,
+}
+
+impl ThemeName {
+    pub fn theme(self) -> Theme {
+        match self {
+            ThemeName::Dark => Theme {
+                passed: Color::Green,
+                failed: Color::Red,
+                warning: Color::Yellow,
+                accent: Color::Yellow,
+                info: Color::Cyan,
+                highlight: Color::Magenta,
+                muted: Color::Gray,
+                dim: Color::DarkGray,
+                border: Color::Blue,
+                selected_fg: Color::Yellow,
+                selected_bg: Color::Blue,
+                badge_fg: Color::Black,
+            },
+            ThemeName::Light => Theme {
+                passed: Color::Indexed(28),
+                failed: Color::Indexed(124),
+                warning: Color::Indexed(130),
+                accent: Color::Indexed(25),
+                info: Color::Indexed(30),
+                highlight: Color::Indexed(90),
+                muted: Color::DarkGray,
+                dim: Color::Gray,
+                border: Color::Indexed(25),
+                selected_fg: Color::White,
+                selected_bg: Color::Indexed(25),
+                badge_fg: Color::White,
+            },
+            ThemeName::HighContrast => Theme {
+                passed: Color::LightGreen,
+                failed: Color::LightRed,
+                warning: Color::LightYellow,
+                accent: Color::LightYellow,
+                info: Color::LightCyan,
+                highlight: Color::LightMagenta,
+                muted: Color::White,
+                dim: Color::Gray,
+                border: Color::White,
+                selected_fg: Color::Black,
+                selected_bg: Color::White,
+                badge_fg: Color::Black,
+            },
+        }
+    }
+}
+
+/// The `[theme]` config section: a built-in theme, with any of its colors replaced
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeOptions {
+    /// Built-in theme to start from
+    pub name: ThemeName,
+    /// Colors replacing the built-in theme's, by role, e.g. `passed = "#00ff00"`
+    pub colors: BTreeMap<String, String>,
+}
+
+impl ThemeOptions {
+    /// The configured theme, or what's wrong with the configured colors
+    pub fn resolve(&self) -> Result<Theme, String> {
+        let mut theme = self.name.theme();
+        for (role, value) in &self.colors {
+            let color = Color::from_str(value).map_err(|_| format!("invalid color '{}' for {}", value, role))?;
+            theme.set(role, color)?;
+        }
+        Ok(theme)
+    }
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+use crate::app::toast::{Toast, ToastKind};
+use crate::widgets::theme::Theme;
+
+/// Widest a toast gets, in columns
+const MAX_TOAST_WIDTH: u16 = 60;
+
+/// Transient notifications stacked in the bottom-right corner, newest at the bottom
+pub struct ToastsWidget<'a> {
+    /// Toasts to show, oldest first
+    pub toasts: Vec<&'a Toast>,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> ToastsWidget<'a> {
+    /// Create a new toasts widget
+    pub fn new(toasts: Vec<&'a Toast>) -> Self {
+        Self {
+            toasts,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Icon and color for each kind of toast
+    fn style(&self, kind: ToastKind) -> (&'static str, Color) {
+        match kind {
+            ToastKind::Success => ("✓", self.theme.passed),
+            ToastKind::Info => ("ℹ", self.theme.info),
+            ToastKind::Error => ("✗", self.theme.failed),
+        }
+    }
+}
+
+impl<'a> Widget for ToastsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let max_width = MAX_TOAST_WIDTH.min(area.width.saturating_sub(2));
+        let mut bottom = area.bottom();
+
+        for toast in self.toasts.iter().rev() {
+            let (icon, color) = self.style(toast.kind);
+            let text = format!("{} {}", icon, toast.message);
+
+            // Wrap long messages onto more lines rather than cutting them off
+            let inner_width = max_width.saturating_sub(2).max(1);
+            let text_width = Line::from(text.as_str()).width() as u16;
+            let width = (text_width + 2).min(max_width);
+            let height = text_width.div_ceil(inner_width).max(1) + 2;
+            if bottom < area.y + height {
+                break;
+            }
+            bottom -= height;
+
+            let toast_area = Rect {
+                x: area.right().saturating_sub(width + 1),
+                y: bottom,
+                width,
+                height,
+            };
+            Clear.render(toast_area, buf);
+            Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .wrap(Wrap { trim: true })
+                .block(Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(color)))
+                .render(toast_area, buf);
+        }
+    }
+}
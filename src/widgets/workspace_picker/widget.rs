@@ -0,0 +1,91 @@
+use std::path::Path;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use crate::runner::workspace::Monorepo;
+use crate::widgets::theme::Theme;
+
+/// Widget for choosing which package of a monorepo to list and run tests in
+pub struct WorkspacePickerWidget<'a> {
+    /// The monorepo and its packages, listed after a "Repository root" entry
+    pub monorepo: &'a Monorepo,
+    /// Currently highlighted entry, where 0 is the repository root
+    pub selected_index: usize,
+    /// Directory currently being searched for tests
+    pub active: Option<&'a Path>,
+    /// Colors to draw with
+    pub theme: Theme,
+}
+
+impl<'a> WorkspacePickerWidget<'a> {
+    /// Create a new workspace picker widget
+    pub fn new(monorepo: &'a Monorepo, selected_index: usize, active: Option<&'a Path>) -> Self {
+        Self {
+            monorepo,
+            selected_index,
+            active,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draw with the given colors
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    
+    /// One entry of the list: its selector, name, and a muted detail
+    fn entry(&self, index: usize, name: String, detail: String, dir: &Path) -> Line<'static> {
+        let is_selected = index == self.selected_index;
+        let selector = if is_selected { "▶ " } else { "  " };
+        let active_marker = if self.active == Some(dir) { " (active)" } else { "" };
+        let style = if is_selected {
+            self.theme.selected()
+        } else {
+            Style::default()
+        };
+        
+        Line::from(vec![
+            Span::styled(format!("{}{}", selector, name), style),
+            Span::styled(format!("  {}{}", detail, active_marker), Style::default().fg(self.theme.muted)),
+        ])
+    }
+}
+
+impl<'a> Widget for WorkspacePickerWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(format!("Choose a Package ({})", self.monorepo.tool))
+            .borders(Borders::ALL);
+        
+        let mut text = Text::default();
+        text.lines.push(self.entry(
+            0,
+            "Repository root".to_string(),
+            self.monorepo.root.display().to_string(),
+            &self.monorepo.root,
+        ));
+        
+        for (idx, package) in self.monorepo.packages.iter().enumerate() {
+            let config = match &package.config {
+                Some(config) => config
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                None => "no config".to_string(),
+            };
+            let dir = self.monorepo.root.join(&package.dir);
+            text.lines.push(self.entry(
+                idx + 1,
+                package.name.clone(),
+                format!("{} · {}", package.dir.display(), config),
+                &dir,
+            ));
+        }
+        
+        Paragraph::new(text)
+            .block(block)
+            .render(area, buf);
+    }
+}